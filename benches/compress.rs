@@ -0,0 +1,53 @@
+use std::io::Write;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use parquet2::compression::{compress, CompressionOptions};
+
+fn add_benchmark(c: &mut Criterion) {
+    let rows_per_page = 1_000i32;
+
+    // 100 small pages of 4kb
+    let pages = (0..100)
+        .map(|page| {
+            (0..rows_per_page)
+                .flat_map(|x| ((page * rows_per_page + x) % 128).to_le_bytes())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut compressed = vec![];
+    c.bench_function("compress 100 pages (zstd)", |b| {
+        b.iter(|| {
+            pages.iter().for_each(|page| {
+                compressed.clear();
+                compress(CompressionOptions::Zstd(None), page, &mut compressed).unwrap();
+            })
+        })
+    });
+
+    // a zstd encoder initialized for every page, which `compress` avoids
+    c.bench_function("compress 100 pages (zstd, new encoder per page)", |b| {
+        b.iter(|| {
+            pages.iter().for_each(|page| {
+                compressed.clear();
+                let mut encoder =
+                    zstd::Encoder::new(&mut compressed, zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+                encoder.write_all(page).unwrap();
+                encoder.finish().unwrap();
+            })
+        })
+    });
+
+    c.bench_function("compress 100 pages (gzip)", |b| {
+        b.iter(|| {
+            pages.iter().for_each(|page| {
+                compressed.clear();
+                compress(CompressionOptions::Gzip(None), page, &mut compressed).unwrap();
+            })
+        })
+    });
+}
+
+criterion_group!(benches, add_benchmark);
+criterion_main!(benches);
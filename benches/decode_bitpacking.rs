@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
-use parquet2::encoding::bitpacking::Decoder;
+use parquet2::encoding::bitpacking::{encode, Decoder};
 
 fn add_benchmark(c: &mut Criterion) {
     (10..=20).step_by(2).for_each(|log2_size| {
@@ -13,7 +13,21 @@ fn add_benchmark(c: &mut Criterion) {
         c.bench_function(&format!("bitpacking 2^{}", log2_size), |b| {
             b.iter(|| Decoder::new(&bytes, 1, size).count())
         });
-    })
+    });
+
+    let size = 2usize.pow(16);
+    [1u8, 7, 16, 20].iter().for_each(|num_bits| {
+        let num_bits = *num_bits;
+        let values = (0..size as u32)
+            .map(|x| x.wrapping_mul(2654435761) >> (32 - num_bits))
+            .collect::<Vec<_>>();
+        let mut bytes = vec![0; size * num_bits as usize / 8];
+        encode(&values, num_bits, &mut bytes);
+
+        c.bench_function(&format!("bitpacking {} bits 2^16", num_bits), |b| {
+            b.iter(|| Decoder::new(&bytes, num_bits, size).fold(0u32, u32::wrapping_add))
+        });
+    });
 }
 
 criterion_group!(benches, add_benchmark);
@@ -0,0 +1,64 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use parquet2::compression::{compress, Compression, CompressionOptions};
+use parquet2::encoding::Encoding;
+use parquet2::metadata::Descriptor;
+use parquet2::page::{CompressedDataPage, DataPageHeader, DataPageHeaderV1};
+use parquet2::read::BasicDecompressor;
+use parquet2::schema::types::{PhysicalType, PrimitiveType};
+use parquet2::FallibleStreamingIterator;
+
+fn add_benchmark(c: &mut Criterion) {
+    let num_rows = 100_000i32;
+    let rows_per_page = 1_000;
+
+    let descriptor = Descriptor {
+        primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32),
+        max_def_level: 0,
+        max_rep_level: 0,
+    };
+
+    let pages = (0..num_rows / rows_per_page)
+        .map(|page| {
+            let values = (0..rows_per_page)
+                .flat_map(|x| ((page * rows_per_page + x) % 128).to_le_bytes())
+                .collect::<Vec<_>>();
+            let mut compressed = vec![];
+            compress(CompressionOptions::Snappy, &values, &mut compressed).unwrap();
+            (compressed, values.len())
+        })
+        .collect::<Vec<_>>();
+
+    let to_page = |(compressed, uncompressed_size): &(Vec<u8>, usize)| {
+        let header = DataPageHeaderV1 {
+            num_values: rows_per_page,
+            encoding: Encoding::Plain.into(),
+            definition_level_encoding: Encoding::Rle.into(),
+            repetition_level_encoding: Encoding::Rle.into(),
+            statistics: None,
+        };
+        Ok(CompressedDataPage::new(
+            DataPageHeader::V1(header),
+            compressed.clone(),
+            Compression::Snappy,
+            *uncompressed_size,
+            None,
+            descriptor.clone(),
+            None,
+        ))
+    };
+
+    c.bench_function("decompress 100k rows (snappy)", |b| {
+        b.iter(|| {
+            let mut decompressor = BasicDecompressor::new(pages.iter().map(to_page), vec![]);
+            let mut length = 0;
+            while let Some(page) = decompressor.next().unwrap() {
+                length += page.buffer().len();
+            }
+            length
+        })
+    });
+}
+
+criterion_group!(benches, add_benchmark);
+criterion_main!(benches);
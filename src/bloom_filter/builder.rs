@@ -0,0 +1,145 @@
+use crate::compression::{decompress_into, Compression};
+use crate::encoding::plain_byte_array::BinaryIter;
+use crate::error::{Error, Result};
+use crate::metadata::ColumnDescriptor;
+use crate::page::{CompressedDictPage, CompressedPage};
+use crate::parquet_bridge::Encoding;
+use crate::read::row::{decode_levels, decode_values, DynamicValue};
+use crate::read::{decompress_into_buffer, DEFAULT_MAX_PAGE_SIZE};
+use crate::schema::types::PhysicalType;
+use crate::types::{decode, NativeType};
+use crate::write::BloomFilterOptions;
+
+use super::{hash_byte, hash_native, BloomFilter};
+
+/// A builder of the [`BloomFilter`] of a column chunk from its (compressed) pages.
+///
+/// Pages are decompressed with this crate's implementation of their compression. The values
+/// of dictionary-encoded data pages are inserted via their dictionary page.
+#[derive(Debug)]
+pub struct BloomFilterBuilder {
+    filter: BloomFilter,
+    physical_type: PhysicalType,
+    buffer: Vec<u8>,
+}
+
+impl BloomFilterBuilder {
+    /// Returns a new [`BloomFilterBuilder`] of a column whose values are of `physical_type`,
+    /// sized according to `options`.
+    /// # Error
+    /// Errors iff the options' false-positive probability is not in `(0, 1)` or
+    /// `physical_type` is boolean, whose columns have no bloom filters.
+    pub fn try_new(options: &BloomFilterOptions, physical_type: PhysicalType) -> Result<Self> {
+        if physical_type == PhysicalType::Boolean {
+            return Err(Error::InvalidParameter(
+                "Boolean columns do not support bloom filters".to_string(),
+            ));
+        }
+        Ok(Self {
+            filter: BloomFilter::try_from_options(options)?,
+            physical_type,
+            buffer: vec![],
+        })
+    }
+
+    /// Inserts the values of `page` into the filter.
+    /// # Error
+    /// Errors iff the page cannot be decompressed or decoded.
+    pub fn insert_page(&mut self, page: &CompressedPage) -> Result<()> {
+        match page {
+            CompressedPage::Dict(page) => self.insert_dict_page(page),
+            CompressedPage::Data(page) => {
+                if matches!(
+                    page.encoding(),
+                    Encoding::PlainDictionary | Encoding::RleDictionary
+                ) {
+                    return Ok(());
+                }
+                // hash the values from the page's buffer, decompressing it into ours if needed
+                let buffer = if page.compression() != Compression::Uncompressed {
+                    decompress_into_buffer(page, &mut self.buffer, None, DEFAULT_MAX_PAGE_SIZE)?;
+                    self.buffer.as_slice()
+                } else {
+                    page.buffer.as_slice()
+                };
+                let descriptor = &page.descriptor;
+                let max_def_level = descriptor.max_def_level as u32;
+                let (_, def) = decode_levels(page.header(), buffer, descriptor)?;
+                let num_values = def.iter().filter(|x| **x == max_def_level).count();
+                for value in decode_values(page.header(), buffer, descriptor, None, num_values)? {
+                    insert_value(&mut self.filter, &value);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn insert_dict_page(&mut self, page: &CompressedDictPage) -> Result<()> {
+        let values = if page.compression() != Compression::Uncompressed {
+            self.buffer.clear();
+            decompress_into(
+                page.compression(),
+                page.buffer(),
+                page.uncompressed_size(),
+                &mut self.buffer,
+            )?;
+            self.buffer.as_slice()
+        } else {
+            page.buffer()
+        };
+        let filter = &mut self.filter;
+        let num_values = page.num_values();
+        match self.physical_type {
+            PhysicalType::Int32 => insert_native::<i32>(filter, values),
+            PhysicalType::Int64 => insert_native::<i64>(filter, values),
+            PhysicalType::Int96 => insert_native::<[u32; 3]>(filter, values),
+            PhysicalType::Float => insert_native::<f32>(filter, values),
+            PhysicalType::Double => insert_native::<f64>(filter, values),
+            PhysicalType::ByteArray => {
+                BinaryIter::new(values, Some(num_values)).for_each(|x| filter.insert(x))
+            }
+            PhysicalType::FixedLenByteArray(size) => values
+                .chunks_exact(size)
+                .take(num_values)
+                .for_each(|x| filter.insert(x)),
+            PhysicalType::Boolean => unreachable!("rejected by `try_new`"),
+        };
+        Ok(())
+    }
+
+    /// Returns the filter with all values inserted so far.
+    pub fn into_filter(self) -> BloomFilter {
+        self.filter
+    }
+}
+
+fn insert_value(filter: &mut BloomFilter, value: &DynamicValue) {
+    let hash = match value {
+        DynamicValue::Int32(x) => hash_native(*x),
+        DynamicValue::Int64(x) => hash_native(*x),
+        DynamicValue::Int96(x) => hash_native(*x),
+        DynamicValue::Float(x) => hash_native(*x),
+        DynamicValue::Double(x) => hash_native(*x),
+        DynamicValue::Binary(x) | DynamicValue::FixedLenBinary(x) => hash_byte(x),
+        DynamicValue::Null | DynamicValue::Boolean(_) | DynamicValue::List(_) => return,
+    };
+    filter.insert_hash(hash);
+}
+
+fn insert_native<T: NativeType>(filter: &mut BloomFilter, values: &[u8]) {
+    values
+        .chunks_exact(std::mem::size_of::<T>())
+        .for_each(|x| filter.insert_native(decode::<T>(x)))
+}
+
+/// Returns a [`BloomFilterBuilder`] of `column` when `options` is set.
+pub(crate) fn try_new_builder(
+    column: &ColumnDescriptor,
+    options: Option<&BloomFilterOptions>,
+) -> Result<Option<BloomFilterBuilder>> {
+    options
+        .map(|options| {
+            BloomFilterBuilder::try_new(options, column.descriptor.primitive_type.physical_type)
+        })
+        .transpose()
+}
@@ -0,0 +1,116 @@
+use crate::error::{Error, Result};
+use crate::types::NativeType;
+use crate::write::BloomFilterOptions;
+
+use super::{hash_byte, hash_native, insert, is_in_set};
+
+/// The minimum size of a bitset, in bytes: one block of the split-block algorithm.
+const MIN_NUM_BYTES: usize = 32;
+/// The maximum size of a bitset, in bytes (128 MiB), as used by parquet-mr.
+const MAX_NUM_BYTES: usize = 128 * 1024 * 1024;
+
+/// Returns the number of bytes of a split-block bloom filter holding `num_distinct_values`
+/// with a false-positive probability of `fpp`.
+///
+/// The result is a power of two between 32 bytes (one block) and 128 MiB.
+/// # Error
+/// Errors iff `fpp` is not in the open interval `(0, 1)`.
+pub fn optimal_num_of_bytes(num_distinct_values: u64, fpp: f64) -> Result<usize> {
+    if !(fpp > 0.0 && fpp < 1.0) {
+        return Err(Error::InvalidParameter(format!(
+            "the false-positive probability of a bloom filter must be in (0, 1); got {}",
+            fpp
+        )));
+    }
+    // m = -k * n / ln(1 - p^(1/k)) bits, with k = 8 hashes per block
+    let num_bits = -8.0 * num_distinct_values as f64 / (1.0 - fpp.powf(1.0 / 8.0)).ln();
+    let num_bytes = (num_bits / 8.0).ceil() as usize;
+    Ok(num_bytes
+        .clamp(MIN_NUM_BYTES, MAX_NUM_BYTES)
+        .next_power_of_two())
+}
+
+/// A split-block bloom filter, as described in the
+/// [Parquet specification](https://github.com/apache/parquet-format/blob/master/BloomFilter.md).
+///
+/// Values are hashed with xxh64: [`BloomFilter::insert`] and [`BloomFilter::might_contain`]
+/// hash the bytes as stored in parquet's plain encoding of `BYTE_ARRAY` and
+/// `FIXED_LEN_BYTE_ARRAY` (i.e. without the length prefix), while their `_native` counterparts
+/// hash the little-endian representation of physical types such as `INT32`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    bitset: Vec<u8>,
+}
+
+impl BloomFilter {
+    /// Returns an empty [`BloomFilter`] of `num_bytes`, rounded up to a multiple of 32 bytes.
+    pub fn new(num_bytes: usize) -> Self {
+        let num_bytes = num_bytes.max(MIN_NUM_BYTES);
+        let num_bytes = num_bytes.div_ceil(MIN_NUM_BYTES) * MIN_NUM_BYTES;
+        Self {
+            bitset: vec![0; num_bytes],
+        }
+    }
+
+    /// Returns an empty [`BloomFilter`] sized according to `options`.
+    /// # Error
+    /// Errors iff the options' false-positive probability is not in `(0, 1)`.
+    pub fn try_from_options(options: &BloomFilterOptions) -> Result<Self> {
+        optimal_num_of_bytes(options.num_distinct_values, options.fpp).map(Self::new)
+    }
+
+    /// Returns a [`BloomFilter`] from a bitset, e.g. read from a file.
+    /// # Error
+    /// Errors iff the bitset's length is not a non-zero multiple of 32 bytes.
+    pub fn try_from_bitset(bitset: Vec<u8>) -> Result<Self> {
+        if bitset.is_empty() || !bitset.len().is_multiple_of(MIN_NUM_BYTES) {
+            return Err(Error::OutOfSpec(format!(
+                "The bitset of a split-block bloom filter must be a non-zero multiple of 32 bytes; got {} bytes",
+                bitset.len()
+            )));
+        }
+        Ok(Self { bitset })
+    }
+
+    /// The bitset of this filter
+    pub fn bitset(&self) -> &[u8] {
+        &self.bitset
+    }
+
+    /// Inserts a hash, as returned by [`hash_byte`] or [`hash_native`].
+    #[inline]
+    pub fn insert_hash(&mut self, hash: u64) {
+        insert(&mut self.bitset, hash)
+    }
+
+    /// Inserts a (`BYTE_ARRAY` or `FIXED_LEN_BYTE_ARRAY`) value.
+    #[inline]
+    pub fn insert(&mut self, value: &[u8]) {
+        self.insert_hash(hash_byte(value))
+    }
+
+    /// Inserts a value of a [`NativeType`].
+    #[inline]
+    pub fn insert_native<T: NativeType>(&mut self, value: T) {
+        self.insert_hash(hash_native(value))
+    }
+
+    /// Returns whether `hash` may have been inserted.
+    #[inline]
+    pub fn might_contain_hash(&self, hash: u64) -> bool {
+        is_in_set(&self.bitset, hash)
+    }
+
+    /// Returns whether a (`BYTE_ARRAY` or `FIXED_LEN_BYTE_ARRAY`) value may have been inserted.
+    /// `false` means that the value was certainly not inserted.
+    #[inline]
+    pub fn might_contain(&self, value: &[u8]) -> bool {
+        self.might_contain_hash(hash_byte(value))
+    }
+
+    /// Returns whether a value of a [`NativeType`] may have been inserted.
+    #[inline]
+    pub fn might_contain_native<T: NativeType>(&self, value: T) -> bool {
+        self.might_contain_hash(hash_native(value))
+    }
+}
@@ -1,11 +1,18 @@
-//! API to read and use bloom filters
+//! API to read, write and use bloom filters
+mod builder;
+mod filter;
 mod hash;
 mod read;
 mod split_block;
+mod write;
 
+pub(crate) use builder::try_new_builder;
+pub use builder::BloomFilterBuilder;
+pub use filter::{optimal_num_of_bytes, BloomFilter};
 pub use hash::{hash_byte, hash_native};
-pub use read::read;
+pub use read::{read, read_bloom_filter};
 pub use split_block::{insert, is_in_set};
+pub use write::write;
 
 #[cfg(test)]
 mod tests {
@@ -68,4 +75,72 @@ mod tests {
         ];
         assert_eq!(bitset, expected);
     }
+
+    #[test]
+    fn optimal_size() {
+        assert_eq!(optimal_num_of_bytes(0, 0.01).unwrap(), 32);
+        // ~1211 bytes, rounded up to a power of two
+        let size = optimal_num_of_bytes(1000, 0.01).unwrap();
+        assert_eq!(size, 2048);
+        assert!(optimal_num_of_bytes(1000, 0.001).unwrap() >= size);
+        assert!(optimal_num_of_bytes(1000, 0.0).is_err());
+        assert!(optimal_num_of_bytes(1000, 1.0).is_err());
+    }
+
+    #[test]
+    fn filter_write_read() -> crate::error::Result<()> {
+        let mut filter = BloomFilter::new(1024);
+        for a in 0..100i32 {
+            filter.insert_native(a);
+            filter.insert(format!("a{}", a).as_bytes());
+        }
+        for a in 0..100i32 {
+            assert!(filter.might_contain_native(a));
+            assert!(filter.might_contain(format!("a{}", a).as_bytes()));
+        }
+
+        let mut buffer = vec![];
+        let written = write(&mut buffer, filter.bitset())?;
+        assert_eq!(written as usize, buffer.len());
+        assert!(buffer.ends_with(filter.bitset()));
+        Ok(())
+    }
+
+    #[test]
+    fn empty_filter() {
+        // e.g. the filter of a column whose values are all null
+        let filter = BloomFilter::try_from_options(&crate::write::BloomFilterOptions {
+            fpp: 0.01,
+            num_distinct_values: 0,
+        })
+        .unwrap();
+        assert!(filter.bitset().iter().all(|x| *x == 0));
+        assert!((0..1000i64).all(|x| !filter.might_contain_native(x)));
+        assert!(!filter.might_contain(b""));
+
+        let read = BloomFilter::try_from_bitset(filter.bitset().to_vec()).unwrap();
+        assert_eq!(read, filter);
+    }
+
+    #[test]
+    fn more_values_than_sized() {
+        let mut filter = BloomFilter::try_from_options(&crate::write::BloomFilterOptions {
+            fpp: 0.01,
+            num_distinct_values: 100,
+        })
+        .unwrap();
+        for a in 0..100_000i64 {
+            filter.insert_native(a);
+        }
+        // the false-positive rate exceeds `fpp`, but there are no false negatives
+        assert!((0..100_000i64).all(|x| filter.might_contain_native(x)));
+        assert!(filter.might_contain_native(-1i64));
+    }
+
+    #[test]
+    fn invalid_bitset() {
+        assert!(BloomFilter::try_from_bitset(vec![]).is_err());
+        assert!(BloomFilter::try_from_bitset(vec![0; 33]).is_err());
+        assert_eq!(BloomFilter::new(33).bitset().len(), 64);
+    }
 }
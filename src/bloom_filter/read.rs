@@ -7,10 +7,13 @@ use parquet_format_async_temp::{
 
 use crate::{error::Error, metadata::ColumnChunkMetaData};
 
+use super::BloomFilter;
+
 /// Reads the bloom filter associated to [`ColumnChunkMetaData`] into `bitset`.
 /// Results in an empty `bitset` if there is no associated bloom filter or the algorithm is not supported.
 /// # Error
-/// Errors if the column contains no metadata or the filter can't be read or deserialized.
+/// Errors if the column contains no metadata, the filter can't be read or deserialized, or the
+/// filter is longer than the remaining of `reader`.
 pub fn read<R: Read + Seek>(
     column_metadata: &ColumnChunkMetaData,
     mut reader: &mut R,
@@ -38,14 +41,56 @@ pub fn read<R: Read + Seek>(
         bitset.clear();
         return Ok(());
     }
+    let num_bytes = filter_length(header.num_bytes, reader)?;
+
     // read bitset
-    if header.num_bytes as usize > bitset.capacity() {
-        *bitset = vec![0; header.num_bytes as usize]
+    if num_bytes > bitset.capacity() {
+        *bitset = vec![0; num_bytes]
     } else {
         bitset.clear();
-        bitset.resize(header.num_bytes as usize, 0); // populate with zeros
+        bitset.resize(num_bytes, 0); // populate with zeros
     }
 
     reader.read_exact(bitset)?;
     Ok(())
 }
+
+/// Returns `num_bytes` as the length of the bitset that starts at the current position of
+/// `reader`, so that a corrupted header does not cause an arbitrarily large allocation.
+fn filter_length<R: Read + Seek>(num_bytes: i32, reader: &mut R) -> Result<usize, Error> {
+    let num_bytes: u64 = num_bytes.try_into().map_err(|_| {
+        Error::OutOfSpec(format!(
+            "The bloom filter's length must be non-negative, but it is {}",
+            num_bytes
+        ))
+    })?;
+
+    let position = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(position))?;
+
+    let remaining = end.saturating_sub(position);
+    if num_bytes > remaining {
+        return Err(Error::OutOfSpec(format!(
+            "The bloom filter has {} bytes, but only {} bytes remain in the file",
+            num_bytes, remaining
+        )));
+    }
+    Ok(num_bytes as usize)
+}
+
+/// Reads the bloom filter associated to [`ColumnChunkMetaData`].
+/// Returns `None` if there is no associated bloom filter or the algorithm is not supported.
+/// # Error
+/// Errors if the column contains no metadata or the filter can't be read or deserialized.
+pub fn read_bloom_filter<R: Read + Seek>(
+    column_metadata: &ColumnChunkMetaData,
+    reader: &mut R,
+) -> Result<Option<BloomFilter>, Error> {
+    let mut bitset = vec![];
+    read(column_metadata, reader, &mut bitset)?;
+    if bitset.is_empty() {
+        return Ok(None);
+    }
+    BloomFilter::try_from_bitset(bitset).map(Some)
+}
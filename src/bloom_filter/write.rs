@@ -0,0 +1,27 @@
+use std::io::Write;
+
+use parquet_format_async_temp::{
+    thrift::protocol::{TCompactOutputProtocol, TOutputProtocol},
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader,
+    SplitBlockAlgorithm, Uncompressed, XxHash,
+};
+
+use crate::error::Result;
+
+/// Writes the header and the bitset of a split-block bloom filter with xxh64 hashing,
+/// returning the number of bytes written.
+pub fn write<W: Write>(writer: &mut W, bitset: &[u8]) -> Result<u64> {
+    let header = BloomFilterHeader::new(
+        bitset.len() as i32,
+        BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+        BloomFilterHash::XXHASH(XxHash {}),
+        BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+    );
+
+    let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+    let header_len = header.write_to_out_protocol(&mut protocol)? as u64;
+    protocol.flush()?;
+
+    writer.write_all(bitset)?;
+    Ok(header_len + bitset.len() as u64)
+}
@@ -1,12 +1,139 @@
 //! Functionality to compress and decompress data according to the parquet specification
-pub use super::parquet_bridge::{Compression, CompressionOptions, ZstdLevel};
+pub use super::parquet_bridge::{
+    BrotliLevel, Compression, CompressionOptions, GzipLevel, ZstdLevel,
+};
 
 use crate::error::{Error, Result};
 
+/// Returns the codecs that this crate can compress and decompress, which depends on
+/// the active cargo features (see [`Compression::is_supported`]).
+pub fn available_codecs() -> Vec<Compression> {
+    [
+        Compression::Uncompressed,
+        Compression::Snappy,
+        Compression::Gzip,
+        Compression::Lzo,
+        Compression::Brotli,
+        Compression::Lz4,
+        Compression::Zstd,
+        Compression::Lz4Raw,
+    ]
+    .into_iter()
+    .filter(|compression| compression.is_supported())
+    .collect()
+}
+
+/// A user-defined codec, registered on [`Compressor`](crate::write::Compressor) and
+/// [`BasicDecompressor`](crate::read::BasicDecompressor) to (de)compress pages whose
+/// [`Compression`] is [`CustomCodec::compression`], instead of this crate's implementation of it.
+///
+/// Dictionary pages are decompressed by the page readers: to read them, the codec must also be
+/// registered on their [`ReadOptions::codec`](crate::read::ReadOptions::codec).
+pub trait CustomCodec: std::fmt::Debug + Send + Sync {
+    /// The [`Compression`] declared in the metadata of pages (de)compressed by this codec.
+    fn compression(&self) -> Compression;
+
+    /// Compresses `input_buf`, appending the result to `output_buf`.
+    fn compress(&self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()>;
+
+    /// Decompresses `input_buf` into `output_buf`, returning the number of bytes written.
+    fn decompress(&self, input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize>;
+}
+
+/// Returns `codec` if it (de)compresses `compression`.
+pub(crate) fn matching_codec(
+    codec: Option<&dyn CustomCodec>,
+    compression: Compression,
+) -> Option<&dyn CustomCodec> {
+    codec.filter(|codec| codec.compression() == compression)
+}
+
+/// A [`CustomCodec`] that (de)compresses zstd pages with a zstd dictionary, e.g. trained on
+/// samples of similar pages, which improves the compression of small pages.
+///
+/// The parquet format does not store dictionaries: the dictionary used to write a file must be
+/// stored elsewhere (e.g. in its key-value metadata) and be given to the reader, via
+/// [`BasicDecompressor::new_with_codec`](crate::read::BasicDecompressor::new_with_codec).
+#[cfg(feature = "zstd")]
+pub struct ZstdDictionaryCodec {
+    id: u32,
+    encoder: zstd::dict::EncoderDictionary<'static>,
+    decoder: zstd::dict::DecoderDictionary<'static>,
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdDictionaryCodec {
+    /// Returns a new [`ZstdDictionaryCodec`] that compresses with `dictionary` at `level`
+    /// (zstd's default when `None`).
+    pub fn new(dictionary: &[u8], level: Option<ZstdLevel>) -> Self {
+        let level = level
+            .map(|v| v.compression_level())
+            .unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL);
+        Self {
+            id: zstd::zstd_safe::get_dict_id_from_dict(dictionary),
+            encoder: zstd::dict::EncoderDictionary::copy(dictionary, level),
+            decoder: zstd::dict::DecoderDictionary::copy(dictionary),
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl std::fmt::Debug for ZstdDictionaryCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZstdDictionaryCodec")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl CustomCodec for ZstdDictionaryCodec {
+    fn compression(&self) -> Compression {
+        Compression::Zstd
+    }
+
+    fn compress(&self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+        let mut encoder = zstd::bulk::Compressor::with_prepared_dictionary(&self.encoder)?;
+        let start = output_buf.len();
+        output_buf.resize(start + zstd::zstd_safe::compress_bound(input_buf.len()), 0);
+        let written = encoder.compress_to_buffer(input_buf, &mut output_buf[start..])?;
+        output_buf.truncate(start + written);
+        Ok(())
+    }
+
+    fn decompress(&self, input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize> {
+        let id = zstd::zstd_safe::get_dict_id_from_frame(input_buf);
+        if id != 0 && id != self.id {
+            return Err(Error::InvalidParameter(format!(
+                "The page was compressed with the zstd dictionary {}, but the codec's dictionary is {}",
+                id, self.id
+            )));
+        }
+        let mut decoder = zstd::bulk::Decompressor::with_prepared_dictionary(&self.decoder)?;
+        Ok(decoder.decompress_to_buffer(input_buf, output_buf)?)
+    }
+}
+
+/// Errors if `input_buf` was compressed with a zstd dictionary, which this crate's zstd
+/// decompression does not have.
+#[cfg(feature = "zstd")]
+fn check_no_zstd_dictionary(input_buf: &[u8]) -> Result<()> {
+    match zstd::zstd_safe::get_dict_id_from_frame(input_buf) {
+        0 => Ok(()),
+        id => Err(Error::InvalidParameter(format!(
+            "The page was compressed with the zstd dictionary {}, which must be given to decompress it (see `ZstdDictionaryCodec`)",
+            id
+        ))),
+    }
+}
+
 /// Compresses data stored in slice `input_buf` and writes the compressed result
 /// to `output_buf`.
 /// Note that you'll need to call `clear()` before reusing the same `output_buf`
 /// across different `compress` calls.
+///
+/// The zstd encoder is kept per thread and re-used across calls, which makes compressing many
+/// small pages faster.
 pub fn compress(
     compression: CompressionOptions,
     input_buf: &[u8],
@@ -14,35 +141,35 @@ pub fn compress(
 ) -> Result<()> {
     match compression {
         #[cfg(feature = "brotli")]
-        CompressionOptions::Brotli => {
+        CompressionOptions::Brotli(level) => {
             use std::io::Write;
             const BROTLI_DEFAULT_BUFFER_SIZE: usize = 4096;
-            const BROTLI_DEFAULT_COMPRESSION_QUALITY: u32 = 1; // supported levels 0-9
-            const BROTLI_DEFAULT_LG_WINDOW_SIZE: u32 = 22; // recommended between 20-22
+            let level = level.unwrap_or_default();
 
             let mut encoder = brotli::CompressorWriter::new(
                 output_buf,
                 BROTLI_DEFAULT_BUFFER_SIZE,
-                BROTLI_DEFAULT_COMPRESSION_QUALITY,
-                BROTLI_DEFAULT_LG_WINDOW_SIZE,
+                level.compression_level(),
+                level.lg_window_size(),
             );
             encoder.write_all(input_buf)?;
             encoder.flush().map_err(|e| e.into())
         }
         #[cfg(not(feature = "brotli"))]
-        CompressionOptions::Brotli => Err(Error::FeatureNotActive(
+        CompressionOptions::Brotli(_) => Err(Error::FeatureNotActive(
             crate::error::Feature::Brotli,
             "compress to brotli".to_string(),
         )),
         #[cfg(feature = "gzip")]
-        CompressionOptions::Gzip => {
+        CompressionOptions::Gzip(level) => {
             use std::io::Write;
-            let mut encoder = flate2::write::GzEncoder::new(output_buf, Default::default());
+            let level = level.unwrap_or_default();
+            let mut encoder = flate2::write::GzEncoder::new(output_buf, level.into());
             encoder.write_all(input_buf)?;
             encoder.try_finish().map_err(|e| e.into())
         }
         #[cfg(not(feature = "gzip"))]
-        CompressionOptions::Gzip => Err(Error::FeatureNotActive(
+        CompressionOptions::Gzip(_) => Err(Error::FeatureNotActive(
             crate::error::Feature::Gzip,
             "compress to gzip".to_string(),
         )),
@@ -77,7 +204,7 @@ pub fn compress(
         CompressionOptions::Lz4Raw => {
             let output_buf_len = output_buf.len();
             let required_len = lz4::block::compress_bound(input_buf.len())?;
-            output_buf.resize(required_len, 0);
+            output_buf.resize(output_buf_len + required_len, 0);
             let size = lz4::block::compress_to_buffer(
                 input_buf,
                 None,
@@ -94,17 +221,11 @@ pub fn compress(
         )),
         #[cfg(feature = "zstd")]
         CompressionOptions::Zstd(level) => {
-            use std::io::Write;
             let level = level
                 .map(|v| v.compression_level())
                 .unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL);
 
-            let mut encoder = zstd::Encoder::new(output_buf, level)?;
-            encoder.write_all(input_buf)?;
-            match encoder.finish() {
-                Ok(_) => Ok(()),
-                Err(e) => Err(e.into()),
-            }
+            compress_zstd(level, input_buf, output_buf)
         }
         #[cfg(not(feature = "zstd"))]
         CompressionOptions::Zstd(_) => Err(Error::FeatureNotActive(
@@ -121,17 +242,46 @@ pub fn compress(
     }
 }
 
+#[cfg(feature = "zstd")]
+thread_local! {
+    // zstd's encoder is re-used across calls to `compress` on the same thread, so that
+    // compressing many (small) pages does not re-initialize it for every page.
+    static ZSTD_COMPRESSOR: std::cell::RefCell<Option<zstd::bulk::Compressor<'static>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+#[cfg(feature = "zstd")]
+fn compress_zstd(level: i32, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+    ZSTD_COMPRESSOR.with(|compressor| {
+        let mut compressor = compressor.borrow_mut();
+        let compressor = match compressor.as_mut() {
+            Some(compressor) => compressor,
+            None => compressor.insert(zstd::bulk::Compressor::new(level)?),
+        };
+        compressor.set_compression_level(level)?;
+
+        let start = output_buf.len();
+        output_buf.resize(start + zstd::zstd_safe::compress_bound(input_buf.len()), 0);
+        let written = compressor.compress_to_buffer(input_buf, &mut output_buf[start..])?;
+        output_buf.truncate(start + written);
+        Ok(())
+    })
+}
+
 /// Decompresses data stored in slice `input_buf` and writes output to `output_buf`.
-/// Returns the total number of bytes written.
-pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [u8]) -> Result<()> {
+/// Returns the total number of bytes written, which callers should compare against the
+/// expected decompressed size: bytes of `output_buf` past it were not written.
+pub fn decompress(
+    compression: Compression,
+    input_buf: &[u8],
+    output_buf: &mut [u8],
+) -> Result<usize> {
     match compression {
         #[cfg(feature = "brotli")]
         Compression::Brotli => {
-            use std::io::Read;
             const BROTLI_DEFAULT_BUFFER_SIZE: usize = 4096;
-            brotli::Decompressor::new(input_buf, BROTLI_DEFAULT_BUFFER_SIZE)
-                .read_exact(output_buf)
-                .map_err(|e| e.into())
+            let decoder = brotli::Decompressor::new(input_buf, BROTLI_DEFAULT_BUFFER_SIZE);
+            read_into(decoder, output_buf)
         }
         #[cfg(not(feature = "brotli"))]
         Compression::Brotli => Err(Error::FeatureNotActive(
@@ -140,9 +290,8 @@ pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [
         )),
         #[cfg(feature = "gzip")]
         Compression::Gzip => {
-            use std::io::Read;
-            let mut decoder = flate2::read::GzDecoder::new(input_buf);
-            decoder.read_exact(output_buf).map_err(|e| e.into())
+            let decoder = flate2::read::GzDecoder::new(input_buf);
+            read_into(decoder, output_buf)
         }
         #[cfg(not(feature = "gzip"))]
         Compression::Gzip => Err(Error::FeatureNotActive(
@@ -154,11 +303,16 @@ pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [
             use snap::raw::{decompress_len, Decoder};
 
             let len = decompress_len(input_buf)?;
-            assert!(len <= output_buf.len());
+            if len > output_buf.len() {
+                return Err(Error::OutOfSpec(format!(
+                    "The snappy stream declares {} bytes but the output has {} bytes",
+                    len,
+                    output_buf.len()
+                )));
+            }
             Decoder::new()
                 .decompress(input_buf, output_buf)
                 .map_err(|e| e.into())
-                .map(|_| ())
         }
         #[cfg(not(feature = "snappy"))]
         Compression::Snappy => Err(Error::FeatureNotActive(
@@ -166,13 +320,12 @@ pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [
             "decompress with snappy".to_string(),
         )),
         #[cfg(all(feature = "lz4_flex", not(feature = "lz4")))]
-        Compression::Lz4Raw => lz4_flex::block::decompress_into(input_buf, output_buf)
-            .map(|_| {})
-            .map_err(|e| e.into()),
+        Compression::Lz4Raw => {
+            lz4_flex::block::decompress_into(input_buf, output_buf).map_err(|e| e.into())
+        }
         #[cfg(feature = "lz4")]
         Compression::Lz4Raw => {
             lz4::block::decompress_to_buffer(input_buf, Some(output_buf.len() as i32), output_buf)
-                .map(|_| {})
                 .map_err(|e| e.into())
         }
         #[cfg(all(not(feature = "lz4"), not(feature = "lz4_flex")))]
@@ -180,11 +333,32 @@ pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [
             crate::error::Feature::Lz4,
             "decompress with lz4".to_string(),
         )),
+        #[cfg(any(feature = "lz4_flex", feature = "lz4"))]
+        Compression::Lz4 => match try_decompress_hadoop(input_buf, output_buf) {
+            Ok(()) => Ok(output_buf.len()),
+            // some writers (e.g. older versions of parquet-cpp) use the `LZ4` codec for
+            // raw LZ4 blocks without the Hadoop framing. Fall back to it, like parquet-cpp does.
+            Err(_) => {
+                let size = lz4_decompress_to_buffer(input_buf, output_buf)?;
+                if size == output_buf.len() {
+                    Ok(size)
+                } else {
+                    Err(Error::OutOfSpec(
+                        "The decompressed size of a lz4 page does not match its header".to_string(),
+                    ))
+                }
+            }
+        },
+        #[cfg(all(not(feature = "lz4"), not(feature = "lz4_flex")))]
+        Compression::Lz4 => Err(Error::FeatureNotActive(
+            crate::error::Feature::Lz4,
+            "decompress with legacy lz4".to_string(),
+        )),
         #[cfg(feature = "zstd")]
         Compression::Zstd => {
-            use std::io::Read;
-            let mut decoder = zstd::Decoder::new(input_buf)?;
-            decoder.read_exact(output_buf).map_err(|e| e.into())
+            check_no_zstd_dictionary(input_buf)?;
+            let decoder = zstd::Decoder::new(input_buf)?;
+            read_into(decoder, output_buf)
         }
         #[cfg(not(feature = "zstd"))]
         Compression::Zstd => Err(Error::FeatureNotActive(
@@ -201,6 +375,257 @@ pub fn decompress(compression: Compression, input_buf: &[u8], output_buf: &mut [
     }
 }
 
+/// Decompresses data stored in slice `input_buf` into `output_buf`, whose contents are replaced
+/// by the decompressed data. Returns the number of bytes written.
+/// Contrarily to [`decompress`], this re-uses the allocation of `output_buf`, which allows
+/// the same buffer to be used across multiple decompressions.
+/// # Errors
+/// Errors with [`Error::OutOfSpec`] if the decompressed size differs from `uncompressed_size`.
+pub fn decompress_into(
+    compression: Compression,
+    input_buf: &[u8],
+    uncompressed_size: usize,
+    output_buf: &mut Vec<u8>,
+) -> Result<usize> {
+    output_buf.clear();
+    let written = match compression {
+        #[cfg(feature = "brotli")]
+        Compression::Brotli => {
+            const BROTLI_DEFAULT_BUFFER_SIZE: usize = 4096;
+            let decoder = brotli::Decompressor::new(input_buf, BROTLI_DEFAULT_BUFFER_SIZE);
+            read_to_end(decoder, uncompressed_size, output_buf)?
+        }
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => {
+            let decoder = flate2::read::GzDecoder::new(input_buf);
+            read_to_end(decoder, uncompressed_size, output_buf)?
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            check_no_zstd_dictionary(input_buf)?;
+            let decoder = zstd::Decoder::new(input_buf)?;
+            read_to_end(decoder, uncompressed_size, output_buf)?
+        }
+        #[cfg(feature = "snappy")]
+        Compression::Snappy => {
+            use snap::raw::{decompress_len, Decoder};
+
+            // the length is declared by the snappy stream, so a mismatch is known before allocating
+            let len = decompress_len(input_buf)?;
+            if len != uncompressed_size {
+                return Err(decompressed_size_mismatch(len, uncompressed_size));
+            }
+            output_buf.resize(len, 0);
+            Decoder::new().decompress(input_buf, output_buf)?
+        }
+        #[cfg(any(feature = "lz4_flex", feature = "lz4"))]
+        Compression::Lz4Raw => {
+            output_buf.resize(uncompressed_size, 0);
+            // lz4 does not distinguish a block larger than the declared size from a corrupt one
+            lz4_decompress_to_buffer(input_buf, output_buf).map_err(|e| {
+                Error::OutOfSpec(format!(
+                    "The page could not be decompressed into its declared {} bytes: {}",
+                    uncompressed_size, e
+                ))
+            })?
+        }
+        _ => {
+            output_buf.resize(uncompressed_size, 0);
+            decompress(compression, input_buf, output_buf)?
+        }
+    };
+    if written != uncompressed_size {
+        return Err(decompressed_size_mismatch(written, uncompressed_size));
+    }
+    Ok(written)
+}
+
+/// A [`std::io::Read`] of the decompressed bytes of a compressed buffer. Contrarily to
+/// [`decompress`], the bytes are decompressed incrementally, as they are read, so that a
+/// buffer of the whole decompressed data is not required.
+///
+/// Only codecs with a streaming format are supported: brotli, gzip and zstd (and uncompressed
+/// buffers).
+pub struct DecompressReader<'a> {
+    inner: Box<dyn std::io::Read + Send + 'a>,
+}
+
+impl<'a> DecompressReader<'a> {
+    /// Returns a new [`DecompressReader`] of `input_buf`, compressed with `compression`.
+    /// # Errors
+    /// Errors iff `compression` cannot be decompressed incrementally or its feature is not active
+    pub fn try_new(compression: Compression, input_buf: &'a [u8]) -> Result<Self> {
+        let inner: Box<dyn std::io::Read + Send + 'a> = match compression {
+            Compression::Uncompressed => Box::new(input_buf),
+            #[cfg(feature = "brotli")]
+            Compression::Brotli => {
+                const BROTLI_DEFAULT_BUFFER_SIZE: usize = 4096;
+                Box::new(brotli::Decompressor::new(
+                    input_buf,
+                    BROTLI_DEFAULT_BUFFER_SIZE,
+                ))
+            }
+            #[cfg(not(feature = "brotli"))]
+            Compression::Brotli => {
+                return Err(Error::FeatureNotActive(
+                    crate::error::Feature::Brotli,
+                    "decompress with brotli".to_string(),
+                ))
+            }
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Box::new(flate2::read::GzDecoder::new(input_buf)),
+            #[cfg(not(feature = "gzip"))]
+            Compression::Gzip => {
+                return Err(Error::FeatureNotActive(
+                    crate::error::Feature::Gzip,
+                    "decompress with gzip".to_string(),
+                ))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                check_no_zstd_dictionary(input_buf)?;
+                Box::new(zstd::Decoder::with_buffer(input_buf)?)
+            }
+            #[cfg(not(feature = "zstd"))]
+            Compression::Zstd => {
+                return Err(Error::FeatureNotActive(
+                    crate::error::Feature::Zstd,
+                    "decompress with zstd".to_string(),
+                ))
+            }
+            _ => {
+                return Err(Error::InvalidParameter(format!(
+                    "Compression {:?} cannot be decompressed incrementally",
+                    compression
+                )))
+            }
+        };
+        Ok(Self { inner })
+    }
+}
+
+impl std::io::Read for DecompressReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+/// Reads `reader` into `output_buf` until either is exhausted. Returns the number of bytes read.
+/// # Errors
+/// Errors if `reader` has more bytes than `output_buf`.
+#[cfg(any(feature = "brotli", feature = "gzip", feature = "zstd"))]
+fn read_into<R: std::io::Read>(mut reader: R, output_buf: &mut [u8]) -> Result<usize> {
+    let mut written = 0;
+    while written < output_buf.len() {
+        match reader.read(&mut output_buf[written..]) {
+            Ok(0) => return Ok(written),
+            Ok(read) => written += read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    // the output is full: the reader must be exhausted
+    let mut extra = [0u8; 1];
+    loop {
+        match reader.read(&mut extra) {
+            Ok(0) => return Ok(written),
+            Ok(_) => {
+                return Err(Error::OutOfSpec(format!(
+                    "The decompressed data is larger than the output of {} bytes",
+                    output_buf.len()
+                )))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Reads `reader` to its end into `output_buf`, without reading more than one byte
+/// past `uncompressed_size`. Returns the number of bytes read.
+#[cfg(any(feature = "brotli", feature = "gzip", feature = "zstd"))]
+fn read_to_end<R: std::io::Read>(
+    reader: R,
+    uncompressed_size: usize,
+    output_buf: &mut Vec<u8>,
+) -> Result<usize> {
+    use std::io::Read;
+    output_buf.reserve(uncompressed_size);
+    reader
+        .take(uncompressed_size as u64 + 1)
+        .read_to_end(output_buf)
+        .map_err(|e| e.into())
+}
+
+pub(crate) fn decompressed_size_mismatch(written: usize, uncompressed_size: usize) -> Error {
+    Error::OutOfSpec(format!(
+        "The page decompressed to {} bytes but its header declares {} bytes",
+        written, uncompressed_size
+    ))
+}
+
+/// Decompresses a single raw LZ4 block into `output_buf`, returning the number of bytes written.
+#[cfg(all(feature = "lz4_flex", not(feature = "lz4")))]
+fn lz4_decompress_to_buffer(input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize> {
+    lz4_flex::block::decompress_into(input_buf, output_buf).map_err(|e| e.into())
+}
+
+/// Decompresses a single raw LZ4 block into `output_buf`, returning the number of bytes written.
+#[cfg(feature = "lz4")]
+fn lz4_decompress_to_buffer(input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize> {
+    lz4::block::decompress_to_buffer(input_buf, Some(output_buf.len() as i32), output_buf)
+        .map_err(|e| e.into())
+}
+
+/// Decompresses data compressed with Hadoop's `Lz4Codec`, used by writers of the legacy `LZ4`
+/// codec. Hadoop frames the data as a sequence of blocks, each prefixed by
+/// its decompressed and compressed size as big-endian `u32`s.
+#[cfg(any(feature = "lz4_flex", feature = "lz4"))]
+fn try_decompress_hadoop(input_buf: &[u8], output_buf: &mut [u8]) -> Result<()> {
+    const SIZE_U32: usize = std::mem::size_of::<u32>();
+    const PREFIX_LEN: usize = SIZE_U32 * 2;
+
+    let mut input = input_buf;
+    let mut output = output_buf;
+    while input.len() >= PREFIX_LEN {
+        let expected_decompressed_size = u32::from_be_bytes(input[..SIZE_U32].try_into()?) as usize;
+        let expected_compressed_size =
+            u32::from_be_bytes(input[SIZE_U32..PREFIX_LEN].try_into()?) as usize;
+        input = &input[PREFIX_LEN..];
+
+        if input.len() < expected_compressed_size {
+            return Err(Error::OutOfSpec(
+                "The compressed size of a Hadoop lz4 block is larger than its buffer".to_string(),
+            ));
+        }
+        if output.len() < expected_decompressed_size {
+            return Err(Error::OutOfSpec(
+                "The decompressed size of a Hadoop lz4 block is larger than its buffer".to_string(),
+            ));
+        }
+
+        let decompressed_size = lz4_decompress_to_buffer(
+            &input[..expected_compressed_size],
+            &mut output[..expected_decompressed_size],
+        )?;
+        if decompressed_size != expected_decompressed_size {
+            return Err(Error::OutOfSpec(
+                "The decompressed size of a Hadoop lz4 block does not match its header".to_string(),
+            ));
+        }
+        input = &input[expected_compressed_size..];
+        output = &mut output[expected_decompressed_size..];
+    }
+
+    if input.is_empty() && output.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::OutOfSpec(
+            "Hadoop lz4 blocks are inconsistent with the page's sizes".to_string(),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,26 +654,372 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "snappy")]
+    #[test]
+    fn test_decompress_into_dirty_buffer() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        let mut compressed = vec![];
+        compress(CompressionOptions::Snappy, &data, &mut compressed).unwrap();
+
+        // larger and smaller buffers with existing data
+        for mut buffer in [vec![1u8; 20000], vec![1u8; 10], vec![]] {
+            let capacity = buffer.capacity();
+            decompress_into(Compression::Snappy, &compressed, data.len(), &mut buffer).unwrap();
+            assert_eq!(data, buffer);
+            if capacity >= data.len() {
+                assert_eq!(buffer.capacity(), capacity);
+            }
+        }
+    }
+
+    #[cfg(all(
+        feature = "snappy",
+        feature = "gzip",
+        feature = "brotli",
+        any(feature = "lz4", feature = "lz4_flex")
+    ))]
+    #[test]
+    fn test_decompress_into_reuse() {
+        let mut buffer = vec![];
+        for c in [
+            CompressionOptions::Snappy,
+            CompressionOptions::Gzip(None),
+            CompressionOptions::Brotli(None),
+            CompressionOptions::Lz4Raw,
+        ] {
+            for size in [10000, 10, 100000] {
+                let data = (0..size).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+                let mut compressed = vec![];
+                compress(c, &data, &mut compressed).unwrap();
+
+                let written = decompress_into(c.into(), &compressed, size, &mut buffer).unwrap();
+                assert_eq!(written, size);
+                assert_eq!(data, buffer);
+            }
+        }
+    }
+
+    #[cfg(all(
+        feature = "snappy",
+        feature = "gzip",
+        feature = "brotli",
+        any(feature = "lz4", feature = "lz4_flex")
+    ))]
+    #[test]
+    fn test_decompress_into_wrong_size() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        let mut buffer = vec![];
+        for c in [
+            CompressionOptions::Snappy,
+            CompressionOptions::Gzip(None),
+            CompressionOptions::Brotli(None),
+            CompressionOptions::Lz4Raw,
+        ] {
+            let mut compressed = vec![];
+            compress(c, &data, &mut compressed).unwrap();
+
+            for size in [data.len() - 1, data.len() + 1] {
+                assert!(matches!(
+                    decompress_into(c.into(), &compressed, size, &mut buffer),
+                    Err(Error::OutOfSpec(_))
+                ));
+            }
+        }
+    }
+
+    #[cfg(all(feature = "snappy", feature = "zstd"))]
+    #[test]
+    fn test_decompress_truncated() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        let mut buffer = vec![];
+        for c in [CompressionOptions::Snappy, CompressionOptions::Zstd(None)] {
+            let mut compressed = vec![];
+            compress(c, &data, &mut compressed).unwrap();
+            compressed.truncate(compressed.len() / 2);
+
+            assert!(decompress_into(c.into(), &compressed, data.len(), &mut buffer).is_err());
+
+            let mut decompressed = vec![0; data.len()];
+            assert!(decompress(c.into(), &compressed, &mut decompressed).is_err());
+        }
+    }
+
+    #[cfg(feature = "snappy")]
+    #[test]
+    fn test_decompress_returns_written() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        let mut compressed = vec![];
+        compress(CompressionOptions::Snappy, &data, &mut compressed).unwrap();
+
+        let mut decompressed = vec![0; data.len() + 10];
+        let written = decompress(Compression::Snappy, &compressed, &mut decompressed).unwrap();
+        assert_eq!(written, data.len());
+
+        let mut decompressed = vec![0; data.len() - 1];
+        assert!(matches!(
+            decompress(Compression::Snappy, &compressed, &mut decompressed),
+            Err(Error::OutOfSpec(_))
+        ));
+    }
+
+    #[cfg(all(feature = "gzip", feature = "brotli", feature = "zstd"))]
+    #[test]
+    fn test_decompress_larger_than_output() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        for c in [
+            CompressionOptions::Gzip(None),
+            CompressionOptions::Brotli(None),
+            CompressionOptions::Zstd(None),
+        ] {
+            let mut compressed = vec![];
+            compress(c, &data, &mut compressed).unwrap();
+
+            let mut decompressed = vec![0; data.len()];
+            let written = decompress(c.into(), &compressed, &mut decompressed).unwrap();
+            assert_eq!(written, data.len());
+
+            let mut decompressed = vec![0; data.len() - 1];
+            assert!(matches!(
+                decompress(c.into(), &compressed, &mut decompressed),
+                Err(Error::OutOfSpec(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_reuse() {
+        for c in [
+            CompressionOptions::Zstd(None),
+            CompressionOptions::Zstd(Some(ZstdLevel::try_new(10).unwrap())),
+            CompressionOptions::Zstd(Some(ZstdLevel::try_new(1).unwrap())),
+        ] {
+            for size in [10000, 0, 10, 100000] {
+                let data = (0..size).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+                let mut compressed = vec![2; 2];
+                compress(c, &data, &mut compressed).unwrap();
+                assert_eq!(compressed[..2], [2, 2]);
+
+                let mut decompressed = vec![0; size];
+                let written = decompress(c.into(), &compressed[2..], &mut decompressed).unwrap();
+                assert_eq!(written, size);
+                assert_eq!(data, decompressed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_available_codecs() {
+        let codecs = available_codecs();
+        assert!(codecs.contains(&Compression::Uncompressed));
+        assert!(!codecs.contains(&Compression::Lzo));
+        assert_eq!(
+            codecs.contains(&Compression::Snappy),
+            cfg!(feature = "snappy")
+        );
+        assert_eq!(codecs.contains(&Compression::Gzip), cfg!(feature = "gzip"));
+        assert_eq!(
+            codecs.contains(&Compression::Brotli),
+            cfg!(feature = "brotli")
+        );
+        assert_eq!(codecs.contains(&Compression::Zstd), cfg!(feature = "zstd"));
+        assert_eq!(
+            codecs.contains(&Compression::Lz4Raw),
+            cfg!(any(feature = "lz4", feature = "lz4_flex"))
+        );
+    }
+
+    #[cfg(not(feature = "snappy"))]
+    #[test]
+    fn test_snappy_not_active() {
+        let error = decompress(Compression::Snappy, &[], &mut []).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::FeatureNotActive(crate::error::Feature::Snappy, _)
+        ));
+        assert!(error.to_string().contains("cargo feature \"snappy\""));
+    }
+
+    #[cfg(feature = "snappy")]
     #[test]
     fn test_codec_snappy() {
         test_codec(CompressionOptions::Snappy);
     }
 
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_codec_gzip_default() {
+        test_codec(CompressionOptions::Gzip(None));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_codec_gzip_low_compression() {
+        test_codec(CompressionOptions::Gzip(Some(
+            GzipLevel::try_new(1).unwrap(),
+        )));
+    }
+
+    #[cfg(feature = "gzip")]
     #[test]
-    fn test_codec_gzip() {
-        test_codec(CompressionOptions::Gzip);
+    fn test_codec_gzip_high_compression() {
+        test_codec(CompressionOptions::Gzip(Some(
+            GzipLevel::try_new(9).unwrap(),
+        )));
     }
 
+    #[cfg(feature = "brotli")]
     #[test]
-    fn test_codec_brotli() {
-        test_codec(CompressionOptions::Brotli);
+    fn test_codec_brotli_default() {
+        test_codec(CompressionOptions::Brotli(None));
     }
 
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_codec_brotli_levels() {
+        // a 64KB page of integers
+        let data = (0..16 * 1024i32)
+            .flat_map(|x| (x % 1000).to_le_bytes())
+            .collect::<Vec<_>>();
+        for level in [
+            BrotliLevel::try_new(2).unwrap(),
+            BrotliLevel::try_new(11).unwrap(),
+            BrotliLevel::try_new_with_window(11, 10).unwrap(),
+        ] {
+            test_roundtrip(CompressionOptions::Brotli(Some(level)), &data);
+        }
+    }
+
+    #[test]
+    fn test_brotli_invalid_level() {
+        assert!(matches!(
+            BrotliLevel::try_new(12),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(BrotliLevel::try_new_with_window(1, 25).is_err());
+        assert!(BrotliLevel::try_new_with_window(1, 9).is_err());
+    }
+
+    #[cfg(any(feature = "lz4", feature = "lz4_flex"))]
     #[test]
     fn test_codec_lz4() {
         test_codec(CompressionOptions::Lz4Raw);
     }
 
+    #[cfg(any(feature = "lz4", feature = "lz4_flex"))]
+    #[test]
+    fn test_codec_lz4_raw_non_empty_buffer() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+
+        // the existing data is larger than the maximum compressed size of `data`
+        let offset = 20000;
+        let mut compressed = vec![2; offset];
+        compress(CompressionOptions::Lz4Raw, &data, &mut compressed).unwrap();
+        assert_eq!(compressed[..offset], vec![2; offset]);
+
+        let mut decompressed = vec![0; data.len()];
+        decompress(
+            Compression::Lz4Raw,
+            &compressed[offset..],
+            &mut decompressed,
+        )
+        .unwrap();
+        assert_eq!(data, decompressed);
+    }
+
+    /// Frames `data` in `num_blocks` blocks following Hadoop's framing of lz4
+    fn hadoop_lz4(data: &[u8], num_blocks: usize) -> Vec<u8> {
+        let mut framed = vec![];
+        let block_size = data.len().div_ceil(num_blocks);
+        for block in data.chunks(block_size) {
+            let mut compressed = vec![];
+            compress(CompressionOptions::Lz4Raw, block, &mut compressed).unwrap();
+            framed.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            framed.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+            framed.extend_from_slice(&compressed);
+        }
+        framed
+    }
+
+    #[cfg(any(feature = "lz4", feature = "lz4_flex"))]
+    #[test]
+    fn test_decompress_lz4_hadoop() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        for num_blocks in [1, 3] {
+            let compressed = hadoop_lz4(&data, num_blocks);
+
+            let mut decompressed = vec![0; data.len()];
+            decompress(Compression::Lz4, &compressed, &mut decompressed).unwrap();
+            assert_eq!(data, decompressed);
+        }
+    }
+
+    #[cfg(any(feature = "lz4", feature = "lz4_flex"))]
+    #[test]
+    fn test_decompress_lz4_fallback_to_raw() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        let mut compressed = vec![];
+        compress(CompressionOptions::Lz4Raw, &data, &mut compressed).unwrap();
+
+        let mut decompressed = vec![0; data.len()];
+        decompress(Compression::Lz4, &compressed, &mut decompressed).unwrap();
+        assert_eq!(data, decompressed);
+    }
+
+    #[cfg(any(feature = "lz4", feature = "lz4_flex"))]
+    #[test]
+    fn test_decompress_lz4_hadoop_wrong_size() {
+        let data = (0..10000).map(|x| (x % 255) as u8).collect::<Vec<_>>();
+        let compressed = hadoop_lz4(&data, 1);
+
+        let mut decompressed = vec![0; data.len() + 1];
+        assert!(decompress(Compression::Lz4, &compressed, &mut decompressed).is_err());
+    }
+
+    fn compressed_len(c: CompressionOptions, data: &[u8]) -> usize {
+        let mut compressed = vec![];
+        compress(c, data, &mut compressed).expect("Error when compressing");
+        compressed.len()
+    }
+
+    /// Returns a compressible sequence of bytes whose compressed size depends on the level
+    fn levels_data() -> Vec<u8> {
+        (0..100000u32)
+            .flat_map(|x| (x.wrapping_mul(2654435761) % 1000).to_le_bytes())
+            .collect()
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_levels_size() {
+        let data = levels_data();
+        let low = compressed_len(
+            CompressionOptions::Zstd(Some(ZstdLevel::try_new(1).unwrap())),
+            &data,
+        );
+        let high = compressed_len(
+            CompressionOptions::Zstd(Some(ZstdLevel::try_new(19).unwrap())),
+            &data,
+        );
+        assert!(high < low);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_levels_size() {
+        let data = levels_data();
+        let low = compressed_len(
+            CompressionOptions::Gzip(Some(GzipLevel::try_new(1).unwrap())),
+            &data,
+        );
+        let high = compressed_len(
+            CompressionOptions::Gzip(Some(GzipLevel::try_new(9).unwrap())),
+            &data,
+        );
+        assert!(high < low);
+    }
+
+    #[cfg(feature = "zstd")]
     #[test]
     fn test_codec_zstd_default() {
         test_codec(CompressionOptions::Zstd(None));
@@ -1,5 +1,7 @@
 use crate::{
-    encoding::{hybrid_rle, plain_byte_array::BinaryIter},
+    encoding::{
+        delta_byte_array, delta_length_byte_array, hybrid_rle, plain_byte_array::BinaryIter,
+    },
     error::Error,
     page::{split_buffer, BinaryPageDict, DataPage},
     parquet_bridge::{Encoding, Repetition},
@@ -14,10 +16,10 @@ pub struct Dictionary<'a> {
 }
 
 impl<'a> Dictionary<'a> {
-    pub fn new(page: &'a DataPage, dict: &'a BinaryPageDict) -> Self {
-        let indexes = utils::dict_indices_decoder(page);
+    pub fn try_new(page: &'a DataPage, dict: &'a BinaryPageDict) -> Result<Self, Error> {
+        let indexes = utils::dict_indices_decoder(page)?;
 
-        Self { indexes, dict }
+        Ok(Self { indexes, dict })
     }
 
     #[inline]
@@ -31,6 +33,17 @@ pub enum BinaryPageState<'a> {
     Required(BinaryIter<'a>),
     RequiredDictionary(Dictionary<'a>),
     OptionalDictionary(utils::DefLevelsDecoder<'a>, Dictionary<'a>),
+    RequiredDelta(delta_length_byte_array::BinaryDecoder<'a>),
+    OptionalDelta(
+        utils::DefLevelsDecoder<'a>,
+        delta_length_byte_array::BinaryDecoder<'a>,
+    ),
+    // boxed, since the decoder of `DeltaByteArray` is much larger than the others
+    RequiredDeltaByteArray(Box<delta_byte_array::BinaryDecoder<'a>>),
+    OptionalDeltaByteArray(
+        utils::DefLevelsDecoder<'a>,
+        Box<delta_byte_array::BinaryDecoder<'a>>,
+    ),
 }
 
 impl<'a> BinaryPageState<'a> {
@@ -41,20 +54,20 @@ impl<'a> BinaryPageState<'a> {
         match (page.encoding(), page.dictionary_page(), is_optional) {
             (Encoding::PlainDictionary | Encoding::RleDictionary, Some(dict), false) => {
                 let dict = dict.as_any().downcast_ref().unwrap();
-                Ok(Self::RequiredDictionary(Dictionary::new(page, dict)))
+                Ok(Self::RequiredDictionary(Dictionary::try_new(page, dict)?))
             }
             (Encoding::PlainDictionary | Encoding::RleDictionary, Some(dict), true) => {
                 let dict = dict.as_any().downcast_ref().unwrap();
 
                 Ok(Self::OptionalDictionary(
-                    utils::DefLevelsDecoder::new(page),
-                    Dictionary::new(page, dict),
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    Dictionary::try_new(page, dict)?,
                 ))
             }
             (Encoding::Plain, _, true) => {
                 let (_, _, values) = split_buffer(page);
 
-                let validity = utils::DefLevelsDecoder::new(page);
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
                 let values = BinaryIter::new(values, None);
 
                 Ok(Self::Optional(validity, values))
@@ -65,6 +78,34 @@ impl<'a> BinaryPageState<'a> {
 
                 Ok(Self::Required(values))
             }
+            (Encoding::DeltaLengthByteArray, _, true) => {
+                let (_, _, values) = split_buffer(page);
+
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+                let values = delta_length_byte_array::BinaryDecoder::try_new(values)?;
+
+                Ok(Self::OptionalDelta(validity, values))
+            }
+            (Encoding::DeltaLengthByteArray, _, false) => {
+                let (_, _, values) = split_buffer(page);
+                let values = delta_length_byte_array::BinaryDecoder::try_new(values)?;
+
+                Ok(Self::RequiredDelta(values))
+            }
+            (Encoding::DeltaByteArray, _, true) => {
+                let (_, _, values) = split_buffer(page);
+
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+                let values = delta_byte_array::BinaryDecoder::try_new(values)?;
+
+                Ok(Self::OptionalDeltaByteArray(validity, Box::new(values)))
+            }
+            (Encoding::DeltaByteArray, _, false) => {
+                let (_, _, values) = split_buffer(page);
+                let values = delta_byte_array::BinaryDecoder::try_new(values)?;
+
+                Ok(Self::RequiredDeltaByteArray(Box::new(values)))
+            }
             _ => Err(Error::General(format!(
                 "Viewing page for encoding {:?} for binary type not supported",
                 page.encoding(),
@@ -1,5 +1,8 @@
 use crate::{
-    encoding::hybrid_rle::BitmapIter,
+    encoding::{
+        get_length,
+        hybrid_rle::{try_plain_bitmap, BitmapIter, HybridRleDecoder},
+    },
     error::Error,
     page::{split_buffer, DataPage},
     parquet_bridge::{Encoding, Repetition},
@@ -12,7 +15,27 @@ use super::utils;
 #[allow(clippy::large_enum_variant)]
 pub enum BooleanPageState<'a> {
     Optional(utils::DefLevelsDecoder<'a>, BitmapIter<'a>),
+    /// The LSB-packed values and their number, see [`try_plain_bitmap`]
     Required(&'a [u8], usize),
+    OptionalRle(utils::DefLevelsDecoder<'a>, HybridRleDecoder<'a>),
+    RequiredRle(HybridRleDecoder<'a>),
+}
+
+/// Returns the runs of the RLE-encoded boolean `values` of a page, which are prefixed
+/// by their length.
+fn rle_runs(values: &[u8]) -> Result<&[u8], Error> {
+    (values.len() >= 4)
+        .then(|| get_length(values) as usize)
+        .and_then(|length| values[4..].get(..length))
+        .ok_or_else(|| {
+            Error::OutOfSpec("The length of RLE-encoded booleans exceeds the page".to_string())
+        })
+}
+
+/// Returns a decoder of the `num_values` RLE-encoded boolean `values` of a page, which are
+/// prefixed by their length.
+pub(crate) fn rle_decoder(values: &[u8], num_values: usize) -> Result<HybridRleDecoder<'_>, Error> {
+    HybridRleDecoder::try_new(rle_runs(values)?, 1, num_values)
 }
 
 impl<'a> BooleanPageState<'a> {
@@ -22,7 +45,7 @@ impl<'a> BooleanPageState<'a> {
 
         match (page.encoding(), page.dictionary_page(), is_optional) {
             (Encoding::Plain, _, true) => {
-                let validity = utils::DefLevelsDecoder::new(page);
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
 
                 let (_, _, values) = split_buffer(page);
                 let values = BitmapIter::new(values, 0, values.len() * 8);
@@ -31,7 +54,22 @@ impl<'a> BooleanPageState<'a> {
             }
             (Encoding::Plain, _, false) => {
                 let (_, _, values) = split_buffer(page);
-                Ok(Self::Required(values, page.num_values()))
+                let (values, length) = try_plain_bitmap(values, page.num_values())?;
+                Ok(Self::Required(values, length))
+            }
+            (Encoding::Rle, _, true) => {
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+
+                let (_, _, values) = split_buffer(page);
+                // the number of non-null values is unknown: it is at most the number of values
+                let values =
+                    HybridRleDecoder::try_new_up_to(rle_runs(values)?, 1, page.num_values())?;
+
+                Ok(Self::OptionalRle(validity, values))
+            }
+            (Encoding::Rle, _, false) => {
+                let (_, _, values) = split_buffer(page);
+                Ok(Self::RequiredRle(rle_decoder(values, page.num_values())?))
             }
             _ => Err(Error::General(format!(
                 "Viewing page for encoding {:?} for boolean type not supported",
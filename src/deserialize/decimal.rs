@@ -0,0 +1,345 @@
+use crate::{
+    encoding::hybrid_rle::BitmapIter,
+    error::{Error, Result},
+    page::DataPage,
+    schema::types::PhysicalType,
+};
+
+use super::{
+    BinaryPageState, DefLevelsDecoder, FixedLenBinaryPageState, HybridEncoded, NativePageState,
+};
+
+/// The largest precision of a decimal that fits in an `i128`
+const MAX_PRECISION: usize = 38;
+
+/// Converts the unscaled value of a decimal, stored as a big-endian two's complement
+/// byte array (`BYTE_ARRAY` or `FIXED_LEN_BYTE_ARRAY`), into an `i128`.
+/// # Errors
+/// Errors iff `bytes` is empty or its value does not fit in an `i128`.
+pub fn decimal_from_be_bytes(bytes: &[u8]) -> Result<i128> {
+    let is_negative = *bytes
+        .first()
+        .ok_or_else(|| Error::OutOfSpec("A decimal must have at least one byte".to_string()))?
+        >= 0x80;
+    let sign_extension = if is_negative { 0xff } else { 0 };
+
+    // leading bytes beyond the 16 bytes of an i128 can only extend the sign
+    let split = bytes.len().saturating_sub(16);
+    let (extension, bytes) = bytes.split_at(split);
+    let fits = extension.iter().all(|x| *x == sign_extension)
+        && (extension.is_empty() || (bytes[0] >= 0x80) == is_negative);
+    if !fits {
+        return Err(Error::OutOfSpec(
+            "The value of a decimal does not fit in an i128".to_string(),
+        ));
+    }
+
+    let mut value = [sign_extension; 16];
+    value[16 - bytes.len()..].copy_from_slice(bytes);
+    Ok(i128::from_be_bytes(value))
+}
+
+fn check_precision(value: i128, precision: usize) -> Result<i128> {
+    if value.unsigned_abs() < 10u128.pow(precision as u32) {
+        Ok(value)
+    } else {
+        Err(Error::OutOfSpec(format!(
+            "The decimal {} has more digits than its precision, {}",
+            value, precision
+        )))
+    }
+}
+
+/// Deserializes the optional values of a page from its definition levels and its non-null
+/// values.
+fn deserialize_optional<I: Iterator<Item = Result<i128>>>(
+    validity: DefLevelsDecoder,
+    mut values: I,
+) -> Result<Vec<Option<i128>>> {
+    let mut next = || {
+        values
+            .next()
+            .transpose()?
+            .ok_or_else(|| {
+                Error::OutOfSpec("A page has less values than its definition levels".to_string())
+            })
+            .map(Some)
+    };
+
+    let mut deserialized = vec![];
+    match validity {
+        DefLevelsDecoder::Bitmap(runs) => {
+            for run in runs {
+                match run {
+                    HybridEncoded::Bitmap(bitmap, length) => {
+                        for is_valid in BitmapIter::new(bitmap, 0, length) {
+                            deserialized.push(if is_valid { next()? } else { None });
+                        }
+                    }
+                    HybridEncoded::Repeated(true, length) => {
+                        for _ in 0..length {
+                            deserialized.push(next()?);
+                        }
+                    }
+                    HybridEncoded::Repeated(false, length) => {
+                        deserialized.resize(deserialized.len() + length, None)
+                    }
+                }
+            }
+        }
+        DefLevelsDecoder::Levels(levels, max_level) => {
+            for level in levels {
+                deserialized.push(if level == max_level { next()? } else { None });
+            }
+        }
+    }
+    Ok(deserialized)
+}
+
+fn deserialize_required<I: Iterator<Item = Result<i128>>>(values: I) -> Result<Vec<Option<i128>>> {
+    values.map(|x| x.map(Some)).collect()
+}
+
+fn read_native<T: crate::types::NativeType + Into<i128>>(
+    page: &DataPage,
+) -> Result<Vec<Option<i128>>> {
+    let ok = |x: T| Ok(x.into());
+    let dict_value = |values: &[T], index: u32| {
+        values
+            .get(index as usize)
+            .copied()
+            .map(Into::into)
+            .ok_or_else(|| {
+                Error::OutOfSpec("A dictionary index is larger than the dictionary".to_string())
+            })
+    };
+    match NativePageState::<T>::try_new(page)? {
+        NativePageState::Optional(validity, values) => {
+            deserialize_optional(validity, values.map(ok))
+        }
+        NativePageState::Required(values) => deserialize_required(values.map(ok)),
+        NativePageState::RequiredDictionary(dict) => {
+            deserialize_required(dict.indexes.map(|x| dict_value(dict.values, x)))
+        }
+        NativePageState::OptionalDictionary(validity, dict) => {
+            deserialize_optional(validity, dict.indexes.map(|x| dict_value(dict.values, x)))
+        }
+        NativePageState::RequiredByteStreamSplit(values) => deserialize_required(values.map(ok)),
+        NativePageState::OptionalByteStreamSplit(validity, values) => {
+            deserialize_optional(validity, values.map(ok))
+        }
+        NativePageState::RequiredDelta(values) => {
+            deserialize_required(values.map(|x| x.and_then(ok)))
+        }
+        NativePageState::OptionalDelta(validity, values) => {
+            deserialize_optional(validity, values.map(|x| x.and_then(ok)))
+        }
+    }
+}
+
+fn read_binary(page: &DataPage) -> Result<Vec<Option<i128>>> {
+    let convert = |x: &[u8]| decimal_from_be_bytes(x);
+    match BinaryPageState::try_new(page)? {
+        BinaryPageState::Optional(validity, values) => {
+            deserialize_optional(validity, values.map(convert))
+        }
+        BinaryPageState::Required(values) => deserialize_required(values.map(convert)),
+        BinaryPageState::RequiredDictionary(dict) => deserialize_required(
+            dict.indexes
+                .map(|x| dict.dict.value(x as usize).and_then(convert)),
+        ),
+        BinaryPageState::OptionalDictionary(validity, dict) => deserialize_optional(
+            validity,
+            dict.indexes
+                .map(|x| dict.dict.value(x as usize).and_then(convert)),
+        ),
+        BinaryPageState::RequiredDelta(values) => deserialize_required(values.map(convert)),
+        BinaryPageState::OptionalDelta(validity, values) => {
+            deserialize_optional(validity, values.map(convert))
+        }
+        BinaryPageState::RequiredDeltaByteArray(values) => {
+            deserialize_required(values.map(|x| convert(&x)))
+        }
+        BinaryPageState::OptionalDeltaByteArray(validity, values) => {
+            deserialize_optional(validity, values.map(|x| convert(&x)))
+        }
+    }
+}
+
+fn read_fixed_len(page: &DataPage) -> Result<Vec<Option<i128>>> {
+    let convert = |x: &[u8]| decimal_from_be_bytes(x);
+    match FixedLenBinaryPageState::try_new(page)? {
+        FixedLenBinaryPageState::Optional(validity, values) => {
+            deserialize_optional(validity, values.map(convert))
+        }
+        FixedLenBinaryPageState::Required(values) => deserialize_required(values.map(convert)),
+        FixedLenBinaryPageState::RequiredDictionary(dict) => deserialize_required(
+            dict.indexes
+                .map(|x| dict.dict.value(x as usize).and_then(convert)),
+        ),
+        FixedLenBinaryPageState::OptionalDictionary(validity, dict) => deserialize_optional(
+            validity,
+            dict.indexes
+                .map(|x| dict.dict.value(x as usize).and_then(convert)),
+        ),
+        FixedLenBinaryPageState::RequiredByteStreamSplit(values) => {
+            deserialize_required(values.map(|x| convert(&x)))
+        }
+        FixedLenBinaryPageState::OptionalByteStreamSplit(validity, values) => {
+            deserialize_optional(validity, values.map(|x| convert(&x)))
+        }
+    }
+}
+
+/// Reads the values of a non-nested page of a `DECIMAL(precision, scale)` column as their
+/// unscaled `i128` values, i.e. the value of the decimal is `value * 10^{-scale}`.
+///
+/// All physical types that can be annotated by `DECIMAL` are supported: `INT32`, `INT64`,
+/// and big-endian two's complement `BYTE_ARRAY` and `FIXED_LEN_BYTE_ARRAY`.
+/// # Errors
+/// Errors iff:
+/// * `precision` is not between 1 and 38 or `scale` is larger than `precision`
+/// * the page is nested or its physical type cannot be annotated by `DECIMAL`
+/// * a value does not fit in an `i128` or has more digits than `precision`
+pub fn read_decimal(page: &DataPage, precision: usize, scale: usize) -> Result<Vec<Option<i128>>> {
+    if precision == 0 || precision > MAX_PRECISION {
+        return Err(Error::InvalidParameter(format!(
+            "The precision of a decimal read as i128 must be between 1 and {}, but it is {}",
+            MAX_PRECISION, precision
+        )));
+    }
+    if scale > precision {
+        return Err(Error::InvalidParameter(format!(
+            "The scale of a decimal ({}) cannot be larger than its precision ({})",
+            scale, precision
+        )));
+    }
+    if page.descriptor.max_rep_level > 0 {
+        return Err(Error::General(
+            "Decimals can only be read from non-nested pages".to_string(),
+        ));
+    }
+
+    let values = match page.descriptor.primitive_type.physical_type {
+        PhysicalType::Int32 => read_native::<i32>(page)?,
+        PhysicalType::Int64 => read_native::<i64>(page)?,
+        PhysicalType::ByteArray => read_binary(page)?,
+        PhysicalType::FixedLenByteArray(_) => read_fixed_len(page)?,
+        other => {
+            return Err(Error::OutOfSpec(format!(
+                "DECIMAL cannot annotate the physical type {:?}",
+                other
+            )))
+        }
+    };
+    values
+        .into_iter()
+        .map(|x| x.map(|x| check_precision(x, precision)).transpose())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::encoding::Encoding;
+    use crate::metadata::Descriptor;
+    use crate::page::{DataPageHeader, DataPageHeaderV1, PrimitivePageDict};
+    use crate::schema::types::{PrimitiveLogicalType, PrimitiveType};
+    use crate::schema::Repetition;
+
+    use super::*;
+
+    #[test]
+    fn from_be_bytes() -> Result<()> {
+        assert_eq!(decimal_from_be_bytes(&[0x01, 0x00])?, 256);
+        assert_eq!(decimal_from_be_bytes(&[0xff])?, -1);
+        assert_eq!(decimal_from_be_bytes(&[0xff, 0x00])?, -256);
+        assert_eq!(decimal_from_be_bytes(&[0x7f])?, 127);
+        assert_eq!(decimal_from_be_bytes(&[0x80])?, -128);
+
+        // 16 bytes
+        let mut bytes = [0xff; 16];
+        bytes[0] = 0x7f;
+        assert_eq!(decimal_from_be_bytes(&bytes)?, i128::MAX);
+        let mut bytes = [0; 16];
+        bytes[0] = 0x80;
+        assert_eq!(decimal_from_be_bytes(&bytes)?, i128::MIN);
+        Ok(())
+    }
+
+    #[test]
+    fn from_be_bytes_sign_extended() -> Result<()> {
+        // more than 16 bytes, whose leading bytes only extend the sign
+        let mut bytes = vec![0xff; 20];
+        bytes[19] = 0xfe;
+        assert_eq!(decimal_from_be_bytes(&bytes)?, -2);
+
+        let mut bytes = vec![0; 20];
+        bytes[19] = 2;
+        assert_eq!(decimal_from_be_bytes(&bytes)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn from_be_bytes_overflow() {
+        // 2^127 does not fit in an i128
+        let mut bytes = vec![0; 17];
+        bytes[1] = 0x80;
+        assert!(decimal_from_be_bytes(&bytes).is_err());
+
+        // -2^127 - 1 does not fit in an i128
+        let mut bytes = vec![0xff; 17];
+        bytes[1] = 0x7f;
+        assert!(decimal_from_be_bytes(&bytes).is_err());
+
+        let mut bytes = vec![0; 17];
+        bytes[0] = 1;
+        assert!(decimal_from_be_bytes(&bytes).is_err());
+
+        assert!(decimal_from_be_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn precision() {
+        assert_eq!(check_precision(99, 2).unwrap(), 99);
+        assert_eq!(check_precision(-99, 2).unwrap(), -99);
+        assert!(check_precision(100, 2).is_err());
+        assert!(check_precision(-100, 2).is_err());
+
+        let max = 10i128.pow(38) - 1;
+        assert_eq!(check_precision(max, 38).unwrap(), max);
+        assert!(check_precision(i128::MAX, 38).is_err());
+        assert!(check_precision(i128::MIN, 38).is_err());
+    }
+
+    #[test]
+    fn dict_index_out_of_bounds() {
+        let mut primitive_type = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        primitive_type.field_info.repetition = Repetition::Required;
+        primitive_type.logical_type = Some(PrimitiveLogicalType::Decimal(9, 2));
+        let header = DataPageHeaderV1 {
+            num_values: 1,
+            encoding: Encoding::RleDictionary.into(),
+            definition_level_encoding: Encoding::Rle.into(),
+            repetition_level_encoding: Encoding::Rle.into(),
+            statistics: None,
+        };
+        // a dictionary of 2 values and a RLE run of the index 3
+        let page = DataPage::new(
+            DataPageHeader::V1(header),
+            vec![2, 1 << 1, 3],
+            Some(Arc::new(PrimitivePageDict::new(vec![10i32, 20]))),
+            Descriptor {
+                primitive_type,
+                max_def_level: 0,
+                max_rep_level: 0,
+            },
+            Some(1),
+        );
+        assert!(matches!(
+            read_decimal(&page, 9, 2),
+            Err(Error::OutOfSpec(_))
+        ));
+    }
+}
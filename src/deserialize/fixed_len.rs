@@ -1,5 +1,5 @@
 use crate::{
-    encoding::hybrid_rle,
+    encoding::{byte_stream_split, hybrid_rle, plain_byte_array::FixedLenBinaryIter},
     error::Error,
     page::{split_buffer, DataPage, FixedLenByteArrayPageDict},
     parquet_bridge::{Encoding, Repetition},
@@ -8,31 +8,8 @@ use crate::{
 
 use super::utils;
 
-#[derive(Debug)]
-pub struct FixexBinaryIter<'a> {
-    values: std::slice::ChunksExact<'a, u8>,
-}
-
-impl<'a> FixexBinaryIter<'a> {
-    pub fn new(values: &'a [u8], size: usize) -> Self {
-        let values = values.chunks_exact(size);
-        Self { values }
-    }
-}
-
-impl<'a> Iterator for FixexBinaryIter<'a> {
-    type Item = &'a [u8];
-
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.values.next()
-    }
-
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.values.size_hint()
-    }
-}
+/// The plain-encoded values of a page of `FIXED_LEN_BYTE_ARRAY`
+pub type FixexBinaryIter<'a> = FixedLenBinaryIter<'a>;
 
 #[derive(Debug)]
 pub struct Dictionary<'a> {
@@ -41,10 +18,10 @@ pub struct Dictionary<'a> {
 }
 
 impl<'a> Dictionary<'a> {
-    pub fn new(page: &'a DataPage, dict: &'a FixedLenByteArrayPageDict) -> Self {
-        let indexes = utils::dict_indices_decoder(page);
+    pub fn try_new(page: &'a DataPage, dict: &'a FixedLenByteArrayPageDict) -> Result<Self, Error> {
+        let indexes = utils::dict_indices_decoder(page)?;
 
-        Self { indexes, dict }
+        Ok(Self { indexes, dict })
     }
 
     #[inline]
@@ -58,6 +35,11 @@ pub enum FixedLenBinaryPageState<'a> {
     Required(FixexBinaryIter<'a>),
     RequiredDictionary(Dictionary<'a>),
     OptionalDictionary(utils::DefLevelsDecoder<'a>, Dictionary<'a>),
+    RequiredByteStreamSplit(byte_stream_split::FixedLenDecoder<'a>),
+    OptionalByteStreamSplit(
+        utils::DefLevelsDecoder<'a>,
+        byte_stream_split::FixedLenDecoder<'a>,
+    ),
 }
 
 impl<'a> FixedLenBinaryPageState<'a> {
@@ -79,30 +61,44 @@ impl<'a> FixedLenBinaryPageState<'a> {
         match (page.encoding(), page.dictionary_page(), is_optional) {
             (Encoding::PlainDictionary | Encoding::RleDictionary, Some(dict), false) => {
                 let dict = dict.as_any().downcast_ref().unwrap();
-                Ok(Self::RequiredDictionary(Dictionary::new(page, dict)))
+                Ok(Self::RequiredDictionary(Dictionary::try_new(page, dict)?))
             }
             (Encoding::PlainDictionary | Encoding::RleDictionary, Some(dict), true) => {
                 let dict = dict.as_any().downcast_ref().unwrap();
 
                 Ok(Self::OptionalDictionary(
-                    utils::DefLevelsDecoder::new(page),
-                    Dictionary::new(page, dict),
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    Dictionary::try_new(page, dict)?,
                 ))
             }
             (Encoding::Plain, _, true) => {
                 let (_, _, values) = split_buffer(page);
 
-                let validity = utils::DefLevelsDecoder::new(page);
-                let values = FixexBinaryIter::new(values, size);
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+                let values = FixedLenBinaryIter::try_new(values, size, None)?;
 
                 Ok(Self::Optional(validity, values))
             }
             (Encoding::Plain, _, false) => {
                 let (_, _, values) = split_buffer(page);
-                let values = FixexBinaryIter::new(values, size);
+                let values = FixedLenBinaryIter::try_new(values, size, Some(page.num_values()))?;
 
                 Ok(Self::Required(values))
             }
+            (Encoding::ByteStreamSplit, _, true) => {
+                let (_, _, values) = split_buffer(page);
+
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+                let values = byte_stream_split::FixedLenDecoder::try_new(values, size)?;
+
+                Ok(Self::OptionalByteStreamSplit(validity, values))
+            }
+            (Encoding::ByteStreamSplit, _, false) => {
+                let (_, _, values) = split_buffer(page);
+                let values = byte_stream_split::FixedLenDecoder::try_new(values, size)?;
+
+                Ok(Self::RequiredByteStreamSplit(values))
+            }
             _ => Err(Error::General(format!(
                 "Viewing page for encoding {:?} for binary type not supported",
                 page.encoding(),
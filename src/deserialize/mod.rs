@@ -1,15 +1,18 @@
 mod binary;
 mod boolean;
+mod decimal;
 mod filtered_rle;
 mod fixed_len;
 mod hybrid_rle;
 mod native;
 mod utils;
 
+pub use crate::types::int96_to_i64_ns;
 pub use binary::*;
 pub use boolean::*;
+pub use decimal::*;
 pub use filtered_rle::*;
 pub use fixed_len::*;
 pub use hybrid_rle::*;
 pub use native::*;
-pub use utils::{DefLevelsDecoder, OptionalValues, SliceFilteredIter};
+pub use utils::{dict_indices_decoder, DefLevelsDecoder, OptionalValues, SliceFilteredIter};
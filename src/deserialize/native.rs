@@ -1,8 +1,9 @@
 use crate::{
-    encoding::hybrid_rle,
+    encoding::{byte_stream_split, delta_bitpacked, hybrid_rle},
     error::Error,
     page::{split_buffer, DataPage, PrimitivePageDict},
     parquet_bridge::{Encoding, Repetition},
+    schema::types::PhysicalType,
     types::{decode, NativeType},
 };
 
@@ -25,6 +26,27 @@ pub fn native_cast<T: NativeType>(page: &DataPage) -> Result<Casted<T>, Error> {
         .map(decode::<T>))
 }
 
+/// Typedef of a fallible iterator over `DELTA_BINARY_PACKED` page values
+pub type Delta<'a, T> =
+    std::iter::Map<delta_bitpacked::Decoder<'a>, fn(Result<i64, Error>) -> Result<T, Error>>;
+
+/// Views the values of the data page as [`Delta`] to [`NativeType`].
+/// # Error
+/// Errors iff `T` is neither `i32` nor `i64`, the only types that can be delta-encoded.
+pub fn native_delta<T: NativeType>(page: &DataPage) -> Result<Delta<'_, T>, Error> {
+    if !matches!(T::TYPE, PhysicalType::Int32 | PhysicalType::Int64) {
+        return Err(Error::OutOfSpec(format!(
+            "The delta binary packed encoding is not valid for {:?}",
+            T::TYPE
+        )));
+    }
+    let (_, _, values) = split_buffer(page);
+
+    // deltas are decoded as i64; i32 values are its (little-endian) lower half
+    Ok(delta_bitpacked::Decoder::try_new(values)?
+        .map(|x| x.map(|x| decode::<T>(&x.to_le_bytes()[..std::mem::size_of::<T>()]))))
+}
+
 #[derive(Debug)]
 pub struct Dictionary<'a, T>
 where
@@ -38,13 +60,13 @@ impl<'a, T> Dictionary<'a, T>
 where
     T: NativeType,
 {
-    pub fn new(page: &'a DataPage, dict: &'a PrimitivePageDict<T>) -> Self {
-        let indexes = utils::dict_indices_decoder(page);
+    pub fn try_new(page: &'a DataPage, dict: &'a PrimitivePageDict<T>) -> Result<Self, Error> {
+        let indexes = utils::dict_indices_decoder(page)?;
 
-        Self {
+        Ok(Self {
             values: dict.values(),
             indexes,
-        }
+        })
     }
 
     pub fn len(&self) -> usize {
@@ -71,6 +93,17 @@ where
     RequiredDictionary(Dictionary<'a, T>),
     /// A page of optional, dictionary-encoded values
     OptionalDictionary(utils::DefLevelsDecoder<'a>, Dictionary<'a, T>),
+    /// A page of required, byte-stream-split-encoded values
+    RequiredByteStreamSplit(byte_stream_split::Decoder<'a, T>),
+    /// A page of optional, byte-stream-split-encoded values
+    OptionalByteStreamSplit(
+        utils::DefLevelsDecoder<'a>,
+        byte_stream_split::Decoder<'a, T>,
+    ),
+    /// A page of required, delta-encoded values
+    RequiredDelta(Delta<'a, T>),
+    /// A page of optional, delta-encoded values
+    OptionalDelta(utils::DefLevelsDecoder<'a>, Delta<'a, T>),
 }
 
 impl<'a, T: NativeType> NativePageState<'a, T> {
@@ -84,23 +117,43 @@ impl<'a, T: NativeType> NativePageState<'a, T> {
         match (page.encoding(), page.dictionary_page(), is_optional) {
             (Encoding::PlainDictionary | Encoding::RleDictionary, Some(dict), false) => {
                 let dict = dict.as_any().downcast_ref().unwrap();
-                Ok(Self::RequiredDictionary(Dictionary::new(page, dict)))
+                Ok(Self::RequiredDictionary(Dictionary::try_new(page, dict)?))
             }
             (Encoding::PlainDictionary | Encoding::RleDictionary, Some(dict), true) => {
                 let dict = dict.as_any().downcast_ref().unwrap();
 
                 Ok(Self::OptionalDictionary(
-                    utils::DefLevelsDecoder::new(page),
-                    Dictionary::new(page, dict),
+                    utils::DefLevelsDecoder::try_new(page)?,
+                    Dictionary::try_new(page, dict)?,
                 ))
             }
             (Encoding::Plain, _, true) => {
-                let validity = utils::DefLevelsDecoder::new(page);
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
                 let values = native_cast(page)?;
 
                 Ok(Self::Optional(validity, values))
             }
             (Encoding::Plain, _, false) => Ok(Self::Required(native_cast(page)?)),
+            (Encoding::ByteStreamSplit, _, true) => {
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+                let (_, _, values) = split_buffer(page);
+                let values = byte_stream_split::Decoder::try_new(values)?;
+
+                Ok(Self::OptionalByteStreamSplit(validity, values))
+            }
+            (Encoding::ByteStreamSplit, _, false) => {
+                let (_, _, values) = split_buffer(page);
+                Ok(Self::RequiredByteStreamSplit(
+                    byte_stream_split::Decoder::try_new(values)?,
+                ))
+            }
+            (Encoding::DeltaBinaryPacked, _, true) => {
+                let validity = utils::DefLevelsDecoder::try_new(page)?;
+                let values = native_delta(page)?;
+
+                Ok(Self::OptionalDelta(validity, values))
+            }
+            (Encoding::DeltaBinaryPacked, _, false) => Ok(Self::RequiredDelta(native_delta(page)?)),
             _ => Err(Error::General(format!(
                 "Viewing page for encoding {:?} for native type {} not supported",
                 page.encoding(),
@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 
 use crate::{
     encoding::hybrid_rle::{self, HybridRleDecoder},
+    error::Error,
     indexes::Interval,
     page::{split_buffer, DataPage},
     read::levels::get_bit_width,
@@ -9,15 +10,28 @@ use crate::{
 
 use super::hybrid_rle::{HybridDecoderBitmapIter, HybridRleIter};
 
-pub(super) fn dict_indices_decoder(page: &DataPage) -> hybrid_rle::HybridRleDecoder {
+/// Returns a [`HybridRleDecoder`] over the dictionary indices of a dictionary-encoded
+/// [`DataPage`]. Runs are decoded on demand, as the indices are iterated.
+/// # Error
+/// Errors iff the page does not start with a valid bit width or its runs are malformed.
+pub fn dict_indices_decoder(page: &DataPage) -> Result<HybridRleDecoder, Error> {
     let (_, _, indices_buffer) = split_buffer(page);
 
     // SPEC: Data page format: the bit width used to encode the entry ids stored as 1 byte (max bit width = 32),
     // SPEC: followed by the values encoded using RLE/Bit packed described above (with the given bit width).
-    let bit_width = indices_buffer[0];
+    let bit_width = *indices_buffer.first().ok_or_else(|| {
+        Error::OutOfSpec("A dictionary-encoded page must contain a bit width".to_string())
+    })?;
+    if bit_width > 32 {
+        return Err(Error::OutOfSpec(format!(
+            "The bit width of dictionary indices must be at most 32, but it is {}",
+            bit_width
+        )));
+    }
     let indices_buffer = &indices_buffer[1..];
 
-    hybrid_rle::HybridRleDecoder::new(indices_buffer, bit_width as u32, page.num_values())
+    // the number of values of the page counts nulls: it is an upper bound of the number of indices
+    HybridRleDecoder::try_new_up_to(indices_buffer, bit_width as u32, page.num_values())
 }
 
 /// Decoder of definition levels.
@@ -32,19 +46,26 @@ pub enum DefLevelsDecoder<'a> {
 }
 
 impl<'a> DefLevelsDecoder<'a> {
-    pub fn new(page: &'a DataPage) -> Self {
+    /// Returns a new [`DefLevelsDecoder`] of the definition levels of `page`.
+    /// # Error
+    /// Errors iff the definition levels are malformed (see [`HybridRleDecoder::try_new`]).
+    pub fn try_new(page: &'a DataPage) -> Result<Self, Error> {
         let (_, def_levels, _) = split_buffer(page);
 
         let max_def_level = page.descriptor.max_def_level;
-        if max_def_level == 1 {
+        Ok(if max_def_level == 1 {
+            hybrid_rle::check_runs(def_levels, 1, page.num_values())?;
             let iter = hybrid_rle::Decoder::new(def_levels, 1);
             let iter = HybridRleIter::new(iter, page.num_values());
             Self::Bitmap(iter)
         } else {
-            let iter =
-                HybridRleDecoder::new(def_levels, get_bit_width(max_def_level), page.num_values());
+            let iter = HybridRleDecoder::try_new(
+                def_levels,
+                get_bit_width(max_def_level),
+                page.num_values(),
+            )?;
             Self::Levels(iter, max_def_level as u32)
-        }
+        })
     }
 }
 
@@ -139,8 +160,107 @@ impl<T, I: Iterator<Item = T>> Iterator for SliceFilteredIter<I> {
 #[cfg(test)]
 mod test {
     use std::collections::VecDeque;
+    use std::sync::Arc;
 
     use super::*;
+    use crate::encoding::hybrid_rle::encode_u32;
+    use crate::encoding::Encoding;
+    use crate::metadata::Descriptor;
+    use crate::page::{DataPageHeader, DataPageHeaderV1, PrimitivePageDict};
+    use crate::schema::types::{PhysicalType, PrimitiveType};
+    use crate::schema::Repetition;
+
+    /// A required page of dictionary indices `buffer` over the dictionary `[10, 20, 30, 40]`
+    fn dict_page(buffer: Vec<u8>, num_values: usize) -> DataPage {
+        let mut primitive_type = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        primitive_type.field_info.repetition = Repetition::Required;
+        let header = DataPageHeaderV1 {
+            num_values: num_values as i32,
+            encoding: Encoding::RleDictionary.into(),
+            definition_level_encoding: Encoding::Rle.into(),
+            repetition_level_encoding: Encoding::Rle.into(),
+            statistics: None,
+        };
+        DataPage::new(
+            DataPageHeader::V1(header),
+            buffer,
+            Some(Arc::new(PrimitivePageDict::new(vec![10i32, 20, 30, 40]))),
+            Descriptor {
+                primitive_type,
+                max_def_level: 0,
+                max_rep_level: 0,
+            },
+            Some(num_values),
+        )
+    }
+
+    fn dict_values(page: &DataPage) -> Result<Vec<i32>, Error> {
+        let dict = page.dictionary_page().unwrap().as_any();
+        let dict = dict.downcast_ref::<PrimitivePageDict<i32>>().unwrap();
+        Ok(dict_indices_decoder(page)?
+            .map(|index| dict.values()[index as usize])
+            .collect())
+    }
+
+    #[test]
+    fn dict_indices() -> Result<(), Error> {
+        let indices = (0..1000u32).map(|x| x % 4).collect::<Vec<_>>();
+        let mut buffer = vec![2];
+        encode_u32(&mut buffer, indices.iter().copied(), 2).unwrap();
+        let page = dict_page(buffer, indices.len());
+
+        // indices are decoded on demand
+        let mut decoder = dict_indices_decoder(&page)?;
+        assert_eq!(decoder.len(), 1000);
+        assert_eq!(decoder.by_ref().take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(decoder.len(), 997);
+
+        let expected = indices
+            .iter()
+            .map(|x| (x + 1) as i32 * 10)
+            .collect::<Vec<_>>();
+        assert_eq!(dict_values(&page)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn dict_indices_zero_bit_width() -> Result<(), Error> {
+        // a dictionary with a single value is encoded with a bit width of 0 and no runs
+        let page = dict_page(vec![0], 5);
+        assert_eq!(dict_values(&page)?, vec![10; 5]);
+        Ok(())
+    }
+
+    #[test]
+    fn dict_indices_partial_bitpacked() -> Result<(), Error> {
+        let buffer = vec![
+            2, // bit width
+            8, 3, // rle run: 4 times 3
+            3, 0b00111001, 0b00000001, // bitpacked run of 8 values: [1, 2, 3, 0, 1, 0, 0, 0]
+        ];
+        // only the first 5 values of the bitpacked run belong to the page
+        let page = dict_page(buffer, 9);
+        assert_eq!(
+            dict_values(&page)?,
+            vec![40, 40, 40, 40, 20, 30, 40, 10, 20]
+        );
+
+        // a bitpacked run without bytes, like missing values, is read as zeros
+        let page = dict_page(vec![2, 3], 2);
+        assert_eq!(dict_values(&page)?, vec![10, 10]);
+        Ok(())
+    }
+
+    #[test]
+    fn dict_indices_invalid() {
+        for buffer in [vec![], vec![33, 0]] {
+            let page = dict_page(buffer, 1);
+            assert!(matches!(
+                dict_indices_decoder(&page),
+                Err(Error::OutOfSpec(_))
+            ));
+        }
+    }
 
     #[test]
     fn basic() {
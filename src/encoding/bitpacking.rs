@@ -74,11 +74,9 @@ impl<'a> Decoder<'a> {
 
         let mut compressed_chunks = compressed.chunks(compressed_block_size);
         let mut current_pack = [0; BitPacker1x::BLOCK_LEN];
-        decode_pack(
-            compressed_chunks.next().unwrap(),
-            num_bits,
-            &mut current_pack,
-        );
+        if let Some(chunk) = compressed_chunks.next() {
+            decode_pack(chunk, num_bits, &mut current_pack);
+        }
 
         Self {
             remaining: length,
@@ -90,6 +88,32 @@ impl<'a> Decoder<'a> {
     }
 }
 
+impl<'a> Decoder<'a> {
+    /// Advances this decoder by `n` values (or all remaining values if there are less than `n`)
+    /// without decoding the packs that are skipped over.
+    ///
+    /// Unlike [`Iterator::skip`], this does not consume the decoder.
+    pub fn skip_values(&mut self, n: usize) {
+        let n = n.min(self.remaining);
+        self.remaining -= n;
+
+        let index = self.current_pack_index + n;
+        if index < BitPacker1x::BLOCK_LEN {
+            self.current_pack_index = index;
+            return;
+        }
+        // the pack of the next value, after the current one
+        let pack = index / BitPacker1x::BLOCK_LEN - 1;
+        if let Some(chunk) = self.compressed_chunks.nth(pack) {
+            decode_pack(chunk, self.num_bits, &mut self.current_pack);
+            self.current_pack_index = index % BitPacker1x::BLOCK_LEN;
+        } else {
+            // there are no values after the skipped ones
+            self.remaining = 0;
+        }
+    }
+}
+
 impl<'a> Iterator for Decoder<'a> {
     type Item = u32;
 
@@ -114,6 +138,159 @@ impl<'a> Iterator for Decoder<'a> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.remaining, Some(self.remaining))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_values(n);
+        self.next()
+    }
+}
+
+/// Encodes `u64` values into a buffer using `num_bits`, which may be up to 64.
+///
+/// The values are packed in chunks of [`BLOCK_LEN`] values with the same layout as [`encode`],
+/// i.e. least significant bit first, so that both are interchangeable for widths up to 32.
+pub fn encode_u64(decompressed: &[u64], num_bits: u8, compressed: &mut [u8]) -> usize {
+    let size = BLOCK_LEN * num_bits as usize / 8;
+
+    let mut compressed_len = 0;
+    decompressed.chunks(BLOCK_LEN).for_each(|chunk| {
+        let mut pack = [0u64; BLOCK_LEN];
+        pack[..chunk.len()].copy_from_slice(chunk);
+        let chunk_compressed = &mut compressed[compressed_len..compressed_len + size];
+        compressed_len += encode_pack_u64(&pack, num_bits, chunk_compressed);
+    });
+
+    ceil8(decompressed.len() * num_bits as usize)
+}
+
+/// Encodes `u64` values into a buffer using `num_bits`, which may be up to 64.
+pub fn encode_pack_u64(
+    decompressed: &[u64; BLOCK_LEN],
+    num_bits: u8,
+    compressed: &mut [u8],
+) -> usize {
+    let num_bits = num_bits as usize;
+    let size = BLOCK_LEN * num_bits / 8;
+    if num_bits == 0 {
+        return 0;
+    }
+    let mask = u128::MAX >> (128 - num_bits);
+
+    let compressed = &mut compressed[..size];
+    compressed.iter_mut().for_each(|x| *x = 0);
+    decompressed.iter().enumerate().for_each(|(i, value)| {
+        let offset = i * num_bits;
+        let (byte, shift) = (offset / 8, offset % 8);
+        let value = ((*value as u128) & mask) << shift;
+        let length = ceil8(shift + num_bits);
+        compressed[byte..byte + length]
+            .iter_mut()
+            .zip(value.to_le_bytes())
+            .for_each(|(c, v)| *c |= v);
+    });
+    size
+}
+
+#[inline]
+fn decode_pack_u64(compressed: &[u8], num_bits: u8, pack: &mut [u64; BLOCK_LEN]) {
+    let num_bits = num_bits as usize;
+    if num_bits == 0 {
+        *pack = [0; BLOCK_LEN];
+        return;
+    }
+    let mask = u128::MAX >> (128 - num_bits);
+
+    let mut buf = [0u8; BLOCK_LEN * mem::size_of::<u64>()];
+    let length = compressed.len().min(BLOCK_LEN * num_bits / 8);
+    buf[..length].copy_from_slice(&compressed[..length]);
+
+    pack.iter_mut().enumerate().for_each(|(i, value)| {
+        let offset = i * num_bits;
+        let (byte, shift) = (offset / 8, offset % 8);
+        let length = ceil8(shift + num_bits);
+        let mut bytes = [0u8; 16];
+        bytes[..length].copy_from_slice(&buf[byte..byte + length]);
+        *value = ((u128::from_le_bytes(bytes) >> shift) & mask) as u64;
+    });
+}
+
+/// A decoder of `u64` values bit-packed with up to 64 bits (see [`encode_u64`]).
+#[derive(Debug, Clone)]
+pub struct DecoderU64<'a> {
+    compressed_chunks: std::slice::Chunks<'a, u8>,
+    num_bits: u8,
+    remaining: usize,
+    current_pack_index: usize, // invariant: <BLOCK_LEN
+    current_pack: [u64; BLOCK_LEN],
+}
+
+impl<'a> DecoderU64<'a> {
+    pub fn new(compressed: &'a [u8], num_bits: u8, length: usize) -> Self {
+        // `chunks` requires a non-zero size, but a width of 0 has no bytes
+        let compressed_block_size = (BLOCK_LEN * num_bits as usize / 8).max(1);
+
+        let mut compressed_chunks = compressed.chunks(compressed_block_size);
+        let mut current_pack = [0; BLOCK_LEN];
+        if let Some(chunk) = compressed_chunks.next() {
+            decode_pack_u64(chunk, num_bits, &mut current_pack);
+        }
+
+        Self {
+            remaining: length,
+            compressed_chunks,
+            num_bits,
+            current_pack,
+            current_pack_index: 0,
+        }
+    }
+
+    /// Advances this decoder by `n` values (or all remaining values if there are less than `n`)
+    /// without decoding the packs that are skipped over.
+    pub fn skip_values(&mut self, n: usize) {
+        let n = n.min(self.remaining);
+        self.remaining -= n;
+
+        let index = self.current_pack_index + n;
+        if index < BLOCK_LEN {
+            self.current_pack_index = index;
+            return;
+        }
+        // missing bytes, e.g. of a width of 0, are decoded as zeros
+        let pack = index / BLOCK_LEN - 1;
+        let chunk = self.compressed_chunks.nth(pack).unwrap_or_default();
+        decode_pack_u64(chunk, self.num_bits, &mut self.current_pack);
+        self.current_pack_index = index % BLOCK_LEN;
+    }
+}
+
+impl<'a> Iterator for DecoderU64<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let result = self.current_pack[self.current_pack_index];
+        self.current_pack_index += 1;
+        if self.current_pack_index == BLOCK_LEN {
+            let chunk = self.compressed_chunks.next().unwrap_or_default();
+            decode_pack_u64(chunk, self.num_bits, &mut self.current_pack);
+            self.current_pack_index = 0;
+        }
+        self.remaining -= 1;
+        Some(result)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_values(n);
+        self.next()
+    }
 }
 
 #[cfg(test)]
@@ -254,4 +431,107 @@ mod tests {
         let decoded = Decoder::new(&data, num_bits, length).collect::<Vec<_>>();
         assert_eq!(decoded, expected);
     }
+
+    #[test]
+    fn skip_values() {
+        let num_bits = 7;
+        let values = (0..200u32).map(|x| x % 128).collect::<Vec<_>>();
+        let mut data = vec![0u8; values.len().div_ceil(BLOCK_LEN) * BLOCK_LEN * num_bits / 8];
+        encode(&values, num_bits as u8, &mut data);
+
+        // within the first pack, to the start of a pack, across packs, mid-pack and past the end
+        for skips in [
+            vec![3],
+            vec![32],
+            vec![31, 1],
+            vec![5, 100, 30],
+            vec![64, 0, 64],
+            vec![199],
+            vec![200],
+            vec![150, 100],
+        ] {
+            let mut decoder = Decoder::new(&data, num_bits as u8, values.len());
+            let mut expected = values.as_slice();
+            for n in skips {
+                decoder.skip_values(n);
+                expected = &expected[n.min(expected.len())..];
+                assert_eq!(decoder.size_hint().0, expected.len());
+                assert_eq!(decoder.clone().collect::<Vec<_>>(), expected);
+                // the decoder continues after the next value
+                assert_eq!(decoder.nth(0), expected.first().copied());
+                expected = expected.get(1..).unwrap_or_default();
+            }
+        }
+    }
+
+    /// Values of width `num_bits` that exercise every bit, including the largest value.
+    fn u64_values(num_bits: usize, length: usize) -> Vec<u64> {
+        let max = if num_bits == 0 {
+            0
+        } else {
+            u64::MAX >> (64 - num_bits)
+        };
+        (0..length as u64)
+            .map(|i| match i % 4 {
+                0 => max,
+                1 => 0,
+                2 => max.wrapping_mul(i) & max,
+                _ => i & max,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn u64_round_trip() {
+        for num_bits in 0..=64 {
+            // a full pack, several packs and partial packs
+            for length in [0, 1, 31, 32, 33, 100] {
+                let values = u64_values(num_bits, length);
+                let mut data = vec![0u8; length.div_ceil(BLOCK_LEN) * BLOCK_LEN * num_bits / 8];
+                let written = encode_u64(&values, num_bits as u8, &mut data);
+                assert_eq!(written, (length * num_bits).div_ceil(8));
+
+                let decoded = DecoderU64::new(&data, num_bits as u8, length).collect::<Vec<_>>();
+                assert_eq!(decoded, values, "num_bits: {}", num_bits);
+
+                // the trailing bytes of the last pack may be omitted
+                let decoded =
+                    DecoderU64::new(&data[..written], num_bits as u8, length).collect::<Vec<_>>();
+                assert_eq!(decoded, values, "num_bits: {}", num_bits);
+            }
+        }
+    }
+
+    #[test]
+    fn u64_compatible_with_u32() {
+        for num_bits in 1..=32 {
+            let values = u64_values(num_bits, 100);
+            let narrow = values.iter().map(|x| *x as u32).collect::<Vec<_>>();
+            let size = values.len().div_ceil(BLOCK_LEN) * BLOCK_LEN * num_bits / 8;
+
+            let mut data = vec![0u8; size];
+            encode_u64(&values, num_bits as u8, &mut data);
+            let mut expected = vec![0u8; size];
+            encode(&narrow, num_bits as u8, &mut expected);
+            assert_eq!(data, expected, "num_bits: {}", num_bits);
+
+            let decoded = Decoder::new(&data, num_bits as u8, values.len()).collect::<Vec<_>>();
+            assert_eq!(decoded, narrow);
+        }
+    }
+
+    #[test]
+    fn u64_skip_values() {
+        let num_bits = 45;
+        let values = u64_values(num_bits, 200);
+        let mut data = vec![0u8; values.len().div_ceil(BLOCK_LEN) * BLOCK_LEN * num_bits / 8];
+        encode_u64(&values, num_bits as u8, &mut data);
+
+        let mut decoder = DecoderU64::new(&data, num_bits as u8, values.len());
+        decoder.skip_values(70);
+        assert_eq!(decoder.next(), Some(values[70]));
+        assert_eq!(decoder.nth(40), Some(values[111]));
+        decoder.skip_values(1000);
+        assert_eq!(decoder.next(), None);
+    }
 }
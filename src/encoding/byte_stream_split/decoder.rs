@@ -0,0 +1,128 @@
+use std::marker::PhantomData;
+
+use crate::error::Error;
+use crate::types::{decode, NativeType};
+
+/// Decodes [Byte stream split](https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9)
+/// values.
+/// # Implementation
+/// This struct does not allocate on the heap.
+/// # Example
+/// ```
+/// use parquet2::encoding::byte_stream_split::Decoder;
+///
+/// // the byte streams of `[1.0f32, 2.0]`
+/// let data = &[0, 0, 0, 0, 128, 0, 63, 64];
+///
+/// let decoder = Decoder::<f32>::try_new(data).unwrap();
+/// assert_eq!(decoder.collect::<Vec<_>>(), vec![1.0, 2.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decoder<'a, T: NativeType> {
+    values: &'a [u8],
+    num_values: usize,
+    index: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: NativeType> Decoder<'a, T> {
+    /// Returns a new [`Decoder`] over `values`.
+    /// # Error
+    /// Errors iff the length of `values` is not a multiple of the size of `T`.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        let size = std::mem::size_of::<T>();
+        if !values.len().is_multiple_of(size) {
+            return Err(Error::OutOfSpec(
+                "A byte stream split page data's len must be a multiple of the type".to_string(),
+            ));
+        }
+        Ok(Self {
+            values,
+            num_values: values.len() / size,
+            index: 0,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<'a, T: NativeType> Iterator for Decoder<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.num_values {
+            return None;
+        }
+        let size = std::mem::size_of::<T>();
+        // the largest native type, `[u32; 3]`, has 12 bytes
+        let mut bytes = [0u8; 16];
+        bytes
+            .iter_mut()
+            .take(size)
+            .enumerate()
+            .for_each(|(k, byte)| *byte = self.values[k * self.num_values + self.index]);
+        self.index += 1;
+        Some(decode(&bytes[..size]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.num_values - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: NativeType> ExactSizeIterator for Decoder<'a, T> {}
+
+/// Decodes [Byte stream split](https://github.com/apache/parquet-format/blob/master/Encodings.md#byte-stream-split-byte_stream_split--9)
+/// values of a fixed-length byte array of `size` bytes.
+/// # Implementation
+/// The bytes of a value are not contiguous, so each item is a new allocation.
+#[derive(Debug, Clone)]
+pub struct FixedLenDecoder<'a> {
+    values: &'a [u8],
+    size: usize,
+    num_values: usize,
+    index: usize,
+}
+
+impl<'a> FixedLenDecoder<'a> {
+    /// Returns a new [`FixedLenDecoder`] over `values`.
+    /// # Error
+    /// Errors iff `size` is zero or the length of `values` is not a multiple of `size`.
+    pub fn try_new(values: &'a [u8], size: usize) -> Result<Self, Error> {
+        if size == 0 || !values.len().is_multiple_of(size) {
+            return Err(Error::OutOfSpec(
+                "A byte stream split page data's len must be a multiple of the type".to_string(),
+            ));
+        }
+        Ok(Self {
+            values,
+            size,
+            num_values: values.len() / size,
+            index: 0,
+        })
+    }
+}
+
+impl<'a> Iterator for FixedLenDecoder<'a> {
+    type Item = Vec<u8>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.num_values {
+            return None;
+        }
+        let value = (0..self.size)
+            .map(|k| self.values[k * self.num_values + self.index])
+            .collect();
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.num_values - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for FixedLenDecoder<'a> {}
@@ -0,0 +1,38 @@
+use crate::types::NativeType;
+
+/// Encodes `values` into `buffer` using the byte stream split encoding.
+/// # Implementation
+/// The `k`-th byte of every value is written to the `k`-th stream and the streams are
+/// concatenated, so that `buffer` is extended by `values.len() * size_of::<T>()` bytes.
+pub fn encode<T: NativeType>(values: &[T], buffer: &mut Vec<u8>) {
+    let num_values = values.len();
+
+    let start = buffer.len();
+    buffer.resize(start + std::mem::size_of_val(values), 0);
+    let streams = &mut buffer[start..];
+
+    for (i, value) in values.iter().enumerate() {
+        for (k, byte) in value.to_le_bytes().as_ref().iter().enumerate() {
+            streams[k * num_values + i] = *byte;
+        }
+    }
+}
+
+/// Encodes `values`, the concatenation of fixed-length byte arrays of `size` bytes, into `buffer`
+/// using the byte stream split encoding.
+/// # Panics
+/// This function panics iff `size` is zero or the length of `values` is not a multiple of `size`.
+pub fn encode_fixed_len(values: &[u8], size: usize, buffer: &mut Vec<u8>) {
+    assert!(size > 0 && values.len().is_multiple_of(size));
+    let num_values = values.len() / size;
+
+    let start = buffer.len();
+    buffer.resize(start + values.len(), 0);
+    let streams = &mut buffer[start..];
+
+    for (i, value) in values.chunks_exact(size).enumerate() {
+        for (k, byte) in value.iter().enumerate() {
+            streams[k * num_values + i] = *byte;
+        }
+    }
+}
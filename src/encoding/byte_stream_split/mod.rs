@@ -0,0 +1,136 @@
+mod decoder;
+mod encoder;
+
+pub use decoder::{Decoder, FixedLenDecoder};
+pub use encoder::{encode, encode_fixed_len};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        let data = vec![1.0f32, 2.0, 3.0];
+
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+        assert_eq!(buffer, vec![0, 0, 0, 0, 0, 0, 128, 0, 64, 63, 64, 64]);
+
+        let result = Decoder::<f32>::try_new(&buffer)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(result, data);
+    }
+
+    #[test]
+    fn f32_special_values() {
+        let data = vec![
+            f32::NAN,
+            f32::MIN_POSITIVE / 2.0,
+            -f32::MIN_POSITIVE / 4.0,
+            0.0,
+            -0.0,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::MAX,
+            f32::MIN,
+        ];
+
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+        assert_eq!(buffer.len(), data.len() * 4);
+
+        let result = Decoder::<f32>::try_new(&buffer)
+            .unwrap()
+            .collect::<Vec<_>>();
+        // bitwise comparison, as NaN != NaN and 0.0 == -0.0
+        assert_eq!(
+            result.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+            data.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+        );
+        assert!(result[1].is_subnormal());
+    }
+
+    #[test]
+    fn f64_special_values() {
+        let data = vec![
+            f64::NAN,
+            f64::MIN_POSITIVE / 2.0,
+            -f64::MIN_POSITIVE / 4.0,
+            0.0,
+            -0.0,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MAX,
+            f64::MIN,
+        ];
+
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+        assert_eq!(buffer.len(), data.len() * 8);
+
+        let result = Decoder::<f64>::try_new(&buffer)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+            data.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+        );
+        assert!(result[1].is_subnormal());
+    }
+
+    #[test]
+    fn f64_round_trip() {
+        let data = (0..1000)
+            .map(|x| (x as f64).sqrt() * if x % 2 == 0 { 1.0 } else { -1e100 })
+            .collect::<Vec<_>>();
+
+        let mut buffer = vec![];
+        encode(&data, &mut buffer);
+
+        let result = Decoder::<f64>::try_new(&buffer)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+            data.iter().map(|x| x.to_bits()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn fixed_len() {
+        let data = vec![[1u8, 2, 3], [4, 5, 6]].concat();
+
+        let mut buffer = vec![];
+        encode_fixed_len(&data, 3, &mut buffer);
+        assert_eq!(buffer, vec![1, 4, 2, 5, 3, 6]);
+
+        let decoder = FixedLenDecoder::try_new(&buffer, 3).unwrap();
+        assert_eq!(decoder.len(), 2);
+        assert_eq!(
+            decoder.collect::<Vec<_>>(),
+            vec![vec![1, 2, 3], vec![4, 5, 6]]
+        );
+    }
+
+    #[test]
+    fn fixed_len_invalid_length() {
+        assert!(FixedLenDecoder::try_new(&[0; 7], 3).is_err());
+        assert!(FixedLenDecoder::try_new(&[], 0).is_err());
+    }
+
+    #[test]
+    fn empty() {
+        let mut buffer = vec![];
+        encode::<f64>(&[], &mut buffer);
+        assert!(buffer.is_empty());
+
+        let mut decoder = Decoder::<f64>::try_new(&buffer).unwrap();
+        assert_eq!(decoder.next(), None);
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert!(Decoder::<f64>::try_new(&[0; 12]).is_err());
+    }
+}
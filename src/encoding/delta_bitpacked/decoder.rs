@@ -1,10 +1,38 @@
 use crate::encoding::ceil8;
+use crate::error::Error;
 
 use super::super::bitpacking;
 use super::super::uleb128;
 use super::super::zigzag_leb128;
 
-#[derive(Debug)]
+/// A bit-packed mini-block. Deltas of `i64` may need more than the 32 bits supported by
+/// [`bitpacking::Decoder`], in which case they are unpacked by [`bitpacking::DecoderU64`].
+#[derive(Debug, Clone)]
+enum MiniBlock<'a> {
+    Narrow(bitpacking::Decoder<'a>),
+    Wide(Box<bitpacking::DecoderU64<'a>>),
+}
+
+impl<'a> MiniBlock<'a> {
+    fn new(values: &'a [u8], num_bits: u8, length: usize) -> Self {
+        if num_bits <= 32 {
+            Self::Narrow(bitpacking::Decoder::new(values, num_bits, length))
+        } else {
+            Self::Wide(Box::new(bitpacking::DecoderU64::new(
+                values, num_bits, length,
+            )))
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        match self {
+            Self::Narrow(decoder) => decoder.next().unwrap() as u64,
+            Self::Wide(decoder) => decoder.next().unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 struct Block<'a> {
     // this is the minimum delta that must be added to every value.
     min_delta: i64,
@@ -13,27 +41,32 @@ struct Block<'a> {
     bitwidths: &'a [u8],
     values: &'a [u8],
     remaining: usize,     // number of elements
-    current_index: usize, // invariant: < values_per_mini_block
+    current_index: usize, // invariant: <= values_per_mini_block
     // None represents a relative delta of zero, in which case there is no miniblock.
-    current_miniblock: Option<bitpacking::Decoder<'a>>,
+    current_miniblock: Option<MiniBlock<'a>>,
     // number of bytes consumed.
     consumed_bytes: usize,
 }
 
 impl<'a> Block<'a> {
-    pub fn new(
+    pub fn try_new(
         mut values: &'a [u8],
         num_mini_blocks: usize,
         values_per_mini_block: usize,
         length: usize,
-    ) -> Self {
+    ) -> Result<Self, Error> {
         let length = std::cmp::min(length, num_mini_blocks * values_per_mini_block);
 
         let mut consumed_bytes = 0;
-        let (min_delta, consumed) = zigzag_leb128::decode(values);
+        let (min_delta, consumed) = zigzag_leb128::decode(values)?;
         consumed_bytes += consumed;
         values = &values[consumed..];
 
+        if values.len() < num_mini_blocks {
+            return Err(Error::OutOfSpec(
+                "The bit widths of a delta-encoded block are truncated".to_string(),
+            ));
+        }
         let bitwidths = &values[..num_mini_blocks];
         consumed_bytes += num_mini_blocks;
         values = &values[num_mini_blocks..];
@@ -51,60 +84,110 @@ impl<'a> Block<'a> {
         };
 
         // Set up first mini-block
-        block.advance_miniblock();
+        block.advance_miniblock()?;
 
-        block
+        Ok(block)
     }
 
-    fn advance_miniblock(&mut self) {
+    /// Sets up the next mini-block.
+    /// # Errors
+    /// Errors iff its bit width is larger than 64 or its values are truncated. The bit widths of
+    /// the mini-blocks after the last value are arbitrary and thus never validated.
+    fn advance_miniblock(&mut self) -> Result<(), Error> {
         let num_bits = self.bitwidths[0];
         self.bitwidths = &self.bitwidths[1..];
 
         self.current_miniblock = if num_bits > 0 {
+            if num_bits > 64 {
+                return Err(Error::OutOfSpec(format!(
+                    "The bit width of a delta-encoded mini-block must be at most 64, but it is {}",
+                    num_bits
+                )));
+            }
             let length = std::cmp::min(self.remaining, self.values_per_mini_block);
 
-            let miniblock_length = ceil8(self.values_per_mini_block * num_bits as usize);
+            // the last mini-block is padded to `values_per_mini_block` values, but only its
+            // `length` values are required
+            let miniblock_length = self
+                .values_per_mini_block
+                .checked_mul(num_bits as usize)
+                .map(ceil8)
+                .unwrap_or(usize::MAX)
+                .min(self.values.len());
+            if miniblock_length < ceil8(length * num_bits as usize) {
+                return Err(Error::OutOfSpec(
+                    "The values of a delta-encoded mini-block are truncated".to_string(),
+                ));
+            }
             let (miniblock, remainder) = self.values.split_at(miniblock_length);
 
             self.values = remainder;
             self.consumed_bytes += miniblock_length;
 
-            Some(bitpacking::Decoder::new(miniblock, num_bits, length))
+            Some(MiniBlock::new(miniblock, num_bits, length))
         } else {
             None
         };
         self.current_index = 0;
+        Ok(())
     }
 }
 
-impl<'a> Iterator for Block<'a> {
-    type Item = i64;
+impl<'a> Block<'a> {
+    /// Skips up to `n` deltas of this block, returning their (wrapping) sum and how many were
+    /// skipped. Mini-blocks of bit width 0 are skipped without being unpacked.
+    fn skip_deltas(&mut self, n: usize) -> Result<(i64, usize), Error> {
+        let mut sum = 0i64;
+        let mut skipped = 0;
+        while skipped < n && self.remaining > 0 {
+            if self.current_index == self.values_per_mini_block {
+                self.advance_miniblock()?;
+            }
+            let length = (n - skipped)
+                .min(self.values_per_mini_block - self.current_index)
+                .min(self.remaining);
+            sum = match self.current_miniblock.as_mut() {
+                Some(miniblock) => (0..length).fold(sum, |sum, _| {
+                    sum.wrapping_add(self.min_delta.wrapping_add(miniblock.next() as i64))
+                }),
+                None => sum.wrapping_add(self.min_delta.wrapping_mul(length as i64)),
+            };
+            self.current_index += length;
+            self.remaining -= length;
+            skipped += length;
+        }
+        Ok((sum, skipped))
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.remaining == 0 {
-            return None;
+    /// Returns the next delta of this block.
+    /// # Panics
+    /// This function panics iff the block has no remaining deltas.
+    fn next_delta(&mut self) -> Result<i64, Error> {
+        assert!(self.remaining > 0);
+        // the next mini-block is set up lazily, so that its errors are returned with its values
+        if self.current_index == self.values_per_mini_block {
+            self.advance_miniblock()?;
         }
-        let result = self.min_delta
-            + self
-                .current_miniblock
+        // deltas are computed with wrapping arithmetic, as the spec requires
+        let result = self.min_delta.wrapping_add(
+            self.current_miniblock
                 .as_mut()
-                .map(|x| x.next().unwrap())
-                .unwrap_or(0) as i64;
+                .map(|x| x.next())
+                .unwrap_or(0) as i64,
+        );
         self.current_index += 1;
         self.remaining -= 1;
-
-        if self.remaining > 0 && self.current_index == self.values_per_mini_block {
-            self.advance_miniblock();
-        }
-
-        Some(result)
+        Ok(result)
     }
 }
 
-/// Decoder of parquets' `DELTA_BINARY_PACKED`. Implements `Iterator<Item = i64>`.
+/// Decoder of parquets' `DELTA_BINARY_PACKED`. Implements `Iterator<Item = Result<i64, Error>>`.
+///
+/// Values are summed with wrapping arithmetic, as the spec requires. The iterator ends after
+/// returning the first error, e.g. of a malformed block.
 /// # Implementation
 /// This struct does not allocate on the heap.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Decoder<'a> {
     num_mini_blocks: usize,
     values_per_mini_block: usize,
@@ -117,40 +200,55 @@ pub struct Decoder<'a> {
 }
 
 impl<'a> Decoder<'a> {
-    pub fn new(mut values: &'a [u8]) -> Self {
+    /// Returns a new [`Decoder`] of the values in `values`.
+    /// # Errors
+    /// This function errors iff the header or the first block of `values` is out of spec.
+    pub fn try_new(mut values: &'a [u8]) -> Result<Self, Error> {
         let mut consumed_bytes = 0;
-        let (block_size, consumed) = uleb128::decode(values);
+        let (block_size, consumed) = uleb128::decode(values)?;
         consumed_bytes += consumed;
-        assert_eq!(block_size % 128, 0);
         values = &values[consumed..];
-        let (num_mini_blocks, consumed) = uleb128::decode(values);
+        let (num_mini_blocks, consumed) = uleb128::decode(values)?;
         let num_mini_blocks = num_mini_blocks as usize;
         consumed_bytes += consumed;
         values = &values[consumed..];
-        let (total_count, consumed) = uleb128::decode(values);
+        let (total_count, consumed) = uleb128::decode(values)?;
         let total_count = total_count as usize;
         consumed_bytes += consumed;
         values = &values[consumed..];
-        let (first_value, consumed) = zigzag_leb128::decode(values);
+        let (first_value, consumed) = zigzag_leb128::decode(values)?;
         consumed_bytes += consumed;
         values = &values[consumed..];
 
-        let values_per_mini_block = block_size as usize / num_mini_blocks;
-        assert_eq!(values_per_mini_block % 8, 0);
+        if block_size == 0 || block_size % 128 != 0 {
+            return Err(Error::OutOfSpec(format!(
+                "The block size of the delta encoding must be a multiple of 128, but it is {}",
+                block_size
+            )));
+        }
+        let values_per_mini_block = (block_size as usize)
+            .checked_div(num_mini_blocks)
+            .filter(|x| x * num_mini_blocks == block_size as usize && x % 8 == 0)
+            .ok_or_else(|| {
+                Error::OutOfSpec(format!(
+                    "The number of values per mini-block of the delta encoding must be a multiple of 8, but there are {} mini-blocks per block of {} values",
+                    num_mini_blocks, block_size
+                ))
+            })?;
 
         // If we only have one value (first_value), there are no blocks.
         let current_block = if total_count > 1 {
-            Some(Block::new(
+            Some(Block::try_new(
                 values,
                 num_mini_blocks,
                 values_per_mini_block,
                 total_count - 1,
-            ))
+            )?)
         } else {
             None
         };
 
-        Self {
+        Ok(Self {
             num_mini_blocks,
             values_per_mini_block,
             values_remaining: total_count,
@@ -158,54 +256,94 @@ impl<'a> Decoder<'a> {
             values,
             current_block,
             consumed_bytes,
-        }
+        })
     }
 
     /// Returns the total number of bytes consumed up to this point by [`Decoder`].
     pub fn consumed_bytes(&self) -> usize {
         self.consumed_bytes + self.current_block.as_ref().map_or(0, |b| b.consumed_bytes)
     }
-}
 
-impl<'a> Iterator for Decoder<'a> {
-    type Item = i64;
+    /// The number of values that have not been returned nor skipped.
+    pub fn values_left(&self) -> usize {
+        self.values_remaining
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.values_remaining == 0 {
-            return None;
+    /// Advances this decoder by `n` values (or all remaining values if there are less than `n`).
+    ///
+    /// Since every value depends on the previous ones, the deltas of the skipped values are
+    /// still summed, but mini-blocks of bit width 0 (i.e. constant deltas) are not unpacked.
+    /// Unlike [`Iterator::skip`], this does not consume the decoder.
+    /// # Errors
+    /// Errors iff a block of the skipped values is out of spec, in which case the decoder ends.
+    pub fn skip_values(&mut self, mut n: usize) -> Result<(), Error> {
+        while n > 0 && self.values_remaining > 0 {
+            // the last value has no delta after it
+            if self.values_remaining == 1 {
+                self.values_remaining = 0;
+                return Ok(());
+            }
+            let deltas = n.min(self.values_remaining - 1);
+            let (sum, skipped) = self.end_on_error(|block| block.skip_deltas(deltas))?;
+            self.next_value = self.next_value.wrapping_add(sum);
+            self.values_remaining -= skipped;
+            n -= skipped;
         }
+        Ok(())
+    }
 
-        let result = Some(self.next_value);
-
-        self.values_remaining -= 1;
-        if self.values_remaining == 0 {
-            return result;
+    /// Applies `op` to the block with the next delta, loading the next block if the current one
+    /// was consumed. Errors end the decoder.
+    fn end_on_error<T>(
+        &mut self,
+        op: impl FnOnce(&mut Block<'a>) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let result = self.block().and_then(op);
+        if result.is_err() {
+            self.values_remaining = 0;
         }
+        result
+    }
 
+    fn block(&mut self) -> Result<&mut Block<'a>, Error> {
         // At this point we must have at least one block and value available
-        let current_block = self.current_block.as_mut().unwrap();
-        let delta = if let Some(x) = current_block.next() {
-            x
-        } else {
-            // load next block
+        let current_block = self.current_block.as_ref().unwrap();
+        if current_block.remaining == 0 {
             self.values = &self.values[current_block.consumed_bytes..];
             self.consumed_bytes += current_block.consumed_bytes;
 
-            let mut next_block = Block::new(
+            self.current_block = Some(Block::try_new(
                 self.values,
                 self.num_mini_blocks,
                 self.values_per_mini_block,
-                self.values_remaining,
-            );
+                // the current value has no delta in the block
+                self.values_remaining - 1,
+            )?);
+        }
+        Ok(self.current_block.as_mut().unwrap())
+    }
+}
 
-            let delta = next_block.next().unwrap();
-            self.current_block = Some(next_block);
+impl<'a> Iterator for Decoder<'a> {
+    type Item = Result<i64, Error>;
 
-            delta
-        };
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.values_remaining == 0 {
+            return None;
+        }
 
-        self.next_value += delta;
-        result
+        let result = self.next_value;
+
+        // the next value is computed before returning this one; the last value has no delta
+        if self.values_remaining > 1 {
+            let delta = match self.end_on_error(|block| block.next_delta()) {
+                Ok(delta) => delta,
+                Err(error) => return Some(Err(error)),
+            };
+            self.next_value = self.next_value.wrapping_add(delta);
+        }
+        self.values_remaining -= 1;
+        Some(Ok(result))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -213,6 +351,8 @@ impl<'a> Iterator for Decoder<'a> {
     }
 }
 
+impl<'a> ExactSizeIterator for Decoder<'a> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,13 +368,25 @@ mod tests {
         // first_value: 2 <=z> 1
         let data = &[128, 1, 4, 1, 2];
 
-        let mut decoder = Decoder::new(data);
-        let r = decoder.by_ref().collect::<Vec<_>>();
+        let mut decoder = Decoder::try_new(data).unwrap();
+        let r = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
 
         assert_eq!(&r[..], &[1]);
         assert_eq!(decoder.consumed_bytes(), 5);
     }
 
+    #[test]
+    fn empty() {
+        // header: [128, 1, 4, 0, 0]
+        // elements: 0
+        // first_value: 0 (unused)
+        let data = &[128, 1, 4, 0, 0];
+
+        let mut decoder = Decoder::try_new(data).unwrap();
+        assert!(decoder.next().is_none());
+        assert_eq!(decoder.consumed_bytes(), 5);
+    }
+
     #[test]
     fn test_from_spec() {
         let expected = (1..=5).collect::<Vec<_>>();
@@ -249,8 +401,8 @@ mod tests {
         // bit_width: 0
         let data = &[128, 1, 4, 5, 2, 2, 0, 0, 0, 0];
 
-        let mut decoder = Decoder::new(data);
-        let r = decoder.by_ref().collect::<Vec<_>>();
+        let mut decoder = Decoder::try_new(data).unwrap();
+        let r = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
 
         assert_eq!(expected, r);
 
@@ -280,8 +432,43 @@ mod tests {
             1, 2, 3,
         ];
 
-        let mut decoder = Decoder::new(data);
-        let r = decoder.by_ref().collect::<Vec<_>>();
+        let mut decoder = Decoder::try_new(data).unwrap();
+        let r = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(expected, r);
+        assert_eq!(decoder.consumed_bytes(), data.len() - 3);
+    }
+
+    #[test]
+    fn block_size_256() {
+        #[rustfmt::skip]
+        let data = &[
+            // Header: [128, 2, 4, 129, 1, 0]
+            128, 2, // block size <=u> 256
+            4,      // number of mini-blocks <=u> 4 (64 values each)
+            129, 1, // number of elements <=u> 129
+            0,      // first_value <=z> 0
+
+            // Block 1 header: [2, 0, 1, 7, 7]
+            2,          // min_delta <=z> 1
+            0, 1, 7, 7, // bit_widths (the last two are not used and are arbitrary)
+
+            // mini-block 1 has a bit width of 0 and thus no values
+            // 64 1-bit values alternating between 1 and 0 for mini-block 2 (8 bytes)
+            0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55, 0x55,
+
+            // these should not be consumed
+            1, 2, 3,
+        ];
+
+        let mut expected = vec![0i64];
+        // mini-block 1: 64 deltas of 1
+        (0..64).for_each(|_| expected.push(expected.last().unwrap() + 1));
+        // mini-block 2: deltas alternating between 2 and 1
+        (0..64).for_each(|i| expected.push(expected.last().unwrap() + 2 - i % 2));
+
+        let mut decoder = Decoder::try_new(data).unwrap();
+        let r = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
 
         assert_eq!(expected, r);
         assert_eq!(decoder.consumed_bytes(), data.len() - 3);
@@ -326,10 +513,114 @@ mod tests {
             -2, 2, 6, 10, 14, 18, 22, 26, 30, 34, 38, 42, 46, 50,
         ];
 
-        let mut decoder = Decoder::new(data);
-        let r = decoder.by_ref().collect::<Vec<_>>();
+        let mut decoder = Decoder::try_new(data).unwrap();
+        let r = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
 
         assert_eq!(&expected[..], &r[..]);
         assert_eq!(decoder.consumed_bytes(), data.len() - 3);
     }
+
+    #[test]
+    fn invalid_header() {
+        // block size of 100, not a multiple of 128
+        assert!(Decoder::try_new(&[100, 4, 1, 2]).is_err());
+        // 0 mini-blocks
+        assert!(Decoder::try_new(&[128, 1, 0, 1, 2]).is_err());
+        // 3 mini-blocks of 128 / 3 values
+        assert!(Decoder::try_new(&[128, 1, 3, 1, 2]).is_err());
+        // truncated header
+        assert!(Decoder::try_new(&[128]).is_err());
+        assert!(Decoder::try_new(&[128, 1, 4, 5]).is_err());
+        // the first block has no bit widths
+        assert!(Decoder::try_new(&[128, 1, 4, 5, 2, 2, 0]).is_err());
+
+        assert!(Decoder::try_new(&[128, 1, 4, 1, 2]).is_ok());
+    }
+
+    #[test]
+    fn invalid_bit_width() {
+        // the first mini-block has a bit width of 65
+        let data = &[128, 1, 4, 2, 2, 0, 65, 0, 0, 0];
+        assert!(matches!(Decoder::try_new(data), Err(Error::OutOfSpec(_))));
+
+        // the second mini-block has a bit width of 65
+        let mut data = vec![128, 1, 4, 34, 2, 0, 0, 65, 0, 0];
+        data.extend(std::iter::repeat(0).take(65 * 4));
+        let mut decoder = Decoder::try_new(&data).unwrap();
+        let values = decoder
+            .by_ref()
+            .take(32)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values, vec![1; 32]);
+        assert!(matches!(decoder.next(), Some(Err(Error::OutOfSpec(_)))));
+        // the decoder ends after an error
+        assert!(decoder.next().is_none());
+
+        let mut decoder = Decoder::try_new(&data).unwrap();
+        assert!(decoder.skip_values(40).is_err());
+        assert!(decoder.next().is_none());
+    }
+
+    #[test]
+    fn truncated_miniblock() {
+        // 3 values of 3 bits need 2 bytes
+        let data = &[128, 1, 4, 4, 2, 0, 3, 0, 0, 0, 0];
+        assert!(matches!(Decoder::try_new(data), Err(Error::OutOfSpec(_))));
+
+        // the last mini-block need not be padded
+        let data = &[128, 1, 4, 4, 2, 0, 3, 0, 0, 0, 0b11010001, 0b0];
+        let values = Decoder::try_new(data)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values, vec![1, 2, 4, 7]);
+
+        // the second block is truncated
+        let mut data = vec![128, 1, 4, 130, 1, 2, 0, 0, 0, 0, 0];
+        let mut decoder = Decoder::try_new(&data).unwrap();
+        assert_eq!(
+            decoder.by_ref().take(128).filter(Result::is_ok).count(),
+            128
+        );
+        assert!(matches!(decoder.next(), Some(Err(Error::OutOfSpec(_)))));
+        assert!(decoder.next().is_none());
+
+        // the second block has no values
+        data.extend_from_slice(&[0, 1, 0, 0, 0]);
+        let mut decoder = Decoder::try_new(&data).unwrap();
+        assert_eq!(
+            decoder.by_ref().take(128).filter(Result::is_ok).count(),
+            128
+        );
+        assert!(matches!(decoder.next(), Some(Err(Error::OutOfSpec(_)))));
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        // a first value of i64::MAX followed by deltas of i64::MAX and -1
+        let mut data = vec![128, 1, 4, 3];
+        let (first_value, length) = zigzag_leb128::encode(i64::MAX);
+        data.extend_from_slice(&first_value[..length]);
+        // a min delta of -1 and relative deltas of [2^63, 0] in a mini-block of 64 bits
+        let (min_delta, length) = zigzag_leb128::encode(-1);
+        data.extend_from_slice(&min_delta[..length]);
+        data.extend_from_slice(&[64, 0, 0, 0]);
+        let mut deltas = vec![0u8; 32 * 8];
+        deltas[..8].copy_from_slice(&(i64::MAX as u64 + 1).to_le_bytes());
+        data.extend_from_slice(&deltas);
+
+        let values = Decoder::try_new(&data)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                i64::MAX,
+                i64::MAX.wrapping_add(i64::MAX),
+                i64::MAX.wrapping_add(i64::MAX).wrapping_sub(1)
+            ]
+        );
+    }
 }
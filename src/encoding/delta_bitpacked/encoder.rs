@@ -1,73 +1,115 @@
 use crate::encoding::ceil8;
-use crate::read::levels::get_bit_width;
 
 use super::super::bitpacking;
 use super::super::uleb128;
 use super::super::zigzag_leb128;
 
-/// Encodes an iterator of `i32` according to parquet's `DELTA_BINARY_PACKED`.
+const BLOCK_SIZE: usize = 128;
+const NUM_MINI_BLOCKS: usize = 4;
+const VALUES_PER_MINI_BLOCK: usize = BLOCK_SIZE / NUM_MINI_BLOCKS;
+
+/// Encodes an iterator of `i64` according to parquet's `DELTA_BINARY_PACKED`.
 /// # Implementation
 /// * This function does not allocate on the heap.
-/// * The number of mini-blocks is always 1. This may change in the future.
-pub fn encode<I: Iterator<Item = i64>>(mut iterator: I, buffer: &mut Vec<u8>) {
-    let block_size = 128;
-    let mini_blocks = 1;
+/// * Blocks have 128 values divided in 4 mini-blocks of 32 values.
+/// * Deltas are computed with wrapping arithmetic, so that any sequence of `i64` is encoded.
+/// # Panics
+/// This function panics if the upper bound of the iterator's `size_hint` is not its length.
+pub fn encode<I: Iterator<Item = i64>>(iterator: I, buffer: &mut Vec<u8>) {
+    encode_deltas(iterator, buffer, |value, prev| value.wrapping_sub(prev))
+}
+
+/// Encodes an iterator of `i32` according to parquet's `DELTA_BINARY_PACKED`.
+///
+/// Like the decoders of other implementations of the `INT32` physical type, deltas are
+/// computed in (wrapping) `i32` arithmetic and thus are bit-packed with at most 32 bits.
+/// # Panics
+/// This function panics if the upper bound of the iterator's `size_hint` is not its length.
+pub fn encode_i32<I: Iterator<Item = i32>>(iterator: I, buffer: &mut Vec<u8>) {
+    encode_deltas(iterator.map(|x| x as i64), buffer, |value, prev| {
+        (value as i32).wrapping_sub(prev as i32) as i64
+    })
+}
 
+fn encode_deltas<I: Iterator<Item = i64>>(
+    mut iterator: I,
+    buffer: &mut Vec<u8>,
+    delta: fn(i64, i64) -> i64,
+) {
     let mut container = [0u8; 10];
-    let encoded_len = uleb128::encode(block_size, &mut container);
+    let encoded_len = uleb128::encode(BLOCK_SIZE as u64, &mut container);
     buffer.extend_from_slice(&container[..encoded_len]);
 
-    let encoded_len = uleb128::encode(mini_blocks, &mut container);
+    let encoded_len = uleb128::encode(NUM_MINI_BLOCKS as u64, &mut container);
     buffer.extend_from_slice(&container[..encoded_len]);
 
     let length = iterator.size_hint().1.unwrap();
     let encoded_len = uleb128::encode(length as u64, &mut container);
     buffer.extend_from_slice(&container[..encoded_len]);
 
-    let mut values = [0i64; 128];
-    let mut deltas = [0u32; 128];
-
-    let first_value = iterator.next().unwrap();
+    // an empty sequence still declares a (unused) first value
+    let first_value = iterator.next().unwrap_or_default();
     let (container, encoded_len) = zigzag_leb128::encode(first_value);
     buffer.extend_from_slice(&container[..encoded_len]);
 
+    let mut deltas = [0i64; BLOCK_SIZE];
     let mut prev = first_value;
-    let mut length = iterator.size_hint().1.unwrap();
-    while length != 0 {
-        for (i, v) in (0..128).zip(&mut iterator) {
-            values[i] = v - prev;
-            prev = v;
+    loop {
+        let mut num_deltas = 0;
+        for (d, value) in deltas.iter_mut().zip(&mut iterator) {
+            *d = delta(value, prev);
+            prev = value;
+            num_deltas += 1;
         }
-        let consumed = std::cmp::min(length - iterator.size_hint().1.unwrap(), 128);
-        let values = &values[..consumed];
-
-        let min_delta = *values.iter().min().unwrap();
-        let max_delta = *values.iter().max().unwrap();
-
-        values.iter().zip(deltas.iter_mut()).for_each(|(v, d)| {
-            *d = (v - min_delta) as u32;
-        });
-
-        // <min delta> <list of bitwidths of miniblocks> <miniblocks>
-        let (container, encoded_len) = zigzag_leb128::encode(min_delta);
-        buffer.extend_from_slice(&container[..encoded_len]);
-
-        let num_bits = get_bit_width((max_delta - min_delta) as i16) as u8;
-        buffer.push(num_bits);
-
-        if num_bits > 0 {
-            let start = buffer.len();
+        if num_deltas == 0 {
+            break;
+        }
+        encode_block(&deltas[..num_deltas], buffer);
+    }
+}
 
-            // bitpack encode all (deltas.len = 128 which is a multiple of 32)
-            let bytes_needed = start + ceil8(deltas.len() * num_bits as usize);
-            buffer.resize(bytes_needed, 0);
-            bitpacking::encode(deltas.as_ref(), num_bits, &mut buffer[start..]);
+/// Encodes a block of (at most [`BLOCK_SIZE`]) deltas:
+/// `<min delta> <list of bitwidths of miniblocks> <miniblocks>`
+fn encode_block(deltas: &[i64], buffer: &mut Vec<u8>) {
+    let min_delta = *deltas.iter().min().unwrap();
+    let (container, encoded_len) = zigzag_leb128::encode(min_delta);
+    buffer.extend_from_slice(&container[..encoded_len]);
 
-            let bytes_needed = start + ceil8(deltas.len() * num_bits as usize);
-            buffer.truncate(bytes_needed);
-        }
+    // relative to `min_delta`, deltas are non-negative and fit in a `u64`
+    let mut relative = [0u64; BLOCK_SIZE];
+    relative
+        .iter_mut()
+        .zip(deltas)
+        .for_each(|(r, d)| *r = d.wrapping_sub(min_delta) as u64);
+    let relative = &relative[..deltas.len()];
+
+    // SPEC: mini-blocks without values have a bitwidth but are not stored
+    let bitwidths_start = buffer.len();
+    buffer.extend_from_slice(&[0; NUM_MINI_BLOCKS]);
+    for (i, mini_block) in relative.chunks(VALUES_PER_MINI_BLOCK).enumerate() {
+        let max = mini_block.iter().copied().max().unwrap();
+        let num_bits = (64 - max.leading_zeros()) as u8;
+        buffer[bitwidths_start + i] = num_bits;
+        encode_mini_block(mini_block, num_bits, buffer);
+    }
+}
 
-        length = iterator.size_hint().1.unwrap();
+/// Bit-packs a mini-block with `num_bits`, padding it with zeros to [`VALUES_PER_MINI_BLOCK`]
+/// values (the last mini-block with values may be partial).
+fn encode_mini_block(values: &[u64], num_bits: u8, buffer: &mut Vec<u8>) {
+    let start = buffer.len();
+    buffer.resize(start + ceil8(VALUES_PER_MINI_BLOCK * num_bits as usize), 0);
+    let packed = &mut buffer[start..];
+
+    if num_bits > 32 {
+        bitpacking::encode_u64(values, num_bits, packed);
+    } else if num_bits > 0 {
+        let mut narrow = [0u32; VALUES_PER_MINI_BLOCK];
+        narrow
+            .iter_mut()
+            .zip(values)
+            .for_each(|(n, v)| *n = *v as u32);
+        bitpacking::encode(&narrow, num_bits, packed);
     }
 }
 
@@ -79,14 +121,14 @@ mod tests {
     fn constant_delta() {
         // header: [128, 1, 1, 5, 2]:
         //  block size: 128    <=u> 128, 1
-        //  mini-blocks: 1     <=u> 1
+        //  mini-blocks: 4     <=u> 4
         //  elements: 5        <=u> 5
         //  first_value: 2     <=z> 1
         // block1: [2, 0, 0, 0, 0]
         //  min_delta: 1        <=z> 2
-        //  bitwidth: 0
+        //  bitwidths: [0, 0, 0, 0]
         let data = 1..=5;
-        let expected = vec![128u8, 1, 1, 5, 2, 2, 0];
+        let expected = vec![128u8, 1, 4, 5, 2, 2, 0, 0, 0, 0];
 
         let mut buffer = vec![];
         encode(data, &mut buffer);
@@ -99,18 +141,18 @@ mod tests {
         let data = vec![1, 2, 3, 4, 5, 1];
         // header: [128, 1, 4, 6, 2]
         //  block size: 128    <=u> 128, 1
-        //  mini-blocks: 1     <=u> 1
-        //  elements: 6        <=u> 5
+        //  mini-blocks: 4     <=u> 4
+        //  elements: 6        <=u> 6
         //  first_value: 2     <=z> 1
-        // block1: [7, 3, 253, 255]
+        // block1: [7, 3, 0, 0, 0, 253, 255]
         //  min_delta: -4        <=z> 7
-        //  bitwidth: 3
+        //  bitwidths: [3, 0, 0, 0]
         //  values: [5, 5, 5, 5, 0] <=b> [
         //      0b01101101
         //      0b00001011
         // ]
-        let mut expected = vec![128u8, 1, 1, 6, 2, 7, 3, 0b01101101, 0b00001011];
-        expected.extend(std::iter::repeat(0).take(128 * 3 / 8 - 2)); // 128 values, 3 bits, 2 already used
+        let mut expected = vec![128u8, 1, 4, 6, 2, 7, 3, 0, 0, 0, 0b01101101, 0b00001011];
+        expected.extend(std::iter::repeat(0).take(32 * 3 / 8 - 2)); // 32 values, 3 bits, 2 already used
 
         let mut buffer = vec![];
         encode(data.into_iter(), &mut buffer);
@@ -2,7 +2,7 @@ mod decoder;
 mod encoder;
 
 pub use decoder::Decoder;
-pub use encoder::encode;
+pub use encoder::{encode, encode_i32};
 
 #[cfg(test)]
 mod tests {
@@ -14,9 +14,9 @@ mod tests {
 
         let mut buffer = vec![];
         encode(data.clone().into_iter(), &mut buffer);
-        let iter = Decoder::new(&buffer);
+        let iter = Decoder::try_new(&buffer).unwrap();
 
-        let result = iter.collect::<Vec<_>>();
+        let result = iter.collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(result, data);
     }
 
@@ -26,9 +26,9 @@ mod tests {
 
         let mut buffer = vec![];
         encode(data.clone().into_iter(), &mut buffer);
-        let iter = Decoder::new(&buffer);
+        let iter = Decoder::try_new(&buffer).unwrap();
 
-        let result = iter.collect::<Vec<_>>();
+        let result = iter.collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(result, data);
     }
 
@@ -41,12 +41,133 @@ mod tests {
 
         let mut buffer = vec![];
         encode(data.clone().into_iter(), &mut buffer);
-        let iter = Decoder::new(&buffer);
+        let iter = Decoder::try_new(&buffer).unwrap();
 
-        let result = iter.collect::<Vec<_>>();
+        let result = iter.collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(result, data);
     }
 
+    #[test]
+    fn empty() {
+        let mut buffer = vec![];
+        encode(std::iter::empty(), &mut buffer);
+        let len = buffer.len();
+        let mut iter = Decoder::try_new(&buffer).unwrap();
+
+        assert!(iter.next().is_none());
+        assert_eq!(iter.consumed_bytes(), len);
+    }
+
+    #[test]
+    fn random_round_trip() {
+        let mut random = crate::tests::xorshift();
+
+        for length in [1, 2, 127, 128, 129, 256, 257, 1000] {
+            for range in [1, 100, i32::MAX as u64] {
+                let data = random
+                    .by_ref()
+                    .take(length)
+                    .map(|x| (x % range) as i64 - (range / 2) as i64)
+                    .collect::<Vec<_>>();
+
+                let mut buffer = vec![];
+                encode(data.clone().into_iter(), &mut buffer);
+                let len = buffer.len();
+                let mut iter = Decoder::try_new(&buffer).unwrap();
+
+                let result = iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+                assert_eq!(result, data);
+                assert_eq!(iter.consumed_bytes(), len);
+            }
+        }
+    }
+
+    fn round_trip(data: Vec<i64>) {
+        let mut buffer = vec![];
+        encode(data.clone().into_iter(), &mut buffer);
+        let len = buffer.len();
+        let mut iter = Decoder::try_new(&buffer).unwrap();
+
+        let result = iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(result, data);
+        assert_eq!(iter.consumed_bytes(), len);
+    }
+
+    #[test]
+    fn sorted_round_trip() {
+        for length in [1, 31, 32, 33, 129, 1000] {
+            round_trip((0..length).map(|x| 1_000_000 + 3 * x).collect());
+        }
+    }
+
+    #[test]
+    fn constant_round_trip() {
+        for value in [0, -7, i64::MIN, i64::MAX] {
+            let data = vec![value; 300];
+            round_trip(data.clone());
+
+            // every bitwidth is 0: the blocks only have the min delta and the bitwidths
+            let mut buffer = vec![];
+            encode(data.into_iter(), &mut buffer);
+            let (_, header) = crate::encoding::zigzag_leb128::encode(value);
+            assert_eq!(buffer.len(), 5 + header + 3 * (1 + 4));
+        }
+    }
+
+    #[test]
+    fn extreme_values_round_trip() {
+        round_trip(vec![
+            i64::MIN,
+            i64::MAX,
+            i64::MIN,
+            0,
+            i64::MAX,
+            -1,
+            i64::MIN,
+        ]);
+        round_trip(vec![i64::MAX, i64::MIN, i64::MAX - 1, i64::MIN + 1]);
+        round_trip((0..200).map(|x| i64::MAX - x).collect());
+        round_trip((0..200).map(|x| i64::MIN + x * x).collect());
+    }
+
+    #[test]
+    fn random_i64_round_trip() {
+        // xorshift, so that the test is deterministic
+        let mut state = 0x9e3779b97f4a7c15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as i64
+        };
+
+        for length in [2, 33, 128, 200, 1000] {
+            round_trip((0..length).map(|_| next()).collect());
+            // deltas of very different widths across mini-blocks
+            round_trip((0..length).map(|x| next() >> (x % 64)).collect());
+        }
+    }
+
+    #[test]
+    fn i32_round_trip() {
+        let data = vec![i32::MIN, i32::MAX, 0, i32::MIN, -1, i32::MAX, 1];
+
+        let mut buffer = vec![];
+        encode_i32(data.clone().into_iter(), &mut buffer);
+        let len = buffer.len();
+        let mut iter = Decoder::try_new(&buffer).unwrap();
+
+        // i32 values are the lower half of the decoded values
+        let result = iter.by_ref().map(|x| x.unwrap() as i32).collect::<Vec<_>>();
+        assert_eq!(result, data);
+        assert_eq!(iter.consumed_bytes(), len);
+
+        // the wrapped deltas of i32 fit in 32 bits
+        let mut i64_buffer = vec![];
+        encode(data.iter().map(|x| *x as i64), &mut i64_buffer);
+        assert!(buffer.len() < i64_buffer.len());
+    }
+
     #[test]
     fn test_another() {
         let data = vec![2, 3, 1, 2, 1];
@@ -54,11 +175,81 @@ mod tests {
         let mut buffer = vec![];
         encode(data.clone().into_iter(), &mut buffer);
         let len = buffer.len();
-        let mut iter = Decoder::new(&buffer);
+        let mut iter = Decoder::try_new(&buffer).unwrap();
 
-        let result = iter.by_ref().collect::<Vec<_>>();
+        let result = iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(result, data);
 
         assert_eq!(iter.consumed_bytes(), len);
     }
+
+    fn check_skips(data: &[i64], skips: &[usize]) {
+        let mut buffer = vec![];
+        encode(data.iter().copied(), &mut buffer);
+
+        let mut decoder = Decoder::try_new(&buffer).unwrap();
+        let mut expected = data;
+        for n in skips {
+            decoder.skip_values(*n).unwrap();
+            expected = &expected[(*n).min(expected.len())..];
+            assert_eq!(decoder.values_left(), expected.len());
+            assert_eq!(decoder.len(), expected.len());
+            assert_eq!(
+                decoder.clone().collect::<Result<Vec<_>, _>>().unwrap(),
+                expected,
+                "{:?}",
+                skips
+            );
+            // the decoder continues after the next value
+            assert_eq!(
+                decoder.next().map(|x| x.unwrap()),
+                expected.first().copied()
+            );
+            expected = expected.get(1..).unwrap_or_default();
+        }
+        decoder.by_ref().for_each(|_| {});
+        assert_eq!(decoder.consumed_bytes(), buffer.len());
+    }
+
+    #[test]
+    fn skip_values() {
+        // 2 blocks of 4 mini-blocks of 32 values, plus some values
+        let data = (0..300i64)
+            .map(|x| (x * x) % 1001 - 500)
+            .collect::<Vec<_>>();
+        for skips in [
+            vec![0],
+            vec![3],
+            vec![31],
+            vec![32],
+            vec![31, 0, 33],
+            vec![100, 27],
+            vec![127],
+            vec![128],
+            vec![130, 100],
+            vec![5, 250],
+            vec![298],
+            vec![299],
+            vec![300],
+            vec![1000],
+        ] {
+            check_skips(&data, &skips);
+        }
+    }
+
+    #[test]
+    fn skip_constant_deltas() {
+        // the deltas are constant, so that mini-blocks have a bit width of 0
+        let data = (0..300i64).map(|x| x * 3 - 100).collect::<Vec<_>>();
+        for skips in [vec![40], vec![128, 1], vec![1, 200], vec![299]] {
+            check_skips(&data, &skips);
+        }
+    }
+
+    #[test]
+    fn skip_single_value() {
+        check_skips(&[7], &[0]);
+        check_skips(&[7], &[1]);
+        check_skips(&[], &[1]);
+    }
 }
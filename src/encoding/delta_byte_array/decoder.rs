@@ -1,3 +1,5 @@
+use crate::error::Error;
+
 use super::super::delta_bitpacked;
 use super::super::delta_length_byte_array;
 
@@ -12,28 +14,118 @@ pub struct Decoder<'a> {
 }
 
 impl<'a> Decoder<'a> {
-    pub fn new(values: &'a [u8]) -> Self {
-        let prefix_lengths = delta_bitpacked::Decoder::new(values);
-        Self {
+    /// Returns a new [`Decoder`] of the prefix lengths, lengths and values in `values`.
+    /// # Errors
+    /// This function errors iff the header of the prefix lengths is out of spec.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        let prefix_lengths = delta_bitpacked::Decoder::try_new(values)?;
+        Ok(Self {
             values,
             prefix_lengths,
-        }
+        })
     }
 
-    pub fn into_lengths(self) -> delta_length_byte_array::Decoder<'a> {
+    /// Consumes this decoder and returns the decoder of the lengths of the suffixes.
+    /// # Panics
+    /// This function panics if this iterator has not been fully consumed.
+    /// # Errors
+    /// This function errors iff the suffixes are missing or their header is out of spec.
+    pub fn into_lengths(self) -> Result<delta_length_byte_array::Decoder<'a>, Error> {
         assert_eq!(self.prefix_lengths.size_hint().0, 0);
-        delta_length_byte_array::Decoder::new(&self.values[self.prefix_lengths.consumed_bytes()..])
+        let suffixes = self
+            .values
+            .get(self.prefix_lengths.consumed_bytes()..)
+            .ok_or_else(|| {
+                Error::OutOfSpec("Delta strings are missing their suffixes".to_string())
+            })?;
+        delta_length_byte_array::Decoder::try_new(suffixes)
     }
 }
 
 impl<'a> Iterator for Decoder<'a> {
-    type Item = u32;
+    type Item = Result<u32, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.prefix_lengths.next().map(|x| x as u32)
+        self.prefix_lengths.next().map(|x| x.map(|x| x as u32))
+    }
+}
+
+/// Decodes [Delta strings](https://github.com/apache/parquet-format/blob/master/Encodings.md#delta-strings-delta_byte_array--7)
+/// into an iterator over each of its values.
+/// # Implementation
+/// Each value is the prefix of the previous value followed by its suffix, so the previous value is
+/// kept in a buffer that is re-used across values. Each item is a new allocation.
+#[derive(Debug)]
+pub struct BinaryDecoder<'a> {
+    prefix_lengths: delta_bitpacked::Decoder<'a>,
+    suffixes: delta_length_byte_array::BinaryDecoder<'a>,
+    last_value: Vec<u8>,
+}
+
+impl<'a> BinaryDecoder<'a> {
+    /// Returns a new [`BinaryDecoder`].
+    /// # Errors
+    /// This function errors iff the prefixes or suffixes are out of spec, their numbers differ
+    /// or a prefix is longer than the previous value (or non-zero for the first value).
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        let prefix_lengths = delta_bitpacked::Decoder::try_new(values)?;
+
+        let mut prefixes = prefix_lengths.clone();
+        prefixes.by_ref().try_for_each(|x| x.map(|_| ()))?;
+        let suffixes = values.get(prefixes.consumed_bytes()..).ok_or_else(|| {
+            Error::OutOfSpec("Delta strings are missing their suffixes".to_string())
+        })?;
+        let suffixes = delta_length_byte_array::BinaryDecoder::try_new(suffixes)?;
+
+        if prefix_lengths.size_hint().0 != suffixes.len() {
+            return Err(Error::OutOfSpec(
+                "Delta strings must have the same number of prefixes and suffixes".to_string(),
+            ));
+        }
+
+        let mut last_length = 0usize;
+        for (prefix_length, suffix) in prefix_lengths.clone().zip(suffixes.clone()) {
+            let prefix_length: usize = prefix_length?.try_into().map_err(|_| {
+                Error::OutOfSpec("Delta strings prefix lengths must be positive".to_string())
+            })?;
+            if prefix_length > last_length {
+                return Err(Error::OutOfSpec(format!(
+                    "Delta strings prefix length {} is larger than the previous value ({} bytes)",
+                    prefix_length, last_length
+                )));
+            }
+            last_length = prefix_length + suffix.len();
+        }
+
+        Ok(Self {
+            prefix_lengths,
+            suffixes,
+            last_value: vec![],
+        })
+    }
+}
+
+impl<'a> Iterator for BinaryDecoder<'a> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // prefix lengths were validated on `try_new`
+        let prefix_length = self.prefix_lengths.next()?.ok()? as usize;
+        let suffix = self.suffixes.next()?;
+
+        self.last_value.truncate(prefix_length);
+        self.last_value.extend_from_slice(suffix);
+        Some(self.last_value.clone())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.suffixes.size_hint()
     }
 }
 
+impl<'a> ExactSizeIterator for BinaryDecoder<'a> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,18 +147,98 @@ mod tests {
         let expected_values = expected.join("");
         let expected_values = expected_values.as_bytes();
 
-        let mut decoder = Decoder::new(data);
-        let prefixes = decoder.by_ref().collect::<Vec<_>>();
+        let mut decoder = Decoder::try_new(data).unwrap();
+        let prefixes = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(prefixes, expected_prefixes);
 
         // move to the lengths
-        let mut decoder = decoder.into_lengths();
+        let mut decoder = decoder.into_lengths().unwrap();
 
-        let lengths = decoder.by_ref().collect::<Vec<_>>();
+        let lengths = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(lengths, expected_lengths);
 
         // move to the values
-        let values = decoder.values();
+        let values = decoder.values().unwrap();
         assert_eq!(values, expected_values);
     }
+
+    /// Encodes `values` as delta strings, using `prefix_lengths` as the prefix of each value.
+    fn encode(values: &[&str], prefix_lengths: &[usize]) -> Vec<u8> {
+        let mut buffer = vec![];
+        delta_bitpacked::encode(prefix_lengths.iter().map(|x| *x as i64), &mut buffer);
+        let suffixes = values
+            .iter()
+            .zip(prefix_lengths)
+            .map(|(value, prefix_length)| &value.as_bytes()[*prefix_length..])
+            .collect::<Vec<_>>();
+        delta_length_byte_array::encode(suffixes.into_iter(), &mut buffer);
+        buffer
+    }
+
+    #[test]
+    fn binary_from_spark() {
+        let data = &[
+            128, 1, 4, 2, 0, 0, 0, 0, 0, 0, 128, 1, 4, 2, 10, 0, 0, 0, 0, 0, 72, 101, 108, 108,
+            111, 87, 111, 114, 108, 100,
+        ];
+
+        let result = BinaryDecoder::try_new(data).unwrap().collect::<Vec<_>>();
+        assert_eq!(result, vec![b"Hello".to_vec(), b"World".to_vec()]);
+    }
+
+    #[test]
+    fn binary_sorted_keys() {
+        let values = [
+            "",
+            "apple",
+            "applesauce",
+            "apricot",
+            "banana",
+            "bandana",
+            "band",
+            "\u{e9}clair",
+            "\u{e9}cole",
+            "\u{e9}cole",
+        ];
+        // the length of the common prefix with the previous value
+        let prefix_lengths = values
+            .iter()
+            .scan("", |last, value| {
+                let prefix = last
+                    .bytes()
+                    .zip(value.bytes())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                *last = value;
+                Some(prefix)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(prefix_lengths, vec![0, 0, 5, 2, 0, 3, 4, 0, 3, 6]);
+
+        let data = encode(&values, &prefix_lengths);
+        let decoder = BinaryDecoder::try_new(&data).unwrap();
+        assert_eq!(decoder.len(), values.len());
+
+        let result = decoder
+            .map(|x| String::from_utf8(x).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(result, values);
+    }
+
+    #[test]
+    fn binary_prefix_too_long() {
+        // the first value can't have a prefix
+        let data = encode(&["aa", "ab"], &[1, 1]);
+        assert!(matches!(
+            BinaryDecoder::try_new(&data),
+            Err(Error::OutOfSpec(_))
+        ));
+
+        // "a" is shorter than the prefix of the next value
+        let data = encode(&["a", "aab"], &[0, 2]);
+        assert!(matches!(
+            BinaryDecoder::try_new(&data),
+            Err(Error::OutOfSpec(_))
+        ));
+    }
 }
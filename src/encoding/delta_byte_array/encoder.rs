@@ -0,0 +1,55 @@
+use crate::encoding::delta_bitpacked;
+
+/// Encodes a clonable iterator of `&[u8]` into `buffer`. This does not allocate on the heap.
+/// # Implementation
+/// This encoding is equivalent to call [`delta_bitpacked::encode`] on the length of the prefix
+/// each item shares with the previous item, followed by encoding the remaining suffixes with
+/// [`delta_length_byte_array::encode`](crate::encoding::delta_length_byte_array::encode).
+pub fn encode<A: AsRef<[u8]>, I: Iterator<Item = A> + Clone>(iterator: I, buffer: &mut Vec<u8>) {
+    // pairs each item with its previous item
+    let with_previous = || {
+        std::iter::once(None)
+            .chain(iterator.clone().map(Some))
+            .zip(iterator.clone())
+    };
+
+    // `take` declares `length` as the upper bound of the size hints
+    let length = iterator.clone().count();
+
+    let prefix_lengths = with_previous().map(|(previous, item)| prefix_length(previous, &item));
+    delta_bitpacked::encode(prefix_lengths.map(|x| x as i64).take(length), buffer);
+
+    let suffixes = || {
+        with_previous().map(|(previous, item)| {
+            let prefix_length = prefix_length(previous, &item);
+            (item, prefix_length)
+        })
+    };
+
+    let mut total_length = 0;
+    delta_bitpacked::encode(
+        suffixes()
+            .map(|(item, prefix_length)| {
+                let len = item.as_ref().len() - prefix_length;
+                total_length += len;
+                len as i64
+            })
+            .take(length),
+        buffer,
+    );
+    buffer.reserve(total_length);
+    suffixes().for_each(|(item, prefix_length)| buffer.extend(&item.as_ref()[prefix_length..]))
+}
+
+/// Returns the length of the prefix shared by `previous` and `item`.
+#[inline]
+fn prefix_length<A: AsRef<[u8]>>(previous: Option<A>, item: &A) -> usize {
+    previous.map_or(0, |previous| {
+        previous
+            .as_ref()
+            .iter()
+            .zip(item.as_ref())
+            .take_while(|(a, b)| a == b)
+            .count()
+    })
+}
@@ -1,3 +1,119 @@
 mod decoder;
+mod encoder;
 
-pub use decoder::Decoder;
+pub use decoder::{BinaryDecoder, Decoder};
+pub use encoder::encode;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &[Vec<u8>]) {
+        let mut buffer = vec![];
+        encode(data.iter(), &mut buffer);
+
+        let result = BinaryDecoder::try_new(&buffer).unwrap().collect::<Vec<_>>();
+        assert_eq!(result, data);
+    }
+
+    #[test]
+    fn basic() {
+        let data = ["Hello", "Help", "", "World", "Worlds", "Worlds"]
+            .iter()
+            .map(|x| x.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+
+        let mut buffer = vec![];
+        encode(data.iter(), &mut buffer);
+
+        let mut decoder = Decoder::try_new(&buffer).unwrap();
+        let prefixes = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(prefixes, vec![0, 3, 0, 0, 5, 6]);
+
+        let mut decoder = decoder.into_lengths().unwrap();
+        let lengths = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(lengths, vec![5, 1, 0, 5, 1, 0]);
+        assert_eq!(decoder.values().unwrap(), b"HellopWorlds");
+
+        round_trip(&data);
+    }
+
+    #[test]
+    fn missing_suffixes() {
+        let mut buffer = vec![];
+        super::super::delta_bitpacked::encode([0i64, 1].into_iter(), &mut buffer);
+
+        let mut decoder = Decoder::try_new(&buffer).unwrap();
+        let prefixes = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(prefixes, vec![0, 1]);
+        assert!(decoder.into_lengths().is_err());
+    }
+
+    #[test]
+    fn empty() {
+        round_trip(&[]);
+        round_trip(&[vec![]]);
+        round_trip(&[vec![], vec![]]);
+    }
+
+    #[test]
+    fn identical_and_non_utf8() {
+        let data = vec![
+            vec![0xff, 0xfe, 0x00],
+            vec![0xff, 0xfe, 0x00],
+            vec![0xff, 0xfe, 0x00, 0xc3],
+            vec![0xff],
+            vec![0xff],
+        ];
+
+        let mut buffer = vec![];
+        encode(data.iter(), &mut buffer);
+
+        // the first value has no prefix and identical values have no suffix
+        let mut decoder = Decoder::try_new(&buffer).unwrap();
+        let prefixes = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(prefixes, vec![0, 3, 3, 1, 1]);
+
+        let mut decoder = decoder.into_lengths().unwrap();
+        let lengths = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(lengths, vec![3, 0, 1, 0, 0]);
+        assert_eq!(decoder.values().unwrap(), &[0xff, 0xfe, 0x00, 0xc3]);
+
+        round_trip(&data);
+    }
+
+    #[test]
+    fn long_prefixes() {
+        // the prefixes and suffixes span multiple blocks of 128 values
+        let prefix = "a".repeat(1000);
+        let data = (0..300)
+            .map(|x| format!("{}{:03}", &prefix[..x * 3], x).into_bytes())
+            .collect::<Vec<_>>();
+        round_trip(&data);
+    }
+
+    #[test]
+    fn random_round_trip() {
+        let mut random = crate::tests::xorshift();
+
+        for length in [1, 2, 127, 128, 129, 300] {
+            // a small alphabet results in common prefixes
+            for alphabet in [1, 2, 256] {
+                let mut data = (0..length)
+                    .map(|_| {
+                        let len = (random.next().unwrap() % 20) as usize;
+                        random
+                            .by_ref()
+                            .take(len)
+                            .map(|x| (x % alphabet) as u8)
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                round_trip(&data);
+
+                data.sort();
+                round_trip(&data);
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::Error;
+
 use super::super::delta_bitpacked;
 
 /// Decodes [Delta-length byte array](https://github.com/apache/parquet-format/blob/master/Encodings.md#delta-length-byte-array-delta_length_byte_array--6)
@@ -16,14 +18,14 @@ use super::super::delta_bitpacked;
 ///     128, 1, 4, 2, 10, 0, 0, 0, 0, 0, 72, 101, 108, 108, 111, 87, 111, 114, 108, 100,
 /// ];
 ///
-/// let mut decoder = Decoder::new(data);
+/// let mut decoder = Decoder::try_new(data).unwrap();
 ///
 /// // Extract the lengths
-/// let lengths = decoder.by_ref().collect::<Vec<_>>();
+/// let lengths = decoder.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
 /// assert_eq!(lengths, expected_lengths);
 ///
 /// // Extract the values. This _must_ be called after consuming all lengths by reference (see above).
-/// let values = decoder.into_values();
+/// let values = decoder.into_values().unwrap();
 ///
 /// assert_eq!(values, expected_values);
 #[derive(Debug)]
@@ -34,42 +36,119 @@ pub struct Decoder<'a> {
 }
 
 impl<'a> Decoder<'a> {
-    pub fn new(values: &'a [u8]) -> Self {
-        let lengths = delta_bitpacked::Decoder::new(values);
-        Self {
+    /// Returns a new [`Decoder`] of the lengths and values in `values`.
+    /// # Errors
+    /// This function errors iff the header of the lengths is out of spec.
+    pub fn try_new(values: &'a [u8]) -> Result<Self, Error> {
+        let lengths = delta_bitpacked::Decoder::try_new(values)?;
+        Ok(Self {
             values,
             lengths,
             total_length: 0,
-        }
+        })
     }
 
     /// Consumes this decoder and returns the slice of concatenated values.
     /// # Panics
     /// This function panics if this iterator has not been fully consumed.
-    pub fn into_values(self) -> &'a [u8] {
-        assert_eq!(self.lengths.size_hint().0, 0);
-        let start = self.lengths.consumed_bytes();
-        &self.values[start..start + self.total_length as usize]
+    /// # Errors
+    /// This function errors iff the values are smaller than the sum of the lengths.
+    pub fn into_values(self) -> Result<&'a [u8], Error> {
+        self.values()
     }
 
     /// Returns the slice of concatenated values.
     /// # Panics
     /// This function panics if this iterator has not yet been fully consumed.
-    pub fn values(&self) -> &'a [u8] {
+    /// # Errors
+    /// This function errors iff the values are smaller than the sum of the lengths.
+    pub fn values(&self) -> Result<&'a [u8], Error> {
         assert_eq!(self.lengths.size_hint().0, 0);
         let start = self.lengths.consumed_bytes();
-        &self.values[start..start + self.total_length as usize]
+        self.values
+            .get(start..)
+            .and_then(|values| values.get(..self.total_length as usize))
+            .ok_or_else(|| {
+                Error::OutOfSpec(
+                    "Delta-length byte array values are smaller than the sum of its lengths"
+                        .to_string(),
+                )
+            })
     }
 }
 
 impl<'a> Iterator for Decoder<'a> {
-    type Item = i32;
+    type Item = Result<i32, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let result = self.lengths.next();
-        if let Some(result) = result {
+        if let Some(Ok(result)) = result {
             self.total_length += result as u32
         }
-        result.map(|x| x as i32)
+        result.map(|x| x.map(|x| x as i32))
+    }
+}
+
+/// Decodes [Delta-length byte array](https://github.com/apache/parquet-format/blob/master/Encodings.md#delta-length-byte-array-delta_length_byte_array--6)
+/// into an iterator over each of its values.
+/// # Implementation
+/// The lengths are decoded twice: once in [`BinaryDecoder::try_new`], to find where the
+/// concatenated values start, and once while iterating.
+/// This struct does not allocate on the heap.
+#[derive(Debug, Clone)]
+pub struct BinaryDecoder<'a> {
+    lengths: delta_bitpacked::Decoder<'a>,
+    values: &'a [u8],
+}
+
+impl<'a> BinaryDecoder<'a> {
+    /// Returns a new [`BinaryDecoder`].
+    /// # Errors
+    /// This function errors iff a length is negative or the values are smaller than the
+    /// sum of the lengths.
+    pub fn try_new(data: &'a [u8]) -> Result<Self, Error> {
+        let lengths = delta_bitpacked::Decoder::try_new(data)?;
+
+        let mut decoder = lengths.clone();
+        let mut total_length = 0usize;
+        for length in decoder.by_ref() {
+            let length: usize = length?.try_into().map_err(|_| {
+                Error::OutOfSpec("Delta-length byte array lengths must be positive".to_string())
+            })?;
+            total_length = total_length.saturating_add(length);
+        }
+        let start = decoder.consumed_bytes();
+
+        let values = data
+            .get(start..)
+            .and_then(|values| values.get(..total_length))
+            .ok_or_else(|| {
+                Error::OutOfSpec(
+                    "Delta-length byte array values are smaller than the sum of its lengths"
+                        .to_string(),
+                )
+            })?;
+
+        Ok(Self { lengths, values })
     }
 }
+
+impl<'a> Iterator for BinaryDecoder<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // lengths were validated on `try_new`
+        let length = self.lengths.next()?.ok()? as usize;
+        let (value, remaining) = self.values.split_at(length);
+        self.values = remaining;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lengths.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for BinaryDecoder<'a> {}
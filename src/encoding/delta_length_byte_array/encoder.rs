@@ -1,17 +1,24 @@
 use crate::encoding::delta_bitpacked;
 
-/// Encodes a clonable iterator of `&[u8]` into `buffer`. This does not allocated on the heap.
+/// Encodes a clonable iterator of `&[u8]` into `buffer`. This does not allocate on the heap.
 /// # Implementation
 /// This encoding is equivalent to call [`delta_bitpacked::encode`] on the lengths of the items
 /// of the iterator followed by extending the buffer from each item of the iterator.
+/// The iterator is cloned to count its items, so it does not need an exact `size_hint`
+/// (e.g. the non-null values of `&[Option<Vec<u8>>]` from `flatten`).
 pub fn encode<A: AsRef<[u8]>, I: Iterator<Item = A> + Clone>(iterator: I, buffer: &mut Vec<u8>) {
+    let length = iterator.clone().count();
     let mut total_length = 0;
     delta_bitpacked::encode(
-        iterator.clone().map(|x| {
-            let len = x.as_ref().len();
-            total_length += len;
-            len as i64
-        }),
+        iterator
+            .clone()
+            .map(|x| {
+                let len = x.as_ref().len();
+                total_length += len;
+                len as i64
+            })
+            // `take` declares `length` as the upper bound of the size hint
+            .take(length),
         buffer,
     );
     buffer.reserve(total_length);
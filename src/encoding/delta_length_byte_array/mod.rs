@@ -1,7 +1,7 @@
 mod decoder;
 mod encoder;
 
-pub use decoder::Decoder;
+pub use decoder::{BinaryDecoder, Decoder};
 pub use encoder::encode;
 
 #[cfg(test)]
@@ -15,15 +15,127 @@ mod tests {
         let mut buffer = vec![];
         encode(data.into_iter().map(|x| x.as_bytes()), &mut buffer);
 
-        let mut iter = Decoder::new(&buffer);
+        let mut iter = Decoder::try_new(&buffer).unwrap();
 
-        let result = iter.by_ref().collect::<Vec<_>>();
+        let result = iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(result, vec![2, 3, 1, 2, 1]);
 
-        let result = iter.values();
+        let result = iter.values().unwrap();
         assert_eq!(result, b"aabbbaaab".as_ref());
     }
 
+    #[test]
+    fn truncated_values() {
+        let mut buffer = vec![];
+        encode(["aa", "bbb"].iter().map(|x| x.as_bytes()), &mut buffer);
+        buffer.pop();
+
+        let mut iter = Decoder::try_new(&buffer).unwrap();
+        let result = iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(result, vec![2, 3]);
+        assert!(iter.values().is_err());
+        assert!(iter.into_values().is_err());
+
+        assert!(Decoder::try_new(&[]).is_err());
+    }
+
+    #[test]
+    fn binary() {
+        let data = vec!["aa", "", "bbb", "", "a"];
+
+        let mut buffer = vec![];
+        encode(data.iter().map(|x| x.as_bytes()), &mut buffer);
+
+        let iter = BinaryDecoder::try_new(&buffer).unwrap();
+        assert_eq!(iter.len(), 5);
+
+        let result = iter.collect::<Vec<_>>();
+        let expected = data.iter().map(|x| x.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn binary_from_bytes() {
+        // header: [128, 1, 4, 3, 10]
+        // block size: 128, 1
+        // mini-blocks: 4
+        // elements: 3
+        // first_value: 10 <=z> 5
+        // block1: [9, 2, 0, 0, 0]
+        // min_delta: 9 <=z> -5
+        // bit_widths: [3, 0, 0, 0]
+        // values: [0b00111000, 0, ...] (12 bytes) <=b> [0, 7, 0, ...]
+        // lengths: [5, 0, 2]
+        #[rustfmt::skip]
+        let data = &[
+            128, 1, 4, 3, 10,
+            9, 3, 0, 0, 0,
+            0b00111000, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            b'H', b'e', b'l', b'l', b'o', b'!', b'!',
+        ];
+
+        let result = BinaryDecoder::try_new(data).unwrap().collect::<Vec<_>>();
+        assert_eq!(result, vec![b"Hello".as_ref(), b"", b"!!"]);
+    }
+
+    #[test]
+    fn binary_empty_values() {
+        let data = vec!["", "", ""];
+
+        let mut buffer = vec![];
+        encode(data.iter().map(|x| x.as_bytes()), &mut buffer);
+
+        let result = BinaryDecoder::try_new(&buffer).unwrap().collect::<Vec<_>>();
+        assert_eq!(result, vec![b"".as_ref(); 3]);
+    }
+
+    #[test]
+    fn binary_single_value() {
+        let mut buffer = vec![];
+        encode(std::iter::once(b"hello"), &mut buffer);
+
+        let result = BinaryDecoder::try_new(&buffer).unwrap().collect::<Vec<_>>();
+        assert_eq!(result, vec![b"hello".as_ref()]);
+    }
+
+    #[test]
+    fn binary_empty_page() {
+        let mut buffer = vec![];
+        encode(std::iter::empty::<&[u8]>(), &mut buffer);
+
+        let iter = BinaryDecoder::try_new(&buffer).unwrap();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.count(), 0);
+    }
+
+    #[test]
+    fn binary_without_exact_size() {
+        // `flatten` does not have an exact size hint
+        let data = vec![
+            Some(b"aa".to_vec()),
+            None,
+            Some(vec![]),
+            Some(b"b".to_vec()),
+        ];
+
+        let mut buffer = vec![];
+        encode(data.iter().flatten(), &mut buffer);
+
+        let result = BinaryDecoder::try_new(&buffer).unwrap().collect::<Vec<_>>();
+        assert_eq!(result, vec![b"aa".as_ref(), b"", b"b"]);
+    }
+
+    #[test]
+    fn binary_truncated() {
+        let data = vec!["aa", "bbb"];
+
+        let mut buffer = vec![];
+        encode(data.iter().map(|x| x.as_bytes()), &mut buffer);
+        buffer.pop();
+
+        assert!(BinaryDecoder::try_new(&buffer).is_err());
+    }
+
     #[test]
     fn many_numbers() {
         let mut data = vec![];
@@ -36,12 +148,12 @@ mod tests {
         let mut buffer = vec![];
         encode(data.into_iter(), &mut buffer);
 
-        let mut iter = Decoder::new(&buffer);
+        let mut iter = Decoder::try_new(&buffer).unwrap();
 
-        let result = iter.by_ref().collect::<Vec<_>>();
+        let result = iter.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
         assert_eq!(result, expected_lengths);
 
-        let result = iter.into_values();
+        let result = iter.into_values().unwrap();
         assert_eq!(result, expected_values.as_str().as_bytes());
     }
 }
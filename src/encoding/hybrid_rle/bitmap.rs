@@ -1,5 +1,8 @@
 use std::io::Write;
 
+use crate::encoding::ceil8;
+use crate::error::Error;
+
 const BIT_MASK: [u8; 8] = [1, 2, 4, 8, 16, 32, 64, 128];
 
 /// Sets bit at position `i` in `byte`
@@ -35,6 +38,37 @@ impl<'a> BitmapIter<'a> {
             current_byte,
         }
     }
+
+    /// Returns a new [`BitmapIter`] over the first `num_values` bits of the plain-encoded
+    /// boolean `values`.
+    /// # Errors
+    /// Errors iff `values` has fewer than `ceil8(num_values)` bytes.
+    pub fn try_new(values: &'a [u8], num_values: usize) -> Result<Self, Error> {
+        let (values, length) = try_plain_bitmap(values, num_values)?;
+        Ok(Self::new(values, 0, length))
+    }
+}
+
+/// Returns the LSB-packed buffer of the first `num_values` plain-encoded booleans of `values`,
+/// together with its length in bits, so that it can be copied into a bitmap as a whole.
+///
+/// The returned buffer has exactly `ceil8(num_values)` bytes; any bytes past it are ignored.
+/// The bits of its last byte past `num_values` are not validated and can be set.
+/// # Errors
+/// Errors iff `values` has fewer than `ceil8(num_values)` bytes.
+pub fn try_plain_bitmap(values: &[u8], num_values: usize) -> Result<(&[u8], usize), Error> {
+    let bytes = ceil8(num_values);
+    values
+        .get(..bytes)
+        .map(|values| (values, num_values))
+        .ok_or_else(|| {
+            Error::OutOfSpec(format!(
+                "{} plain-encoded booleans require {} bytes but the page only has {}",
+                num_values,
+                bytes,
+                values.len()
+            ))
+        })
 }
 
 impl<'a> Iterator for BitmapIter<'a> {
@@ -98,3 +132,51 @@ pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_bitmap_partial_byte() {
+        let values = [
+            true, false, true, true, false, false, true, false, true, true, false,
+        ];
+        let mut buffer = vec![];
+        encode_bool(&mut buffer, values.iter().copied()).unwrap();
+        assert_eq!(buffer.len(), 2);
+
+        let (bitmap, length) = try_plain_bitmap(&buffer, values.len()).unwrap();
+        assert_eq!(bitmap, buffer.as_slice());
+        assert_eq!(length, 11);
+
+        let decoded = BitmapIter::try_new(&buffer, values.len())
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn plain_bitmap_trailing_garbage() {
+        // 3 values: the unused bits of the first byte and the second byte are garbage
+        let buffer = [0b1111_1101, 0xff];
+
+        let (bitmap, length) = try_plain_bitmap(&buffer, 3).unwrap();
+        assert_eq!(bitmap, &[0b1111_1101]);
+        assert_eq!(length, 3);
+
+        let decoded = BitmapIter::try_new(&buffer, 3).unwrap().collect::<Vec<_>>();
+        assert_eq!(decoded, vec![true, false, true]);
+
+        let (bitmap, length) = try_plain_bitmap(&buffer, 16).unwrap();
+        assert_eq!(bitmap, &buffer);
+        assert_eq!(length, 16);
+    }
+
+    #[test]
+    fn plain_bitmap_too_short() {
+        assert!(try_plain_bitmap(&[0], 9).is_err());
+        assert!(BitmapIter::try_new(&[], 1).is_err());
+        assert_eq!(try_plain_bitmap(&[], 0).unwrap(), (&[][..], 0));
+    }
+}
@@ -28,11 +28,18 @@ impl<'a> Iterator for Decoder<'a> {
         if self.values.is_empty() {
             return None;
         }
-        let (indicator, consumed) = uleb128::decode(self.values);
+        // malformed runs end the iterator; see `check_runs` to detect them.
+        let (indicator, consumed) = match uleb128::decode(self.values) {
+            Ok(header) => header,
+            Err(_) => {
+                self.values = &[];
+                return None;
+            }
+        };
         self.values = &self.values[consumed..];
         if indicator & 1 == 1 {
             // is bitpacking
-            let bytes = (indicator as usize >> 1) * self.num_bits as usize;
+            let bytes = (indicator as usize >> 1).saturating_mul(self.num_bits as usize);
             let bytes = std::cmp::min(bytes, self.values.len());
             let result = Some(HybridEncoded::Bitpacked(&self.values[..bytes]));
             self.values = &self.values[bytes..];
@@ -42,6 +49,10 @@ impl<'a> Iterator for Decoder<'a> {
             let run_length = indicator as usize >> 1;
             // repeated-value := value that is repeated, using a fixed-width of round-up-to-next-byte(bit-width)
             let rle_bytes = ceil8(self.num_bits as usize);
+            if rle_bytes > self.values.len() {
+                self.values = &[];
+                return None;
+            }
             let result = Some(HybridEncoded::Rle(&self.values[..rle_bytes], run_length));
             self.values = &self.values[rle_bytes..];
             result
@@ -5,26 +5,57 @@ use std::io::Write;
 
 use super::bitpacked_encode;
 
-/// RLE-hybrid encoding of `u32`. This currently only yields bitpacked values.
+/// RLE-hybrid encoding of `u32`, choosing between RLE and bitpacked runs.
+/// # Implementation
+/// Values are bitpacked in groups of 8 values, and repeated values starting at the beginning
+/// of a group are written as an RLE run when there are at least 8 of them.
+/// This function allocates the values in memory.
 pub fn encode_u32<W: Write, I: Iterator<Item = u32>>(
     writer: &mut W,
     iterator: I,
     num_bits: u8,
 ) -> std::io::Result<()> {
-    // the length of the iterator.
-    let length = iterator.size_hint().1.unwrap();
+    let values = iterator.collect::<Vec<_>>();
+    encode_runs(
+        writer,
+        &values,
+        |writer, values| write_bitpacked_u32(writer, values, num_bits),
+        |writer, value, run| write_rle_u32(writer, value, run, num_bits),
+    )
+}
 
+fn write_bitpacked_u32<W: Write>(
+    writer: &mut W,
+    values: &[u32],
+    num_bits: u8,
+) -> std::io::Result<()> {
+    if values.is_empty() {
+        return Ok(());
+    }
     // write the length + indicator
-    let mut header = ceil8(length as usize) as u64;
+    let mut header = ceil8(values.len()) as u64;
     header <<= 1;
     header |= 1; // it is bitpacked => first bit is set
     let mut container = [0; 10];
     let used = uleb128::encode(header, &mut container);
     writer.write_all(&container[..used])?;
 
-    bitpacked_encode_u32(writer, iterator, num_bits)?;
+    bitpacked_encode_u32(writer, values.iter().copied(), num_bits)
+}
 
-    Ok(())
+fn write_rle_u32<W: Write>(
+    writer: &mut W,
+    value: u32,
+    run: usize,
+    num_bits: u8,
+) -> std::io::Result<()> {
+    // the indicator bit is not set for RLE runs
+    let header = (run as u64) << 1;
+    let mut container = [0; 10];
+    let used = uleb128::encode(header, &mut container);
+    writer.write_all(&container[..used])?;
+    // the value is written in ceil8(num_bits) bytes, little-endian
+    writer.write_all(&value.to_le_bytes()[..ceil8(num_bits as usize)])
 }
 
 fn bitpacked_encode_u32<W: Write, I: Iterator<Item = u32>>(
@@ -57,7 +88,9 @@ fn bitpacked_encode_u32<W: Write, I: Iterator<Item = u32>>(
         iterator.enumerate().for_each(|(i, x)| {
             buffer[i] = x;
         });
-        let compressed_remainder_size = ceil8(remainder * num_bits as usize);
+        // SPEC: bitpacked runs are multiples of 8 values, so the last group of 8 values is
+        // padded with zeros, as announced by the header of the run
+        let compressed_remainder_size = ceil8(remainder) * num_bits as usize;
         bitpacking::encode_pack(buffer, num_bits, compressed_chunk.as_mut());
         writer.write_all(&compressed_chunk[..compressed_remainder_size])?;
     };
@@ -85,9 +118,77 @@ pub fn encode_bool<W: Write, I: Iterator<Item = bool>>(
     bitpacked_encode(writer, iterator)
 }
 
+/// The minimum number of repeated values encoded as a RLE run. Shorter runs are bitpacked.
+const MIN_RLE_RUN: usize = 8;
+
+/// RLE-hybrid encoding of `bool`, choosing between RLE and bitpacked runs.
+/// # Implementation
+/// Values are bitpacked in groups of 8 values (1 byte), and repeated values starting at the
+/// beginning of a group are written as an RLE run when there are at least 8 of them.
+/// Unlike [`encode_bool`], this function allocates the values in memory.
+pub fn encode_bool_rle<W: Write, I: Iterator<Item = bool>>(
+    writer: &mut W,
+    iterator: I,
+) -> std::io::Result<()> {
+    let values = iterator.collect::<Vec<_>>();
+    encode_runs(writer, &values, write_bitpacked_bool, write_rle_bool)
+}
+
+/// Splits `values` in runs of at least [`MIN_RLE_RUN`] repeated values starting at the
+/// beginning of a group of 8 values, written with `rle`, and the values in between, written
+/// with `bitpacked`.
+fn encode_runs<W, T, B, R>(
+    writer: &mut W,
+    values: &[T],
+    mut bitpacked: B,
+    mut rle: R,
+) -> std::io::Result<()>
+where
+    W: Write,
+    T: Copy + PartialEq,
+    B: FnMut(&mut W, &[T]) -> std::io::Result<()>,
+    R: FnMut(&mut W, T, usize) -> std::io::Result<()>,
+{
+    // the start of the values not yet written, all to be bitpacked
+    let mut pending = 0;
+    let mut index = 0;
+    while index < values.len() {
+        let value = values[index];
+        let run = values[index..].iter().take_while(|x| **x == value).count();
+        if run >= MIN_RLE_RUN {
+            bitpacked(writer, &values[pending..index])?;
+            rle(writer, value, run)?;
+            index += run;
+            pending = index;
+        } else {
+            // bitpacked runs must be a multiple of 8 values, except for the last run
+            index = std::cmp::min(index + 8, values.len());
+        }
+    }
+    bitpacked(writer, &values[pending..])
+}
+
+fn write_bitpacked_bool<W: Write>(writer: &mut W, values: &[bool]) -> std::io::Result<()> {
+    if values.is_empty() {
+        return Ok(());
+    }
+    encode_bool(writer, values.iter().copied())
+}
+
+fn write_rle_bool<W: Write>(writer: &mut W, value: bool, run: usize) -> std::io::Result<()> {
+    // the indicator bit is not set for RLE runs
+    let header = (run as u64) << 1;
+    let mut container = [0; 10];
+    let used = uleb128::encode(header, &mut container);
+    writer.write_all(&container[..used])?;
+    // the value is written in ceil8(bit_width) = 1 byte
+    writer.write_all(&[value as u8])
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::bitmap::BitmapIter;
+    use super::super::HybridRleDecoder;
     use super::*;
 
     #[test]
@@ -116,6 +217,88 @@ mod tests {
         Ok(())
     }
 
+    fn decode_bool(data: &[u8], length: usize) -> Vec<bool> {
+        HybridRleDecoder::try_new(data, 1, length)
+            .unwrap()
+            .map(|x| x == 1)
+            .collect()
+    }
+
+    #[test]
+    fn bool_rle_constant() -> std::io::Result<()> {
+        for value in [true, false] {
+            let mut vec = vec![];
+            encode_bool_rle(&mut vec, std::iter::repeat(value).take(10000))?;
+
+            // uleb128(10000 << 1) + the value
+            assert_eq!(vec, vec![0xa0, 0x9c, 0x01, value as u8]);
+            assert_eq!(decode_bool(&vec, 10000), vec![value; 10000]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bool_rle_single() -> std::io::Result<()> {
+        let mut vec = vec![];
+        encode_bool_rle(&mut vec, std::iter::once(true))?;
+
+        assert_eq!(vec, vec![(1 << 1 | 1), 0b00000001]);
+        assert_eq!(decode_bool(&vec, 1), vec![true]);
+        Ok(())
+    }
+
+    #[test]
+    fn bool_rle_empty() -> std::io::Result<()> {
+        let mut vec = vec![];
+        encode_bool_rle(&mut vec, std::iter::empty())?;
+        assert!(vec.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn bool_rle_mixed() -> std::io::Result<()> {
+        // bitpacked, RLE run, bitpacked remainder
+        let values = [true, false, true]
+            .into_iter()
+            .chain(std::iter::repeat(false).take(5))
+            .chain(std::iter::repeat(true).take(20))
+            .chain([false, true, false])
+            .collect::<Vec<_>>();
+
+        let mut vec = vec![];
+        encode_bool_rle(&mut vec, values.iter().copied())?;
+
+        assert_eq!(
+            vec,
+            vec![
+                (1 << 1 | 1),
+                0b00000101,
+                20 << 1,
+                1,
+                (1 << 1 | 1),
+                0b00000010
+            ]
+        );
+        assert_eq!(decode_bool(&vec, values.len()), values);
+        Ok(())
+    }
+
+    #[test]
+    fn bool_rle_round_trip() -> std::io::Result<()> {
+        let mut random = crate::tests::xorshift();
+        let mut values = vec![];
+        while values.len() < 5000 {
+            let state = random.next().unwrap();
+            let run = (state % 40) as usize;
+            values.extend(std::iter::repeat(state % 3 == 0).take(run));
+        }
+
+        let mut vec = vec![];
+        encode_bool_rle(&mut vec, values.iter().copied())?;
+        assert_eq!(decode_bool(&vec, values.len()), values);
+        Ok(())
+    }
+
     #[test]
     fn test_encode_u32() -> std::io::Result<()> {
         let mut vec = vec![];
@@ -124,7 +307,14 @@ mod tests {
 
         assert_eq!(
             vec,
-            vec![(2 << 1 | 1), 0b01_10_01_00, 0b00_01_01_10, 0b_00_00_00_11]
+            // the second group of 8 values is padded with zeros
+            vec![
+                (2 << 1 | 1),
+                0b01_10_01_00,
+                0b00_01_01_10,
+                0b_00_00_00_11,
+                0
+            ]
         );
         Ok(())
     }
@@ -147,6 +337,91 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn u32_rle_runs() -> std::io::Result<()> {
+        // bitpacked, RLE run, bitpacked remainder
+        let values = vec![0, 1, 2, 3, 0, 1, 2, 3]
+            .into_iter()
+            .chain(std::iter::repeat(2).take(12))
+            .chain([1, 0])
+            .collect::<Vec<_>>();
+
+        let mut vec = vec![];
+        encode_u32(&mut vec, values.iter().copied(), 2)?;
+
+        assert_eq!(
+            vec,
+            vec![
+                (1 << 1 | 1),
+                0b11_10_01_00,
+                0b11_10_01_00,
+                12 << 1,
+                2,
+                (1 << 1 | 1),
+                0b00_00_01,
+                0
+            ]
+        );
+        let result = HybridRleDecoder::try_new(&vec, 2, values.len())
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(result, values);
+        Ok(())
+    }
+
+    #[test]
+    fn u32_rle_wide_value() -> std::io::Result<()> {
+        let mut vec = vec![];
+        encode_u32(&mut vec, std::iter::repeat(300).take(100), 9)?;
+
+        // the value is written in 2 bytes
+        assert_eq!(vec, vec![100 << 1, 0x01, 44, 1]);
+        let result = HybridRleDecoder::try_new(&vec, 9, 100)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(result, vec![300; 100]);
+        Ok(())
+    }
+
+    #[test]
+    fn u32_rle_definition_levels() -> std::io::Result<()> {
+        // the definition levels of a column without nulls
+        let length = 10000;
+        let mut vec = vec![];
+        encode_u32(&mut vec, std::iter::repeat(1).take(length), 1)?;
+
+        let mut bitpacked = vec![];
+        write_bitpacked_u32(&mut bitpacked, &vec![1; length], 1)?;
+
+        assert_eq!(vec.len(), 4);
+        // uleb128(1250 << 1 | 1) + the bitpacked values
+        assert_eq!(bitpacked.len(), 2 + length / 8);
+        let result = HybridRleDecoder::try_new(&vec, 1, length)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(result, vec![1; length]);
+        Ok(())
+    }
+
+    #[test]
+    fn u32_rle_round_trip() -> std::io::Result<()> {
+        let mut random = crate::tests::xorshift();
+        let mut values = vec![];
+        while values.len() < 5000 {
+            let state = random.next().unwrap();
+            let run = (state % 40) as usize;
+            values.extend(std::iter::repeat((state % 5) as u32).take(run));
+        }
+
+        let mut vec = vec![];
+        encode_u32(&mut vec, values.iter().copied(), 3)?;
+        let result = HybridRleDecoder::try_new(&vec, 3, values.len())
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(result, values);
+        Ok(())
+    }
+
     #[test]
     fn test_u32_other() -> std::io::Result<()> {
         let values = vec![3, 3, 0, 3, 2, 3, 3, 3, 3, 1, 3, 3, 3, 0, 3].into_iter();
@@ -2,11 +2,12 @@
 mod bitmap;
 mod decoder;
 mod encoder;
-pub use bitmap::{encode_bool as bitpacked_encode, BitmapIter};
+pub use bitmap::{encode_bool as bitpacked_encode, try_plain_bitmap, BitmapIter};
 pub use decoder::Decoder;
-pub use encoder::{encode_bool, encode_u32};
+pub use encoder::{encode_bool, encode_bool_rle, encode_u32};
 
-use super::bitpacking;
+use super::{bitpacking, ceil8, uleb128};
+use crate::error::Error;
 
 /// The two possible states of an RLE-encoded run.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,9 +59,99 @@ fn read_next<'a, 'b>(decoder: &'b mut Decoder<'a>, remaining: usize) -> State<'a
     }
 }
 
+/// Checks that the runs of `data` encode `num_values` values of `num_bits` bits without
+/// reading past the end of `data`, i.e. that decoding them does not fail. Runs are not decoded.
+///
+/// The last bitpacked run may end before the values its header declares (e.g. pages whose
+/// `num_values` counts nulls, written by writers that do not pad their last run): its missing
+/// values are zeros.
+/// # Errors
+/// Errors iff `num_bits` is larger than 32, a run header or the value of a RLE run is
+/// truncated, or the runs encode less than `num_values` values.
+pub(crate) fn check_runs(data: &[u8], num_bits: u32, num_values: usize) -> Result<(), Error> {
+    let length = runs_length(data, num_bits, num_values)?;
+    if length < num_values {
+        return Err(Error::OutOfSpec(format!(
+            "The hybrid RLE-encoded runs contain {} values, but {} were expected",
+            length, num_values
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the number of values, up to `max_num_values`, encoded by the runs of `data`,
+/// erroring like [`check_runs`] when they are malformed.
+fn runs_length(mut data: &[u8], num_bits: u32, max_num_values: usize) -> Result<usize, Error> {
+    if num_bits > 32 {
+        return Err(Error::OutOfSpec(format!(
+            "The bit width of hybrid RLE-encoded values must be at most 32, but it is {}",
+            num_bits
+        )));
+    }
+    if num_bits == 0 {
+        // no value is read: all values are zeros
+        return Ok(max_num_values);
+    }
+    let num_bits = num_bits as usize;
+    let truncated = |kind: &str| {
+        Err(Error::OutOfSpec(format!(
+            "The {} of a hybrid RLE-encoded run is truncated",
+            kind
+        )))
+    };
+
+    // every run, including those of zero values, consumes its header: this ends
+    let mut remaining = max_num_values;
+    while remaining > 0 && !data.is_empty() {
+        let (indicator, consumed) = uleb128::decode(data)?;
+        data = &data[consumed..];
+        let length = if indicator & 1 == 1 {
+            // the last bitpacked run may be shorter than its header: its missing values are zeros
+            let bytes = (indicator as usize >> 1)
+                .saturating_mul(num_bits)
+                .min(data.len());
+            data = &data[bytes..];
+            (indicator as usize >> 1).saturating_mul(8)
+        } else {
+            let bytes = ceil8(num_bits);
+            if bytes > data.len() {
+                return truncated("value");
+            }
+            data = &data[bytes..];
+            indicator as usize >> 1
+        };
+        remaining -= length.min(remaining);
+    }
+    Ok(max_num_values - remaining)
+}
+
 impl<'a> HybridRleDecoder<'a> {
-    /// Returns a new [`HybridRleDecoder`]
-    pub fn new(data: &'a [u8], num_bits: u32, num_values: usize) -> Self {
+    /// Returns a new [`HybridRleDecoder`] of `num_values` values of `num_bits` bits encoded
+    /// in `data`.
+    /// # Errors
+    /// Errors iff the runs of `data` are malformed: `num_bits` is larger than 32, a run is
+    /// truncated or the runs encode less than `num_values` values.
+    pub fn try_new(data: &'a [u8], num_bits: u32, num_values: usize) -> Result<Self, Error> {
+        check_runs(data, num_bits, num_values)?;
+        Ok(Self::new_unchecked(data, num_bits, num_values))
+    }
+
+    /// Returns a new [`HybridRleDecoder`] of the values of `num_bits` bits encoded in `data`,
+    /// up to `max_num_values`. Use it when only an upper bound of the number of values is known,
+    /// e.g. the dictionary indices of a page whose number of values counts nulls.
+    /// # Errors
+    /// Errors iff the runs of `data` are malformed: `num_bits` is larger than 32 or a run is
+    /// truncated.
+    pub fn try_new_up_to(
+        data: &'a [u8],
+        num_bits: u32,
+        max_num_values: usize,
+    ) -> Result<Self, Error> {
+        let num_values = runs_length(data, num_bits, max_num_values)?;
+        Ok(Self::new_unchecked(data, num_bits, num_values))
+    }
+
+    fn new_unchecked(data: &'a [u8], num_bits: u32, num_values: usize) -> Self {
         let mut decoder = Decoder::new(data, num_bits);
         let state = read_next(&mut decoder, num_values);
         Self {
@@ -71,6 +162,40 @@ impl<'a> HybridRleDecoder<'a> {
     }
 }
 
+impl<'a> HybridRleDecoder<'a> {
+    /// Advances this decoder by `n` values (or all remaining values if there are less than `n`)
+    /// without decoding them: RLE runs are consumed whole and bitpacked runs are skipped over
+    /// pack by pack.
+    ///
+    /// Unlike [`Iterator::skip`], this does not consume the decoder.
+    pub fn skip_values(&mut self, n: usize) {
+        let mut n = n.min(self.remaining);
+        while n > 0 {
+            let skipped = match &mut self.state {
+                State::Bitpacked(decoder) => {
+                    let skipped = n.min(decoder.size_hint().0);
+                    decoder.skip_values(skipped);
+                    skipped
+                }
+                State::Rle(iter) => {
+                    let skipped = n.min(iter.size_hint().0);
+                    if skipped > 0 {
+                        iter.nth(skipped - 1);
+                    }
+                    skipped
+                }
+                // values past the last bitpacked run are zeros, like in `next`
+                State::None => n,
+            };
+            n -= skipped;
+            self.remaining -= skipped;
+            if n > 0 {
+                self.state = read_next(&mut self.decoder, self.remaining);
+            }
+        }
+    }
+}
+
 impl<'a> Iterator for HybridRleDecoder<'a> {
     type Item = u32;
 
@@ -78,23 +203,29 @@ impl<'a> Iterator for HybridRleDecoder<'a> {
         if self.remaining == 0 {
             return None;
         };
-        let result = match &mut self.state {
-            State::Bitpacked(decoder) => decoder.next(),
-            State::Rle(iter) => iter.next(),
-            State::None => Some(0),
-        };
-        if let Some(result) = result {
-            self.remaining -= 1;
-            Some(result)
-        } else {
+        // runs may be empty: read the next one until a value is found
+        loop {
+            let result = match &mut self.state {
+                State::Bitpacked(decoder) => decoder.next(),
+                State::Rle(iter) => iter.next(),
+                State::None => Some(0),
+            };
+            if let Some(result) = result {
+                self.remaining -= 1;
+                return Some(result);
+            }
             self.state = read_next(&mut self.decoder, self.remaining);
-            self.next()
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.remaining, Some(self.remaining))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_values(n);
+        self.next()
+    }
 }
 
 impl<'a> ExactSizeIterator for HybridRleDecoder<'a> {}
@@ -112,7 +243,7 @@ mod tests {
 
         encode_u32(&mut buffer, data.iter().cloned(), num_bits).unwrap();
 
-        let decoder = HybridRleDecoder::new(&buffer, num_bits as u32, data.len());
+        let decoder = HybridRleDecoder::try_new(&buffer, num_bits as u32, data.len()).unwrap();
 
         let result = decoder.collect::<Vec<_>>();
 
@@ -195,7 +326,7 @@ mod tests {
         ];
         let num_bits = 10;
 
-        let decoder = HybridRleDecoder::new(&data, num_bits as u32, 1000);
+        let decoder = HybridRleDecoder::try_new(&data, num_bits as u32, 1000).unwrap();
 
         let result = decoder.collect::<Vec<_>>();
 
@@ -208,7 +339,7 @@ mod tests {
 
         let num_bits = 3;
 
-        let decoder = HybridRleDecoder::new(&data, num_bits as u32, 1);
+        let decoder = HybridRleDecoder::try_new(&data, num_bits as u32, 1).unwrap();
 
         let result = decoder.collect::<Vec<_>>();
 
@@ -221,7 +352,7 @@ mod tests {
 
         let num_bits = 0;
 
-        let decoder = HybridRleDecoder::new(&data, num_bits as u32, 2);
+        let decoder = HybridRleDecoder::try_new(&data, num_bits as u32, 2).unwrap();
 
         let result = decoder.collect::<Vec<_>>();
 
@@ -234,10 +365,174 @@ mod tests {
 
         let num_bits = 1;
 
-        let decoder = HybridRleDecoder::new(&data, num_bits as u32, 100);
+        assert!(matches!(
+            HybridRleDecoder::try_new(&data, num_bits as u32, 100),
+            Err(Error::OutOfSpec(_))
+        ));
+
+        let decoder = HybridRleDecoder::try_new_up_to(&data, num_bits as u32, 100).unwrap();
 
         let result = decoder.collect::<Vec<_>>();
 
-        assert_eq!(result, vec![0; 100]);
+        assert_eq!(result, vec![]);
+    }
+
+    #[test]
+    fn fewer_values() {
+        // a RLE run of 4 `1`
+        let data = [4 << 1, 1];
+        assert!(matches!(
+            HybridRleDecoder::try_new(&data, 1, 10),
+            Err(Error::OutOfSpec(_))
+        ));
+
+        let decoder = HybridRleDecoder::try_new_up_to(&data, 1, 10).unwrap();
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![1; 4]);
+        let decoder = HybridRleDecoder::try_new_up_to(&data, 1, 3).unwrap();
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![1; 3]);
+    }
+
+    #[test]
+    fn empty_runs() {
+        // many RLE runs of zero values before a RLE run of 2 `1`
+        let mut data = [0, 1].repeat(1 << 20);
+        data.extend_from_slice(&[2 << 1, 1]);
+        let decoder = HybridRleDecoder::try_new(&data, 1, 2).unwrap();
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![1, 1]);
+
+        // many bitpacked runs of zero values
+        let mut data = vec![1; 1 << 21];
+        data.extend_from_slice(&[2 << 1, 1]);
+        let mut decoder = HybridRleDecoder::try_new(&data, 1, 2).unwrap();
+        assert_eq!(decoder.clone().collect::<Vec<_>>(), vec![1, 1]);
+        decoder.skip_values(1);
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![1]);
+    }
+
+    /// Returns RLE and bitpacked runs of 3-bit values and the values they encode
+    fn mixed_runs() -> (Vec<u8>, Vec<u32>) {
+        let bitpacked = (0..40).map(|x| x % 8).collect::<Vec<_>>();
+
+        // a RLE run of 20 `5`, a bitpacked run of 40 values and a RLE run of 50 `2`
+        let mut data = vec![20 << 1, 5];
+        encode_u32(&mut data, bitpacked.iter().copied(), 3).unwrap();
+        data.extend_from_slice(&[50 << 1, 2]);
+
+        let values = std::iter::repeat(5)
+            .take(20)
+            .chain(bitpacked)
+            .chain(std::iter::repeat(2).take(50))
+            .collect();
+        (data, values)
+    }
+
+    #[test]
+    fn skip_values() {
+        let (data, values) = mixed_runs();
+        assert_eq!(
+            HybridRleDecoder::try_new(&data, 3, values.len())
+                .unwrap()
+                .collect::<Vec<_>>(),
+            values
+        );
+
+        // within and to the end of a RLE run, into and within the bitpacked run, across runs
+        // and past the end
+        for skips in [
+            vec![0],
+            vec![10],
+            vec![20],
+            vec![25],
+            vec![19, 1, 3, 30],
+            vec![50, 40],
+            vec![59, 0],
+            vec![5, 100],
+            vec![109],
+            vec![110],
+            vec![1000],
+        ] {
+            let mut decoder = HybridRleDecoder::try_new(&data, 3, values.len()).unwrap();
+            let mut expected = values.as_slice();
+            for n in skips {
+                decoder.skip_values(n);
+                expected = &expected[n.min(expected.len())..];
+                assert_eq!(decoder.len(), expected.len());
+                assert_eq!(decoder.clone().collect::<Vec<_>>(), expected);
+                assert_eq!(decoder.nth(0), expected.first().copied());
+                expected = expected.get(1..).unwrap_or_default();
+            }
+        }
+    }
+
+    #[test]
+    fn skip_values_past_runs() {
+        // values past the last bitpacked run are zeros
+        let mut decoder = HybridRleDecoder::try_new(&[2 << 1 | 1, 0b1111], 1, 16).unwrap();
+        decoder.skip_values(6);
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![0; 10]);
+
+        let mut decoder = HybridRleDecoder::try_new(&[3], 0, 10).unwrap();
+        assert_eq!(decoder.nth(8), Some(0));
+        assert_eq!(decoder.len(), 1);
+    }
+
+    #[test]
+    fn malformed_runs() {
+        // a truncated run header
+        assert!(HybridRleDecoder::try_new(&[0x80], 1, 10).is_err());
+        assert!(HybridRleDecoder::try_new(&[4 << 1, 1, 0xff], 1, 10).is_err());
+        // a run header that overflows a u64
+        assert!(HybridRleDecoder::try_new(&[0xff; 11], 1, 10).is_err());
+        // the value of a RLE run of 10 bits requires 2 bytes
+        assert!(HybridRleDecoder::try_new(&[4 << 1, 1], 10, 4).is_err());
+        // more than 32 bits
+        assert!(HybridRleDecoder::try_new(&[4 << 1, 1, 0, 0, 0, 0], 33, 4).is_err());
+    }
+
+    #[test]
+    fn short_bitpacked_run() {
+        // the 8 values of 3 bits of a bitpacked run require 3 bytes: the missing values are zeros
+        let decoder = HybridRleDecoder::try_new(&[1 << 1 | 1, 0b11_111_111], 3, 8).unwrap();
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![7, 7, 0, 0, 0, 0, 0, 0]);
+        let decoder = HybridRleDecoder::try_new(&[1 << 1 | 1], 3, 3).unwrap();
+        assert_eq!(decoder.collect::<Vec<_>>(), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn truncated_levels_do_not_panic() {
+        let (data, values) = mixed_runs();
+        let mut levels = vec![];
+        let random = crate::tests::xorshift().take(200);
+        encode_u32(&mut levels, random.map(|x| (x % 3) as u32), 2).unwrap();
+
+        for (data, num_bits, num_values) in [(data, 3, values.len()), (levels, 2, 200)] {
+            for length in 0..data.len() {
+                if let Ok(decoder) =
+                    HybridRleDecoder::try_new(&data[..length], num_bits, num_values)
+                {
+                    assert_eq!(decoder.count(), num_values);
+                }
+                Decoder::new(&data[..length], num_bits).for_each(|_| {});
+            }
+        }
+    }
+
+    #[test]
+    fn random_data_does_not_panic() {
+        let mut random = crate::tests::xorshift();
+        for i in 0..200 {
+            let data = random
+                .by_ref()
+                .take(64)
+                .map(|x| x as u8)
+                .collect::<Vec<_>>();
+            let length = i % data.len();
+            let num_bits = (i % 34) as u32;
+            if let Ok(mut decoder) = HybridRleDecoder::try_new(&data[..length], num_bits, 100) {
+                decoder.skip_values(i % 7);
+                assert_eq!(decoder.count(), 100 - i % 7);
+            }
+            Decoder::new(&data[..length], num_bits).for_each(|_| {});
+        }
     }
 }
@@ -1,6 +1,9 @@
 use std::convert::TryInto;
 
+use crate::error::{Error, Result};
+
 pub mod bitpacking;
+pub mod byte_stream_split;
 pub mod delta_bitpacked;
 pub mod delta_byte_array;
 pub mod delta_length_byte_array;
@@ -23,3 +26,142 @@ pub fn get_length(values: &[u8]) -> u32 {
 pub fn ceil8(value: usize) -> usize {
     value / 8 + ((value % 8 != 0) as usize)
 }
+
+/// Returns the number of bits needed to bit-pack values up to `max`, e.g. the levels of a column
+/// whose maximum level is `max`, or the indices of a dictionary of `max + 1` entries.
+#[inline]
+pub fn get_bit_width(max: u32) -> u8 {
+    (32 - max.leading_zeros()) as u8
+}
+
+/// Encodes the values of a `BYTE_ARRAY` column with `encoding` into `buffer`, e.g. to build
+/// the values of a [`DataPage`](crate::page::DataPage) whose header declares `encoding`.
+///
+/// The supported encodings are [`Encoding::Plain`], [`Encoding::DeltaLengthByteArray`] and
+/// [`Encoding::DeltaByteArray`].
+/// # Errors
+/// Errors with [`Error::InvalidParameter`] iff `encoding` is not supported.
+pub fn encode_binary<A: AsRef<[u8]>, I: Iterator<Item = A> + Clone>(
+    iterator: I,
+    encoding: Encoding,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    match encoding {
+        Encoding::Plain => iterator.for_each(|x| {
+            let x = x.as_ref();
+            buffer.extend_from_slice(&(x.len() as u32).to_le_bytes());
+            buffer.extend_from_slice(x);
+        }),
+        Encoding::DeltaLengthByteArray => delta_length_byte_array::encode(iterator, buffer),
+        Encoding::DeltaByteArray => delta_byte_array::encode(iterator, buffer),
+        other => {
+            return Err(Error::InvalidParameter(format!(
+                "The values of a byte array column cannot be encoded with {:?}",
+                other
+            )))
+        }
+    };
+    Ok(())
+}
+
+/// Encodes the values of a `BOOLEAN` column with `encoding` into `buffer`, e.g. to build
+/// the values of a [`DataPage`](crate::page::DataPage) whose header declares `encoding`.
+///
+/// The supported encodings are [`Encoding::Plain`] (bitpacked) and [`Encoding::Rle`], whose
+/// values are prefixed by their length as a little-endian `u32`. [`Encoding::Rle`] results in
+/// much smaller pages when values are repeated in long runs.
+/// # Errors
+/// Errors with [`Error::InvalidParameter`] iff `encoding` is not supported.
+pub fn encode_boolean<I: Iterator<Item = bool>>(
+    iterator: I,
+    encoding: Encoding,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    match encoding {
+        Encoding::Plain => {
+            // the bitpacked encoder requires the exact number of values
+            let values = iterator.collect::<Vec<_>>();
+            hybrid_rle::bitpacked_encode(buffer, values.into_iter())?
+        }
+        Encoding::Rle => {
+            let start = buffer.len();
+            buffer.extend_from_slice(&[0; 4]);
+            hybrid_rle::encode_bool_rle(buffer, iterator)?;
+            let length = (buffer.len() - start - 4) as u32;
+            buffer[start..start + 4].copy_from_slice(&length.to_le_bytes());
+        }
+        other => {
+            return Err(Error::InvalidParameter(format!(
+                "The values of a boolean column cannot be encoded with {:?}",
+                other
+            )))
+        }
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_width() {
+        assert_eq!(get_bit_width(0), 0);
+        assert_eq!(get_bit_width(1), 1);
+        assert_eq!(get_bit_width(2), 2);
+        assert_eq!(get_bit_width(3), 2);
+        assert_eq!(get_bit_width(4), 3);
+        assert_eq!(get_bit_width(255), 8);
+        assert_eq!(get_bit_width(256), 9);
+        assert_eq!(get_bit_width(u32::MAX), 32);
+        for num_bits in 1..32 {
+            assert_eq!(get_bit_width((1 << num_bits) - 1), num_bits as u8);
+            assert_eq!(get_bit_width(1 << num_bits), num_bits as u8 + 1);
+        }
+    }
+
+    #[test]
+    fn boolean_rle_length() -> Result<()> {
+        let mut buffer = vec![1];
+        encode_boolean(
+            std::iter::repeat(true).take(100),
+            Encoding::Rle,
+            &mut buffer,
+        )?;
+        // the existing content, the length and a single RLE run of 100 `true`
+        assert_eq!(buffer, vec![1, 3, 0, 0, 0, 0xc8, 0x01, 1]);
+
+        let decoded = hybrid_rle::HybridRleDecoder::try_new(&buffer[5..], 1, 100).unwrap();
+        assert!(decoded
+            .map(|x| x == 1)
+            .eq(std::iter::repeat(true).take(100)));
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_alternating() -> Result<()> {
+        let values = (0..1000).map(|x| x % 2 == 0);
+
+        let mut plain = vec![];
+        encode_boolean(values.clone(), Encoding::Plain, &mut plain)?;
+        let mut rle = vec![];
+        encode_boolean(values.clone(), Encoding::Rle, &mut rle)?;
+        // without runs, RLE only adds its headers
+        assert_eq!(plain.len(), 125);
+        assert!(rle.len() <= plain.len() + 4 + 2, "{}", rle.len());
+
+        let decoded = hybrid_rle::HybridRleDecoder::try_new(&rle[4..], 1, 1000).unwrap();
+        assert!(decoded.map(|x| x == 1).eq(values));
+        Ok(())
+    }
+
+    #[test]
+    fn boolean_unsupported() {
+        let result = encode_boolean(
+            std::iter::once(true),
+            Encoding::DeltaBinaryPacked,
+            &mut vec![],
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::Error;
+
 /// Decodes according to [Plain strings](https://github.com/apache/parquet-format/blob/master/Encodings.md#plain-plain--0),
 /// prefixes, lengths and values
 /// # Implementation
@@ -38,3 +40,103 @@ impl<'a> Iterator for BinaryIter<'a> {
         (self.length.unwrap_or_default(), self.length)
     }
 }
+
+/// Decodes according to [Plain fixed-length byte arrays](https://github.com/apache/parquet-format/blob/master/Encodings.md#plain-plain--0),
+/// i.e. the values of `FIXED_LEN_BYTE_ARRAY(size)` stored one after the other.
+/// # Implementation
+/// This struct does not allocate on the heap.
+#[derive(Debug, Clone)]
+pub struct FixedLenBinaryIter<'a> {
+    values: std::slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> FixedLenBinaryIter<'a> {
+    /// Returns a new [`FixedLenBinaryIter`] over the values of `size` bytes in `values`.
+    /// When `num_values` is known, e.g. for pages without nulls, it is validated against the
+    /// length of `values`.
+    /// # Errors
+    /// Errors iff `size` is zero, the length of `values` is not a multiple of `size` or it is
+    /// not the length of `num_values` values.
+    pub fn try_new(
+        values: &'a [u8],
+        size: usize,
+        num_values: Option<usize>,
+    ) -> Result<Self, Error> {
+        if size == 0 {
+            return Err(Error::OutOfSpec(
+                "The size of a FixedLenByteArray must be positive".to_string(),
+            ));
+        }
+        let length = values.len() / size;
+        if length * size != values.len() {
+            return Err(Error::OutOfSpec(format!(
+                "The plain-encoded values of a FixedLenByteArray({}) have {} bytes, which is not a multiple of the size",
+                size,
+                values.len()
+            )));
+        }
+        if let Some(num_values) = num_values {
+            if length != num_values {
+                return Err(Error::OutOfSpec(format!(
+                    "Expected {} plain-encoded values of a FixedLenByteArray({}), but there are {}",
+                    num_values, size, length
+                )));
+            }
+        }
+        Ok(Self {
+            values: values.chunks_exact(size),
+        })
+    }
+}
+
+impl<'a> Iterator for FixedLenBinaryIter<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.values.nth(n)
+    }
+}
+
+impl<'a> ExactSizeIterator for FixedLenBinaryIter<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_len() -> Result<(), Error> {
+        let values = [1u8, 2, 3, 4, 5, 6];
+        let iter = FixedLenBinaryIter::try_new(&values, 2, Some(3))?;
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&[1, 2], &[3, 4], &[5, 6]]);
+
+        let mut iter = FixedLenBinaryIter::try_new(&values, 3, None)?;
+        assert_eq!(iter.nth(1), Some(&values[3..]));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(FixedLenBinaryIter::try_new(&[], 4, Some(0))?.count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_len_invalid() {
+        let values = [1u8, 2, 3, 4, 5, 6];
+        // not a multiple of the size
+        assert!(FixedLenBinaryIter::try_new(&values, 4, None).is_err());
+        // more or less values than expected
+        assert!(FixedLenBinaryIter::try_new(&values, 2, Some(2)).is_err());
+        assert!(FixedLenBinaryIter::try_new(&values, 2, Some(4)).is_err());
+        assert!(FixedLenBinaryIter::try_new(&values, 0, None).is_err());
+    }
+}
@@ -1,23 +1,27 @@
-pub fn decode(values: &[u8]) -> (u64, usize) {
-    let mut result = 0;
-    let mut shift = 0;
-
-    let mut consumed = 0;
-    for byte in values {
-        consumed += 1;
-        if shift == 63 && *byte > 1 {
-            panic!()
-        };
-
-        result |= u64::from(byte & 0b01111111) << shift;
+use crate::error::Error;
 
+/// Decodes a ULEB128-encoded `u64` from the start of `values`, returning the value and the
+/// number of bytes consumed.
+/// # Errors
+/// Errors iff `values` ends before the last byte of the value or the value overflows a `u64`,
+/// i.e. it has more than 10 bytes.
+pub fn decode(values: &[u8]) -> Result<(u64, usize), Error> {
+    let mut result = 0;
+    for (i, byte) in values.iter().enumerate() {
+        // a u64 has at most 10 bytes, of which the last can only hold 1 bit
+        if i == 10 || (i == 9 && *byte > 1) {
+            return Err(Error::OutOfSpec(
+                "A ULEB128-encoded value overflows a u64".to_string(),
+            ));
+        }
+        result |= u64::from(byte & 0b01111111) << (7 * i);
         if byte & 0b10000000 == 0 {
-            break;
+            return Ok((result, i + 1));
         }
-
-        shift += 7;
     }
-    (result, consumed)
+    Err(Error::OutOfSpec(
+        "A ULEB128-encoded value is truncated".to_string(),
+    ))
 }
 
 /// Encodes `value` in ULEB128 into `container`. The exact number of bytes written
@@ -48,48 +52,90 @@ mod tests {
     use super::*;
 
     #[test]
-    fn decode_1() {
+    fn decode_1() -> Result<(), Error> {
         let data = vec![0xe5, 0x8e, 0x26, 0xDE, 0xAD, 0xBE, 0xEF];
-        let (value, len) = decode(&data);
+        let (value, len) = decode(&data)?;
         assert_eq!(value, 624_485);
         assert_eq!(len, 3);
+        Ok(())
     }
 
     #[test]
-    fn decode_2() {
+    fn decode_2() -> Result<(), Error> {
         let data = vec![0b00010000, 0b00000001, 0b00000011, 0b00000011];
-        let (value, len) = decode(&data);
+        let (value, len) = decode(&data)?;
         assert_eq!(value, 16);
         assert_eq!(len, 1);
+        Ok(())
     }
 
     #[test]
-    fn round_trip() {
+    fn round_trip() -> Result<(), Error> {
         let original = 123124234u64;
         let mut container = [0u8; 10];
         let encoded_len = encode(original, &mut container);
-        let (value, len) = decode(&container);
+        let (value, len) = decode(&container)?;
         assert_eq!(value, original);
         assert_eq!(len, encoded_len);
+        Ok(())
     }
 
     #[test]
-    fn min_value() {
+    fn min_value() -> Result<(), Error> {
         let original = u64::MIN;
         let mut container = [0u8; 10];
         let encoded_len = encode(original, &mut container);
-        let (value, len) = decode(&container);
+        let (value, len) = decode(&container)?;
         assert_eq!(value, original);
         assert_eq!(len, encoded_len);
+        Ok(())
     }
 
     #[test]
-    fn max_value() {
+    fn max_value() -> Result<(), Error> {
         let original = u64::MAX;
         let mut container = [0u8; 10];
         let encoded_len = encode(original, &mut container);
-        let (value, len) = decode(&container);
+        // the largest value has the largest number of bytes
+        assert_eq!(encoded_len, 10);
+        let (value, len) = decode(&container)?;
         assert_eq!(value, original);
         assert_eq!(len, encoded_len);
+        Ok(())
+    }
+
+    #[test]
+    fn powers_of_two() -> Result<(), Error> {
+        for shift in 0..64 {
+            for original in [1u64 << shift, (1u64 << shift) - 1] {
+                let mut container = [0u8; 10];
+                let encoded_len = encode(original, &mut container);
+                assert_eq!(decode(&container[..encoded_len])?, (original, encoded_len));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn decode_truncated() {
+        assert!(decode(&[]).is_err());
+        assert!(decode(&[0xe5, 0x8e]).is_err());
+
+        // the largest value without its last byte
+        let mut container = [0u8; 10];
+        encode(u64::MAX, &mut container);
+        assert!(matches!(decode(&container[..9]), Err(Error::OutOfSpec(_))));
+    }
+
+    #[test]
+    fn decode_overflow() {
+        let mut data = vec![0xff; 9];
+        data.push(0x02);
+        assert!(matches!(decode(&data), Err(Error::OutOfSpec(_))));
+        // more than 10 bytes
+        assert!(matches!(decode(&[0x80; 11]), Err(Error::OutOfSpec(_))));
+        let mut data = vec![0x80; 10];
+        data.push(0x00);
+        assert!(decode(&data).is_err());
     }
 }
@@ -1,10 +1,16 @@
 use super::uleb128;
+use crate::error::Error;
 
-pub fn decode(values: &[u8]) -> (i64, usize) {
-    let (u, consumed) = uleb128::decode(values);
-    ((u >> 1) as i64 ^ -((u & 1) as i64), consumed)
+/// Decodes a zigzag ULEB128-encoded `i64` from the start of `values`, returning the value
+/// and the number of bytes consumed.
+/// # Errors
+/// Errors iff the ULEB128-encoded value is out of spec (see [`uleb128::decode`]).
+pub fn decode(values: &[u8]) -> Result<(i64, usize), Error> {
+    let (u, consumed) = uleb128::decode(values)?;
+    Ok(((u >> 1) as i64 ^ -((u & 1) as i64), consumed))
 }
 
+/// Encodes `value` in zigzag ULEB128, returning the bytes and how many of them are used.
 pub fn encode(value: i64) -> ([u8; 10], usize) {
     let value = ((value << 1) ^ (value >> (64 - 1))) as u64;
     let mut a = [0u8; 10];
@@ -32,7 +38,7 @@ mod tests {
             (9, -5),
         ];
         for (data, expected) in cases {
-            let (result, _) = decode(&[data]);
+            let (result, _) = decode(&[data]).unwrap();
             assert_eq!(result, expected)
         }
     }
@@ -62,7 +68,24 @@ mod tests {
     fn test_roundtrip() {
         let value = -1001212312;
         let (data, size) = encode(value);
-        let (result, _) = decode(&data[..size]);
+        let (result, _) = decode(&data[..size]).unwrap();
         assert_eq!(value, result);
     }
+
+    #[test]
+    fn extreme_values() {
+        for value in [i64::MIN, i64::MAX, 0, -1] {
+            let (data, size) = encode(value);
+            assert_eq!(decode(&data[..size]).unwrap(), (value, size));
+        }
+        assert_eq!(encode(i64::MIN).1, 10);
+        assert_eq!(encode(i64::MAX).1, 10);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let (data, size) = encode(i64::MIN);
+        assert!(decode(&data[..size - 1]).is_err());
+        assert!(decode(&[0xff; 11]).is_err());
+    }
 }
@@ -18,6 +18,20 @@ pub enum Feature {
     Zstd,
 }
 
+impl std::fmt::Display for Feature {
+    /// Writes the name of the cargo feature
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Feature::Snappy => "snappy",
+            Feature::Brotli => "brotli",
+            Feature::Gzip => "gzip",
+            Feature::Lz4 => "lz4",
+            Feature::Zstd => "zstd",
+        };
+        write!(fmt, "{}", name)
+    }
+}
+
 /// Errors generated by this crate
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -28,6 +42,10 @@ pub enum Error {
     FeatureNotActive(Feature, String),
     /// When the parquet file is known to be out of spec.
     OutOfSpec(String),
+    /// When a parameter passed to this crate is invalid (e.g. an out-of-range compression level).
+    InvalidParameter(String),
+    /// When the data of a page does not match the checksum declared in its header.
+    Corruption(String),
     /// An error originating from a consumer or dependency
     External(String, Arc<dyn std::error::Error + Send + Sync>),
 }
@@ -43,13 +61,19 @@ impl std::fmt::Display for Error {
             Error::FeatureNotActive(feature, reason) => {
                 write!(
                     fmt,
-                    "The feature \"{:?}\" needs to be active to {}",
+                    "The cargo feature \"{}\" needs to be active to {}",
                     feature, reason
                 )
             }
             Error::OutOfSpec(message) => {
                 write!(fmt, "{}", message)
             }
+            Error::InvalidParameter(message) => {
+                write!(fmt, "Invalid parameter: {}", message)
+            }
+            Error::Corruption(message) => {
+                write!(fmt, "Corrupted data: {}", message)
+            }
             Error::External(message, err) => {
                 write!(fmt, "{}: {}", message, err)
             }
@@ -78,6 +102,13 @@ impl From<lz4_flex::block::CompressError> for Error {
     }
 }
 
+#[cfg(feature = "object_store")]
+impl From<object_store::Error> for Error {
+    fn from(e: object_store::Error) -> Error {
+        Error::External("underlying object store error".to_string(), Arc::new(e))
+    }
+}
+
 impl From<parquet_format_async_temp::thrift::Error> for Error {
     fn from(e: parquet_format_async_temp::thrift::Error) -> Error {
         Error::General(format!("underlying thrift error: {}", e))
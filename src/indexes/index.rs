@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::cmp::Ordering;
 
 use parquet_format_async_temp::ColumnIndex;
 
@@ -13,6 +14,15 @@ pub trait Index: Send + Sync + std::fmt::Debug {
     fn as_any(&self) -> &dyn Any;
 
     fn physical_type(&self) -> &PhysicalType;
+
+    /// Returns the minimum and maximum values of page `page` as they are stored in the column
+    /// index, so that pages can be pruned without knowing the concrete type of the index.
+    ///
+    /// Values of [`NativeType`] are plain-encoded (little-endian), booleans are a single byte
+    /// and byte arrays are returned as is (i.e. without the length prefix of plain encoding).
+    ///
+    /// Returns `None` iff all values of the page are null or the page does not exist.
+    fn min_max_as_bytes(&self, page: usize) -> Option<(Vec<u8>, Vec<u8>)>;
 }
 
 impl PartialEq for dyn Index + '_ {
@@ -99,13 +109,14 @@ impl<T: NativeType> NativeIndex<T> {
             .zip(index.max_values.into_iter())
             .zip(index.null_pages.into_iter())
             .zip(null_counts.into_iter())
-            .map(|(((min, max), is_null), null_count)| {
+            .enumerate()
+            .map(|(page, (((min, max), is_null), null_count))| {
                 let (min, max) = if is_null {
                     (None, None)
                 } else {
-                    let min = min.as_slice().try_into()?;
-                    let max = max.as_slice().try_into()?;
-                    (Some(T::from_le_bytes(min)), Some(T::from_le_bytes(max)))
+                    let min = native_value::<T>(min, "minimum", page)?;
+                    let max = native_value::<T>(&max, "maximum", page)?;
+                    (Some(min), Some(max))
                 };
                 Ok(PageIndex {
                     min,
@@ -121,6 +132,74 @@ impl<T: NativeType> NativeIndex<T> {
             boundary_order: index.boundary_order.try_into()?,
         })
     }
+
+    /// Returns the minimum value of all pages of this index.
+    ///
+    /// Returns `None` when the minimum of any page is `None` (i.e. all its values are null) or
+    /// NaN, since the minimum of the column is then unknown, or when the index has no pages.
+    pub fn global_min(&self) -> Option<&T> {
+        global_value(&self.indexes, |index| index.min.as_ref(), Ordering::Less)
+    }
+
+    /// Returns the maximum value of all pages of this index.
+    ///
+    /// Returns `None` when the maximum of any page is `None` (i.e. all its values are null) or
+    /// NaN, since the maximum of the column is then unknown, or when the index has no pages.
+    pub fn global_max(&self) -> Option<&T> {
+        global_value(&self.indexes, |index| index.max.as_ref(), Ordering::Greater)
+    }
+
+    /// Returns the number of null values of all pages of this index.
+    ///
+    /// Returns `None` when the null count of any page is unknown.
+    pub fn global_null_count(&self) -> Option<i64> {
+        self.indexes.iter().map(|index| index.null_count).sum()
+    }
+}
+
+/// Returns the smallest (`order` is [`Ordering::Less`]) or largest (`order` is
+/// [`Ordering::Greater`]) of the values of `indexes`, or `None` if any of them is `None` or NaN.
+fn global_value<T: NativeType, F: Fn(&PageIndex<T>) -> Option<&T>>(
+    indexes: &[PageIndex<T>],
+    value: F,
+    order: Ordering,
+) -> Option<&T> {
+    let mut result = None;
+    for index in indexes {
+        let value = value(index).filter(|value| !value.is_nan())?;
+        result = match result {
+            Some(result) if T::ord(result, value) == order => Some(result),
+            _ => Some(value),
+        };
+    }
+    result
+}
+
+fn native_value<T: NativeType>(value: &[u8], name: &str, page: usize) -> Result<T, Error> {
+    let bytes = value.try_into().map_err(|_| {
+        Error::OutOfSpec(format!(
+            "The {} value of page {} has {} bytes, but a value of type {:?} has {}",
+            name,
+            page,
+            value.len(),
+            T::TYPE,
+            std::mem::size_of::<T::Bytes>()
+        ))
+    })?;
+    Ok(T::from_le_bytes(bytes))
+}
+
+/// Returns the min and max of page `page` of `indexes`, converted to bytes with `to_bytes`.
+fn min_max_as_bytes<T, F: Fn(&T) -> Vec<u8>>(
+    indexes: &[PageIndex<T>],
+    page: usize,
+    to_bytes: F,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+    let index = indexes.get(page)?;
+    match (&index.min, &index.max) {
+        (Some(min), Some(max)) => Some((to_bytes(min), to_bytes(max))),
+        _ => None,
+    }
 }
 
 /// The index of a page, containing the min and max values of the page.
@@ -142,6 +221,10 @@ impl<T: NativeType> Index for NativeIndex<T> {
     fn physical_type(&self) -> &PhysicalType {
         &T::TYPE
     }
+
+    fn min_max_as_bytes(&self, page: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+        min_max_as_bytes(&self.indexes, page, |x| x.to_le_bytes().as_ref().to_vec())
+    }
 }
 
 /// An index of a column of bytes physical type
@@ -202,6 +285,10 @@ impl Index for ByteIndex {
     fn physical_type(&self) -> &PhysicalType {
         &PhysicalType::ByteArray
     }
+
+    fn min_max_as_bytes(&self, page: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+        min_max_as_bytes(&self.indexes, page, Clone::clone)
+    }
 }
 
 /// An index of a column of fixed len byte physical type
@@ -262,6 +349,10 @@ impl Index for FixedLenByteIndex {
     fn physical_type(&self) -> &PhysicalType {
         &self.primitive_type.physical_type
     }
+
+    fn min_max_as_bytes(&self, page: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+        min_max_as_bytes(&self.indexes, page, Clone::clone)
+    }
 }
 
 /// An index of a column of boolean physical type
@@ -318,4 +409,8 @@ impl Index for BooleanIndex {
     fn physical_type(&self) -> &PhysicalType {
         &PhysicalType::Boolean
     }
+
+    fn min_max_as_bytes(&self, page: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+        min_max_as_bytes(&self.indexes, page, |x| vec![*x as u8])
+    }
 }
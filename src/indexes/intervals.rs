@@ -97,13 +97,39 @@ fn is_in(probe: Interval, intervals: &[Interval]) -> Vec<Interval> {
         .collect()
 }
 
+/// Returns the sorted set of non-empty [`Interval`]s covering the same rows as `intervals`,
+/// where overlapping and adjacent intervals are merged.
+pub fn intervals_merge(intervals: &[Interval]) -> Vec<Interval> {
+    let mut sorted = intervals
+        .iter()
+        .copied()
+        .filter(|interval| interval.length > 0)
+        .collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|interval| interval.start);
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(sorted.len());
+    for interval in sorted {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.start + last.length => {
+                let end = (last.start + last.length).max(interval.start + interval.length);
+                last.length = end - last.start;
+            }
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
 /// Given a set of selected [Interval]s of rows and the set of [`PageLocation`], returns the
 /// a set of [`FilteredPage`] with the same number of items as `locations`.
+/// Overlapping intervals are merged (see [`intervals_merge`]), so that no row is selected twice.
+/// Each page is returned exactly once, with the rows of all the intervals that overlap it.
 pub fn select_pages(
     intervals: &[Interval],
     locations: &[PageLocation],
     num_rows: usize,
 ) -> Result<Vec<FilteredPage>, Error> {
+    let intervals = &intervals_merge(intervals);
     let page_intervals = compute_page_row_intervals(locations, num_rows)?;
 
     page_intervals
@@ -120,3 +146,20 @@ pub fn select_pages(
         })
         .collect()
 }
+
+/// Like [`select_pages`], but errors when `intervals` are not sorted by their start.
+/// # Error
+/// Errors with [`Error::InvalidParameter`] iff `intervals` are not sorted.
+pub fn select_pages_checked(
+    intervals: &[Interval],
+    locations: &[PageLocation],
+    num_rows: usize,
+) -> Result<Vec<FilteredPage>, Error> {
+    if let Some(position) = intervals.windows(2).position(|x| x[0].start > x[1].start) {
+        return Err(Error::InvalidParameter(format!(
+            "Intervals must be sorted, but the interval at position {} starts after the next one",
+            position
+        )));
+    }
+    select_pages(intervals, locations, num_rows)
+}
@@ -5,13 +5,16 @@ pub use crate::parquet_bridge::BoundaryOrder;
 pub use parquet_format_async_temp::PageLocation;
 
 pub use self::index::{BooleanIndex, ByteIndex, FixedLenByteIndex, Index, NativeIndex, PageIndex};
-pub use intervals::{compute_rows, select_pages, FilteredPage, Interval};
+pub use intervals::{
+    compute_rows, intervals_merge, select_pages, select_pages_checked, FilteredPage, Interval,
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use crate::schema::types::{PhysicalType, PrimitiveType};
+    use crate::types::NativeType;
 
     #[test]
     fn test_basic() {
@@ -63,7 +66,7 @@ mod tests {
         let selector = |page: &PageIndex<Vec<u8>>| {
             page.max
                 .as_ref()
-                .map(|x| x.as_slice() > &[97])
+                .map(|x| x.as_slice() > &[97u8][..])
                 .unwrap_or(false) // no max is present => all nulls => not selected
         };
         let selected = index.indexes.iter().map(selector).collect::<Vec<_>>();
@@ -232,4 +235,321 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_merge() {
+        let intervals = &[
+            Interval::new(10, 5),
+            Interval::new(0, 3),
+            Interval::new(2, 3),
+            Interval::new(5, 1),
+            Interval::new(8, 0),
+            Interval::new(12, 1),
+        ];
+        assert_eq!(
+            intervals_merge(intervals),
+            vec![Interval::new(0, 6), Interval::new(10, 5)]
+        );
+        assert_eq!(intervals_merge(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_overlapping_intervals() {
+        let locations = &[
+            PageLocation {
+                offset: 100,
+                compressed_page_size: 20,
+                first_row_index: 0,
+            },
+            PageLocation {
+                offset: 120,
+                compressed_page_size: 20,
+                first_row_index: 4,
+            },
+        ];
+        let num_rows = 10;
+
+        let intervals = &[Interval::new(0, 3), Interval::new(2, 3)];
+
+        let pages = select_pages(intervals, locations, num_rows).unwrap();
+
+        assert_eq!(
+            pages,
+            vec![
+                FilteredPage {
+                    start: 100,
+                    length: 20,
+                    selected_rows: vec![Interval::new(0, 4)],
+                    num_rows: 4,
+                },
+                FilteredPage {
+                    start: 120,
+                    length: 20,
+                    selected_rows: vec![Interval::new(0, 1)],
+                    num_rows: 6
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsorted_intervals() {
+        let locations = &[PageLocation {
+            offset: 100,
+            compressed_page_size: 10,
+            first_row_index: 0,
+        }];
+        let num_rows = 10;
+
+        let intervals = &[Interval::new(5, 1), Interval::new(0, 1)];
+        assert!(matches!(
+            select_pages_checked(intervals, locations, num_rows),
+            Err(crate::error::Error::InvalidParameter(_))
+        ));
+
+        let sorted = &[Interval::new(0, 1), Interval::new(5, 1)];
+        assert_eq!(
+            select_pages_checked(sorted, locations, num_rows).unwrap(),
+            select_pages(intervals, locations, num_rows).unwrap()
+        );
+    }
+
+    fn three_pages() -> [PageLocation; 3] {
+        [
+            PageLocation {
+                offset: 100,
+                compressed_page_size: 20,
+                first_row_index: 0,
+            },
+            PageLocation {
+                offset: 120,
+                compressed_page_size: 20,
+                first_row_index: 10,
+            },
+            PageLocation {
+                offset: 140,
+                compressed_page_size: 20,
+                first_row_index: 20,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_overlapping_intervals_select_pages_once() {
+        let locations = &three_pages();
+        let num_rows = 30;
+
+        // all intervals overlap the second page
+        let intervals = &[
+            Interval::new(12, 3),
+            Interval::new(5, 10),
+            Interval::new(14, 2),
+            Interval::new(12, 3),
+        ];
+
+        let pages = select_pages(intervals, locations, num_rows).unwrap();
+
+        assert_eq!(pages.len(), locations.len());
+        assert_eq!(
+            pages
+                .iter()
+                .map(|page| page.selected_rows.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![Interval::new(5, 5)], vec![Interval::new(0, 6)], vec![]]
+        );
+    }
+
+    #[test]
+    fn test_intervals_touching_at_page_boundary() {
+        let locations = &three_pages();
+        let num_rows = 30;
+
+        // the intervals are adjacent at the start of the second page
+        let intervals = &[Interval::new(10, 5), Interval::new(5, 5)];
+
+        let pages = select_pages(intervals, locations, num_rows).unwrap();
+
+        assert_eq!(
+            pages
+                .iter()
+                .map(|page| page.selected_rows.clone())
+                .collect::<Vec<_>>(),
+            vec![vec![Interval::new(5, 5)], vec![Interval::new(0, 5)], vec![]]
+        );
+    }
+
+    #[test]
+    fn test_interval_covering_row_group() {
+        let locations = &three_pages();
+        let num_rows = 30;
+
+        let intervals = &[Interval::new(0, num_rows), Interval::new(10, 10)];
+
+        let pages = select_pages(intervals, locations, num_rows).unwrap();
+
+        assert_eq!(
+            pages,
+            vec![
+                FilteredPage {
+                    start: 100,
+                    length: 20,
+                    selected_rows: vec![Interval::new(0, 10)],
+                    num_rows: 10,
+                },
+                FilteredPage {
+                    start: 120,
+                    length: 20,
+                    selected_rows: vec![Interval::new(0, 10)],
+                    num_rows: 10,
+                },
+                FilteredPage {
+                    start: 140,
+                    length: 20,
+                    selected_rows: vec![Interval::new(0, 10)],
+                    num_rows: 10,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_intervals() {
+        let locations = &three_pages();
+        let num_rows = 30;
+
+        let pages = select_pages(&[], locations, num_rows).unwrap();
+
+        assert_eq!(pages.len(), locations.len());
+        assert!(pages.iter().all(|page| page.selected_rows.is_empty()));
+
+        assert_eq!(select_pages(&[], &[], 0).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn min_max_as_bytes() {
+        let index = NativeIndex::<i32> {
+            primitive_type: PrimitiveType::from_physical("c1".to_string(), PhysicalType::Int32),
+            indexes: vec![
+                PageIndex {
+                    min: Some(-1),
+                    max: Some(256),
+                    null_count: Some(0),
+                },
+                PageIndex {
+                    min: None,
+                    max: None,
+                    null_count: Some(10),
+                },
+            ],
+            boundary_order: Default::default(),
+        };
+        let index = &index as &dyn Index;
+
+        assert_eq!(
+            index.min_max_as_bytes(0),
+            Some((vec![255, 255, 255, 255], vec![0, 1, 0, 0]))
+        );
+        // all values are null
+        assert_eq!(index.min_max_as_bytes(1), None);
+        assert_eq!(index.min_max_as_bytes(2), None);
+
+        let index = ByteIndex {
+            primitive_type: PrimitiveType::from_physical("c1".to_string(), PhysicalType::ByteArray),
+            indexes: vec![
+                PageIndex {
+                    min: None,
+                    max: None,
+                    null_count: Some(1),
+                },
+                PageIndex {
+                    min: Some(vec![]),
+                    max: Some(b"abc".to_vec()),
+                    null_count: Some(0),
+                },
+            ],
+            boundary_order: Default::default(),
+        };
+        let index = &index as &dyn Index;
+
+        assert_eq!(index.min_max_as_bytes(0), None);
+        // byte arrays are not prefixed by their length
+        assert_eq!(index.min_max_as_bytes(1), Some((vec![], b"abc".to_vec())));
+    }
+
+    fn native_index<T: NativeType>(
+        pages: Vec<(Option<T>, Option<T>, Option<i64>)>,
+    ) -> NativeIndex<T> {
+        NativeIndex {
+            primitive_type: PrimitiveType::from_physical("c1".to_string(), T::TYPE),
+            indexes: pages
+                .into_iter()
+                .map(|(min, max, null_count)| PageIndex {
+                    min,
+                    max,
+                    null_count,
+                })
+                .collect(),
+            boundary_order: Default::default(),
+        }
+    }
+
+    /// Checks the global statistics of indexes of type `T`, where `values` are sorted
+    fn global_statistics<T: NativeType + PartialEq>(values: [T; 4]) {
+        let [a, b, c, d] = values;
+
+        let index = native_index(vec![
+            (Some(b), Some(c), Some(1)),
+            (Some(a), Some(b), Some(0)),
+            (Some(c), Some(d), Some(2)),
+        ]);
+        assert_eq!(index.global_min(), Some(&a));
+        assert_eq!(index.global_max(), Some(&d));
+        assert_eq!(index.global_null_count(), Some(3));
+
+        // a page whose values are all null has no min nor max
+        let index = native_index(vec![(Some(a), Some(d), Some(0)), (None, None, Some(5))]);
+        assert_eq!(index.global_min(), None);
+        assert_eq!(index.global_max(), None);
+        assert_eq!(index.global_null_count(), Some(5));
+
+        // unknown null count
+        let index = native_index(vec![(Some(a), Some(d), None), (Some(b), Some(c), Some(1))]);
+        assert_eq!(index.global_min(), Some(&a));
+        assert_eq!(index.global_max(), Some(&d));
+        assert_eq!(index.global_null_count(), None);
+
+        let index = native_index::<T>(vec![]);
+        assert_eq!(index.global_min(), None);
+        assert_eq!(index.global_max(), None);
+        assert_eq!(index.global_null_count(), Some(0));
+    }
+
+    #[test]
+    fn global_statistics_integers() {
+        global_statistics::<i32>([i32::MIN, -1, 0, i32::MAX]);
+        global_statistics::<i64>([i64::MIN, -1, 0, i64::MAX]);
+        // ordered by day and then by nanoseconds within the day
+        global_statistics::<[u32; 3]>([[0, 0, 0], [u32::MAX, 0, 0], [0, 1, 0], [0, 0, 1]]);
+    }
+
+    #[test]
+    fn global_statistics_floats() {
+        global_statistics::<f32>([f32::NEG_INFINITY, -0.5, 1.5, f32::MAX]);
+        global_statistics::<f64>([f64::MIN, -0.5, 1.5, f64::INFINITY]);
+
+        // a NaN min or max makes the min or max unknown
+        let index = native_index(vec![
+            (Some(f32::NAN), Some(2.0), Some(0)),
+            (Some(1.0), Some(3.0), Some(0)),
+        ]);
+        assert_eq!(index.global_min(), None);
+        assert_eq!(index.global_max(), Some(&3.0));
+
+        let index = native_index(vec![
+            (Some(1.0), Some(3.0), Some(0)),
+            (Some(0.0), Some(f64::NAN), Some(0)),
+        ]);
+        assert_eq!(index.global_min(), Some(&0.0));
+        assert_eq!(index.global_max(), None);
+    }
 }
@@ -0,0 +1,893 @@
+//! Reading of the Arrow schema that Arrow implementations (e.g. pyarrow and arrow-rs) embed in
+//! the key-value metadata of the parquet files they write, under the key [`ARROW_SCHEMA_KEY`].
+//!
+//! The schema is a base64-encoded Arrow IPC `Schema` message, a flatbuffer, which is read here
+//! without depending on an Arrow implementation.
+use crate::error::{Error, Result};
+use crate::metadata::{KeyValue, SchemaDescriptor};
+use crate::schema::types::ParquetType;
+use crate::schema::Repetition;
+
+/// The key of the key-value metadata under which the Arrow schema is stored.
+pub const ARROW_SCHEMA_KEY: &str = "ARROW:schema";
+
+/// The continuation marker that prefixes IPC messages since Arrow 0.15.
+const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+/// The type of the header of IPC messages containing a schema.
+const MESSAGE_HEADER_SCHEMA: u8 = 1;
+/// The maximum nesting of fields, so that malicious schemas cannot overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// The unit of an Arrow time, timestamp or duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+/// The unit of an Arrow date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateUnit {
+    Day,
+    Millisecond,
+}
+
+/// The unit of an Arrow interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalUnit {
+    YearMonth,
+    DayTime,
+    MonthDayNano,
+}
+
+/// The logical type of an Arrow field. The types of nested fields, e.g. the type of the items
+/// of a list, are the types of its [`ArrowField::children`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArrowDataType {
+    Null,
+    Boolean,
+    Int {
+        bit_width: i32,
+        is_signed: bool,
+    },
+    Float16,
+    Float32,
+    Float64,
+    Binary,
+    LargeBinary,
+    Utf8,
+    LargeUtf8,
+    FixedSizeBinary(i32),
+    Decimal {
+        precision: i32,
+        scale: i32,
+        bit_width: i32,
+    },
+    Date(DateUnit),
+    Time(TimeUnit, i32),
+    Timestamp(TimeUnit, Option<String>),
+    Duration(TimeUnit),
+    Interval(IntervalUnit),
+    List,
+    LargeList,
+    FixedSizeList(i32),
+    Struct,
+    Map {
+        keys_sorted: bool,
+    },
+    Union,
+    /// A type not known by this module, with its tag in the `Type` union of the IPC format
+    Other(u8),
+}
+
+/// A field of an Arrow schema.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrowField {
+    pub name: String,
+    pub data_type: ArrowDataType,
+    pub nullable: bool,
+    /// Whether the field is dictionary-encoded
+    pub is_dictionary: bool,
+    pub children: Vec<ArrowField>,
+    pub metadata: Vec<KeyValue>,
+}
+
+/// An Arrow schema.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArrowSchema {
+    pub fields: Vec<ArrowField>,
+    pub metadata: Vec<KeyValue>,
+}
+
+impl ArrowSchema {
+    /// Returns the top-level field named `name`, if any.
+    pub fn field(&self, name: &str) -> Option<&ArrowField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Reads the Arrow schema stored in `key_value_metadata` (see
+/// [`FileMetaData::key_value_metadata`](crate::metadata::FileMetaData::key_value_metadata)).
+/// Returns `None` if there is no Arrow schema.
+/// # Errors
+/// Errors iff the Arrow schema is not base64-encoded or is not a valid IPC schema message.
+pub fn read_arrow_schema(key_value_metadata: Option<&[KeyValue]>) -> Result<Option<ArrowSchema>> {
+    let encoded = key_value_metadata
+        .unwrap_or_default()
+        .iter()
+        .find(|x| x.key == ARROW_SCHEMA_KEY)
+        .and_then(|x| x.value.as_ref());
+    encoded
+        .map(|encoded| deserialize_schema(&decode_base64(encoded)?))
+        .transpose()
+}
+
+/// A parquet field with the Arrow field it was written from, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedField<'a> {
+    pub parquet: &'a ParquetType,
+    pub arrow: Option<&'a ArrowField>,
+    /// The matched children of a group: the fields of a struct, matched by name, or the
+    /// element of a list or the key-value group of a map, matched with the Arrow field's
+    /// only child.
+    pub children: Vec<MatchedField<'a>>,
+}
+
+/// Returns the fields of `schema`, recursively, with the fields of `arrow` they were written
+/// from, so that the Arrow types of the parquet fields can be recovered.
+///
+/// The top-level fields and the fields of structs are matched by name. The element of a
+/// (three-level) parquet list, i.e. the only field of its repeated group, and the repeated
+/// group of a two-level list or of a map are matched with the only child of the Arrow list
+/// or map.
+pub fn match_fields<'a>(
+    schema: &'a SchemaDescriptor,
+    arrow: &'a ArrowSchema,
+) -> Vec<MatchedField<'a>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| match_field(field, arrow.field(field.name())))
+        .collect()
+}
+
+fn match_field<'a>(parquet: &'a ParquetType, arrow: Option<&'a ArrowField>) -> MatchedField<'a> {
+    let children = match parquet {
+        ParquetType::PrimitiveType(_) => vec![],
+        ParquetType::GroupType { fields, .. } => {
+            let arrow_children = arrow.map(|x| (&x.data_type, x.children.as_slice()));
+            match (arrow_children, repeated_element(fields)) {
+                (
+                    Some((
+                        ArrowDataType::List
+                        | ArrowDataType::LargeList
+                        | ArrowDataType::FixedSizeList(_)
+                        | ArrowDataType::Map { .. },
+                        [item],
+                    )),
+                    Some(element),
+                ) => vec![match_field(element, Some(item))],
+                (arrow_children, _) => fields
+                    .iter()
+                    .map(|field| {
+                        let arrow = arrow_children.and_then(|(_, children)| {
+                            children.iter().find(|x| x.name == field.name())
+                        });
+                        match_field(field, arrow)
+                    })
+                    .collect(),
+            }
+        }
+    };
+    MatchedField {
+        parquet,
+        arrow,
+        children,
+    }
+}
+
+/// Returns the element of a list or map whose fields are `fields`: the only field of its
+/// only (repeated) group when it has one field, and the repeated field itself otherwise.
+fn repeated_element(fields: &[ParquetType]) -> Option<&ParquetType> {
+    match fields {
+        [repeated] if repeated.get_field_info().repetition == Repetition::Repeated => {
+            match repeated {
+                ParquetType::GroupType { fields, .. } if fields.len() == 1 => Some(&fields[0]),
+                other => Some(other),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Deserializes an Arrow IPC message containing a schema, as stored in [`ARROW_SCHEMA_KEY`].
+/// # Errors
+/// Errors iff `bytes` is not a valid IPC schema message.
+pub fn deserialize_schema(bytes: &[u8]) -> Result<ArrowSchema> {
+    // <continuation: 0xFFFFFFFF (since 0.15)> <metadata length: i32> <flatbuffer>
+    let bytes = bytes.strip_prefix(&CONTINUATION_MARKER).unwrap_or(bytes);
+    let length = read_u32(bytes, 0)? as usize;
+    let message = bytes
+        .get(4..4 + length)
+        .ok_or_else(|| out_of_spec("The IPC message is shorter than its length"))?;
+
+    let message = Table::root(message)?;
+    // Message { version: short, header_type: ubyte, header: MessageHeader, ... }
+    if message.u8(1, 0)? != MESSAGE_HEADER_SCHEMA {
+        return Err(out_of_spec("The IPC message does not contain a schema"));
+    }
+    let schema = message
+        .table(2)?
+        .ok_or_else(|| out_of_spec("The IPC message has no schema"))?;
+
+    // Schema { endianness: short, fields: [Field], custom_metadata: [KeyValue], ... }
+    Ok(ArrowSchema {
+        fields: schema
+            .tables(1)?
+            .iter()
+            .map(|field| deserialize_field(field, 0))
+            .collect::<Result<_>>()?,
+        metadata: deserialize_metadata(&schema, 2)?,
+    })
+}
+
+fn deserialize_field(field: &Table, depth: usize) -> Result<ArrowField> {
+    if depth > MAX_DEPTH {
+        return Err(out_of_spec(
+            "The fields of the Arrow schema are nested too deep",
+        ));
+    }
+    // Field { name: string, nullable: bool, type_type: ubyte, type: Type,
+    //   dictionary: DictionaryEncoding, children: [Field], custom_metadata: [KeyValue] }
+    Ok(ArrowField {
+        name: field.string(0)?.unwrap_or_default().to_string(),
+        nullable: field.bool(1, false)?,
+        data_type: deserialize_type(field.u8(2, 0)?, field.table(3)?)?,
+        is_dictionary: field.table(4)?.is_some(),
+        children: field
+            .tables(5)?
+            .iter()
+            .map(|child| deserialize_field(child, depth + 1))
+            .collect::<Result<_>>()?,
+        metadata: deserialize_metadata(field, 6)?,
+    })
+}
+
+fn deserialize_type(tag: u8, type_: Option<Table>) -> Result<ArrowDataType> {
+    let time_unit = |value| match value {
+        0 => Ok(TimeUnit::Second),
+        1 => Ok(TimeUnit::Millisecond),
+        2 => Ok(TimeUnit::Microsecond),
+        3 => Ok(TimeUnit::Nanosecond),
+        other => Err(out_of_spec(&format!("Unknown Arrow time unit {}", other))),
+    };
+    // the parameters of the type, with the defaults of the IPC format when absent
+    let i16_ = |index, default| type_.map_or(Ok(default), |x| x.i16(index, default));
+    let i32_ = |index, default| type_.map_or(Ok(default), |x| x.i32(index, default));
+    let bool_ = |index| type_.map_or(Ok(false), |x| x.bool(index, false));
+
+    Ok(match tag {
+        1 => ArrowDataType::Null,
+        2 => ArrowDataType::Int {
+            bit_width: i32_(0, 0)?,
+            is_signed: bool_(1)?,
+        },
+        3 => match i16_(0, 0)? {
+            0 => ArrowDataType::Float16,
+            1 => ArrowDataType::Float32,
+            2 => ArrowDataType::Float64,
+            other => return Err(out_of_spec(&format!("Unknown Arrow float {}", other))),
+        },
+        4 => ArrowDataType::Binary,
+        5 => ArrowDataType::Utf8,
+        6 => ArrowDataType::Boolean,
+        7 => ArrowDataType::Decimal {
+            precision: i32_(0, 0)?,
+            scale: i32_(1, 0)?,
+            bit_width: i32_(2, 128)?,
+        },
+        8 => match i16_(0, 1)? {
+            0 => ArrowDataType::Date(DateUnit::Day),
+            1 => ArrowDataType::Date(DateUnit::Millisecond),
+            other => return Err(out_of_spec(&format!("Unknown Arrow date unit {}", other))),
+        },
+        9 => ArrowDataType::Time(time_unit(i16_(0, 1)?)?, i32_(1, 32)?),
+        10 => ArrowDataType::Timestamp(
+            time_unit(i16_(0, 0)?)?,
+            type_
+                .map(|x| x.string(1))
+                .transpose()?
+                .flatten()
+                .map(|x| x.to_string()),
+        ),
+        11 => match i16_(0, 0)? {
+            0 => ArrowDataType::Interval(IntervalUnit::YearMonth),
+            1 => ArrowDataType::Interval(IntervalUnit::DayTime),
+            2 => ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+            other => {
+                return Err(out_of_spec(&format!(
+                    "Unknown Arrow interval unit {}",
+                    other
+                )))
+            }
+        },
+        12 => ArrowDataType::List,
+        13 => ArrowDataType::Struct,
+        14 => ArrowDataType::Union,
+        15 => ArrowDataType::FixedSizeBinary(i32_(0, 0)?),
+        16 => ArrowDataType::FixedSizeList(i32_(0, 0)?),
+        17 => ArrowDataType::Map {
+            keys_sorted: bool_(0)?,
+        },
+        18 => ArrowDataType::Duration(time_unit(i16_(0, 1)?)?),
+        19 => ArrowDataType::LargeBinary,
+        20 => ArrowDataType::LargeUtf8,
+        21 => ArrowDataType::LargeList,
+        0 => return Err(out_of_spec("An Arrow field has no type")),
+        other => ArrowDataType::Other(other),
+    })
+}
+
+fn deserialize_metadata(table: &Table, index: usize) -> Result<Vec<KeyValue>> {
+    // KeyValue { key: string, value: string }
+    table
+        .tables(index)?
+        .iter()
+        .map(|x| {
+            Ok(KeyValue {
+                key: x.string(0)?.unwrap_or_default().to_string(),
+                value: x.string(1)?.map(|x| x.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn out_of_spec(message: &str) -> Error {
+    Error::OutOfSpec(format!("Invalid Arrow schema: {}", message))
+}
+
+fn read_bytes<const N: usize>(buffer: &[u8], position: usize) -> Result<[u8; N]> {
+    buffer
+        .get(position..position.saturating_add(N))
+        .map(|x| x.try_into().unwrap())
+        .ok_or_else(|| out_of_spec("An offset is out of bounds"))
+}
+
+fn read_u32(buffer: &[u8], position: usize) -> Result<u32> {
+    read_bytes(buffer, position).map(u32::from_le_bytes)
+}
+
+/// Follows the offset at `position`, which is relative to `position`.
+fn follow(buffer: &[u8], position: usize) -> Result<usize> {
+    Ok(position.saturating_add(read_u32(buffer, position)? as usize))
+}
+
+/// A flatbuffer table.
+#[derive(Debug, Clone, Copy)]
+struct Table<'a> {
+    buffer: &'a [u8],
+    position: usize,
+    vtable: usize,
+    vtable_length: usize,
+}
+
+impl<'a> Table<'a> {
+    fn root(buffer: &'a [u8]) -> Result<Self> {
+        Self::try_new(buffer, follow(buffer, 0)?)
+    }
+
+    fn try_new(buffer: &'a [u8], position: usize) -> Result<Self> {
+        let offset = i32::from_le_bytes(read_bytes(buffer, position)?) as i64;
+        let vtable = usize::try_from(position as i64 - offset)
+            .map_err(|_| out_of_spec("A vtable is out of bounds"))?;
+        let vtable_length = u16::from_le_bytes(read_bytes(buffer, vtable)?) as usize;
+        Ok(Self {
+            buffer,
+            position,
+            vtable,
+            vtable_length,
+        })
+    }
+
+    /// The position of the field `index`, if present.
+    fn field(&self, index: usize) -> Result<Option<usize>> {
+        // the vtable is <vtable length: u16> <table length: u16> <field offsets: [u16]>
+        let entry = 4 + 2 * index;
+        if entry + 2 > self.vtable_length {
+            return Ok(None);
+        }
+        let offset = u16::from_le_bytes(read_bytes(self.buffer, self.vtable + entry)?);
+        Ok((offset != 0).then(|| self.position + offset as usize))
+    }
+
+    fn u8(&self, index: usize, default: u8) -> Result<u8> {
+        self.field(index)?.map_or(Ok(default), |x| {
+            read_bytes::<1>(self.buffer, x).map(|x| x[0])
+        })
+    }
+
+    fn bool(&self, index: usize, default: bool) -> Result<bool> {
+        self.u8(index, default as u8).map(|x| x != 0)
+    }
+
+    fn i16(&self, index: usize, default: i16) -> Result<i16> {
+        self.field(index)?.map_or(Ok(default), |x| {
+            read_bytes(self.buffer, x).map(i16::from_le_bytes)
+        })
+    }
+
+    fn i32(&self, index: usize, default: i32) -> Result<i32> {
+        self.field(index)?.map_or(Ok(default), |x| {
+            read_bytes(self.buffer, x).map(i32::from_le_bytes)
+        })
+    }
+
+    fn table(&self, index: usize) -> Result<Option<Table<'a>>> {
+        self.field(index)?
+            .map(|x| Table::try_new(self.buffer, follow(self.buffer, x)?))
+            .transpose()
+    }
+
+    fn string(&self, index: usize) -> Result<Option<&'a str>> {
+        self.field(index)?
+            .map(|x| {
+                let position = follow(self.buffer, x)?;
+                let length = read_u32(self.buffer, position)? as usize;
+                let bytes = self
+                    .buffer
+                    .get(position + 4..(position + 4).saturating_add(length))
+                    .ok_or_else(|| out_of_spec("A string is out of bounds"))?;
+                std::str::from_utf8(bytes).map_err(|_| out_of_spec("A string is not utf8"))
+            })
+            .transpose()
+    }
+
+    fn tables(&self, index: usize) -> Result<Vec<Table<'a>>> {
+        let position = match self.field(index)? {
+            Some(x) => follow(self.buffer, x)?,
+            None => return Ok(vec![]),
+        };
+        let length = read_u32(self.buffer, position)? as usize;
+        // every element is at least 4 bytes long
+        if length > self.buffer.len() / 4 {
+            return Err(out_of_spec("A vector is out of bounds"));
+        }
+        (0..length)
+            .map(|i| {
+                let element = position + 4 + 4 * i;
+                Table::try_new(self.buffer, follow(self.buffer, element)?)
+            })
+            .collect()
+    }
+}
+
+/// Decodes standard base64, with or without padding.
+fn decode_base64(encoded: &str) -> Result<Vec<u8>> {
+    let value = |x: u8| match x {
+        b'A'..=b'Z' => Ok(x - b'A'),
+        b'a'..=b'z' => Ok(x - b'a' + 26),
+        b'0'..=b'9' => Ok(x - b'0' + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(out_of_spec("The schema is not base64-encoded")),
+    };
+    let encoded = encoded.trim_end_matches('=').as_bytes();
+    if encoded.len() % 4 == 1 {
+        return Err(out_of_spec("The schema is not base64-encoded"));
+    }
+
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.chunks(4) {
+        let mut group = 0u32;
+        for (i, x) in chunk.iter().enumerate() {
+            group |= (value(*x)? as u32) << (18 - 6 * i);
+        }
+        decoded.extend_from_slice(&group.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::PhysicalType;
+
+    /// A flatbuffer object, written by [`write`].
+    enum Fb {
+        Table(Vec<Option<Fb>>),
+        Vector(Vec<Fb>),
+        String(&'static str),
+        U8(u8),
+        I16(i16),
+        I32(i32),
+    }
+
+    /// Writes `object` at the end of `buffer`, with its children after it (flatbuffers only
+    /// require children to be after the offsets to them), returning its position.
+    fn write(buffer: &mut Vec<u8>, object: &Fb) -> usize {
+        match object {
+            Fb::Table(fields) => {
+                // the vtable, then the table
+                let vtable = buffer.len();
+                let sizes = fields
+                    .iter()
+                    .map(|x| match x {
+                        None => 0,
+                        Some(Fb::U8(_)) => 1,
+                        Some(Fb::I16(_)) => 2,
+                        Some(_) => 4,
+                    })
+                    .collect::<Vec<_>>();
+                let table_length = 4 + sizes.iter().sum::<usize>();
+                buffer.extend_from_slice(&(4 + 2 * fields.len() as u16).to_le_bytes());
+                buffer.extend_from_slice(&(table_length as u16).to_le_bytes());
+                let mut offset = 4u16;
+                for size in &sizes {
+                    let entry = if *size == 0 { 0 } else { offset };
+                    buffer.extend_from_slice(&entry.to_le_bytes());
+                    offset += *size as u16;
+                }
+
+                let table = buffer.len();
+                buffer.extend_from_slice(&((table - vtable) as i32).to_le_bytes());
+                let mut children = vec![];
+                for field in fields.iter().flatten() {
+                    match field {
+                        Fb::U8(x) => buffer.push(*x),
+                        Fb::I16(x) => buffer.extend_from_slice(&x.to_le_bytes()),
+                        Fb::I32(x) => buffer.extend_from_slice(&x.to_le_bytes()),
+                        child => {
+                            children.push((buffer.len(), child));
+                            buffer.extend_from_slice(&[0; 4]);
+                        }
+                    }
+                }
+                for (slot, child) in children {
+                    let position = write(buffer, child);
+                    let offset = (position - slot) as u32;
+                    buffer[slot..slot + 4].copy_from_slice(&offset.to_le_bytes());
+                }
+                table
+            }
+            Fb::Vector(elements) => {
+                let vector = buffer.len();
+                buffer.extend_from_slice(&(elements.len() as u32).to_le_bytes());
+                buffer.resize(vector + 4 + 4 * elements.len(), 0);
+                for (i, element) in elements.iter().enumerate() {
+                    let slot = vector + 4 + 4 * i;
+                    let position = write(buffer, element);
+                    let offset = (position - slot) as u32;
+                    buffer[slot..slot + 4].copy_from_slice(&offset.to_le_bytes());
+                }
+                vector
+            }
+            Fb::String(x) => {
+                let string = buffer.len();
+                buffer.extend_from_slice(&(x.len() as u32).to_le_bytes());
+                buffer.extend_from_slice(x.as_bytes());
+                buffer.push(0);
+                string
+            }
+            _ => unreachable!("scalars are written inline"),
+        }
+    }
+
+    /// Serializes a schema message with `fields` as an IPC message, with the continuation
+    /// marker and the length of the flatbuffer.
+    fn message(fields: Vec<Fb>, metadata: Vec<Fb>) -> Vec<u8> {
+        let schema = Fb::Table(vec![
+            Some(Fb::I16(0)),
+            Some(Fb::Vector(fields)),
+            Some(Fb::Vector(metadata)),
+        ]);
+        let message = Fb::Table(vec![
+            Some(Fb::I16(4)),
+            Some(Fb::U8(MESSAGE_HEADER_SCHEMA)),
+            Some(schema),
+        ]);
+        let mut flatbuffer = vec![0; 4];
+        let root = write(&mut flatbuffer, &message);
+        flatbuffer[..4].copy_from_slice(&(root as u32).to_le_bytes());
+
+        let mut bytes = CONTINUATION_MARKER.to_vec();
+        bytes.extend_from_slice(&(flatbuffer.len() as u32).to_le_bytes());
+        bytes.extend(flatbuffer);
+        bytes
+    }
+
+    fn field(name: &'static str, nullable: bool, tag: u8, type_: Fb, children: Vec<Fb>) -> Fb {
+        Fb::Table(vec![
+            Some(Fb::String(name)),
+            Some(Fb::U8(nullable as u8)),
+            Some(Fb::U8(tag)),
+            Some(type_),
+            None,
+            Some(Fb::Vector(children)),
+        ])
+    }
+
+    fn key_value(key: &'static str, value: &'static str) -> Fb {
+        Fb::Table(vec![Some(Fb::String(key)), Some(Fb::String(value))])
+    }
+
+    fn encode_base64(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut encoded = String::new();
+        for chunk in bytes.chunks(3) {
+            let mut group = [0u8; 4];
+            group[1..1 + chunk.len()].copy_from_slice(chunk);
+            let group = u32::from_be_bytes(group);
+            for i in 0..=chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * i) & 63) as usize] as char);
+            }
+            (chunk.len()..3).for_each(|_| encoded.push('='));
+        }
+        encoded
+    }
+
+    fn schema_bytes() -> Vec<u8> {
+        let int64 = Fb::Table(vec![Some(Fb::I32(64)), Some(Fb::U8(1))]);
+        let decimal = Fb::Table(vec![Some(Fb::I32(10)), Some(Fb::I32(2))]);
+        let timestamp = Fb::Table(vec![Some(Fb::I16(2)), Some(Fb::String("UTC"))]);
+        let empty = || Fb::Table(vec![]);
+        message(
+            vec![
+                field("a", false, 2, int64, vec![]),
+                field("b", true, 5, empty(), vec![]),
+                field("c", true, 7, decimal, vec![]),
+                field("d", true, 10, timestamp, vec![]),
+                field(
+                    "e",
+                    true,
+                    12,
+                    empty(),
+                    vec![field("item", true, 6, empty(), vec![])],
+                ),
+            ],
+            vec![key_value("pandas", "{}")],
+        )
+    }
+
+    #[test]
+    fn deserialize() -> Result<()> {
+        let schema = deserialize_schema(&schema_bytes())?;
+
+        let types = schema
+            .fields
+            .iter()
+            .map(|x| (x.name.as_str(), x.nullable, x.data_type.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                (
+                    "a",
+                    false,
+                    ArrowDataType::Int {
+                        bit_width: 64,
+                        is_signed: true
+                    }
+                ),
+                ("b", true, ArrowDataType::Utf8),
+                (
+                    "c",
+                    true,
+                    ArrowDataType::Decimal {
+                        precision: 10,
+                        scale: 2,
+                        bit_width: 128
+                    }
+                ),
+                (
+                    "d",
+                    true,
+                    ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".to_string()))
+                ),
+                ("e", true, ArrowDataType::List),
+            ]
+        );
+        assert_eq!(schema.fields[4].children[0].name, "item");
+        assert_eq!(
+            schema.fields[4].children[0].data_type,
+            ArrowDataType::Boolean
+        );
+        assert_eq!(
+            schema.metadata,
+            vec![KeyValue {
+                key: "pandas".to_string(),
+                value: Some("{}".to_string())
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_key_value_metadata() -> Result<()> {
+        assert_eq!(read_arrow_schema(None)?, None);
+
+        let key_value_metadata = vec![
+            KeyValue {
+                key: "other".to_string(),
+                value: None,
+            },
+            KeyValue {
+                key: ARROW_SCHEMA_KEY.to_string(),
+                value: Some(encode_base64(&schema_bytes())),
+            },
+        ];
+        assert_eq!(read_arrow_schema(Some(&key_value_metadata[..1]))?, None);
+        let arrow = read_arrow_schema(Some(&key_value_metadata))?.unwrap();
+        assert_eq!(arrow, deserialize_schema(&schema_bytes())?);
+
+        let schema = SchemaDescriptor::new(
+            "schema".to_string(),
+            vec![
+                ParquetType::from_physical("a".to_string(), PhysicalType::Int64),
+                ParquetType::from_physical("z".to_string(), PhysicalType::Int32),
+            ],
+        );
+        let matched = match_fields(&schema, &arrow)
+            .into_iter()
+            .map(|x| (x.parquet.name(), x.arrow.map(|x| x.data_type.clone())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            matched,
+            vec![
+                (
+                    "a",
+                    Some(ArrowDataType::Int {
+                        bit_width: 64,
+                        is_signed: true
+                    })
+                ),
+                ("z", None)
+            ]
+        );
+        Ok(())
+    }
+
+    fn arrow_field(name: &str, data_type: ArrowDataType, children: Vec<ArrowField>) -> ArrowField {
+        ArrowField {
+            name: name.to_string(),
+            data_type,
+            nullable: true,
+            is_dictionary: false,
+            children,
+            metadata: vec![],
+        }
+    }
+
+    fn group(name: &str, repetition: Repetition, fields: Vec<ParquetType>) -> ParquetType {
+        ParquetType::from_group(name.to_string(), repetition, None, None, fields, None)
+    }
+
+    /// The names of the parquet fields matched with an Arrow field, depth first
+    fn matched_names(fields: &[MatchedField]) -> Vec<(String, Option<String>)> {
+        fields
+            .iter()
+            .flat_map(|x| {
+                std::iter::once((
+                    x.parquet.name().to_string(),
+                    x.arrow.map(|x| x.name.clone()),
+                ))
+                .chain(matched_names(&x.children))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn match_nested() {
+        let int32 = |name: &str| ParquetType::from_physical(name.to_string(), PhysicalType::Int32);
+        let schema = SchemaDescriptor::new(
+            "schema".to_string(),
+            vec![
+                // a list<int32> of three levels
+                group(
+                    "list",
+                    Repetition::Optional,
+                    vec![group("list", Repetition::Repeated, vec![int32("element")])],
+                ),
+                // a map<int32, int32>
+                group(
+                    "map",
+                    Repetition::Optional,
+                    vec![group(
+                        "key_value",
+                        Repetition::Repeated,
+                        vec![int32("key"), int32("value")],
+                    )],
+                ),
+                // a struct<a: int32, b: int32> whose field b is not in the Arrow schema
+                group("struct", Repetition::Optional, vec![int32("a"), int32("b")]),
+            ],
+        );
+        let int32 = |name: &str| {
+            let data_type = ArrowDataType::Int {
+                bit_width: 32,
+                is_signed: true,
+            };
+            arrow_field(name, data_type, vec![])
+        };
+        let arrow = ArrowSchema {
+            fields: vec![
+                arrow_field("list", ArrowDataType::List, vec![int32("item")]),
+                arrow_field(
+                    "map",
+                    ArrowDataType::Map { keys_sorted: false },
+                    vec![arrow_field(
+                        "entries",
+                        ArrowDataType::Struct,
+                        vec![int32("key"), int32("value")],
+                    )],
+                ),
+                arrow_field("struct", ArrowDataType::Struct, vec![int32("a")]),
+            ],
+            metadata: vec![],
+        };
+
+        let matched = match_fields(&schema, &arrow);
+        let some = |x: &str| Some(x.to_string());
+        assert_eq!(
+            matched_names(&matched),
+            vec![
+                ("list".to_string(), some("list")),
+                ("element".to_string(), some("item")),
+                ("map".to_string(), some("map")),
+                ("key_value".to_string(), some("entries")),
+                ("key".to_string(), some("key")),
+                ("value".to_string(), some("value")),
+                ("struct".to_string(), some("struct")),
+                ("a".to_string(), some("a")),
+                ("b".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn base64() -> Result<()> {
+        for bytes in [
+            &b""[..],
+            b"a",
+            b"ab",
+            b"abc",
+            b"abcd",
+            &[0xff, 0x00, 0xfe, 0x7f],
+        ] {
+            let encoded = encode_base64(bytes);
+            assert_eq!(decode_base64(&encoded)?, bytes);
+            assert_eq!(decode_base64(encoded.trim_end_matches('='))?, bytes);
+        }
+        assert_eq!(decode_base64("aGVsbG8=")?, b"hello");
+        assert!(decode_base64("a").is_err());
+        assert!(decode_base64("a!==").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = schema_bytes();
+        // truncated messages
+        for length in [0, 3, 8, 20, bytes.len() - 1] {
+            assert!(deserialize_schema(&bytes[..length]).is_err());
+        }
+        // messages with random bytes do not panic
+        let mut random = crate::tests::xorshift();
+        for _ in 0..1000 {
+            let mut bytes = bytes.clone();
+            for state in random.by_ref().take(4) {
+                let index = 8 + state as usize % (bytes.len() - 8);
+                bytes[index] = (state >> 24) as u8;
+            }
+            let _ = deserialize_schema(&bytes);
+        }
+
+        let key_value_metadata = vec![KeyValue {
+            key: ARROW_SCHEMA_KEY.to_string(),
+            value: Some("not base64!".to_string()),
+        }];
+        assert!(read_arrow_schema(Some(&key_value_metadata)).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+//! Interoperability with the schemas of other formats, stored in the key-value metadata of
+//! parquet files.
+pub mod arrow;
@@ -9,6 +9,8 @@ pub mod compression;
 pub mod deserialize;
 pub mod encoding;
 pub mod indexes;
+#[cfg(feature = "arrow-schema")]
+pub mod interop;
 pub mod metadata;
 pub mod page;
 mod parquet_bridge;
@@ -23,6 +25,8 @@ pub use streaming_decompression::FallibleStreamingIterator;
 
 const FOOTER_SIZE: u64 = 8;
 const PARQUET_MAGIC: [u8; 4] = [b'P', b'A', b'R', b'1'];
+/// The magic of parquet files whose footer is encrypted (modular encryption), which is not supported.
+const PARQUET_ENCRYPTED_MAGIC: [u8; 4] = [b'P', b'A', b'R', b'E'];
 
 /// The number of bytes read at the end of the parquet file on first read
 const DEFAULT_FOOTER_READ_SIZE: u64 = 64 * 1024;
@@ -36,4 +40,16 @@ mod tests {
 
         PathBuf::from(dir).join("testing/parquet-testing/data")
     }
+
+    /// Returns an endless sequence of pseudo-random numbers (xorshift), so that tests are
+    /// deterministic.
+    pub fn xorshift() -> impl Iterator<Item = u64> {
+        let mut state = 0x2545f4914f6cdd1du64;
+        std::iter::repeat_with(move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        })
+    }
 }
@@ -4,7 +4,7 @@ use parquet_format_async_temp::{ColumnChunk, ColumnMetaData, Encoding};
 
 use super::column_descriptor::ColumnDescriptor;
 use crate::compression::Compression;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::schema::types::PhysicalType;
 use crate::statistics::{deserialize_statistics, Statistics};
 
@@ -90,6 +90,31 @@ impl ColumnChunkMetaData {
         self.metadata().total_uncompressed_size
     }
 
+    /// The total size in bytes of the compressed pages of this column chunk, including their
+    /// headers (`total_compressed_size` in the spec). Same as [`Self::compressed_size`].
+    pub fn total_compressed_size(&self) -> i64 {
+        self.metadata().total_compressed_size
+    }
+
+    /// The total size in bytes of the pages of this column chunk once uncompressed, including
+    /// their headers (`total_uncompressed_size` in the spec). Same as
+    /// [`Self::uncompressed_size`].
+    pub fn total_uncompressed_size(&self) -> i64 {
+        self.metadata().total_uncompressed_size
+    }
+
+    /// The ratio between the uncompressed and the compressed sizes of this column chunk, e.g.
+    /// `2.0` when compression halves its size and `1.0` when it is uncompressed.
+    /// The ratio of an empty column chunk is `1.0`.
+    pub fn compression_ratio(&self) -> f64 {
+        let compressed = self.total_compressed_size();
+        if compressed == 0 {
+            1.0
+        } else {
+            self.total_uncompressed_size() as f64 / compressed as f64
+        }
+    }
+
     /// Returns the offset for the column data.
     pub fn data_page_offset(&self) -> i64 {
         self.metadata().data_page_offset
@@ -135,6 +160,13 @@ impl ColumnChunkMetaData {
         column_descr: ColumnDescriptor,
         column_chunk: ColumnChunk,
     ) -> Result<Self> {
+        if column_chunk.meta_data.is_none() {
+            return Err(if column_chunk.encrypted_column_metadata.is_some() {
+                Error::General("Encrypted column metadata is not supported".to_string())
+            } else {
+                Error::OutOfSpec("A column chunk must contain its metadata".to_string())
+            });
+        }
         Ok(Self {
             column_chunk,
             column_descr,
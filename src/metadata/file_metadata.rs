@@ -45,9 +45,25 @@ impl FileMetaData {
         &self.schema_descr
     }
 
-    /// returns the metadata
-    pub fn key_value_metadata(&self) -> &Option<Vec<KeyValue>> {
-        &self.key_value_metadata
+    /// The key-value metadata of this file, e.g. the Arrow schema written by Arrow
+    /// implementations under the key `"ARROW:schema"`, if any.
+    pub fn key_value_metadata(&self) -> Option<&[KeyValue]> {
+        self.key_value_metadata.as_deref()
+    }
+
+    /// Returns the number of rows of this file, i.e. the sum of the number of rows of its
+    /// row groups.
+    pub fn row_count(&self) -> usize {
+        self.row_groups.iter().map(|rg| rg.num_rows()).sum()
+    }
+
+    /// Returns the names of the leaf columns of this file, in the order of its schema.
+    pub fn column_names(&self) -> Vec<&str> {
+        self.schema_descr
+            .columns()
+            .iter()
+            .map(|column| column.descriptor.primitive_type.field_info.name.as_str())
+            .collect()
     }
 
     /// Returns column order for `i`th column in this file.
@@ -127,3 +143,37 @@ fn parse_column_orders(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_count_and_column_names() -> Result<(), Error> {
+        let schema_descr = SchemaDescriptor::try_from_message(
+            "message schema {
+                required int32 a;
+                optional group b {
+                    repeated group c { required int64 d; }
+                    optional binary e (UTF8);
+                }
+            }",
+        )?;
+        let metadata = FileMetaData {
+            version: 1,
+            num_rows: 15,
+            created_by: None,
+            row_groups: vec![
+                RowGroupMetaData::new(vec![], 10, 0),
+                RowGroupMetaData::new(vec![], 5, 0),
+            ],
+            key_value_metadata: None,
+            schema_descr,
+            column_orders: None,
+        };
+
+        assert_eq!(metadata.row_count(), 15);
+        assert_eq!(metadata.column_names(), vec!["a", "d", "e"]);
+        Ok(())
+    }
+}
@@ -11,5 +11,5 @@ pub use column_descriptor::{ColumnDescriptor, Descriptor};
 pub use column_order::ColumnOrder;
 pub use file_metadata::{FileMetaData, KeyValue};
 pub use row_metadata::RowGroupMetaData;
-pub use schema_descriptor::SchemaDescriptor;
+pub use schema_descriptor::{SchemaDescriptor, SchemaIncompatibility};
 pub use sort::*;
@@ -1,6 +1,9 @@
 use parquet_format_async_temp::RowGroup;
 
-use super::{column_chunk_metadata::ColumnChunkMetaData, schema_descriptor::SchemaDescriptor};
+use super::{
+    column_chunk_metadata::ColumnChunkMetaData,
+    schema_descriptor::{is_dotted_path, is_dotted_prefix, SchemaDescriptor},
+};
 use crate::{
     error::{Error, Result},
     write::ColumnOffsetsMetadata,
@@ -33,6 +36,35 @@ impl RowGroupMetaData {
         &self.columns
     }
 
+    /// Returns the column chunk whose dot-separated path in the schema is `name`
+    /// (e.g. `a.b` for the field `b` of the struct `a`), if any.
+    ///
+    /// The comparison is case-sensitive, since parquet allows fields whose names only differ
+    /// in case. Like [`SchemaDescriptor::column_by_dotted`], this returns `None` when more
+    /// than one column matches `name`, since names may contain dots.
+    pub fn column_by_name(&self, name: &str) -> Option<&ColumnChunkMetaData> {
+        let mut matches = self
+            .columns
+            .iter()
+            .filter(|column| is_dotted_path(&column.descriptor().path_in_schema, name));
+        match (matches.next(), matches.next()) {
+            (Some(column), None) => Some(column),
+            _ => None,
+        }
+    }
+
+    /// Returns the column chunks whose dot-separated path in the schema is `prefix` or starts
+    /// with `prefix` followed by a `.`, e.g. all columns of the struct `a` for the prefix `a`.
+    /// An empty prefix matches all column chunks.
+    ///
+    /// Like [`RowGroupMetaData::column_by_name`], the comparison is case-sensitive.
+    pub fn columns_by_prefix(&self, prefix: &str) -> Vec<&ColumnChunkMetaData> {
+        self.columns
+            .iter()
+            .filter(|column| is_dotted_prefix(&column.descriptor().path_in_schema, prefix))
+            .collect()
+    }
+
     /// Number of rows in this row group.
     pub fn num_rows(&self) -> usize {
         self.num_rows
@@ -99,3 +131,97 @@ impl RowGroupMetaData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use parquet_format_async_temp::ColumnChunk;
+
+    use super::*;
+    use crate::metadata::{ColumnDescriptor, Descriptor};
+    use crate::schema::types::{ParquetType, PhysicalType, PrimitiveType};
+
+    fn column(path: &[&str]) -> ColumnChunkMetaData {
+        let primitive_type =
+            PrimitiveType::from_physical(path.last().unwrap().to_string(), PhysicalType::Int32);
+        let descriptor = ColumnDescriptor::new(
+            Descriptor {
+                primitive_type: primitive_type.clone(),
+                max_def_level: 0,
+                max_rep_level: 0,
+            },
+            path.iter().map(|x| x.to_string()).collect(),
+            ParquetType::PrimitiveType(primitive_type),
+        );
+        let column_chunk = ColumnChunk {
+            file_path: None,
+            file_offset: 0,
+            meta_data: None,
+            offset_index_offset: None,
+            offset_index_length: None,
+            column_index_offset: None,
+            column_index_length: None,
+            crypto_metadata: None,
+            encrypted_column_metadata: None,
+        };
+        ColumnChunkMetaData::new(column_chunk, descriptor)
+    }
+
+    fn paths(columns: Vec<&ColumnChunkMetaData>) -> Vec<String> {
+        columns
+            .into_iter()
+            .map(|x| x.descriptor().path_in_schema.join("."))
+            .collect()
+    }
+
+    #[test]
+    fn columns_by_name() {
+        let row_group = RowGroupMetaData::new(
+            vec![
+                column(&["a"]),
+                column(&["A"]),
+                column(&["b", "c"]),
+                column(&["b", "d", "e"]),
+                column(&["bc"]),
+            ],
+            0,
+            0,
+        );
+
+        let name = |name| row_group.column_by_name(name).map(|x| paths(vec![x]));
+        assert_eq!(name("a"), Some(vec!["a".to_string()]));
+        assert_eq!(name("A"), Some(vec!["A".to_string()]));
+        assert_eq!(name("b.d.e"), Some(vec!["b.d.e".to_string()]));
+        assert_eq!(name("b"), None);
+        assert_eq!(name("B.c"), None);
+
+        assert_eq!(
+            paths(row_group.columns_by_prefix("b")),
+            vec!["b.c".to_string(), "b.d.e".to_string()]
+        );
+        assert_eq!(
+            paths(row_group.columns_by_prefix("b.d")),
+            vec!["b.d.e".to_string()]
+        );
+        assert_eq!(
+            paths(row_group.columns_by_prefix("a")),
+            vec!["a".to_string()]
+        );
+        assert!(row_group.columns_by_prefix("b.").is_empty());
+        assert_eq!(row_group.columns_by_prefix("").len(), 5);
+    }
+
+    #[test]
+    fn column_by_ambiguous_name() {
+        let row_group = RowGroupMetaData::new(vec![column(&["a", "b"]), column(&["a.b"])], 0, 0);
+
+        assert!(row_group.column_by_name("a.b").is_none());
+        assert_eq!(
+            paths(row_group.columns_by_prefix("a.b")),
+            vec!["a.b".to_string(), "a.b".to_string()]
+        );
+        assert_eq!(
+            paths(row_group.columns_by_prefix("a")),
+            vec!["a.b".to_string()]
+        );
+    }
+}
@@ -1,3 +1,9 @@
+use std::collections::HashMap;
+
+use parquet_format_async_temp::thrift::protocol::{
+    TCompactInputProtocol, TCompactOutputProtocol, TInputProtocol, TListIdentifier,
+    TOutputProtocol, TType,
+};
 use parquet_format_async_temp::SchemaElement;
 
 use crate::{
@@ -8,9 +14,35 @@ use crate::{error::Result, schema::types::FieldInfo};
 
 use super::column_descriptor::{ColumnDescriptor, Descriptor};
 
+/// A difference between two schemas that prevents data of one from being read as the other
+/// (see [`SchemaDescriptor::is_compatible_with`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIncompatibility {
+    /// A column that cannot be null is in the other schema but not in this one
+    AddedColumn {
+        /// The path of the column
+        path: Vec<String>,
+    },
+    /// A column that cannot be null is in this schema but not in the other one
+    RemovedColumn {
+        /// The path of the column
+        path: Vec<String>,
+    },
+    /// A column is in both schemas, with a different physical type, repetition or
+    /// logical type
+    TypeMismatch {
+        /// The path of the column
+        path: Vec<String>,
+        /// The column's descriptor in this schema
+        expected: Box<Descriptor>,
+        /// The column's descriptor in the other schema
+        found: Box<Descriptor>,
+    },
+}
+
 /// A schema descriptor. This encapsulates the top-level schemas for all the columns,
 /// as well as all descriptors for all the primitive columns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SchemaDescriptor {
     name: String,
     // The top-level schema (the "message" type).
@@ -45,6 +77,58 @@ impl SchemaDescriptor {
         &self.leaves
     }
 
+    /// Returns the [`ColumnDescriptor`] of the leaf column whose path is `path`, if any.
+    pub fn column_by_path(&self, path: &[String]) -> Option<&ColumnDescriptor> {
+        self.leaves
+            .iter()
+            .find(|column| column.path_in_schema == path)
+    }
+
+    /// Returns the [`ColumnDescriptor`] of the leaf column whose path, with its names
+    /// separated by dots, is `path` (e.g. `"a.b.c"`).
+    ///
+    /// Since names may contain dots, a dotted path may match more than one column
+    /// (e.g. the column `b` of the group `a` and the column `a.b`), in which case this
+    /// returns `None`: use [`SchemaDescriptor::column_by_path`] instead.
+    pub fn column_by_dotted(&self, path: &str) -> Option<&ColumnDescriptor> {
+        let mut matches = self
+            .leaves
+            .iter()
+            .filter(|column| is_dotted_path(&column.path_in_schema, path));
+        match (matches.next(), matches.next()) {
+            (Some(column), None) => Some(column),
+            _ => None,
+        }
+    }
+
+    /// Returns the indices, in [`SchemaDescriptor::columns`], of the leaf columns of the
+    /// top-level `fields`, in the order of the columns. A group field contributes all its
+    /// (nested) leaf columns.
+    ///
+    /// This allows reading only the column chunks of a projection of the schema.
+    /// # Errors
+    /// Errors iff a field is not a top-level field of this schema and `ignore_unknown` is false.
+    pub fn leaves_for_fields(&self, fields: &[&str], ignore_unknown: bool) -> Result<Vec<usize>> {
+        if !ignore_unknown {
+            if let Some(field) = fields
+                .iter()
+                .find(|field| !self.fields.iter().any(|f| f.name() == **field))
+            {
+                return Err(Error::InvalidParameter(format!(
+                    "The schema has no field named \"{}\"",
+                    field
+                )));
+            }
+        }
+        Ok(self
+            .leaves
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| fields.contains(&column.path_in_schema[0].as_str()))
+            .map(|(index, _)| index)
+            .collect())
+    }
+
     /// The schemas' name.
     pub fn name(&self) -> &str {
         &self.name
@@ -55,6 +139,63 @@ impl SchemaDescriptor {
         &self.fields
     }
 
+    /// Checks whether data of `other` can be read as this schema, i.e. whether every column in
+    /// both schemas has the same physical type, repetition and logical and converted types,
+    /// and whether the columns in only one of them can be null.
+    ///
+    /// Columns are matched by their path, so schemas whose columns are in a different order
+    /// are compatible.
+    /// # Errors
+    /// Errors with every [`SchemaIncompatibility`] between the schemas
+    pub fn is_compatible_with(
+        &self,
+        other: &SchemaDescriptor,
+    ) -> std::result::Result<(), Vec<SchemaIncompatibility>> {
+        let other_columns = other
+            .columns()
+            .iter()
+            .map(|column| (&column.path_in_schema, &column.descriptor))
+            .collect::<HashMap<_, _>>();
+        let columns = self
+            .columns()
+            .iter()
+            .map(|column| (&column.path_in_schema, &column.descriptor))
+            .collect::<HashMap<_, _>>();
+
+        let mut incompatibilities = vec![];
+        for column in self.columns() {
+            let path = &column.path_in_schema;
+            let expected = &column.descriptor;
+            match other_columns.get(path) {
+                Some(found) if !is_same_type(expected, found) => {
+                    incompatibilities.push(SchemaIncompatibility::TypeMismatch {
+                        path: path.clone(),
+                        expected: Box::new(expected.clone()),
+                        found: Box::new((*found).clone()),
+                    })
+                }
+                Some(_) => {}
+                None if expected.max_def_level == 0 => {
+                    incompatibilities
+                        .push(SchemaIncompatibility::RemovedColumn { path: path.clone() });
+                }
+                None => {}
+            }
+        }
+        for column in other.columns() {
+            let path = &column.path_in_schema;
+            if !columns.contains_key(path) && column.descriptor.max_def_level == 0 {
+                incompatibilities.push(SchemaIncompatibility::AddedColumn { path: path.clone() });
+            }
+        }
+
+        if incompatibilities.is_empty() {
+            Ok(())
+        } else {
+            Err(incompatibilities)
+        }
+    }
+
     pub(crate) fn into_thrift(self) -> Vec<SchemaElement> {
         ParquetType::GroupType {
             field_info: FieldInfo {
@@ -90,6 +231,51 @@ impl SchemaDescriptor {
         let schema = from_message(message)?;
         Self::try_from_type(schema)
     }
+
+    /// Deserializes a schema from `bytes`, the thrift compact encoding of the list of
+    /// [`SchemaElement`]s of a file's metadata, as written by [`SchemaDescriptor::to_thrift_bytes`].
+    pub fn from_thrift_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut protocol = TCompactInputProtocol::new(bytes);
+        let list = protocol.read_list_begin()?;
+        if list.element_type != TType::Struct {
+            return Err(Error::OutOfSpec(format!(
+                "A schema must be a list of structs, but its elements are {:?}",
+                list.element_type
+            )));
+        }
+        let elements = (0..list.size)
+            .map(|_| SchemaElement::read_from_in_protocol(&mut protocol))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        protocol.read_list_end()?;
+        Self::try_from_thrift(&elements)
+    }
+
+    /// Serializes this schema to the thrift compact encoding of its list of [`SchemaElement`]s.
+    pub fn to_thrift_bytes(&self) -> Result<Vec<u8>> {
+        let elements = self.clone().into_thrift();
+
+        let mut bytes = vec![];
+        let mut protocol = TCompactOutputProtocol::new(&mut bytes);
+        protocol.write_list_begin(&TListIdentifier::new(TType::Struct, elements.len() as i32))?;
+        for element in &elements {
+            element.write_to_out_protocol(&mut protocol)?;
+        }
+        protocol.write_list_end()?;
+        protocol.flush()?;
+        Ok(bytes)
+    }
+}
+
+/// Whether two columns have the same physical type, repetition (including the repetition of
+/// their ancestors), logical type and converted type.
+fn is_same_type(lhs: &Descriptor, rhs: &Descriptor) -> bool {
+    let (lhs_type, rhs_type) = (&lhs.primitive_type, &rhs.primitive_type);
+    lhs_type.physical_type == rhs_type.physical_type
+        && lhs_type.field_info.repetition == rhs_type.field_info.repetition
+        && lhs_type.logical_type == rhs_type.logical_type
+        && lhs_type.converted_type == rhs_type.converted_type
+        && lhs.max_def_level == rhs.max_def_level
+        && lhs.max_rep_level == rhs.max_rep_level
 }
 
 fn build_tree<'a>(
@@ -140,3 +326,319 @@ fn build_tree<'a>(
         }
     }
 }
+
+/// Returns whether `names`, separated by dots, are equal to `dotted`, without allocating.
+pub(crate) fn is_dotted_path(names: &[String], dotted: &str) -> bool {
+    let mut remaining = dotted;
+    for (i, name) in names.iter().enumerate() {
+        if i > 0 {
+            match remaining.strip_prefix('.') {
+                Some(rest) => remaining = rest,
+                None => return false,
+            }
+        }
+        match remaining.strip_prefix(name.as_str()) {
+            Some(rest) => remaining = rest,
+            None => return false,
+        }
+    }
+    remaining.is_empty()
+}
+
+/// Returns whether the first names of `names`, separated by dots, are equal to `dotted`,
+/// without allocating. An empty `dotted` is a prefix of every path.
+pub(crate) fn is_dotted_prefix(names: &[String], dotted: &str) -> bool {
+    (0..=names.len()).any(|len| is_dotted_path(&names[..len], dotted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clone_nested() -> Result<()> {
+        let message = "
+        message schema {
+            optional group a {
+                repeated group b {
+                    required int64 c;
+                    optional group d {
+                        optional binary e (UTF8);
+                    }
+                }
+            }
+            required int32 f;
+        }
+        ";
+        let schema = SchemaDescriptor::try_from_message(message)?;
+        let cloned = schema.clone();
+        assert_eq!(schema, cloned);
+
+        let columns = schema.columns().to_vec();
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns.as_slice(), cloned.columns());
+        assert_eq!(columns[1].path_in_schema, vec!["a", "b", "d", "e"]);
+        assert_eq!(columns[1].descriptor.max_def_level, 4);
+        assert_eq!(columns[1].descriptor.max_rep_level, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn compatible_added_nullable_column() -> Result<()> {
+        let schema = SchemaDescriptor::try_from_message(
+            "message schema { required int32 a; optional binary b (UTF8); }",
+        )?;
+        let other = SchemaDescriptor::try_from_message(
+            "message schema {
+                required int32 a;
+                optional binary b (UTF8);
+                optional int64 c;
+                optional group d { required int32 e; }
+            }",
+        )?;
+        assert_eq!(schema.is_compatible_with(&other), Ok(()));
+        // the nullable columns are removed
+        assert_eq!(other.is_compatible_with(&schema), Ok(()));
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_added_required_column() -> Result<()> {
+        let schema = SchemaDescriptor::try_from_message("message schema { required int32 a; }")?;
+        let other = SchemaDescriptor::try_from_message(
+            "message schema { required int32 a; required int32 b; }",
+        )?;
+        let path = vec!["b".to_string()];
+        assert_eq!(
+            schema.is_compatible_with(&other),
+            Err(vec![SchemaIncompatibility::AddedColumn {
+                path: path.clone()
+            }])
+        );
+        assert_eq!(
+            other.is_compatible_with(&schema),
+            Err(vec![SchemaIncompatibility::RemovedColumn { path }])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn incompatible_types() -> Result<()> {
+        let schema = SchemaDescriptor::try_from_message(
+            "message schema {
+                optional int32 a;
+                optional binary b (UTF8);
+                optional int32 c;
+                optional group d { required int32 e; }
+            }",
+        )?;
+        let other = SchemaDescriptor::try_from_message(
+            "message schema {
+                optional int64 a;
+                optional binary b;
+                required int32 c;
+                required group d { required int32 e; }
+            }",
+        )?;
+        let result = schema.is_compatible_with(&other).unwrap_err();
+        let paths = result
+            .iter()
+            .map(|incompatibility| match incompatibility {
+                SchemaIncompatibility::TypeMismatch { path, .. } => path.join("."),
+                other => panic!("{:?}", other),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["a", "b", "c", "d.e"]);
+
+        assert_eq!(
+            result[0],
+            SchemaIncompatibility::TypeMismatch {
+                path: vec!["a".to_string()],
+                expected: Box::new(schema.columns()[0].descriptor.clone()),
+                found: Box::new(other.columns()[0].descriptor.clone()),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn compatible_reordered_columns() -> Result<()> {
+        let schema = SchemaDescriptor::try_from_message(
+            "message schema { required int32 a; required group b { required int64 c; } }",
+        )?;
+        let other = SchemaDescriptor::try_from_message(
+            "message schema { required group b { required int64 c; } required int32 a; }",
+        )?;
+        assert_eq!(schema.is_compatible_with(&other), Ok(()));
+        Ok(())
+    }
+
+    fn test_thrift_bytes_round_trip(message: &str) -> Result<()> {
+        let schema = SchemaDescriptor::try_from_message(message)?;
+        let bytes = schema.to_thrift_bytes()?;
+        assert_eq!(SchemaDescriptor::from_thrift_bytes(&bytes)?, schema);
+        Ok(())
+    }
+
+    #[test]
+    fn thrift_bytes_nested() -> Result<()> {
+        test_thrift_bytes_round_trip(
+            "
+        message schema {
+            optional group a {
+                repeated group b {
+                    required int64 c;
+                    optional group d {
+                        optional binary e (UTF8);
+                    }
+                }
+            }
+            required int32 f;
+        }
+        ",
+        )
+    }
+
+    #[test]
+    fn thrift_bytes_repeated() -> Result<()> {
+        test_thrift_bytes_round_trip(
+            "
+        message schema {
+            repeated int32 a;
+            optional group b (LIST) {
+                repeated group list {
+                    optional binary element (UTF8);
+                }
+            }
+            optional group c (MAP) {
+                repeated group key_value {
+                    required binary key (UTF8);
+                    optional int64 value;
+                }
+            }
+        }
+        ",
+        )
+    }
+
+    #[test]
+    fn thrift_bytes_logical_types() -> Result<()> {
+        test_thrift_bytes_round_trip(
+            "
+        message schema {
+            required int32 a (DATE);
+            optional int64 b (TIMESTAMP(MILLIS, true));
+            optional int64 c (TIMESTAMP(NANOS, false));
+            optional int32 d (INTEGER(8, false));
+            optional fixed_len_byte_array (16) e (DECIMAL(38, 18));
+            optional binary f (STRING);
+            optional fixed_len_byte_array (16) g (UUID);
+        }
+        ",
+        )
+    }
+
+    #[test]
+    fn thrift_bytes_invalid() {
+        assert!(SchemaDescriptor::from_thrift_bytes(&[]).is_err());
+        // a list of 1 i32
+        assert!(SchemaDescriptor::from_thrift_bytes(&[0x15, 0x02]).is_err());
+    }
+
+    #[test]
+    fn column_by_path() -> Result<()> {
+        use crate::schema::types::PhysicalType;
+
+        let group = |name: &str, fields| {
+            ParquetType::from_group(
+                name.to_string(),
+                Repetition::Optional,
+                None,
+                None,
+                fields,
+                None,
+            )
+        };
+        let leaf = |name: &str| ParquetType::from_physical(name.to_string(), PhysicalType::Int32);
+        let schema = SchemaDescriptor::new(
+            "schema".to_string(),
+            vec![
+                group("a", vec![leaf("b"), group("c", vec![leaf("d")])]),
+                leaf("a.b"),
+                leaf("d"),
+                group("e", vec![leaf("d")]),
+            ],
+        );
+        let path = |names: &[&str]| names.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        let find = |names: &[&str]| {
+            schema
+                .column_by_path(&path(names))
+                .map(|column| column.path_in_schema.clone())
+        };
+
+        assert_eq!(find(&["a", "b"]), Some(path(&["a", "b"])));
+        assert_eq!(find(&["a.b"]), Some(path(&["a.b"])));
+        assert_eq!(find(&["a", "c", "d"]), Some(path(&["a", "c", "d"])));
+        assert_eq!(find(&["d"]), Some(path(&["d"])));
+        assert_eq!(find(&["e", "d"]), Some(path(&["e", "d"])));
+        // groups are not leaf columns
+        assert_eq!(find(&["a"]), None);
+        assert_eq!(find(&["a", "c"]), None);
+        assert_eq!(find(&[]), None);
+
+        let find = |dotted| {
+            schema
+                .column_by_dotted(dotted)
+                .map(|column| column.path_in_schema.clone())
+        };
+        assert_eq!(find("a.c.d"), Some(path(&["a", "c", "d"])));
+        assert_eq!(find("d"), Some(path(&["d"])));
+        assert_eq!(find("e.d"), Some(path(&["e", "d"])));
+        // "a.b" is both the column `b` of `a` and the column `a.b`
+        assert_eq!(find("a.b"), None);
+        assert_eq!(find("a"), None);
+        assert_eq!(find("a.c"), None);
+        assert_eq!(find("a.c.d.e"), None);
+        assert_eq!(find(""), None);
+        Ok(())
+    }
+
+    #[test]
+    fn leaves_for_fields() -> Result<()> {
+        let schema = SchemaDescriptor::try_from_message(
+            "
+        message schema {
+            required int32 a;
+            optional group b {
+                optional int64 c;
+                repeated group d {
+                    required binary e (UTF8);
+                    optional double f;
+                }
+            }
+            optional binary g (UTF8);
+            optional group h { required boolean i; }
+        }
+        ",
+        )?;
+        assert_eq!(schema.leaves_for_fields(&["a"], false)?, vec![0]);
+        // groups contribute all their nested leaves
+        assert_eq!(schema.leaves_for_fields(&["b"], false)?, vec![1, 2, 3]);
+        // the order of the columns is preserved
+        assert_eq!(
+            schema.leaves_for_fields(&["h", "g", "a"], false)?,
+            vec![0, 4, 5]
+        );
+        assert_eq!(schema.leaves_for_fields(&[], false)?, Vec::<usize>::new());
+        // nested fields are not top-level fields
+        assert_eq!(schema.leaves_for_fields(&["c"], true)?, Vec::<usize>::new());
+        assert!(matches!(
+            schema.leaves_for_fields(&["c"], false),
+            Err(Error::InvalidParameter(_))
+        ));
+
+        assert_eq!(schema.leaves_for_fields(&["g", "z"], true)?, vec![4]);
+        assert!(schema.leaves_for_fields(&["g", "z"], false).is_err());
+        Ok(())
+    }
+}
@@ -87,6 +87,11 @@ impl CompressedDataPage {
         self.buffer.len()
     }
 
+    /// The compressed, encoded data of this page.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
     /// The compression of the data in this page.
     /// Note that what is compressed in a page depends on its version:
     /// in V1, the whole data (`[repetition levels][definition levels][values]`) is compressed; in V2 only the values are compressed.
@@ -104,6 +109,14 @@ impl CompressedDataPage {
         self.header.num_values()
     }
 
+    /// The encoding of the values of this page.
+    pub fn encoding(&self) -> Encoding {
+        match &self.header {
+            DataPageHeader::V1(d) => d.encoding(),
+            DataPageHeader::V2(d) => d.encoding(),
+        }
+    }
+
     /// Decodes the raw statistics into a statistics
     pub fn statistics(&self) -> Option<Result<Arc<dyn Statistics>>> {
         match &self.header {
@@ -260,6 +273,16 @@ pub enum EncodedPage {
     Dict(EncodedDictPage),
 }
 
+impl EncodedPage {
+    /// The encoded (uncompressed) data of this page.
+    pub fn buffer(&self) -> &[u8] {
+        match self {
+            EncodedPage::Data(page) => page.buffer(),
+            EncodedPage::Dict(page) => page.buffer(),
+        }
+    }
+}
+
 /// A [`CompressedPage`] is a compressed, encoded representation of a Parquet page. It holds actual data
 /// and thus cloning it is expensive.
 #[derive(Debug)]
@@ -270,7 +293,15 @@ pub enum CompressedPage {
 }
 
 impl CompressedPage {
-    pub(crate) fn buffer(&mut self) -> &mut Vec<u8> {
+    /// The compressed, encoded data of this page.
+    pub fn buffer(&self) -> &[u8] {
+        match self {
+            CompressedPage::Data(page) => page.buffer(),
+            CompressedPage::Dict(page) => page.buffer(),
+        }
+    }
+
+    pub(crate) fn buffer_mut(&mut self) -> &mut Vec<u8> {
         match self {
             CompressedPage::Data(page) => &mut page.buffer,
             CompressedPage::Dict(page) => &mut page.buffer,
@@ -340,20 +371,25 @@ pub fn split_buffer_v2(
 
 /// Splits the page buffer into 3 slices corresponding to (encoded rep levels, encoded def levels, encoded values).
 pub fn split_buffer(page: &DataPage) -> (&[u8], &[u8], &[u8]) {
-    match page.header() {
+    split_page_buffer(page.header(), page.buffer(), &page.descriptor)
+}
+
+/// Splits the (uncompressed) `buffer` of a page with `header` like [`split_buffer`].
+pub(crate) fn split_page_buffer<'a>(
+    header: &DataPageHeader,
+    buffer: &'a [u8],
+    descriptor: &Descriptor,
+) -> (&'a [u8], &'a [u8], &'a [u8]) {
+    match header {
         DataPageHeader::V1(_) => split_buffer_v1(
-            page.buffer(),
-            page.descriptor.max_rep_level > 0,
-            page.descriptor.max_def_level > 0,
+            buffer,
+            descriptor.max_rep_level > 0,
+            descriptor.max_def_level > 0,
         ),
         DataPageHeader::V2(header) => {
             let def_level_buffer_length = header.definition_levels_byte_length as usize;
             let rep_level_buffer_length = header.repetition_levels_byte_length as usize;
-            split_buffer_v2(
-                page.buffer(),
-                rep_level_buffer_length,
-                def_level_buffer_length,
-            )
+            split_buffer_v2(buffer, rep_level_buffer_length, def_level_buffer_length)
         }
     }
 }
@@ -24,11 +24,23 @@ impl BinaryPageDict {
         &self.offsets
     }
 
+    /// Returns the value at `index`.
+    /// # Error
+    /// Errors iff `index` is larger than the dictionary or its offsets are invalid.
     #[inline]
     pub fn value(&self, index: usize) -> Result<&[u8], Error> {
-        let start: usize = self.offsets[index].try_into()?;
-        let end: usize = self.offsets[(index + 1)].try_into()?;
-        Ok(&self.values[start..end])
+        let out_of_bounds =
+            || Error::OutOfSpec("A dictionary index is larger than the dictionary".to_string());
+        let start = *self.offsets.get(index).ok_or_else(out_of_bounds)?;
+        let end = *self
+            .offsets
+            .get(index.checked_add(1).ok_or_else(out_of_bounds)?)
+            .ok_or_else(out_of_bounds)?;
+        let start: usize = start.try_into()?;
+        let end: usize = end.try_into()?;
+        self.values.get(start..end).ok_or_else(|| {
+            Error::OutOfSpec("The offsets of a dictionary are out of bounds".to_string())
+        })
     }
 }
 
@@ -1,6 +1,7 @@
 use std::{any::Any, sync::Arc};
 
-use crate::error::Result;
+use crate::encoding::plain_byte_array::FixedLenBinaryIter;
+use crate::error::{Error, Result};
 use crate::schema::types::PhysicalType;
 
 use super::DictPage;
@@ -29,9 +30,17 @@ impl FixedLenByteArrayPageDict {
         self.size
     }
 
+    /// Returns the value at `index`.
+    /// # Error
+    /// Errors iff `index` is larger than the dictionary.
     #[inline]
-    pub fn value(&self, index: usize) -> &[u8] {
-        &self.values[index * self.size..(index + 1) * self.size]
+    pub fn value(&self, index: usize) -> Result<&[u8]> {
+        index
+            .checked_mul(self.size)
+            .and_then(|start| self.values.get(start..start.checked_add(self.size)?))
+            .ok_or_else(|| {
+                Error::OutOfSpec("A dictionary index is larger than the dictionary".to_string())
+            })
     }
 }
 
@@ -45,14 +54,11 @@ impl DictPage for FixedLenByteArrayPageDict {
     }
 }
 
-fn read_plain(bytes: &[u8], size: usize, length: usize) -> Vec<u8> {
-    bytes[..size * length].to_vec()
-}
-
 pub fn read(buf: &[u8], size: usize, num_values: usize) -> Result<Arc<dyn DictPage>> {
-    let values = read_plain(buf, size, num_values);
+    // validates that the page contains `num_values` values
+    FixedLenBinaryIter::try_new(buf, size, Some(num_values))?;
     Ok(Arc::new(FixedLenByteArrayPageDict::new(
-        values,
+        buf.to_vec(),
         PhysicalType::FixedLenByteArray(size),
         size,
     )))
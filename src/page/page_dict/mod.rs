@@ -8,7 +8,9 @@ pub use primitive::PrimitivePageDict;
 
 use std::{any::Any, sync::Arc};
 
-use crate::compression::{decompress, Compression};
+use crate::compression::{
+    decompress_into, decompressed_size_mismatch, matching_codec, Compression, CustomCodec,
+};
 use crate::error::{Error, Result};
 use crate::schema::types::PhysicalType;
 
@@ -30,6 +32,11 @@ impl EncodedDictPage {
     pub fn new(buffer: Vec<u8>, num_values: usize) -> Self {
         Self { buffer, num_values }
     }
+
+    /// The encoded data of this page.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
 }
 
 /// An encoded and compressed dictionary page.
@@ -42,7 +49,12 @@ pub struct CompressedDictPage {
 }
 
 impl CompressedDictPage {
-    pub fn new(buffer: Vec<u8>, compression: Compression, uncompressed_page_size: usize, num_values: usize) -> Self {
+    pub fn new(
+        buffer: Vec<u8>,
+        compression: Compression,
+        uncompressed_page_size: usize,
+        num_values: usize,
+    ) -> Self {
         Self {
             buffer,
             compression,
@@ -55,6 +67,21 @@ impl CompressedDictPage {
     pub fn compression(&self) -> Compression {
         self.compression
     }
+
+    /// The compressed, encoded data of this page.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// The size of the data of this page once decompressed.
+    pub fn uncompressed_size(&self) -> usize {
+        self.uncompressed_page_size
+    }
+
+    /// The number of values in this page.
+    pub fn num_values(&self) -> usize {
+        self.num_values
+    }
 }
 
 pub fn read_dict_page(
@@ -62,10 +89,35 @@ pub fn read_dict_page(
     compression: (Compression, usize),
     is_sorted: bool,
     physical_type: PhysicalType,
+) -> Result<Arc<dyn DictPage>> {
+    read_dict_page_with_codec(page, compression, is_sorted, physical_type, None)
+}
+
+/// Reads a dictionary page like [`read_dict_page`], decompressing it with `codec` instead of
+/// this crate's implementation when it matches the compression.
+pub fn read_dict_page_with_codec(
+    page: &EncodedDictPage,
+    compression: (Compression, usize),
+    is_sorted: bool,
+    physical_type: PhysicalType,
+    codec: Option<&dyn CustomCodec>,
 ) -> Result<Arc<dyn DictPage>> {
     if compression.0 != Compression::Uncompressed {
-        let mut decompressed = vec![0; compression.1];
-        decompress(compression.0, &page.buffer, &mut decompressed)?;
+        let mut decompressed = vec![];
+        if let Some(codec) = matching_codec(codec, compression.0) {
+            decompressed.resize(compression.1, 0);
+            let written = codec.decompress(&page.buffer, &mut decompressed)?;
+            if written != compression.1 {
+                return Err(decompressed_size_mismatch(written, compression.1));
+            }
+        } else {
+            decompress_into(
+                compression.0,
+                &page.buffer,
+                compression.1,
+                &mut decompressed,
+            )?;
+        }
         deserialize(&decompressed, page.num_values, is_sorted, physical_type)
     } else {
         deserialize(&page.buffer, page.num_values, is_sorted, physical_type)
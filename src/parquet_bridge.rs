@@ -63,6 +63,24 @@ pub enum Compression {
     Lz4Raw,
 }
 
+impl Compression {
+    /// Returns whether this crate was compiled with support to (de)compress this codec,
+    /// depending on the active features.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Compression::Uncompressed => true,
+            Compression::Snappy => cfg!(feature = "snappy"),
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Lzo => false,
+            Compression::Brotli => cfg!(feature = "brotli"),
+            Compression::Lz4 | Compression::Lz4Raw => {
+                cfg!(any(feature = "lz4", feature = "lz4_flex"))
+            }
+            Compression::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+}
+
 impl TryFrom<CompressionCodec> for Compression {
     type Error = Error;
 
@@ -101,9 +119,9 @@ impl From<Compression> for CompressionCodec {
 pub enum CompressionOptions {
     Uncompressed,
     Snappy,
-    Gzip,
+    Gzip(Option<GzipLevel>),
     Lzo,
-    Brotli,
+    Brotli(Option<BrotliLevel>),
     Lz4,
     Zstd(Option<ZstdLevel>),
     Lz4Raw,
@@ -114,9 +132,9 @@ impl From<CompressionOptions> for Compression {
         match value {
             CompressionOptions::Uncompressed => Compression::Uncompressed,
             CompressionOptions::Snappy => Compression::Snappy,
-            CompressionOptions::Gzip => Compression::Gzip,
+            CompressionOptions::Gzip(_) => Compression::Gzip,
             CompressionOptions::Lzo => Compression::Lzo,
-            CompressionOptions::Brotli => Compression::Brotli,
+            CompressionOptions::Brotli(_) => Compression::Brotli,
             CompressionOptions::Lz4 => Compression::Lz4,
             CompressionOptions::Zstd(_) => Compression::Zstd,
             CompressionOptions::Lz4Raw => Compression::Lz4Raw,
@@ -129,9 +147,9 @@ impl From<CompressionOptions> for CompressionCodec {
         match codec {
             CompressionOptions::Uncompressed => CompressionCodec::UNCOMPRESSED,
             CompressionOptions::Snappy => CompressionCodec::SNAPPY,
-            CompressionOptions::Gzip => CompressionCodec::GZIP,
+            CompressionOptions::Gzip(_) => CompressionCodec::GZIP,
             CompressionOptions::Lzo => CompressionCodec::LZO,
-            CompressionOptions::Brotli => CompressionCodec::BROTLI,
+            CompressionOptions::Brotli(_) => CompressionCodec::BROTLI,
             CompressionOptions::Lz4 => CompressionCodec::LZ4,
             CompressionOptions::Zstd(_) => CompressionCodec::ZSTD,
             CompressionOptions::Lz4Raw => CompressionCodec::LZ4_RAW,
@@ -139,6 +157,119 @@ impl From<CompressionOptions> for CompressionCodec {
     }
 }
 
+/// Represents a valid brotli compression level, composed by its quality and the
+/// (base-2 logarithm of the) size of its sliding window.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct BrotliLevel {
+    quality: u32,
+    lg_window_size: u32,
+}
+
+impl BrotliLevel {
+    const MINIMUM_QUALITY: u32 = 0;
+    const MAXIMUM_QUALITY: u32 = 11;
+    const MINIMUM_LG_WINDOW_SIZE: u32 = 10;
+    const MAXIMUM_LG_WINDOW_SIZE: u32 = 24;
+    const DEFAULT_QUALITY: u32 = 1;
+    const DEFAULT_LG_WINDOW_SIZE: u32 = 22; // recommended between 20-22
+
+    /// Attempts to create a brotli compression level from a given quality, using the
+    /// default window size.
+    ///
+    /// The quality must be valid (i.e. be between 0 and 11).
+    pub fn try_new(quality: u32) -> Result<Self, Error> {
+        Self::try_new_with_window(quality, Self::DEFAULT_LG_WINDOW_SIZE)
+    }
+
+    /// Attempts to create a brotli compression level from a given quality and
+    /// (base-2 logarithm of the) window size.
+    ///
+    /// The quality must be between 0 and 11 and the window size between 10 and 24.
+    pub fn try_new_with_window(quality: u32, lg_window_size: u32) -> Result<Self, Error> {
+        if !(Self::MINIMUM_QUALITY..=Self::MAXIMUM_QUALITY).contains(&quality) {
+            return Err(Error::InvalidParameter(format!(
+                "valid compression range {}..={} exceeded.",
+                Self::MINIMUM_QUALITY,
+                Self::MAXIMUM_QUALITY
+            )));
+        }
+        if !(Self::MINIMUM_LG_WINDOW_SIZE..=Self::MAXIMUM_LG_WINDOW_SIZE).contains(&lg_window_size)
+        {
+            return Err(Error::InvalidParameter(format!(
+                "valid window size range {}..={} exceeded.",
+                Self::MINIMUM_LG_WINDOW_SIZE,
+                Self::MAXIMUM_LG_WINDOW_SIZE
+            )));
+        }
+        Ok(Self {
+            quality,
+            lg_window_size,
+        })
+    }
+
+    /// Returns the brotli compression level (quality).
+    pub fn compression_level(&self) -> u32 {
+        self.quality
+    }
+
+    /// Returns the base-2 logarithm of the brotli window size.
+    pub fn lg_window_size(&self) -> u32 {
+        self.lg_window_size
+    }
+}
+
+impl Default for BrotliLevel {
+    fn default() -> Self {
+        Self {
+            quality: Self::DEFAULT_QUALITY,
+            lg_window_size: Self::DEFAULT_LG_WINDOW_SIZE,
+        }
+    }
+}
+
+/// Represents a valid gzip compression level.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+pub struct GzipLevel(u8);
+
+impl GzipLevel {
+    const MINIMUM_LEVEL: u8 = 0;
+    const MAXIMUM_LEVEL: u8 = 9;
+
+    /// Attempts to create a gzip compression level from a given compression level.
+    ///
+    /// Compression levels must be valid (i.e. be between 0 and 9).
+    pub fn try_new(level: u8) -> Result<Self, Error> {
+        if (Self::MINIMUM_LEVEL..=Self::MAXIMUM_LEVEL).contains(&level) {
+            Ok(Self(level))
+        } else {
+            Err(Error::InvalidParameter(format!(
+                "valid compression range {}..={} exceeded.",
+                Self::MINIMUM_LEVEL,
+                Self::MAXIMUM_LEVEL
+            )))
+        }
+    }
+
+    /// Returns the gzip compression level.
+    pub fn compression_level(&self) -> u8 {
+        self.0
+    }
+}
+
+impl Default for GzipLevel {
+    fn default() -> Self {
+        // the default level of `flate2` and `zlib`
+        Self(6)
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl From<GzipLevel> for flate2::Compression {
+    fn from(level: GzipLevel) -> Self {
+        Self::new(level.compression_level() as u32)
+    }
+}
+
 /// Represents a valid zstd compression level.
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub struct ZstdLevel(i32);
@@ -153,7 +284,7 @@ impl ZstdLevel {
         if compression_range.contains(&level) {
             Ok(Self(level))
         } else {
-            Err(Error::General(format!(
+            Err(Error::InvalidParameter(format!(
                 "valid compression range {}..={} exceeded.",
                 compression_range.start(),
                 compression_range.end()
@@ -265,6 +396,54 @@ impl TryFrom<ParquetEncoding> for Encoding {
     }
 }
 
+impl Encoding {
+    /// The name of this encoding in the parquet specification, e.g. `DELTA_BINARY_PACKED`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Plain => "PLAIN",
+            Encoding::PlainDictionary => "PLAIN_DICTIONARY",
+            Encoding::Rle => "RLE",
+            Encoding::BitPacked => "BIT_PACKED",
+            Encoding::DeltaBinaryPacked => "DELTA_BINARY_PACKED",
+            Encoding::DeltaLengthByteArray => "DELTA_LENGTH_BYTE_ARRAY",
+            Encoding::DeltaByteArray => "DELTA_BYTE_ARRAY",
+            Encoding::RleDictionary => "RLE_DICTIONARY",
+            Encoding::ByteStreamSplit => "BYTE_STREAM_SPLIT",
+        }
+    }
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Encoding {
+    type Err = Error;
+
+    /// Parses the name of an encoding in the parquet specification (see [`Encoding::name`]).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name {
+            "PLAIN" => Encoding::Plain,
+            "PLAIN_DICTIONARY" => Encoding::PlainDictionary,
+            "RLE" => Encoding::Rle,
+            "BIT_PACKED" => Encoding::BitPacked,
+            "DELTA_BINARY_PACKED" => Encoding::DeltaBinaryPacked,
+            "DELTA_LENGTH_BYTE_ARRAY" => Encoding::DeltaLengthByteArray,
+            "DELTA_BYTE_ARRAY" => Encoding::DeltaByteArray,
+            "RLE_DICTIONARY" => Encoding::RleDictionary,
+            "BYTE_STREAM_SPLIT" => Encoding::ByteStreamSplit,
+            other => {
+                return Err(Error::InvalidParameter(format!(
+                    "Unknown encoding \"{}\"",
+                    other
+                )))
+            }
+        })
+    }
+}
+
 impl From<Encoding> for ParquetEncoding {
     fn from(encoding: Encoding) -> Self {
         match encoding {
@@ -615,6 +794,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn round_trip_encoding_name() -> Result<(), Error> {
+        let names = [
+            "PLAIN",
+            "PLAIN_DICTIONARY",
+            "RLE",
+            "BIT_PACKED",
+            "DELTA_BINARY_PACKED",
+            "DELTA_LENGTH_BYTE_ARRAY",
+            "DELTA_BYTE_ARRAY",
+            "RLE_DICTIONARY",
+            "BYTE_STREAM_SPLIT",
+        ];
+        // every encoding of the thrift definition, in the order of `names`
+        assert_eq!(ParquetEncoding::ENUM_VALUES.len(), names.len());
+        for (thrift, name) in ParquetEncoding::ENUM_VALUES.iter().zip(names) {
+            let encoding: Encoding = (*thrift).try_into()?;
+            assert_eq!(encoding.to_string(), name);
+            assert_eq!(encoding.name(), name);
+            assert_eq!(name.parse::<Encoding>()?, encoding);
+        }
+
+        for name in ["", "plain", "Plain", " PLAIN", "DELTA", "UNKNOWN"] {
+            assert!(matches!(
+                name.parse::<Encoding>(),
+                Err(Error::InvalidParameter(_))
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn compression_is_supported() {
+        assert!(Compression::Uncompressed.is_supported());
+        assert!(!Compression::Lzo.is_supported());
+        assert_eq!(Compression::Zstd.is_supported(), cfg!(feature = "zstd"));
+        assert_eq!(Compression::Snappy.is_supported(), cfg!(feature = "snappy"));
+    }
+
     #[test]
     fn round_compression() -> Result<(), Error> {
         use Compression::*;
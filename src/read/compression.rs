@@ -1,22 +1,62 @@
+use std::sync::Arc;
+
 use parquet_format_async_temp::DataPageHeaderV2;
 use streaming_decompression;
 
-use crate::compression::{self, Compression};
+use crate::compression::{self, matching_codec, Compression, CustomCodec};
 use crate::error::{Error, Result};
 use crate::page::{CompressedDataPage, DataPage, DataPageHeader};
 use crate::FallibleStreamingIterator;
 
 use super::page::PageIterator;
+use super::streaming::StreamingDecompressor;
+
+/// The default maximum size of a page, in bytes, that readers and decompressors accept
+/// before allocating it (2 GiB).
+pub const DEFAULT_MAX_PAGE_SIZE: usize = 2 * 1024 * 1024 * 1024;
 
-fn decompress_v1(compressed: &[u8], compression: Compression, buffer: &mut [u8]) -> Result<()> {
-    compression::decompress(compression, compressed, buffer)
+/// Errors if a page's `size` (e.g. declared in its header) is larger than `max_page_size`,
+/// so that corrupted or malicious pages do not cause arbitrarily large allocations.
+pub(crate) fn check_page_size(kind: &str, size: usize, max_page_size: usize) -> Result<()> {
+    if size > max_page_size {
+        return Err(Error::OutOfSpec(format!(
+            "The {} size of a page ({} bytes) is larger than the maximum page size ({} bytes)",
+            kind, size, max_page_size
+        )));
+    }
+    Ok(())
+}
+
+fn decompress_v1(
+    compressed: &[u8],
+    compression: Compression,
+    codec: Option<&dyn CustomCodec>,
+    uncompressed_size: usize,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    if let Some(codec) = matching_codec(codec, compression) {
+        buffer.clear();
+        buffer.resize(uncompressed_size, 0);
+        let written = codec.decompress(compressed, buffer)?;
+        if written != uncompressed_size {
+            return Err(compression::decompressed_size_mismatch(
+                written,
+                uncompressed_size,
+            ));
+        }
+    } else {
+        compression::decompress_into(compression, compressed, uncompressed_size, buffer)?;
+    }
+    Ok(())
 }
 
 fn decompress_v2(
     compressed: &[u8],
     page_header: &DataPageHeaderV2,
     compression: Compression,
-    buffer: &mut [u8],
+    codec: Option<&dyn CustomCodec>,
+    uncompressed_size: usize,
+    buffer: &mut Vec<u8>,
 ) -> Result<()> {
     // When processing data page v2, depending on enabled compression for the
     // page, we should account for uncompressed data ('offset') of
@@ -29,12 +69,29 @@ fn decompress_v2(
     // When is_compressed flag is missing the page is considered compressed
     let can_decompress = page_header.is_compressed.unwrap_or(true);
 
+    buffer.clear();
     if can_decompress {
-        (&mut buffer[..offset]).copy_from_slice(&compressed[..offset]);
+        if offset > compressed.len() || offset > uncompressed_size {
+            return Err(Error::OutOfSpec(
+                "The levels of a data page v2 are larger than the page".to_string(),
+            ));
+        }
+        buffer.extend_from_slice(&compressed[..offset]);
+        buffer.resize(uncompressed_size, 0);
 
-        compression::decompress(compression, &compressed[offset..], &mut buffer[offset..])?;
+        let (compressed, output) = (&compressed[offset..], &mut buffer[offset..]);
+        let written = match matching_codec(codec, compression) {
+            Some(codec) => codec.decompress(compressed, output)?,
+            None => compression::decompress(compression, compressed, output)?,
+        };
+        if offset + written != uncompressed_size {
+            return Err(compression::decompressed_size_mismatch(
+                offset + written,
+                uncompressed_size,
+            ));
+        }
     } else {
-        buffer.copy_from_slice(compressed);
+        buffer.extend_from_slice(compressed);
     }
     Ok(())
 }
@@ -42,32 +99,33 @@ fn decompress_v2(
 /// decompresses a [`CompressedDataPage`] into `buffer`.
 /// If the page is un-compressed, `buffer` is swapped instead.
 /// Returns whether the page was decompressed.
+/// The allocation of `buffer` is re-used whenever its capacity is large enough for the page.
+/// Pages larger than [`DEFAULT_MAX_PAGE_SIZE`] are not decompressed.
 pub fn decompress_buffer(
     compressed_page: &mut CompressedDataPage,
     buffer: &mut Vec<u8>,
+) -> Result<bool> {
+    decompress_buffer_with_codec(compressed_page, buffer, None)
+}
+
+/// decompresses a [`CompressedDataPage`] into `buffer` like [`decompress_buffer`], using `codec`
+/// instead of this crate's implementation when it matches the page's compression.
+pub fn decompress_buffer_with_codec(
+    compressed_page: &mut CompressedDataPage,
+    buffer: &mut Vec<u8>,
+    codec: Option<&dyn CustomCodec>,
+) -> Result<bool> {
+    decompress_buffer_with_options(compressed_page, buffer, codec, DEFAULT_MAX_PAGE_SIZE)
+}
+
+fn decompress_buffer_with_options(
+    compressed_page: &mut CompressedDataPage,
+    buffer: &mut Vec<u8>,
+    codec: Option<&dyn CustomCodec>,
+    max_page_size: usize,
 ) -> Result<bool> {
     if compressed_page.compression() != Compression::Uncompressed {
-        let compressed_buffer = &compressed_page.buffer;
-
-        // prepare the compression buffer
-        let read_size = compressed_page.uncompressed_size();
-        if read_size > buffer.len() {
-            // dealloc and ignore region, replacing it by a new region
-            *buffer = vec![0; read_size]
-        } else {
-            buffer.truncate(read_size);
-        }
-        match compressed_page.header() {
-            DataPageHeader::V1(_) => {
-                decompress_v1(compressed_buffer, compressed_page.compression(), buffer)?
-            }
-            DataPageHeader::V2(header) => decompress_v2(
-                compressed_buffer,
-                header,
-                compressed_page.compression(),
-                buffer,
-            )?,
-        }
+        decompress_into_buffer(compressed_page, buffer, codec, max_page_size)?;
         Ok(true)
     } else {
         // page.buffer is already decompressed => swap it with `buffer`, making `page.buffer` the
@@ -77,14 +135,71 @@ pub fn decompress_buffer(
     }
 }
 
+/// Decompresses the buffer of a compressed `compressed_page` into `buffer`, leaving the page
+/// untouched.
+pub(crate) fn decompress_into_buffer(
+    compressed_page: &CompressedDataPage,
+    buffer: &mut Vec<u8>,
+    codec: Option<&dyn CustomCodec>,
+    max_page_size: usize,
+) -> Result<()> {
+    let compressed_buffer = &compressed_page.buffer;
+    let uncompressed_size = compressed_page.uncompressed_size();
+
+    let result = check_page_size("uncompressed", uncompressed_size, max_page_size);
+    let result = result.and_then(|_| match compressed_page.header() {
+        DataPageHeader::V1(_) => decompress_v1(
+            compressed_buffer,
+            compressed_page.compression(),
+            codec,
+            uncompressed_size,
+            buffer,
+        ),
+        DataPageHeader::V2(header) => decompress_v2(
+            compressed_buffer,
+            header,
+            compressed_page.compression(),
+            codec,
+            uncompressed_size,
+            buffer,
+        ),
+    });
+    result.map_err(|error| match error {
+        Error::OutOfSpec(message) => Error::OutOfSpec(format!(
+            "Failed to decompress a data page of column \"{}\": {}",
+            compressed_page.descriptor.primitive_type.field_info.name, message
+        )),
+        other => other,
+    })
+}
+
 /// Decompresses the page, using `buffer` for decompression.
 /// If `page.buffer.len() == 0`, there was no decompression and the buffer was moved.
 /// Else, decompression took place.
-pub fn decompress(
+/// Pages larger than [`DEFAULT_MAX_PAGE_SIZE`] are not decompressed.
+pub fn decompress(compressed_page: CompressedDataPage, buffer: &mut Vec<u8>) -> Result<DataPage> {
+    decompress_with_codec(compressed_page, buffer, None)
+}
+
+/// Decompresses the page like [`decompress`], using `codec` instead of this crate's
+/// implementation when it matches the page's compression.
+pub fn decompress_with_codec(
+    compressed_page: CompressedDataPage,
+    buffer: &mut Vec<u8>,
+    codec: Option<&dyn CustomCodec>,
+) -> Result<DataPage> {
+    decompress_with_options(compressed_page, buffer, codec, DEFAULT_MAX_PAGE_SIZE)
+}
+
+/// Decompresses the page like [`decompress_with_codec`], erroring on pages whose uncompressed
+/// size is larger than `max_page_size` bytes instead of [`DEFAULT_MAX_PAGE_SIZE`].
+pub fn decompress_with_options(
     mut compressed_page: CompressedDataPage,
     buffer: &mut Vec<u8>,
+    codec: Option<&dyn CustomCodec>,
+    max_page_size: usize,
 ) -> Result<DataPage> {
-    decompress_buffer(&mut compressed_page, buffer)?;
+    decompress_buffer_with_options(&mut compressed_page, buffer, codec, max_page_size)?;
     Ok(DataPage::new_read(
         compressed_page.header,
         std::mem::take(buffer),
@@ -205,7 +320,7 @@ impl<P: PageIterator> FallibleStreamingIterator for Decompressor<P> {
 type _Decompressor<I> = streaming_decompression::Decompressor<
     CompressedDataPage,
     DataPage,
-    fn(CompressedDataPage, &mut Vec<u8>) -> Result<DataPage>,
+    Box<dyn Fn(CompressedDataPage, &mut Vec<u8>) -> Result<DataPage> + Send + Sync>,
     Error,
     I,
 >;
@@ -239,8 +354,42 @@ where
 {
     /// Returns a new [`BasicDecompressor`].
     pub fn new(iter: I, buffer: Vec<u8>) -> Self {
+        Self::new_with_codec(iter, buffer, None)
+    }
+
+    /// Returns a new [`BasicDecompressor`] that decompresses pages with `codec` when it matches
+    /// their compression.
+    pub fn new_with_codec(iter: I, buffer: Vec<u8>, codec: Option<Arc<dyn CustomCodec>>) -> Self {
+        Self::new_with_options(iter, buffer, codec, DEFAULT_MAX_PAGE_SIZE)
+    }
+
+    /// Returns a new [`BasicDecompressor`] that errors on pages whose uncompressed size is
+    /// larger than `max_page_size` bytes (by default [`DEFAULT_MAX_PAGE_SIZE`]).
+    pub fn new_with_page_size(iter: I, buffer: Vec<u8>, max_page_size: usize) -> Self {
+        Self::new_with_options(iter, buffer, None, max_page_size)
+    }
+
+    /// Returns a [`StreamingDecompressor`], that decompresses the values of each page as they are
+    /// read, at most `buffer_size` bytes at a time, instead of into a buffer of the whole page.
+    /// This is useful for pages too large to be decompressed in memory.
+    pub fn new_streaming(iter: I, buffer_size: usize) -> StreamingDecompressor<I> {
+        StreamingDecompressor::new(iter, buffer_size)
+    }
+
+    /// Returns a new [`BasicDecompressor`] that decompresses pages with `codec` when it matches
+    /// their compression and errors on pages whose uncompressed size is larger than
+    /// `max_page_size` bytes.
+    pub fn new_with_options(
+        iter: I,
+        buffer: Vec<u8>,
+        codec: Option<Arc<dyn CustomCodec>>,
+        max_page_size: usize,
+    ) -> Self {
+        let decompress = move |page, buffer: &mut Vec<u8>| {
+            decompress_with_options(page, buffer, codec.as_deref(), max_page_size)
+        };
         Self {
-            iter: _Decompressor::new(iter, buffer, decompress),
+            iter: _Decompressor::new(iter, buffer, Box::new(decompress)),
         }
     }
 
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::metadata::{
+    ColumnChunkMetaData, FileMetaData, RowGroupMetaData, SchemaDescriptor, SchemaIncompatibility,
+};
+
+use super::read_metadata;
+
+/// A row group of one of the files of a [`DatasetReader`].
+#[derive(Debug, Clone, Copy)]
+pub struct DatasetRowGroup<'a> {
+    /// The path of the file containing this row group
+    pub source_file: &'a Path,
+    /// The metadata of the file containing this row group
+    pub file_metadata: &'a FileMetaData,
+    /// The index of this row group in its file
+    pub index: usize,
+    /// The metadata of this row group
+    pub metadata: &'a RowGroupMetaData,
+    /// The index in the file of each column of the [schema of the dataset](DatasetReader::schema),
+    /// or `None` if the file does not have that column
+    pub column_indices: &'a [Option<usize>],
+}
+
+impl<'a> DatasetRowGroup<'a> {
+    /// Opens the file containing this row group, from which its column chunks can be read,
+    /// e.g. with [`get_page_iterator`](super::get_page_iterator).
+    pub fn open(&self) -> Result<File> {
+        Ok(File::open(self.source_file)?)
+    }
+
+    /// Returns the column chunk of the column `index` of the
+    /// [schema of the dataset](DatasetReader::schema), or `None` if the file of this row group
+    /// does not have that column or `index` is out of bounds.
+    ///
+    /// Since the columns of the files of a dataset may be in a different order, the index of
+    /// a column in the dataset may differ from its index in [`RowGroupMetaData::columns`].
+    pub fn column(&self, index: usize) -> Option<&'a ColumnChunkMetaData> {
+        self.column_indices
+            .get(index)
+            .copied()
+            .flatten()
+            .map(|index| &self.metadata.columns()[index])
+    }
+}
+
+/// A reader of a dataset composed by multiple parquet files with compatible schemas
+/// (see [`SchemaDescriptor::is_compatible_with`]). The columns of the files are matched by
+/// their path, so they may be in a different order (see [`DatasetRowGroup::column`]).
+///
+/// Only the metadata of the files is kept in memory: files are opened to read their metadata
+/// and closed afterwards, and are opened again when their row groups are read
+/// (see [`DatasetRowGroup::open`]), so that at most one file descriptor is used at a time.
+#[derive(Debug, Clone)]
+pub struct DatasetReader {
+    files: Vec<(PathBuf, FileMetaData)>,
+    // the index of every column of the schema in each file, see `DatasetRowGroup::column_indices`
+    column_indices: Vec<Vec<Option<usize>>>,
+}
+
+impl DatasetReader {
+    /// Reads the metadata of the files at `paths` and returns a new [`DatasetReader`].
+    /// # Errors
+    /// Errors iff `paths` is empty, a file cannot be opened or its metadata cannot be read,
+    /// or the schema of a file is not compatible with the schema of the first file.
+    pub fn new(paths: Vec<PathBuf>) -> Result<Self> {
+        if paths.is_empty() {
+            return Err(Error::InvalidParameter(
+                "A dataset requires at least one file".to_string(),
+            ));
+        }
+
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let mut file = File::open(&path)?;
+                let metadata = read_metadata(&mut file)?;
+                Ok((path, metadata))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let (first_path, first) = &files[0];
+        for (path, metadata) in &files[1..] {
+            if let Err(incompatibilities) = first.schema().is_compatible_with(metadata.schema()) {
+                return Err(Error::InvalidParameter(format!(
+                    "The schema of \"{}\" is not compatible with the schema of \"{}\": {}",
+                    path.display(),
+                    first_path.display(),
+                    incompatibilities
+                        .iter()
+                        .map(describe)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+
+        let column_indices = files
+            .iter()
+            .map(|(_, metadata)| {
+                let indices = metadata
+                    .schema()
+                    .columns()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| (&column.path_in_schema, index))
+                    .collect::<HashMap<_, _>>();
+                first
+                    .schema()
+                    .columns()
+                    .iter()
+                    .map(|column| indices.get(&column.path_in_schema).copied())
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            files,
+            column_indices,
+        })
+    }
+
+    /// The schema of the dataset, i.e. the schema of its first file.
+    pub fn schema(&self) -> &SchemaDescriptor {
+        self.files[0].1.schema()
+    }
+
+    /// The paths and metadata of the files of the dataset.
+    pub fn files(&self) -> &[(PathBuf, FileMetaData)] {
+        &self.files
+    }
+
+    /// The total number of rows of the dataset.
+    pub fn num_rows(&self) -> usize {
+        self.files
+            .iter()
+            .map(|(_, metadata)| metadata.num_rows)
+            .sum()
+    }
+
+    /// Returns an iterator over the row groups of all files of the dataset, in the order of
+    /// the files.
+    pub fn row_groups(&self) -> impl Iterator<Item = DatasetRowGroup<'_>> {
+        self.files.iter().zip(self.column_indices.iter()).flat_map(
+            |((path, file_metadata), column_indices)| {
+                file_metadata
+                    .row_groups
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, metadata)| DatasetRowGroup {
+                        source_file: path,
+                        file_metadata,
+                        index,
+                        metadata,
+                        column_indices,
+                    })
+            },
+        )
+    }
+}
+
+fn describe(incompatibility: &SchemaIncompatibility) -> String {
+    match incompatibility {
+        SchemaIncompatibility::AddedColumn { path } => {
+            format!("column \"{}\" was added", path.join("."))
+        }
+        SchemaIncompatibility::RemovedColumn { path } => {
+            format!("column \"{}\" was removed", path.join("."))
+        }
+        SchemaIncompatibility::TypeMismatch { path, .. } => {
+            format!("column \"{}\" has a different type", path.join("."))
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! APIs to format the values of a column as human-readable strings, e.g. to print them.
+//!
+//! Like [`RowGroupRowIterator`](super::RowGroupRowIterator), this is intended for data
+//! inspection and debugging, not for performance-sensitive workloads.
+use std::fmt::Write;
+
+use crate::error::{Error, Result};
+use crate::metadata::ColumnDescriptor;
+use crate::page::CompressedDataPage;
+use crate::parquet_bridge::{IntegerType, PrimitiveLogicalType, TimeUnit};
+use crate::schema::types::{PrimitiveConvertedType, PrimitiveType};
+use crate::FallibleStreamingIterator;
+
+use super::row::RowAssembler;
+use super::{BasicDecompressor, DynamicValue};
+
+/// A [`FallibleStreamingIterator`] of the values of a column, one per row, formatted by
+/// [`format_value`].
+pub struct ColumnFormatter<I: Iterator<Item = Result<CompressedDataPage>>> {
+    pages: BasicDecompressor<I>,
+    descriptor: ColumnDescriptor,
+    assembler: RowAssembler,
+    finished: bool,
+    values: std::vec::IntoIter<DynamicValue>,
+    current: Option<String>,
+}
+
+impl<I: Iterator<Item = Result<CompressedDataPage>>> ColumnFormatter<I> {
+    /// Returns a new [`ColumnFormatter`] of the values of the pages of `pages`, whose column
+    /// is described by `descriptor`.
+    /// # Error
+    /// Errors iff the path of `descriptor` is inconsistent with its schema.
+    pub fn try_new(pages: BasicDecompressor<I>, descriptor: ColumnDescriptor) -> Result<Self> {
+        let assembler = RowAssembler::try_new(&descriptor)?;
+        Ok(Self {
+            pages,
+            descriptor,
+            assembler,
+            finished: false,
+            values: vec![].into_iter(),
+            current: None,
+        })
+    }
+
+    /// The [`ColumnDescriptor`] of the column
+    pub fn descriptor(&self) -> &ColumnDescriptor {
+        &self.descriptor
+    }
+}
+
+impl<I: Iterator<Item = Result<CompressedDataPage>>> FallibleStreamingIterator
+    for ColumnFormatter<I>
+{
+    type Item = String;
+    type Error = Error;
+
+    fn advance(&mut self) -> Result<()> {
+        loop {
+            if let Some(value) = self.values.next() {
+                self.current = Some(format_value(
+                    &value,
+                    &self.descriptor.descriptor.primitive_type,
+                ));
+                return Ok(());
+            }
+            if self.finished {
+                self.current = None;
+                return Ok(());
+            }
+            let mut values = vec![];
+            match self.pages.next()? {
+                Some(page) => self.assembler.push_page(page, &mut values)?,
+                None => {
+                    // the last row
+                    self.assembler.finish(&mut values);
+                    self.finished = true;
+                }
+            };
+            self.values = values.into_iter();
+        }
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        self.current.as_ref()
+    }
+}
+
+/// The interpretation of the values of a column, derived from its logical or converted type.
+enum Logical {
+    Plain,
+    String,
+    Unsigned,
+    Decimal(usize),
+    Date,
+    Time(TimeUnit),
+    Timestamp(TimeUnit, bool),
+    Uuid,
+}
+
+fn logical(primitive_type: &PrimitiveType) -> Logical {
+    if let Some(logical_type) = primitive_type.logical_type {
+        return match logical_type {
+            PrimitiveLogicalType::String
+            | PrimitiveLogicalType::Enum
+            | PrimitiveLogicalType::Json => Logical::String,
+            PrimitiveLogicalType::Decimal(_, scale) => Logical::Decimal(scale),
+            PrimitiveLogicalType::Date => Logical::Date,
+            PrimitiveLogicalType::Time { unit, .. } => Logical::Time(unit),
+            PrimitiveLogicalType::Timestamp {
+                unit,
+                is_adjusted_to_utc,
+            } => Logical::Timestamp(unit, is_adjusted_to_utc),
+            PrimitiveLogicalType::Integer(
+                IntegerType::UInt8
+                | IntegerType::UInt16
+                | IntegerType::UInt32
+                | IntegerType::UInt64,
+            ) => Logical::Unsigned,
+            PrimitiveLogicalType::Uuid => Logical::Uuid,
+            _ => Logical::Plain,
+        };
+    }
+    match primitive_type.converted_type {
+        Some(
+            PrimitiveConvertedType::Utf8
+            | PrimitiveConvertedType::Enum
+            | PrimitiveConvertedType::Json,
+        ) => Logical::String,
+        Some(PrimitiveConvertedType::Decimal(_, scale)) => Logical::Decimal(scale),
+        Some(PrimitiveConvertedType::Date) => Logical::Date,
+        Some(PrimitiveConvertedType::TimeMillis) => Logical::Time(TimeUnit::Milliseconds),
+        Some(PrimitiveConvertedType::TimeMicros) => Logical::Time(TimeUnit::Microseconds),
+        // converted timestamps are always adjusted to UTC
+        Some(PrimitiveConvertedType::TimestampMillis) => {
+            Logical::Timestamp(TimeUnit::Milliseconds, true)
+        }
+        Some(PrimitiveConvertedType::TimestampMicros) => {
+            Logical::Timestamp(TimeUnit::Microseconds, true)
+        }
+        Some(
+            PrimitiveConvertedType::Uint8
+            | PrimitiveConvertedType::Uint16
+            | PrimitiveConvertedType::Uint32
+            | PrimitiveConvertedType::Uint64,
+        ) => Logical::Unsigned,
+        _ => Logical::Plain,
+    }
+}
+
+/// Formats `value` of a column of type `primitive_type` as a human-readable string:
+/// * nulls as `null` and repeated values as `[a, b]`
+/// * dates, times and timestamps (including `INT96`) in ISO-8601, e.g. `2021-01-01T10:00:00.000Z`
+/// * decimals with their scale, e.g. `-1.50`
+/// * strings as UTF-8, UUIDs in their hyphenated form, and any other byte array as hex
+pub fn format_value(value: &DynamicValue, primitive_type: &PrimitiveType) -> String {
+    match value {
+        DynamicValue::Null => "null".to_string(),
+        DynamicValue::Boolean(x) => x.to_string(),
+        DynamicValue::Int32(x) => format_integer(*x as i64, *x as u32 as u64, primitive_type),
+        DynamicValue::Int64(x) => format_integer(*x, *x as u64, primitive_type),
+        DynamicValue::Int96(x) => match int96_to_ns(*x) {
+            Some(x) => format_timestamp(x, TimeUnit::Nanoseconds, false),
+            None => hex(&x.iter().flat_map(|x| x.to_le_bytes()).collect::<Vec<_>>()),
+        },
+        DynamicValue::Float(x) => x.to_string(),
+        DynamicValue::Double(x) => x.to_string(),
+        DynamicValue::Binary(x) | DynamicValue::FixedLenBinary(x) => {
+            format_bytes(x, primitive_type)
+        }
+        DynamicValue::List(items) => {
+            let items = items
+                .iter()
+                .map(|x| format_value(x, primitive_type))
+                .collect::<Vec<_>>();
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
+
+/// Returns the nanoseconds since the epoch of an `INT96` timestamp, like
+/// [`int96_to_i64_ns`](crate::types::int96_to_i64_ns), or `None` when they overflow an `i64`.
+fn int96_to_ns(value: [u32; 3]) -> Option<i64> {
+    const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
+    const NANOS_PER_DAY: i64 = 86_400 * 1_000_000_000;
+
+    let nanoseconds = i64::try_from(((value[1] as u64) << 32) | value[0] as u64).ok()?;
+    (value[2] as i64 - JULIAN_DAY_OF_EPOCH)
+        .checked_mul(NANOS_PER_DAY)?
+        .checked_add(nanoseconds)
+}
+
+/// Formats an integer, where `unsigned` is its bits as an unsigned integer.
+fn format_integer(value: i64, unsigned: u64, primitive_type: &PrimitiveType) -> String {
+    match logical(primitive_type) {
+        Logical::Unsigned => unsigned.to_string(),
+        Logical::Decimal(scale) => format_decimal(value as i128, scale),
+        Logical::Date => format_date(value),
+        Logical::Time(unit) => format_time(value, unit),
+        Logical::Timestamp(unit, is_adjusted_to_utc) => {
+            format_timestamp(value, unit, is_adjusted_to_utc)
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn format_bytes(value: &[u8], primitive_type: &PrimitiveType) -> String {
+    match logical(primitive_type) {
+        Logical::String => match std::str::from_utf8(value) {
+            Ok(value) => value.to_string(),
+            Err(_) => hex(value),
+        },
+        // decimals are big-endian two's complement integers
+        Logical::Decimal(scale) if !value.is_empty() && value.len() <= 16 => {
+            let sign = if value[0] & 0x80 != 0 { -1 } else { 0 };
+            let value = value
+                .iter()
+                .fold(sign, |acc: i128, x| (acc << 8) | *x as i128);
+            format_decimal(value, scale)
+        }
+        Logical::Uuid if value.len() == 16 => format!(
+            "{}-{}-{}-{}-{}",
+            hex(&value[..4]),
+            hex(&value[4..6]),
+            hex(&value[6..8]),
+            hex(&value[8..10]),
+            hex(&value[10..])
+        ),
+        _ => hex(value),
+    }
+}
+
+fn hex(value: &[u8]) -> String {
+    value.iter().fold(String::new(), |mut acc, x| {
+        let _ = write!(acc, "{:02x}", x);
+        acc
+    })
+}
+
+fn format_decimal(value: i128, scale: usize) -> String {
+    if scale == 0 {
+        return value.to_string();
+    }
+    let digits = format!("{:0>width$}", value.unsigned_abs(), width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// The number of values of `unit` per second and the number of digits of its fraction
+fn unit_info(unit: TimeUnit) -> (i64, usize) {
+    match unit {
+        TimeUnit::Milliseconds => (1_000, 3),
+        TimeUnit::Microseconds => (1_000_000, 6),
+        TimeUnit::Nanoseconds => (1_000_000_000, 9),
+    }
+}
+
+/// Returns the (year, month, day) of the number of days since 1970-01-01 in the proleptic
+/// Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn format_time(value: i64, unit: TimeUnit) -> String {
+    let (per_second, digits) = unit_info(unit);
+    let seconds = value.div_euclid(per_second);
+    format!(
+        "{:02}:{:02}:{:02}.{:0digits$}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60,
+        value.rem_euclid(per_second),
+        digits = digits
+    )
+}
+
+fn format_timestamp(value: i64, unit: TimeUnit, is_adjusted_to_utc: bool) -> String {
+    const SECONDS_PER_DAY: i64 = 86_400;
+    let (per_second, _) = unit_info(unit);
+    let seconds = value.div_euclid(per_second);
+    let time = seconds.rem_euclid(SECONDS_PER_DAY) * per_second + value.rem_euclid(per_second);
+    format!(
+        "{}T{}{}",
+        format_date(seconds.div_euclid(SECONDS_PER_DAY)),
+        format_time(time, unit),
+        if is_adjusted_to_utc { "Z" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::PhysicalType;
+
+    fn logical_type(
+        physical_type: PhysicalType,
+        logical_type: PrimitiveLogicalType,
+    ) -> PrimitiveType {
+        let mut primitive_type = PrimitiveType::from_physical("a".to_string(), physical_type);
+        primitive_type.logical_type = Some(logical_type);
+        primitive_type
+    }
+
+    #[test]
+    fn plain() {
+        let int32 = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        assert_eq!(format_value(&DynamicValue::Null, &int32), "null");
+        assert_eq!(format_value(&DynamicValue::Int32(-1), &int32), "-1");
+        assert_eq!(
+            format_value(
+                &DynamicValue::List(vec![DynamicValue::Int32(1), DynamicValue::Null]),
+                &int32
+            ),
+            "[1, null]"
+        );
+        let binary = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        assert_eq!(
+            format_value(&DynamicValue::Binary(vec![0, 15, 255]), &binary),
+            "000fff"
+        );
+    }
+
+    #[test]
+    fn unsigned() {
+        let type_ = logical_type(
+            PhysicalType::Int32,
+            PrimitiveLogicalType::Integer(IntegerType::UInt32),
+        );
+        assert_eq!(format_value(&DynamicValue::Int32(-1), &type_), "4294967295");
+    }
+
+    #[test]
+    fn decimal() {
+        let type_ = logical_type(PhysicalType::Int64, PrimitiveLogicalType::Decimal(10, 2));
+        assert_eq!(format_value(&DynamicValue::Int64(-150), &type_), "-1.50");
+        assert_eq!(format_value(&DynamicValue::Int64(5), &type_), "0.05");
+
+        let mut type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        type_.converted_type = Some(PrimitiveConvertedType::Decimal(10, 3));
+        assert_eq!(
+            format_value(&DynamicValue::Binary(vec![0xff, 0x85]), &type_),
+            "-0.123"
+        );
+        assert_eq!(
+            format_value(&DynamicValue::Binary(vec![0x30, 0x39]), &type_),
+            "12.345"
+        );
+    }
+
+    #[test]
+    fn temporal() {
+        let date = logical_type(PhysicalType::Int32, PrimitiveLogicalType::Date);
+        assert_eq!(format_value(&DynamicValue::Int32(0), &date), "1970-01-01");
+        assert_eq!(
+            format_value(&DynamicValue::Int32(18_628), &date),
+            "2021-01-01"
+        );
+        assert_eq!(format_value(&DynamicValue::Int32(-1), &date), "1969-12-31");
+
+        let time = logical_type(
+            PhysicalType::Int32,
+            PrimitiveLogicalType::Time {
+                unit: TimeUnit::Milliseconds,
+                is_adjusted_to_utc: true,
+            },
+        );
+        assert_eq!(
+            format_value(&DynamicValue::Int32(37_230_005), &time),
+            "10:20:30.005"
+        );
+
+        let timestamp = logical_type(
+            PhysicalType::Int64,
+            PrimitiveLogicalType::Timestamp {
+                unit: TimeUnit::Microseconds,
+                is_adjusted_to_utc: true,
+            },
+        );
+        assert_eq!(
+            format_value(&DynamicValue::Int64(1_609_495_200_000_001), &timestamp),
+            "2021-01-01T10:00:00.000001Z"
+        );
+        assert_eq!(
+            format_value(&DynamicValue::Int64(-1), &timestamp),
+            "1969-12-31T23:59:59.999999Z"
+        );
+
+        let int96 = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int96);
+        assert_eq!(
+            format_value(&DynamicValue::Int96([1, 0, 2_440_589]), &int96),
+            "1970-01-02T00:00:00.000000001"
+        );
+        // timestamps out of the range of nanoseconds in an `i64` are formatted as hex
+        assert_eq!(
+            format_value(&DynamicValue::Int96([0, 0, u32::MAX]), &int96),
+            "0000000000000000ffffffff"
+        );
+        assert_eq!(
+            format_value(&DynamicValue::Int96([0, u32::MAX, 2_440_588]), &int96),
+            "00000000ffffffff8c3d2500"
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let mut type_ = PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        type_.converted_type = Some(PrimitiveConvertedType::Utf8);
+        assert_eq!(
+            format_value(&DynamicValue::Binary(b"hello".to_vec()), &type_),
+            "hello"
+        );
+        // invalid utf8 is shown as hex
+        assert_eq!(format_value(&DynamicValue::Binary(vec![255]), &type_), "ff");
+
+        let uuid = logical_type(
+            PhysicalType::FixedLenByteArray(16),
+            PrimitiveLogicalType::Uuid,
+        );
+        assert_eq!(
+            format_value(&DynamicValue::FixedLenBinary((0..16).collect()), &uuid),
+            "00010203-0405-0607-0809-0a0b0c0d0e0f"
+        );
+    }
+}
@@ -3,11 +3,26 @@ use std::io::Cursor;
 use parquet_format_async_temp::{thrift::protocol::TCompactInputProtocol, ColumnIndex};
 
 use crate::error::Error;
+use crate::metadata::ColumnDescriptor;
 use crate::schema::types::{PhysicalType, PrimitiveType};
 
 use crate::indexes::{BooleanIndex, ByteIndex, FixedLenByteIndex, Index, NativeIndex};
 
-pub fn deserialize(data: &[u8], primitive_type: PrimitiveType) -> Result<Box<dyn Index>, Error> {
+/// Deserializes the column index of `column` from `data`.
+/// # Errors
+/// Errors iff the index is out of spec, with a message containing the path of `column`
+pub fn deserialize(data: &[u8], column: &ColumnDescriptor) -> Result<Box<dyn Index>, Error> {
+    let primitive_type = column.descriptor.primitive_type.clone();
+    deserialize_index(data, primitive_type).map_err(|error| {
+        Error::OutOfSpec(format!(
+            "column '{}': failed to deserialize its column index: {}",
+            column.path_in_schema.join("."),
+            error
+        ))
+    })
+}
+
+fn deserialize_index(data: &[u8], primitive_type: PrimitiveType) -> Result<Box<dyn Index>, Error> {
     let mut d = Cursor::new(data);
     let mut prot = TCompactInputProtocol::new(&mut d);
 
@@ -28,3 +43,60 @@ pub fn deserialize(data: &[u8], primitive_type: PrimitiveType) -> Result<Box<dyn
 
     Ok(index)
 }
+
+#[cfg(test)]
+mod tests {
+    use parquet_format_async_temp::thrift::protocol::TCompactOutputProtocol;
+
+    use crate::metadata::Descriptor;
+    use crate::schema::types::ParquetType;
+
+    use super::*;
+
+    fn column(physical_type: PhysicalType) -> ColumnDescriptor {
+        let primitive_type = PrimitiveType::from_physical("c".to_string(), physical_type);
+        ColumnDescriptor::new(
+            Descriptor {
+                primitive_type: primitive_type.clone(),
+                max_def_level: 1,
+                max_rep_level: 0,
+            },
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            ParquetType::PrimitiveType(primitive_type),
+        )
+    }
+
+    fn serialize(index: &ColumnIndex) -> Vec<u8> {
+        let mut data = vec![];
+        let mut protocol = TCompactOutputProtocol::new(&mut data);
+        index.write_to_out_protocol(&mut protocol).unwrap();
+        data
+    }
+
+    #[test]
+    fn native_index_of_wrong_size() {
+        let index = ColumnIndex {
+            null_pages: vec![false, false],
+            min_values: vec![0i32.to_le_bytes().to_vec(), vec![1, 0, 0]],
+            max_values: vec![1i32.to_le_bytes().to_vec(), 2i32.to_le_bytes().to_vec()],
+            boundary_order: parquet_format_async_temp::BoundaryOrder::UNORDERED,
+            null_counts: None,
+        };
+        let data = serialize(&index);
+
+        assert!(deserialize(&data, &column(PhysicalType::Int64)).is_err());
+        let error = deserialize(&data, &column(PhysicalType::Int32)).unwrap_err();
+        assert!(matches!(error, Error::OutOfSpec(_)));
+        let message = error.to_string();
+        assert!(message.starts_with("column 'a.b.c': "), "{}", message);
+        assert!(message.contains("page 1"), "{}", message);
+    }
+
+    #[test]
+    fn corrupted_index() {
+        let data = [0xff; 10];
+        let error = deserialize(&data, &column(PhysicalType::Int32)).unwrap_err();
+        assert!(matches!(error, Error::OutOfSpec(_)));
+        assert!(error.to_string().starts_with("column 'a.b.c': "));
+    }
+}
@@ -72,15 +72,14 @@ fn deserialize_column_indexes(
     chunks
         .iter()
         .zip(data)
-        .map(|(chunk, data)| {
-            let primitive_type = chunk.descriptor().descriptor.primitive_type.clone();
-            deserialize(data, primitive_type)
-        })
+        .map(|(chunk, data)| deserialize(data, chunk.descriptor()))
         .collect()
 }
 
 /// Reads the column indexes of all [`ColumnChunkMetaData`] and deserializes them into [`Index`].
 /// Returns an empty vector if indexes are not available
+/// # Errors
+/// Errors if an index is out of spec, with a message containing the path of its column
 pub fn read_columns_indexes<R: Read + Seek>(
     reader: &mut R,
     chunks: &[ColumnChunkMetaData],
@@ -112,7 +111,8 @@ fn deserialize_page_locations(
 }
 
 /// Read [`PageLocation`]s from the [`ColumnChunkMetaData`]s.
-/// Returns an empty vector if indexes are not available
+/// Returns an empty vector if indexes are not available.
+/// See [`read_offset_index`] to read the offset index of a single column.
 pub fn read_pages_locations<R: Read + Seek>(
     reader: &mut R,
     chunks: &[ColumnChunkMetaData],
@@ -127,3 +127,30 @@ pub fn read_pages_locations<R: Read + Seek>(
 
     deserialize_page_locations(&data, chunks.len())
 }
+
+/// Reads the [`PageLocation`]s of the offset index of a single column chunk, `column`.
+///
+/// Contrarily to [`read_pages_locations`], this only reads the offset index of `column`, and,
+/// like it, does not read the column index. Use it when only the locations of the pages are
+/// needed (e.g. to skip rows with [`select_pages`](crate::indexes::select_pages)).
+///
+/// Returns `None` if `column` has no offset index.
+/// # Errors
+/// Errors iff the offset index cannot be read or is out of spec
+pub fn read_offset_index<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+) -> Result<Option<Vec<PageLocation>>, Error> {
+    let (offset, lengths) = prepare_offset_index_read(std::slice::from_ref(column))?;
+    let length = if let Some(length) = lengths.first() {
+        *length
+    } else {
+        return Ok(None);
+    };
+
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut data = vec![0; length];
+    reader.read_exact(&mut data)?;
+
+    Ok(deserialize_page_locations(&data, 1)?.pop())
+}
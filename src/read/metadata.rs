@@ -1,20 +1,63 @@
 use std::convert::TryInto;
 use std::{
     cmp::min,
-    io::{Cursor, Read, Seek, SeekFrom},
+    io::{Read, Seek, SeekFrom},
 };
 
 use parquet_format_async_temp::thrift::protocol::TCompactInputProtocol;
 use parquet_format_async_temp::FileMetaData as TFileMetaData;
 
-use super::super::{metadata::FileMetaData, DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, PARQUET_MAGIC};
+use super::super::{
+    metadata::FileMetaData, DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, PARQUET_ENCRYPTED_MAGIC,
+    PARQUET_MAGIC,
+};
 
 use crate::error::{Error, Result};
 
-pub(super) fn metadata_len(buffer: &[u8], len: usize) -> i32 {
+fn metadata_len(buffer: &[u8], len: usize) -> i32 {
     i32::from_le_bytes(buffer[len - 8..len - 4].try_into().unwrap())
 }
 
+/// Parses the footer at the end of `end` (the last bytes of a file of `file_size` bytes) and
+/// returns the length of the metadata and footer.
+pub(super) fn footer_metadata_len(end: &[u8], file_size: u64) -> Result<u64> {
+    let end_len = end.len();
+    // check this is indeed a parquet file
+    if end[end_len - 4..] == PARQUET_ENCRYPTED_MAGIC {
+        return Err(general_err!(
+            "Parquet files with encrypted footers are not supported"
+        ));
+    }
+    if end[end_len - 4..] != PARQUET_MAGIC {
+        return Err(general_err!("Invalid Parquet file. Corrupt footer"));
+    }
+
+    let metadata_len = metadata_len(end, end_len);
+    if metadata_len < 0 {
+        return Err(general_err!(
+            "Invalid Parquet file. Metadata length is less than zero ({})",
+            metadata_len
+        ));
+    }
+    let footer_metadata_len = FOOTER_SIZE + metadata_len as u64;
+    if footer_metadata_len > file_size {
+        return Err(general_err!(
+            "Invalid Parquet file. Metadata start is less than zero ({})",
+            file_size as i64 - footer_metadata_len as i64
+        ));
+    }
+    Ok(footer_metadata_len)
+}
+
+/// Deserializes the metadata from `buffer`, which starts with the (thrift-encoded) metadata.
+pub(super) fn deserialize_metadata(buffer: &[u8]) -> Result<FileMetaData> {
+    let mut prot = TCompactInputProtocol::new(buffer);
+    let metadata = TFileMetaData::read_from_in_protocol(&mut prot)
+        .map_err(|e| Error::General(format!("Could not parse metadata: {}", e)))?;
+
+    FileMetaData::try_from_thrift(metadata)
+}
+
 // see (unstable) Seek::stream_len
 fn stream_len(seek: &mut impl Seek) -> std::result::Result<u64, std::io::Error> {
     let old_pos = seek.seek(SeekFrom::Current(0))?;
@@ -30,6 +73,9 @@ fn stream_len(seek: &mut impl Seek) -> std::result::Result<u64, std::io::Error>
 }
 
 /// Reads a file's metadata.
+/// # Error
+/// Errors iff the file is not a parquet file, its metadata is out of spec, or its footer is
+/// encrypted (modular encryption is not supported).
 // Layout of Parquet file
 // +---------------------------+-----+---+
 // |      Rest of file         |  B  | A |
@@ -37,7 +83,8 @@ fn stream_len(seek: &mut impl Seek) -> std::result::Result<u64, std::io::Error>
 // where A: parquet footer, B: parquet metadata.
 //
 // The reader first reads DEFAULT_FOOTER_SIZE bytes from the end of the file.
-// If it is not enough according to the length indicated in the footer, it reads more bytes.
+// If it is not enough according to the length indicated in the footer, it reads the
+// missing bytes before them.
 pub fn read_metadata<R: Read + Seek>(reader: &mut R) -> Result<FileMetaData> {
     // check file is large enough to hold footer
     let file_size = stream_len(reader)?;
@@ -53,55 +100,41 @@ pub fn read_metadata<R: Read + Seek>(reader: &mut R) -> Result<FileMetaData> {
     let mut default_len_end_buf = vec![0; default_end_len];
     reader.read_exact(&mut default_len_end_buf)?;
 
-    // check this is indeed a parquet file
-    if default_len_end_buf[default_end_len - 4..] != PARQUET_MAGIC {
-        return Err(general_err!("Invalid Parquet file. Corrupt footer"));
-    }
-
-    let metadata_len = metadata_len(&default_len_end_buf, default_end_len);
+    let footer_metadata_len = footer_metadata_len(&default_len_end_buf, file_size)? as usize;
 
-    if metadata_len < 0 {
-        return Err(general_err!(
-            "Invalid Parquet file. Metadata length is less than zero ({})",
-            metadata_len
-        ));
-    }
-    let footer_metadata_len = FOOTER_SIZE + metadata_len as u64;
-
-    let metadata = if footer_metadata_len > file_size {
-        return Err(general_err!(
-            "Invalid Parquet file. Metadata start is less than zero ({})",
-            file_size as i64 - footer_metadata_len as i64
-        ));
-    } else if footer_metadata_len < DEFAULT_FOOTER_READ_SIZE {
+    if footer_metadata_len <= default_end_len {
         // the whole metadata is in the bytes we already read
-        // build up the reader covering the entire metadata
-        let mut reader = Cursor::new(default_len_end_buf);
-        reader.seek(SeekFrom::End(-(footer_metadata_len as i64)))?;
-
-        let mut prot = TCompactInputProtocol::new(reader);
-        TFileMetaData::read_from_in_protocol(&mut prot)
+        deserialize_metadata(&default_len_end_buf[default_end_len - footer_metadata_len..])
     } else {
-        // the end of file read by default is not long enough, read again including all metadata.
+        // the end of file read by default is not long enough: read the missing bytes before it
         reader.seek(SeekFrom::End(-(footer_metadata_len as i64)))?;
-
-        let mut prot = TCompactInputProtocol::new(reader);
-        TFileMetaData::read_from_in_protocol(&mut prot)
+        let mut buffer = vec![0; footer_metadata_len - default_end_len];
+        reader.read_exact(&mut buffer)?;
+        buffer.extend_from_slice(&default_len_end_buf);
+        deserialize_metadata(&buffer)
     }
-    .map_err(|e| Error::General(format!("Could not parse metadata: {}", e)))?;
-
-    FileMetaData::try_from_thrift(metadata)
 }
 
 #[cfg(test)]
 mod tests {
     use std::fs::File;
+    use std::io::Cursor;
 
     use super::*;
 
     use crate::schema::{types::PhysicalType, Repetition};
     use crate::tests::get_path;
 
+    #[test]
+    fn encrypted_footer() {
+        let mut data = vec![0u8; 12];
+        data.extend_from_slice(&4i32.to_le_bytes());
+        data.extend_from_slice(&PARQUET_ENCRYPTED_MAGIC);
+
+        let error = read_metadata(&mut Cursor::new(data)).unwrap_err();
+        assert!(error.to_string().contains("encrypted"));
+    }
+
     #[test]
     fn test_basics() {
         let mut testdata = get_path();
@@ -1,30 +1,79 @@
 mod compression;
+mod dataset;
+mod formatter;
 mod indexes;
 pub mod levels;
 mod metadata;
+#[cfg(feature = "object_store")]
+mod object_store;
 mod page;
+pub mod predicate;
+pub(crate) mod row;
 #[cfg(feature = "stream")]
 mod stream;
+mod streaming;
+#[cfg(feature = "tokio")]
+mod tokio_reader;
 
 use std::io::{Read, Seek, SeekFrom};
 use std::sync::Arc;
 use std::vec::IntoIter;
 
-pub use compression::{decompress, BasicDecompressor, Decompressor};
+#[cfg(feature = "object_store")]
+pub use self::object_store::ObjectStoreReader;
+#[cfg(feature = "bloom_filter")]
+pub(crate) use compression::decompress_into_buffer;
+pub use compression::{
+    decompress, decompress_with_codec, decompress_with_options, BasicDecompressor, Decompressor,
+    DEFAULT_MAX_PAGE_SIZE,
+};
+pub use dataset::{DatasetReader, DatasetRowGroup};
+pub use formatter::{format_value, ColumnFormatter};
 pub use metadata::read_metadata;
 #[cfg(feature = "stream")]
-pub use page::get_page_stream;
-pub use page::{IndexedPageReader, PageFilter, PageIterator, PageReader};
+pub use page::{get_page_stream, get_page_stream_with_options};
+pub(crate) use page::{page_size, read_page_header};
+pub use page::{read_compressed_pages, IndexedPageReader, PageFilter, PageIterator, PageReader};
+pub use row::{DynamicRow, DynamicValue, RowGroupRowIterator};
 #[cfg(feature = "stream")]
 pub use stream::read_metadata as read_metadata_async;
+pub use streaming::{PlainValues, StreamingDecompressor, StreamingPage};
+#[cfg(feature = "tokio")]
+pub use tokio_reader::TokioReader;
 
-use crate::error::Error;
+use crate::compression::{Compression, CustomCodec};
+use crate::error::{Error, Feature};
 use crate::metadata::{ColumnChunkMetaData, RowGroupMetaData};
 use crate::page::CompressedDataPage;
 use crate::schema::types::ParquetType;
 use crate::{error::Result, metadata::FileMetaData};
 
-pub use indexes::{read_columns_indexes, read_pages_locations};
+pub use indexes::{read_columns_indexes, read_offset_index, read_pages_locations};
+
+/// Options of the readers of pages of this crate (e.g. [`PageReader`] and [`IndexedPageReader`])
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    /// Whether to verify the CRC32 checksum of the pages whose header declares one.
+    /// Pages whose data does not match it error with [`Error::Corruption`].
+    pub verify_crc: bool,
+    /// The maximum compressed or uncompressed size of a page (by default
+    /// [`DEFAULT_MAX_PAGE_SIZE`]).
+    pub max_page_size: usize,
+    /// A codec to decompress the dictionary pages whose compression it matches, instead of this
+    /// crate's implementation. Data pages are decompressed by [`BasicDecompressor`], which has
+    /// its own codec (see [`BasicDecompressor::new_with_codec`]).
+    pub codec: Option<Arc<dyn CustomCodec>>,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            verify_crc: false,
+            max_page_size: DEFAULT_MAX_PAGE_SIZE,
+            codec: None,
+        }
+    }
+}
 
 /// Filters row group metadata to only those row groups,
 /// for which the predicate function returns true
@@ -43,18 +92,84 @@ pub fn filter_row_groups(
     metadata
 }
 
+/// Checks that the codecs of all column chunks of `metadata` are supported by this crate,
+/// so that reading can fail before any page is read.
+/// # Error
+/// Errors iff a column chunk is compressed with a codec that is not supported, e.g. because
+/// its feature is not active (see [`crate::compression::Compression::is_supported`]).
+pub fn check_compression(metadata: &FileMetaData) -> Result<()> {
+    for row_group in &metadata.row_groups {
+        for column in row_group.columns() {
+            let compression = column.compression();
+            if compression.is_supported() {
+                continue;
+            }
+            let reason = format!(
+                "read column \"{}\" compressed with {:?}",
+                column.descriptor().path_in_schema.join("."),
+                compression
+            );
+            let feature = match compression {
+                Compression::Snappy => Feature::Snappy,
+                Compression::Gzip => Feature::Gzip,
+                Compression::Brotli => Feature::Brotli,
+                Compression::Lz4 | Compression::Lz4Raw => Feature::Lz4,
+                Compression::Zstd => Feature::Zstd,
+                Compression::Uncompressed | Compression::Lzo => {
+                    return Err(Error::General(format!("Unable to {}", reason)))
+                }
+            };
+            return Err(Error::FeatureNotActive(feature, reason));
+        }
+    }
+    Ok(())
+}
+
 /// Returns a new [`PageReader`] by seeking `reader` to the begining of `column_chunk`.
+/// # Error
+/// Errors iff `column_chunk` is encrypted (modular encryption is not supported).
 pub fn get_page_iterator<R: Read + Seek>(
+    column_chunk: &ColumnChunkMetaData,
+    reader: R,
+    pages_filter: Option<PageFilter>,
+    buffer: Vec<u8>,
+) -> Result<PageReader<R>> {
+    get_page_iterator_with_options(
+        column_chunk,
+        reader,
+        pages_filter,
+        buffer,
+        ReadOptions::default(),
+    )
+}
+
+/// Returns a new [`PageReader`] that reads pages according to `options`, by seeking `reader`
+/// to the begining of `column_chunk`.
+/// # Error
+/// Errors iff `column_chunk` is encrypted (modular encryption is not supported).
+pub fn get_page_iterator_with_options<R: Read + Seek>(
     column_chunk: &ColumnChunkMetaData,
     mut reader: R,
     pages_filter: Option<PageFilter>,
     buffer: Vec<u8>,
+    options: ReadOptions,
 ) -> Result<PageReader<R>> {
+    if column_chunk.column_chunk().crypto_metadata.is_some() {
+        return Err(Error::General(
+            "Reading encrypted column chunks is not supported".to_string(),
+        ));
+    }
     let pages_filter = pages_filter.unwrap_or_else(|| Arc::new(|_, _| true));
 
     let (col_start, _) = column_chunk.byte_range();
     reader.seek(SeekFrom::Start(col_start))?;
-    Ok(PageReader::new(reader, column_chunk, pages_filter, buffer))
+    Ok(PageReader::new_with_options(
+        reader,
+        column_chunk,
+        pages_filter,
+        buffer,
+        options,
+    ))
 }
 
 /// Returns an [`Iterator`] of [`ColumnChunkMetaData`] corresponding to the columns
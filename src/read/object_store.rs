@@ -0,0 +1,356 @@
+//! Reading of parquet files from an [`ObjectStore`], with range requests.
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use ::object_store::{path::Path, ObjectStore};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::{ready, AsyncRead, AsyncSeek, FutureExt};
+
+use crate::error::Result;
+use crate::metadata::{ColumnChunkMetaData, FileMetaData};
+
+fn to_io_error(error: ::object_store::Error) -> std::io::Error {
+    std::io::Error::other(error)
+}
+
+enum State {
+    Idle,
+    /// Fetching the size of the object, to seek relative to its end
+    Seeking(BoxFuture<'static, std::io::Result<u64>>, i64),
+    /// Fetching the size of the object (when unknown) and a range of it
+    Reading(BoxFuture<'static, std::io::Result<(u64, Bytes)>>),
+}
+
+/// A reader of an object of an [`ObjectStore`] implementing [`AsyncRead`] and [`AsyncSeek`],
+/// so that it can be used by the async readers of this crate, such as
+/// [`read_metadata_async`](super::read_metadata_async).
+///
+/// Every read is a range request of the bytes read and seeking from the end of the object
+/// requests its size once. The footer of a file is therefore read with one or two range
+/// requests, without downloading the file. Use [`ObjectStoreReader::read_column_chunks`] to read
+/// whole column chunks, since reading pages directly from this reader issues a request per
+/// read of their headers.
+pub struct ObjectStoreReader {
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    position: u64,
+    size: Option<u64>,
+    state: State,
+}
+
+impl std::fmt::Debug for ObjectStoreReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectStoreReader")
+            .field("store", &self.store)
+            .field("path", &self.path)
+            .field("position", &self.position)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl ObjectStoreReader {
+    /// Returns a new [`ObjectStoreReader`] of the object at `path` of `store`, positioned
+    /// at its start.
+    pub fn new(store: Arc<dyn ObjectStore>, path: Path) -> Self {
+        Self {
+            store,
+            path,
+            position: 0,
+            size: None,
+            state: State::Idle,
+        }
+    }
+
+    /// Sets the size of the object, when known, so that it is not requested.
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Reads the metadata of the parquet file with range requests of its footer.
+    pub async fn read_metadata(&mut self) -> Result<FileMetaData> {
+        super::read_metadata_async(self).await
+    }
+
+    /// Reads the bytes of each of `columns` with range requests, which the store may coalesce.
+    ///
+    /// Each column chunk spans its [`ColumnChunkMetaData::byte_range`], which ends at its
+    /// [`ColumnChunkMetaData::file_offset`] in files written by this crate.
+    /// Its pages can be read with a [`PageReader`](super::PageReader) of
+    /// `std::io::Cursor::new(bytes)`.
+    pub async fn read_column_chunks(
+        &self,
+        columns: &[ColumnChunkMetaData],
+    ) -> Result<Vec<Vec<u8>>> {
+        let ranges = columns
+            .iter()
+            .map(|column| {
+                let (start, length) = column.byte_range();
+                start..start + length
+            })
+            .collect::<Vec<_>>();
+        let chunks = self.store.get_ranges(&self.path, &ranges).await?;
+        Ok(chunks.into_iter().map(Vec::from).collect())
+    }
+
+    fn fetch_size(&self) -> BoxFuture<'static, std::io::Result<u64>> {
+        let store = self.store.clone();
+        let path = self.path.clone();
+        async move {
+            let meta = store.head(&path).await.map_err(to_io_error)?;
+            Ok(meta.size)
+        }
+        .boxed()
+    }
+
+    fn fetch_range(&self, length: u64) -> BoxFuture<'static, std::io::Result<(u64, Bytes)>> {
+        let store = self.store.clone();
+        let path = self.path.clone();
+        let size = self.size;
+        let start = self.position;
+        async move {
+            let size = match size {
+                Some(size) => size,
+                None => store.head(&path).await.map_err(to_io_error)?.size,
+            };
+            let end = size.min(start.saturating_add(length));
+            let bytes = if start < end {
+                store
+                    .get_range(&path, start..end)
+                    .await
+                    .map_err(to_io_error)?
+            } else {
+                Bytes::new()
+            };
+            Ok((size, bytes))
+        }
+        .boxed()
+    }
+
+    fn set_position(&mut self, base: u64, offset: i64) -> std::io::Result<u64> {
+        let position = base.checked_add_signed(offset).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        self.position = position;
+        Ok(position)
+    }
+}
+
+impl AsyncRead for ObjectStoreReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        if let State::Idle = this.state {
+            if buf.is_empty() || this.size.is_some_and(|size| this.position >= size) {
+                return Poll::Ready(Ok(0));
+            }
+            this.state = State::Reading(this.fetch_range(buf.len() as u64));
+        }
+        match &mut this.state {
+            State::Reading(future) => {
+                let result = ready!(future.poll_unpin(cx));
+                this.state = State::Idle;
+                let (size, bytes) = result?;
+                this.size = Some(size);
+
+                // the pending range may be longer than `buf` when polled with a shorter one
+                let length = bytes.len().min(buf.len());
+                buf[..length].copy_from_slice(&bytes[..length]);
+                this.position += length as u64;
+                Poll::Ready(Ok(length))
+            }
+            _ => Poll::Ready(Err(std::io::Error::other(
+                "cannot read while a seek is pending",
+            ))),
+        }
+    }
+}
+
+impl AsyncSeek for ObjectStoreReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = &mut *self;
+        let offset = match pos {
+            SeekFrom::Start(position) => {
+                this.state = State::Idle;
+                this.position = position;
+                return Poll::Ready(Ok(position));
+            }
+            SeekFrom::Current(offset) => {
+                this.state = State::Idle;
+                return Poll::Ready(this.set_position(this.position, offset));
+            }
+            SeekFrom::End(offset) => offset,
+        };
+        if let Some(size) = this.size {
+            this.state = State::Idle;
+            return Poll::Ready(this.set_position(size, offset));
+        }
+        if !matches!(&this.state, State::Seeking(_, pending) if *pending == offset) {
+            this.state = State::Seeking(this.fetch_size(), offset);
+        }
+        if let State::Seeking(future, _) = &mut this.state {
+            let result = ready!(future.poll_unpin(cx));
+            this.state = State::Idle;
+            let size = result?;
+            this.size = Some(size);
+            Poll::Ready(this.set_position(size, offset))
+        } else {
+            unreachable!()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use ::object_store::memory::InMemory;
+    use ::object_store::{GetOptions, GetResult, ListResult, ObjectMeta, PutPayload};
+    use futures::{AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn read_and_seek() -> std::io::Result<()> {
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("data");
+        let data = (0..100u8).collect::<Vec<_>>();
+        store
+            .put(&path, PutPayload::from(data.clone()))
+            .await
+            .map_err(to_io_error)?;
+
+        let mut reader = ObjectStoreReader::new(store, path);
+        assert_eq!(reader.seek(SeekFrom::End(-10)).await?, 90);
+        let mut buffer = vec![0; 10];
+        reader.read_exact(&mut buffer).await?;
+        assert_eq!(buffer, &data[90..]);
+        // at the end of the object
+        assert_eq!(reader.read(&mut buffer).await?, 0);
+
+        assert_eq!(reader.seek(SeekFrom::Current(-20)).await?, 80);
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer).await?;
+        assert_eq!(buffer, &data[80..]);
+
+        assert_eq!(reader.seek(SeekFrom::Start(95)).await?, 95);
+        let mut buffer = vec![0; 10];
+        assert_eq!(reader.read(&mut buffer).await?, 5);
+        assert_eq!(&buffer[..5], &data[95..]);
+
+        assert!(reader.seek(SeekFrom::End(-101)).await.is_err());
+        Ok(())
+    }
+
+    /// An [`ObjectStore`] that counts the requests it receives
+    #[derive(Debug)]
+    struct CountingStore {
+        inner: InMemory,
+        requests: AtomicUsize,
+        bytes: AtomicUsize,
+    }
+
+    impl std::fmt::Display for CountingStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "CountingStore")
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ObjectStore for CountingStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: ::object_store::PutOptions,
+        ) -> ::object_store::Result<::object_store::PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: ::object_store::PutMultipartOptions,
+        ) -> ::object_store::Result<Box<dyn ::object_store::MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(
+            &self,
+            location: &Path,
+            options: GetOptions,
+        ) -> ::object_store::Result<GetResult> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            let head = options.head;
+            let result = self.inner.get_opts(location, options).await?;
+            if !head {
+                let range = result.range.end - result.range.start;
+                self.bytes.fetch_add(range as usize, Ordering::SeqCst);
+            }
+            Ok(result)
+        }
+
+        async fn delete(&self, location: &Path) -> ::object_store::Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(
+            &self,
+            prefix: Option<&Path>,
+        ) -> futures::stream::BoxStream<'static, ::object_store::Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(
+            &self,
+            prefix: Option<&Path>,
+        ) -> ::object_store::Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> ::object_store::Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> ::object_store::Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    #[tokio::test]
+    async fn footer_with_range_requests() -> Result<()> {
+        // a large object whose last bytes are a (too short) parquet footer
+        let mut data = vec![0; 1_000_000];
+        data.extend_from_slice(&100u32.to_le_bytes());
+        data.extend_from_slice(b"PAR1");
+
+        let store = Arc::new(CountingStore {
+            inner: InMemory::new(),
+            requests: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+        });
+        let path = Path::from("file.parquet");
+        store.put(&path, PutPayload::from(data)).await?;
+
+        let mut reader = ObjectStoreReader::new(store.clone(), path);
+        // the metadata is invalid, but only the end of the file was requested
+        assert!(reader.read_metadata().await.is_err());
+        assert!(store.requests.load(Ordering::SeqCst) <= 2);
+        assert!(store.bytes.load(Ordering::SeqCst) < 100_000);
+        Ok(())
+    }
+}
@@ -12,7 +12,8 @@ use crate::{
     parquet_bridge::Compression,
 };
 
-use super::reader::{finish_page, read_page_header, FinishedPage};
+use super::super::ReadOptions;
+use super::reader::{finish_page, read_page_header, verify_crc, FinishedPage};
 
 enum LazyDict {
     // The dictionary has been read and deserialized
@@ -42,6 +43,8 @@ pub struct IndexedPageReader<R: Read + Seek> {
     data_buffer: Vec<u8>,
 
     pages: VecDeque<FilteredPage>,
+
+    options: ReadOptions,
 }
 
 fn resize_buffer(buffer: &mut Vec<u8>, length: usize) {
@@ -61,6 +64,7 @@ fn read_page<R: Read + Seek>(
     length: usize,
     buffer: &mut Vec<u8>,
     data: &mut Vec<u8>,
+    options: &ReadOptions,
 ) -> Result<ParquetPageHeader, Error> {
     // seek to the page
     reader.seek(SeekFrom::Start(start))?;
@@ -78,21 +82,32 @@ fn read_page<R: Read + Seek>(
     // copy [data]
     data.clear();
     data.extend_from_slice(&buffer[header_size..]);
+    if options.verify_crc {
+        verify_crc(&page_header, data)?;
+    }
     Ok(page_header)
 }
 
 fn read_dict_page<R: Read + Seek>(
     reader: &mut R,
-    start: u64,
-    length: usize,
+    (start, length): (u64, usize),
     buffer: &mut Vec<u8>,
     data: &mut Vec<u8>,
     compression: Compression,
     descriptor: &Descriptor,
+    options: &ReadOptions,
 ) -> Result<Arc<dyn DictPage>, Error> {
-    let page_header = read_page(reader, start, length, buffer, data)?;
+    let page_header = read_page(reader, start, length, buffer, data, options)?;
 
-    let result = finish_page(page_header, data, compression, &None, descriptor, None)?;
+    let result = finish_page(
+        page_header,
+        data,
+        compression,
+        &None,
+        descriptor,
+        None,
+        options,
+    )?;
     match result {
         FinishedPage::Data(_) => Err(Error::OutOfSpec(
             "The first page is not a dictionary page but it should".to_string(),
@@ -109,6 +124,25 @@ impl<R: Read + Seek> IndexedPageReader<R> {
         pages: Vec<FilteredPage>,
         buffer: Vec<u8>,
         data_buffer: Vec<u8>,
+    ) -> Self {
+        Self::new_with_options(
+            reader,
+            column,
+            pages,
+            buffer,
+            data_buffer,
+            ReadOptions::default(),
+        )
+    }
+
+    /// Returns a new [`IndexedPageReader`] that reads pages according to `options`.
+    pub fn new_with_options(
+        reader: R,
+        column: &ColumnChunkMetaData,
+        pages: Vec<FilteredPage>,
+        buffer: Vec<u8>,
+        data_buffer: Vec<u8>,
+        options: ReadOptions,
     ) -> Self {
         let column_start = column.byte_range().0;
         // a dictionary page exists iff the first data page is not at the start of
@@ -134,6 +168,7 @@ impl<R: Read + Seek> IndexedPageReader<R> {
             data_buffer,
             pages,
             dictionary,
+            options,
         }
     }
 
@@ -160,12 +195,12 @@ impl<R: Read + Seek> IndexedPageReader<R> {
                 LazyDict::Range(start, length) => {
                     let maybe_page = read_dict_page(
                         &mut self.reader,
-                        *start,
-                        *length,
+                        (*start, *length),
                         &mut self.buffer,
                         &mut data,
                         self.compression,
                         &self.descriptor,
+                        &self.options,
                     );
 
                     match maybe_page {
@@ -179,7 +214,14 @@ impl<R: Read + Seek> IndexedPageReader<R> {
             })
             .transpose()?;
 
-        let page_header = read_page(&mut self.reader, start, length, &mut self.buffer, &mut data)?;
+        let page_header = read_page(
+            &mut self.reader,
+            start,
+            length,
+            &mut self.buffer,
+            &mut data,
+            &self.options,
+        )?;
 
         finish_page(
             page_header,
@@ -188,6 +230,7 @@ impl<R: Read + Seek> IndexedPageReader<R> {
             &dict,
             &self.descriptor,
             Some(selected_rows),
+            &self.options,
         )
     }
 }
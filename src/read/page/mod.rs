@@ -6,11 +6,12 @@ mod stream;
 use crate::{error::Error, page::CompressedDataPage};
 
 pub use indexed_reader::IndexedPageReader;
-pub use reader::{PageFilter, PageReader};
+pub(crate) use reader::{page_size, read_page_header};
+pub use reader::{read_compressed_pages, PageFilter, PageReader};
 
 pub trait PageIterator: Iterator<Item = Result<CompressedDataPage, Error>> {
     fn swap_buffer(&mut self, buffer: &mut Vec<u8>);
 }
 
 #[cfg(feature = "stream")]
-pub use stream::get_page_stream;
+pub use stream::{get_page_stream, get_page_stream_with_options};
@@ -1,18 +1,23 @@
 use std::convert::TryInto;
-use std::{io::Read, sync::Arc};
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
 
 use parquet_format_async_temp::thrift::protocol::TCompactInputProtocol;
 
-use crate::compression::Compression;
-use crate::error::Result;
+use crate::compression::{decompress, decompressed_size_mismatch, Compression};
+use crate::encoding::hybrid_rle::HybridRleDecoder;
+use crate::error::{Error, Result};
 use crate::indexes::Interval;
 use crate::metadata::{ColumnChunkMetaData, Descriptor};
 
 use crate::page::{
-    read_dict_page, CompressedDataPage, DataPageHeader, DictPage, EncodedDictPage, PageType,
-    ParquetPageHeader,
+    read_dict_page_with_codec, split_buffer_v1, CompressedDataPage, CompressedDictPage,
+    CompressedPage, DataPageHeader, DictPage, EncodedDictPage, PageType, ParquetPageHeader,
 };
 
+use super::super::compression::check_page_size;
+use super::super::levels::get_bit_width;
+use super::super::ReadOptions;
 use super::PageIterator;
 
 /// Type declaration for a page filter
@@ -42,6 +47,9 @@ pub struct PageReader<R: Read> {
 
     descriptor: Descriptor,
 
+    // The maximum size of a page, whether to verify checksums and the codec of dictionary pages
+    options: ReadOptions,
+
     // The currently allocated buffer.
     pub(crate) buffer: Vec<u8>,
 }
@@ -55,6 +63,19 @@ impl<R: Read> PageReader<R> {
         column: &ColumnChunkMetaData,
         pages_filter: PageFilter,
         buffer: Vec<u8>,
+    ) -> Self {
+        Self::new_with_options(reader, column, pages_filter, buffer, ReadOptions::default())
+    }
+
+    /// Returns a new [`PageReader`] that reads pages according to `options`.
+    ///
+    /// It assumes that the reader has been `seeked` to the beginning of `column`.
+    pub fn new_with_options(
+        reader: R,
+        column: &ColumnChunkMetaData,
+        pages_filter: PageFilter,
+        buffer: Vec<u8>,
+        options: ReadOptions,
     ) -> Self {
         Self {
             reader,
@@ -64,6 +85,7 @@ impl<R: Read> PageReader<R> {
             current_dictionary: None,
             descriptor: column.descriptor().descriptor.clone(),
             pages_filter,
+            options,
             buffer,
         }
     }
@@ -104,7 +126,7 @@ impl<R: Read> Iterator for PageReader<R> {
 }
 
 /// Reads Page header from Thrift.
-pub(super) fn read_page_header<R: Read>(reader: &mut R) -> Result<ParquetPageHeader> {
+pub(crate) fn read_page_header<R: Read>(reader: &mut R) -> Result<ParquetPageHeader> {
     let mut prot = TCompactInputProtocol::new(reader);
     let page_header = ParquetPageHeader::read_from_in_protocol(&mut prot)?;
     Ok(page_header)
@@ -141,7 +163,11 @@ pub(super) fn build_page<R: Read>(
         .map(|x| x.num_values() as i64)
         .unwrap_or_default();
 
-    let read_size = page_header.compressed_page_size as usize;
+    let read_size = page_size(
+        "compressed",
+        page_header.compressed_page_size,
+        reader.options.max_page_size,
+    )?;
     if read_size > 0 {
         if read_size > buffer.len() {
             // dealloc and ignore region, replacing it by a new region
@@ -151,6 +177,9 @@ pub(super) fn build_page<R: Read>(
         }
         reader.reader.read_exact(buffer)?;
     }
+    if reader.options.verify_crc {
+        verify_crc(&page_header, &buffer[..read_size])?;
+    }
 
     let result = finish_page(
         page_header,
@@ -159,6 +188,7 @@ pub(super) fn build_page<R: Read>(
         &reader.current_dictionary,
         &reader.descriptor,
         None,
+        &reader.options,
     )?;
 
     match result {
@@ -170,6 +200,152 @@ pub(super) fn build_page<R: Read>(
     }
 }
 
+/// Reads all pages of the column chunk `column`, including its dictionary page, without
+/// decompressing them, e.g. to write them to another file with
+/// [`FileWriter::write_compressed_pages`](crate::write::FileWriter::write_compressed_pages).
+///
+/// The data pages have no dictionary page attached: use [`PageReader`] to read pages to
+/// decompress and decode them.
+pub fn read_compressed_pages<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    options: ReadOptions,
+) -> Result<Vec<CompressedPage>> {
+    if column.column_chunk().crypto_metadata.is_some() {
+        return Err(Error::General(
+            "Reading encrypted column chunks is not supported".to_string(),
+        ));
+    }
+    let (start, _) = column.byte_range();
+    reader.seek(SeekFrom::Start(start))?;
+
+    let compression = column.compression();
+    let descriptor = &column.descriptor().descriptor;
+    let mut pages = vec![];
+    let mut seen_values = 0;
+    while seen_values < column.num_values() {
+        let page_header = read_page_header(reader)?;
+        let compressed_size = page_size(
+            "compressed",
+            page_header.compressed_page_size,
+            options.max_page_size,
+        )?;
+        let uncompressed_size = page_size(
+            "uncompressed",
+            page_header.uncompressed_page_size,
+            options.max_page_size,
+        )?;
+        let mut buffer = vec![0; compressed_size];
+        reader.read_exact(&mut buffer)?;
+        if options.verify_crc {
+            verify_crc(&page_header, &buffer)?;
+        }
+
+        let page = match get_page_header(&page_header) {
+            Some(header) => {
+                seen_values += header.num_values() as i64;
+                let num_rows =
+                    page_num_rows(&header, &buffer, compression, uncompressed_size, descriptor)?;
+                CompressedPage::Data(CompressedDataPage::new_read(
+                    header,
+                    buffer,
+                    compression,
+                    uncompressed_size,
+                    None,
+                    descriptor.clone(),
+                    Some(vec![Interval::new(0, num_rows)]),
+                ))
+            }
+            None => {
+                let num_values = page_header
+                    .dictionary_page_header
+                    .as_ref()
+                    .ok_or_else(|| {
+                        Error::OutOfSpec(
+                            "The dictionary page header is missing from its page header"
+                                .to_string(),
+                        )
+                    })?
+                    .num_values;
+                CompressedPage::Dict(CompressedDictPage::new(
+                    buffer,
+                    compression,
+                    uncompressed_size,
+                    num_values.try_into()?,
+                ))
+            }
+        };
+        pages.push(page);
+    }
+    Ok(pages)
+}
+
+/// Returns the number of rows of the data page of `header` and (compressed) `buffer`.
+/// Only the V1 pages of repeated columns are decompressed, to count the rows of their
+/// repetition levels.
+fn page_num_rows(
+    header: &DataPageHeader,
+    buffer: &[u8],
+    compression: Compression,
+    uncompressed_size: usize,
+    descriptor: &Descriptor,
+) -> Result<usize> {
+    match header {
+        DataPageHeader::V2(header) => Ok(header.num_rows.try_into()?),
+        DataPageHeader::V1(header) if descriptor.max_rep_level == 0 => {
+            Ok(header.num_values.try_into()?)
+        }
+        DataPageHeader::V1(header) => {
+            let decompressed;
+            let buffer = if compression != Compression::Uncompressed {
+                let mut output = vec![0; uncompressed_size];
+                let written = decompress(compression, buffer, &mut output)?;
+                if written != uncompressed_size {
+                    return Err(decompressed_size_mismatch(written, uncompressed_size));
+                }
+                decompressed = output;
+                &decompressed
+            } else {
+                buffer
+            };
+            let (rep, _, _) = split_buffer_v1(buffer, true, descriptor.max_def_level > 0);
+            let num_values = header.num_values.try_into()?;
+            let num_bits = get_bit_width(descriptor.max_rep_level);
+            let levels = HybridRleDecoder::try_new(rep, num_bits, num_values)?;
+            // SPEC: a row starts at every repetition level of 0
+            Ok(levels.filter(|level| *level == 0).count())
+        }
+    }
+}
+
+/// Verifies that the CRC32 of `data`, the (compressed) data of a page, matches the checksum
+/// declared in its header, if any.
+pub(super) fn verify_crc(header: &ParquetPageHeader, data: &[u8]) -> Result<()> {
+    if let Some(expected) = header.crc {
+        let crc = crc32fast::hash(data);
+        if crc != expected as u32 {
+            return Err(Error::Corruption(format!(
+                "The CRC32 of the data of a page is {:#010x}, but its header declares {:#010x}",
+                crc, expected as u32
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns a size declared in a page header, erroring if it is negative or larger than
+/// `max_page_size`.
+pub(crate) fn page_size(kind: &str, size: i32, max_page_size: usize) -> Result<usize> {
+    let size: usize = size.try_into().map_err(|_| {
+        Error::OutOfSpec(format!(
+            "The {} size of a page must be positive, but it is {}",
+            kind, size
+        ))
+    })?;
+    check_page_size(kind, size, max_page_size)?;
+    Ok(size)
+}
+
 #[allow(clippy::large_enum_variant)]
 pub(super) enum FinishedPage {
     Data(CompressedDataPage),
@@ -183,7 +359,13 @@ pub(super) fn finish_page(
     current_dictionary: &Option<Arc<dyn DictPage>>,
     descriptor: &Descriptor,
     selected_rows: Option<Vec<Interval>>,
+    options: &ReadOptions,
 ) -> Result<FinishedPage> {
+    let uncompressed_page_size = page_size(
+        "uncompressed",
+        page_header.uncompressed_page_size,
+        options.max_page_size,
+    )?;
     let type_ = page_header.type_.try_into()?;
     match type_ {
         PageType::DictionaryPage => {
@@ -194,11 +376,12 @@ pub(super) fn finish_page(
             let mut dict_page =
                 EncodedDictPage::new(std::mem::take(data), dict_header.num_values as usize);
 
-            let page = read_dict_page(
+            let page = read_dict_page_with_codec(
                 &dict_page,
-                (compression, page_header.uncompressed_page_size as usize),
+                (compression, uncompressed_page_size),
                 is_sorted,
                 descriptor.primitive_type.physical_type,
+                options.codec.as_deref(),
             )?;
             // take the buffer out of the `dict_page` to re-use it
             std::mem::swap(&mut dict_page.buffer, data);
@@ -212,7 +395,7 @@ pub(super) fn finish_page(
                 DataPageHeader::V1(header),
                 std::mem::take(data),
                 compression,
-                page_header.uncompressed_page_size as usize,
+                uncompressed_page_size,
                 current_dictionary.clone(),
                 descriptor.clone(),
                 selected_rows,
@@ -225,7 +408,7 @@ pub(super) fn finish_page(
                 DataPageHeader::V2(header),
                 std::mem::take(data),
                 compression,
-                page_header.uncompressed_page_size as usize,
+                uncompressed_page_size,
                 current_dictionary.clone(),
                 descriptor.clone(),
                 selected_rows,
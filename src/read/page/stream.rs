@@ -5,11 +5,12 @@ use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, Stream};
 use parquet_format_async_temp::thrift::protocol::TCompactInputStreamProtocol;
 
 use crate::compression::Compression;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::metadata::{ColumnChunkMetaData, Descriptor};
 use crate::page::{CompressedDataPage, ParquetPageHeader};
 
-use super::reader::{finish_page, get_page_header, FinishedPage};
+use super::super::ReadOptions;
+use super::reader::{finish_page, get_page_header, page_size, verify_crc, FinishedPage};
 use super::PageFilter;
 
 /// Returns a stream of compressed data pages
@@ -19,6 +20,30 @@ pub async fn get_page_stream<'a, RR: AsyncRead + Unpin + Send + AsyncSeek>(
     buffer: Vec<u8>,
     pages_filter: PageFilter,
 ) -> Result<impl Stream<Item = Result<CompressedDataPage>> + 'a> {
+    get_page_stream_with_options(
+        column_metadata,
+        reader,
+        buffer,
+        pages_filter,
+        ReadOptions::default(),
+    )
+    .await
+}
+
+/// Returns a stream of compressed data pages read according to `options`, like
+/// [`PageReader::new_with_options`](super::PageReader::new_with_options).
+pub async fn get_page_stream_with_options<'a, RR: AsyncRead + Unpin + Send + AsyncSeek>(
+    column_metadata: &'a ColumnChunkMetaData,
+    reader: &'a mut RR,
+    buffer: Vec<u8>,
+    pages_filter: PageFilter,
+    options: ReadOptions,
+) -> Result<impl Stream<Item = Result<CompressedDataPage>> + 'a> {
+    if column_metadata.column_chunk().crypto_metadata.is_some() {
+        return Err(Error::General(
+            "Reading encrypted column chunks is not supported".to_string(),
+        ));
+    }
     let (col_start, _) = column_metadata.byte_range();
     reader.seek(SeekFrom::Start(col_start)).await?;
     Ok(_get_page_stream(
@@ -28,6 +53,7 @@ pub async fn get_page_stream<'a, RR: AsyncRead + Unpin + Send + AsyncSeek>(
         &column_metadata.descriptor().descriptor,
         buffer,
         pages_filter,
+        options,
     ))
 }
 
@@ -38,6 +64,7 @@ fn _get_page_stream<'a, R: AsyncRead + AsyncSeek + Unpin + Send>(
     descriptor: &'a Descriptor,
     mut buffer: Vec<u8>,
     pages_filter: PageFilter,
+    options: ReadOptions,
 ) -> impl Stream<Item = Result<CompressedDataPage>> + 'a {
     let mut seen_values = 0i64;
     let mut current_dictionary = None;
@@ -60,11 +87,18 @@ fn _get_page_stream<'a, R: AsyncRead + AsyncSeek + Unpin + Send>(
             }
 
             // followed by the buffer
-            let read_size = read_size as usize;
+            let read_size = page_size(
+                "compressed",
+                page_header.compressed_page_size,
+                options.max_page_size,
+            )?;
+            buffer.resize(read_size, 0);
             if read_size > 0 {
-                buffer.resize(read_size, 0);
                 reader.read_exact(&mut buffer).await?;
             }
+            if options.verify_crc {
+                verify_crc(&page_header, &buffer)?;
+            }
             let result = finish_page(
                 page_header,
                 &mut buffer,
@@ -72,6 +106,7 @@ fn _get_page_stream<'a, R: AsyncRead + AsyncSeek + Unpin + Send>(
                 &current_dictionary,
                 descriptor,
                 None,
+                &options,
             )?;
 
             match result {
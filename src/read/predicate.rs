@@ -0,0 +1,285 @@
+//! Pruning of row groups with predicates on the statistics of their column chunks.
+use crate::metadata::{get_sort_order, RowGroupMetaData, SortOrder};
+use crate::statistics::{
+    BinaryStatistics, BooleanStatistics, FixedLenStatistics, PrimitiveStatistics, Statistics,
+};
+use crate::types::NativeType;
+
+/// A predicate on the values of the columns of a row group, evaluated on the statistics of
+/// its column chunks.
+pub trait ColumnPredicate {
+    /// Returns whether the column chunk of the column `column_index` whose statistics are
+    /// `stats` might contain values that satisfy the predicate.
+    ///
+    /// It must return `true` when this is unknown, e.g. for columns the predicate is not about.
+    fn might_include(&self, column_index: usize, stats: &dyn Statistics) -> bool;
+}
+
+/// Returns the row groups of `row_groups` that might contain rows satisfying `predicate`.
+///
+/// A row group is excluded iff the predicate excludes one of its column chunks. Column chunks
+/// without (valid) statistics are never excluded.
+pub fn filter_row_groups<'a>(
+    row_groups: &'a [RowGroupMetaData],
+    predicate: &dyn ColumnPredicate,
+) -> Vec<&'a RowGroupMetaData> {
+    row_groups
+        .iter()
+        .filter(|row_group| {
+            row_group
+                .columns()
+                .iter()
+                .enumerate()
+                .all(|(index, column)| match column.statistics() {
+                    Some(Ok(stats)) => predicate.might_include(index, stats.as_ref()),
+                    _ => true,
+                })
+        })
+        .collect()
+}
+
+/// A value that can be compared with the min and max values of [`Statistics`].
+pub trait StatisticsValue: PartialOrd + Sized {
+    /// Returns the (min, max) values of `stats`, or `None` when `stats` are not of this type
+    /// or are not ordered like this type (e.g. `i32` statistics of unsigned integers).
+    fn bounds(stats: &dyn Statistics) -> Option<(Option<Self>, Option<Self>)>;
+}
+
+fn primitive_bounds<T: NativeType>(stats: &dyn Statistics) -> Option<(Option<T>, Option<T>)> {
+    let stats = stats.as_any().downcast_ref::<PrimitiveStatistics<T>>()?;
+    let primitive_type = &stats.primitive_type;
+    let sort_order = get_sort_order(
+        &primitive_type.logical_type,
+        &primitive_type.converted_type,
+        &primitive_type.physical_type,
+    );
+    (sort_order == SortOrder::Signed).then_some((stats.min_value, stats.max_value))
+}
+
+macro_rules! impl_primitive_value {
+    ($type:ty) => {
+        impl StatisticsValue for $type {
+            fn bounds(stats: &dyn Statistics) -> Option<(Option<Self>, Option<Self>)> {
+                primitive_bounds::<$type>(stats)
+            }
+        }
+    };
+}
+
+impl_primitive_value!(i32);
+impl_primitive_value!(i64);
+impl_primitive_value!(f32);
+impl_primitive_value!(f64);
+
+impl StatisticsValue for bool {
+    fn bounds(stats: &dyn Statistics) -> Option<(Option<Self>, Option<Self>)> {
+        let stats = stats.as_any().downcast_ref::<BooleanStatistics>()?;
+        Some((stats.min_value, stats.max_value))
+    }
+}
+
+impl StatisticsValue for Vec<u8> {
+    fn bounds(stats: &dyn Statistics) -> Option<(Option<Self>, Option<Self>)> {
+        let any = stats.as_any();
+        let (primitive_type, min, max) = if let Some(stats) = any.downcast_ref::<BinaryStatistics>()
+        {
+            (&stats.primitive_type, &stats.min_value, &stats.max_value)
+        } else {
+            let stats = any.downcast_ref::<FixedLenStatistics>()?;
+            (&stats.primitive_type, &stats.min_value, &stats.max_value)
+        };
+        let sort_order = get_sort_order(
+            &primitive_type.logical_type,
+            &primitive_type.converted_type,
+            &primitive_type.physical_type,
+        );
+        // byte arrays are compared lexicographically, i.e. in unsigned order
+        (sort_order == SortOrder::Unsigned).then(|| (min.clone(), max.clone()))
+    }
+}
+
+/// Returns whether values between `min` and `max` (inclusive) might be in the bounds of
+/// `stats`. Comparisons with NaN are false, so NaN never excludes a column chunk.
+fn overlaps<T: StatisticsValue>(stats: &dyn Statistics, min: Option<&T>, max: Option<&T>) -> bool {
+    let (stats_min, stats_max) = match T::bounds(stats) {
+        Some(bounds) => bounds,
+        None => return true,
+    };
+    let below = matches!((max, &stats_min), (Some(max), Some(stats_min)) if max < stats_min);
+    let above = matches!((min, &stats_max), (Some(min), Some(stats_max)) if min > stats_max);
+    !(below || above)
+}
+
+/// The predicate `column == value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqPredicate<T> {
+    /// The index of the column in the row group
+    pub column: usize,
+    /// The value the column is compared to
+    pub value: T,
+}
+
+impl<T> EqPredicate<T> {
+    /// Returns a new [`EqPredicate`] of `column == value`.
+    pub fn new(column: usize, value: T) -> Self {
+        Self { column, value }
+    }
+}
+
+impl<T: StatisticsValue> ColumnPredicate for EqPredicate<T> {
+    fn might_include(&self, column_index: usize, stats: &dyn Statistics) -> bool {
+        column_index != self.column || overlaps(stats, Some(&self.value), Some(&self.value))
+    }
+}
+
+/// The predicate `min <= column <= max`, where a bound of `None` is unbounded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangePredicate<T> {
+    /// The index of the column in the row group
+    pub column: usize,
+    /// The inclusive lower bound of the values
+    pub min: Option<T>,
+    /// The inclusive upper bound of the values
+    pub max: Option<T>,
+}
+
+impl<T> RangePredicate<T> {
+    /// Returns a new [`RangePredicate`] of `min <= column <= max`.
+    pub fn new(column: usize, min: Option<T>, max: Option<T>) -> Self {
+        Self { column, min, max }
+    }
+}
+
+impl<T: StatisticsValue> ColumnPredicate for RangePredicate<T> {
+    fn might_include(&self, column_index: usize, stats: &dyn Statistics) -> bool {
+        column_index != self.column || overlaps(stats, self.min.as_ref(), self.max.as_ref())
+    }
+}
+
+/// The predicate `column IS NULL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NullPredicate {
+    /// The index of the column in the row group
+    pub column: usize,
+}
+
+impl NullPredicate {
+    /// Returns a new [`NullPredicate`] of `column IS NULL`.
+    pub fn new(column: usize) -> Self {
+        Self { column }
+    }
+}
+
+impl ColumnPredicate for NullPredicate {
+    fn might_include(&self, column_index: usize, stats: &dyn Statistics) -> bool {
+        column_index != self.column || stats.null_count() != Some(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::{PhysicalType, PrimitiveConvertedType, PrimitiveType};
+
+    fn int32(min: Option<i32>, max: Option<i32>) -> PrimitiveStatistics<i32> {
+        PrimitiveStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32),
+            null_count: Some(0),
+            distinct_count: None,
+            min_value: min,
+            max_value: max,
+        }
+    }
+
+    fn float64(min: f64, max: f64) -> PrimitiveStatistics<f64> {
+        PrimitiveStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Double),
+            null_count: Some(0),
+            distinct_count: None,
+            min_value: Some(min),
+            max_value: Some(max),
+        }
+    }
+
+    #[test]
+    fn eq() {
+        let predicate = EqPredicate::new(0, 42i32);
+        assert!(!predicate.might_include(0, &int32(Some(0), Some(41))));
+        assert!(predicate.might_include(0, &int32(Some(0), Some(42))));
+        assert!(predicate.might_include(0, &int32(Some(42), Some(100))));
+        assert!(!predicate.might_include(0, &int32(Some(43), Some(100))));
+        // missing bounds and other columns
+        assert!(predicate.might_include(0, &int32(None, None)));
+        assert!(predicate.might_include(0, &int32(None, Some(100))));
+        assert!(predicate.might_include(1, &int32(Some(0), Some(41))));
+        // statistics of another type
+        assert!(predicate.might_include(0, &float64(0.0, 1.0)));
+    }
+
+    #[test]
+    fn unsigned() {
+        // -1i32 is u32::MAX: the statistics are not in the order of i32
+        let mut stats = int32(Some(0), Some(-1));
+        stats.primitive_type.converted_type = Some(PrimitiveConvertedType::Uint32);
+        assert!(EqPredicate::new(0, 42i32).might_include(0, &stats));
+    }
+
+    #[test]
+    fn range() {
+        let stats = int32(Some(10), Some(20));
+        let cases = [
+            (None, None, true),
+            (Some(0), Some(9), false),
+            (Some(0), Some(10), true),
+            (Some(15), Some(16), true),
+            (Some(20), None, true),
+            (Some(21), None, false),
+            (None, Some(9), false),
+        ];
+        for (min, max, expected) in cases {
+            let predicate = RangePredicate::new(0, min, max);
+            assert_eq!(
+                predicate.might_include(0, &stats),
+                expected,
+                "{:?}",
+                predicate
+            );
+        }
+    }
+
+    #[test]
+    fn nan() {
+        let stats = float64(1.0, 2.0);
+        assert!(!EqPredicate::new(0, 3.0f64).might_include(0, &stats));
+        // NaN is not ordered: it never excludes a column chunk
+        assert!(EqPredicate::new(0, f64::NAN).might_include(0, &stats));
+        assert!(RangePredicate::new(0, Some(f64::NAN), None).might_include(0, &stats));
+        assert!(EqPredicate::new(0, 3.0f64).might_include(0, &float64(f64::NAN, f64::NAN)));
+        assert!(EqPredicate::new(0, 3.0f64).might_include(0, &float64(1.0, f64::NAN)));
+    }
+
+    #[test]
+    fn binary() {
+        let stats = BinaryStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray),
+            null_count: Some(1),
+            distinct_count: None,
+            min_value: Some(b"b".to_vec()),
+            max_value: Some(b"d".to_vec()),
+        };
+        assert!(!EqPredicate::new(0, b"a".to_vec()).might_include(0, &stats));
+        assert!(EqPredicate::new(0, b"c".to_vec()).might_include(0, &stats));
+        assert!(!EqPredicate::new(0, b"da".to_vec()).might_include(0, &stats));
+        assert!(NullPredicate::new(0).might_include(0, &stats));
+    }
+
+    #[test]
+    fn null() {
+        let mut stats = int32(Some(0), Some(1));
+        assert!(!NullPredicate::new(0).might_include(0, &stats));
+        stats.null_count = Some(2);
+        assert!(NullPredicate::new(0).might_include(0, &stats));
+        stats.null_count = None;
+        assert!(NullPredicate::new(0).might_include(0, &stats));
+    }
+}
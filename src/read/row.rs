@@ -0,0 +1,639 @@
+//! APIs to read a row group as an iterator of rows with dynamically-typed values.
+//!
+//! This is intended for data inspection; it decodes the whole row group in memory and
+//! is therefore not suitable for performance-sensitive workloads.
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::sync::Arc;
+
+use crate::encoding::{
+    byte_stream_split, delta_bitpacked, delta_byte_array, delta_length_byte_array,
+    hybrid_rle::{BitmapIter, HybridRleDecoder},
+    plain_byte_array::{BinaryIter, FixedLenBinaryIter},
+};
+use crate::error::{Error, Result};
+use crate::metadata::Descriptor;
+use crate::metadata::{ColumnDescriptor, FileMetaData};
+use crate::page::{
+    split_page_buffer, BinaryPageDict, DataPage, DataPageHeader, DictPage,
+    FixedLenByteArrayPageDict, PrimitivePageDict,
+};
+use crate::parquet_bridge::{DataPageHeaderExt, Encoding, Repetition};
+use crate::schema::types::{ParquetType, PhysicalType};
+use crate::types::{decode, NativeType};
+use crate::FallibleStreamingIterator;
+
+use super::levels::get_bit_width;
+use super::{get_page_iterator, BasicDecompressor};
+
+/// A dynamically-typed value of a parquet column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicValue {
+    /// A null value
+    Null,
+    /// A boolean value
+    Boolean(bool),
+    /// An `INT32` value
+    Int32(i32),
+    /// An `INT64` value
+    Int64(i64),
+    /// An `INT96` value
+    Int96([u32; 3]),
+    /// A `FLOAT` value
+    Float(f32),
+    /// A `DOUBLE` value
+    Double(f64),
+    /// A `BYTE_ARRAY` value
+    Binary(Vec<u8>),
+    /// A `FIXED_LEN_BYTE_ARRAY` value
+    FixedLenBinary(Vec<u8>),
+    /// The values of a repeated field
+    List(Vec<DynamicValue>),
+}
+
+/// A row, mapping the dotted path of each column to its value.
+pub type DynamicRow = HashMap<String, DynamicValue>;
+
+/// An [`Iterator`] of [`DynamicRow`] over a row group.
+#[derive(Debug)]
+pub struct RowGroupRowIterator {
+    names: Vec<String>,
+    columns: Vec<std::vec::IntoIter<DynamicValue>>,
+}
+
+impl RowGroupRowIterator {
+    /// Reads and decodes all columns of the row group `row_group` of `metadata`,
+    /// returning a [`RowGroupRowIterator`] over its rows.
+    /// # Error
+    /// Errors iff the pages can't be read, decompressed or decoded.
+    pub fn try_new<R: Read + Seek>(
+        reader: &mut R,
+        metadata: &FileMetaData,
+        row_group: usize,
+    ) -> Result<Self> {
+        let row_group = metadata.row_groups.get(row_group).ok_or_else(|| {
+            Error::General(format!(
+                "The file only has {} row groups",
+                metadata.row_groups.len()
+            ))
+        })?;
+
+        let mut names = Vec::with_capacity(row_group.columns().len());
+        let mut columns = Vec::with_capacity(row_group.columns().len());
+        for column in row_group.columns() {
+            let descriptor = column.descriptor();
+            let mut assembler = RowAssembler::try_new(descriptor)?;
+
+            let pages = get_page_iterator(column, &mut *reader, None, vec![])?;
+            let mut pages = BasicDecompressor::new(pages, vec![]);
+
+            let mut values = vec![];
+            while let Some(page) = pages.next()? {
+                assembler.push_page(page, &mut values)?;
+            }
+            assembler.finish(&mut values);
+            if values.len() != row_group.num_rows() {
+                return Err(Error::OutOfSpec(format!(
+                    "The column {} has {} rows but its row group has {}",
+                    descriptor.path_in_schema.join("."),
+                    values.len(),
+                    row_group.num_rows()
+                )));
+            }
+
+            names.push(descriptor.path_in_schema.join("."));
+            columns.push(values.into_iter());
+        }
+
+        Ok(Self { names, columns })
+    }
+}
+
+impl Iterator for RowGroupRowIterator {
+    type Item = DynamicRow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.names
+            .iter()
+            .zip(self.columns.iter_mut())
+            .map(|(name, values)| values.next().map(|value| (name.clone(), value)))
+            .collect::<Option<DynamicRow>>()
+            .filter(|row| !row.is_empty())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.columns
+            .first()
+            .map(|x| x.size_hint())
+            .unwrap_or((0, Some(0)))
+    }
+}
+
+/// Returns the definition level of each repeated node in the path of `descriptor`,
+/// from the outermost to the innermost.
+pub(super) fn repeated_def_levels(descriptor: &ColumnDescriptor) -> Result<Vec<u32>> {
+    let mut levels = vec![];
+    let mut def_level = 0;
+    let mut current = Some(&descriptor.base_type);
+    for name in descriptor.path_in_schema.iter().skip(1) {
+        let type_ = current.ok_or_else(|| {
+            Error::OutOfSpec("A column's path is inconsistent with its schema".to_string())
+        })?;
+        match type_.get_field_info().repetition {
+            Repetition::Optional => def_level += 1,
+            Repetition::Repeated => {
+                def_level += 1;
+                levels.push(def_level);
+            }
+            Repetition::Required => {}
+        }
+        current = match type_ {
+            ParquetType::GroupType { fields, .. } => fields.iter().find(|f| f.name() == name),
+            ParquetType::PrimitiveType(_) => None,
+        };
+    }
+    let type_ = current.ok_or_else(|| {
+        Error::OutOfSpec("A column's path is inconsistent with its schema".to_string())
+    })?;
+    if type_.get_field_info().repetition == Repetition::Repeated {
+        levels.push(def_level + 1);
+    }
+    Ok(levels)
+}
+
+fn decode_level(buffer: &[u8], max_level: i16, num_values: usize) -> Result<Vec<u32>> {
+    Ok(if max_level == 0 {
+        vec![0; num_values]
+    } else {
+        HybridRleDecoder::try_new(buffer, get_bit_width(max_level), num_values)?.collect()
+    })
+}
+
+/// Decodes the (repetition, definition) levels of the (uncompressed) `buffer` of a page
+/// with `header`.
+pub(crate) fn decode_levels(
+    header: &DataPageHeader,
+    buffer: &[u8],
+    descriptor: &Descriptor,
+) -> Result<(Vec<u32>, Vec<u32>)> {
+    let (rep, def, _) = split_page_buffer(header, buffer, descriptor);
+    let num_values = header.num_values();
+    let rep = decode_level(rep, descriptor.max_rep_level, num_values)?;
+    let def = decode_level(def, descriptor.max_def_level, num_values)?;
+    if rep.len() != num_values || def.len() != num_values {
+        return Err(Error::OutOfSpec(
+            "The number of levels of a page does not match its number of values".to_string(),
+        ));
+    }
+    Ok((rep, def))
+}
+
+/// Decodes `num_values` non-null values of the (uncompressed) `buffer` of a page with `header`.
+pub(crate) fn decode_values(
+    header: &DataPageHeader,
+    buffer: &[u8],
+    descriptor: &Descriptor,
+    dictionary_page: Option<&Arc<dyn DictPage>>,
+    num_values: usize,
+) -> Result<Vec<DynamicValue>> {
+    let physical_type = descriptor.primitive_type.physical_type;
+    let (_, _, values) = split_page_buffer(header, buffer, descriptor);
+
+    let encoding = match header {
+        DataPageHeader::V1(d) => d.encoding(),
+        DataPageHeader::V2(d) => d.encoding(),
+    };
+    let values = match encoding {
+        Encoding::Plain => match physical_type {
+            PhysicalType::Boolean => BitmapIter::try_new(values, num_values)?
+                .map(DynamicValue::Boolean)
+                .collect(),
+            PhysicalType::Int32 => native(values, DynamicValue::Int32),
+            PhysicalType::Int64 => native(values, DynamicValue::Int64),
+            PhysicalType::Int96 => native(values, DynamicValue::Int96),
+            PhysicalType::Float => native(values, DynamicValue::Float),
+            PhysicalType::Double => native(values, DynamicValue::Double),
+            PhysicalType::ByteArray => BinaryIter::new(values, Some(num_values))
+                .map(|x| DynamicValue::Binary(x.to_vec()))
+                .collect(),
+            PhysicalType::FixedLenByteArray(size) => {
+                FixedLenBinaryIter::try_new(values, size, Some(num_values))?
+                    .map(|x| DynamicValue::FixedLenBinary(x.to_vec()))
+                    .collect()
+            }
+        },
+        Encoding::PlainDictionary | Encoding::RleDictionary => {
+            let dict = dictionary_page.ok_or_else(|| {
+                Error::OutOfSpec("A dictionary-encoded page requires a dictionary".to_string())
+            })?;
+            let bit_width = *values.first().ok_or_else(|| {
+                Error::OutOfSpec("A dictionary-encoded page must contain a bit width".to_string())
+            })?;
+            let indices = HybridRleDecoder::try_new(&values[1..], bit_width as u32, num_values)?
+                .map(|x| x as usize);
+
+            let dict = dict.as_any();
+            match physical_type {
+                PhysicalType::Int32 => dict_native(dict, indices, DynamicValue::Int32)?,
+                PhysicalType::Int64 => dict_native(dict, indices, DynamicValue::Int64)?,
+                PhysicalType::Int96 => dict_native(dict, indices, DynamicValue::Int96)?,
+                PhysicalType::Float => dict_native(dict, indices, DynamicValue::Float)?,
+                PhysicalType::Double => dict_native(dict, indices, DynamicValue::Double)?,
+                PhysicalType::ByteArray => {
+                    let dict = downcast_dict::<BinaryPageDict>(dict)?;
+                    indices
+                        .map(|index| dict.value(index).map(|x| DynamicValue::Binary(x.to_vec())))
+                        .collect::<Result<_>>()?
+                }
+                PhysicalType::FixedLenByteArray(_) => {
+                    let dict = downcast_dict::<FixedLenByteArrayPageDict>(dict)?;
+                    indices
+                        .map(|index| {
+                            dict.value(index)
+                                .map(|x| DynamicValue::FixedLenBinary(x.to_vec()))
+                        })
+                        .collect::<Result<_>>()?
+                }
+                PhysicalType::Boolean => {
+                    return Err(Error::OutOfSpec(
+                        "Boolean pages can't be dictionary-encoded".to_string(),
+                    ))
+                }
+            }
+        }
+        Encoding::Rle => match physical_type {
+            PhysicalType::Boolean => crate::deserialize::rle_decoder(values, num_values)?
+                .map(|x| DynamicValue::Boolean(x == 1))
+                .collect(),
+            other => {
+                return Err(Error::OutOfSpec(format!(
+                    "The RLE encoding is not valid for the values of {:?}",
+                    other
+                )))
+            }
+        },
+        Encoding::DeltaBinaryPacked => {
+            let values = delta_bitpacked::Decoder::try_new(values)?;
+            match physical_type {
+                PhysicalType::Int32 => values
+                    .map(|x| x.map(|x| DynamicValue::Int32(x as i32)))
+                    .collect::<Result<_>>()?,
+                PhysicalType::Int64 => values
+                    .map(|x| x.map(DynamicValue::Int64))
+                    .collect::<Result<_>>()?,
+                other => {
+                    return Err(Error::OutOfSpec(format!(
+                        "The delta binary packed encoding is not valid for {:?}",
+                        other
+                    )))
+                }
+            }
+        }
+        Encoding::DeltaLengthByteArray => match physical_type {
+            PhysicalType::ByteArray => delta_length_byte_array::BinaryDecoder::try_new(values)?
+                .map(|x| DynamicValue::Binary(x.to_vec()))
+                .collect(),
+            other => {
+                return Err(Error::OutOfSpec(format!(
+                    "The delta length byte array encoding is not valid for {:?}",
+                    other
+                )))
+            }
+        },
+        Encoding::DeltaByteArray => match physical_type {
+            PhysicalType::ByteArray => delta_byte_array::BinaryDecoder::try_new(values)?
+                .map(DynamicValue::Binary)
+                .collect(),
+            other => {
+                return Err(Error::OutOfSpec(format!(
+                    "The delta byte array encoding is not valid for {:?}",
+                    other
+                )))
+            }
+        },
+        Encoding::ByteStreamSplit => match physical_type {
+            PhysicalType::Float => byte_stream_split(values, DynamicValue::Float)?,
+            PhysicalType::Double => byte_stream_split(values, DynamicValue::Double)?,
+            PhysicalType::FixedLenByteArray(size) => {
+                byte_stream_split::FixedLenDecoder::try_new(values, size)?
+                    .map(DynamicValue::FixedLenBinary)
+                    .collect()
+            }
+            other => {
+                return Err(Error::OutOfSpec(format!(
+                    "The byte stream split encoding is not valid for {:?}",
+                    other
+                )))
+            }
+        },
+        other => {
+            return Err(Error::General(format!(
+                "Reading rows from pages encoded with {:?} is not supported",
+                other
+            )))
+        }
+    };
+    Ok(values)
+}
+
+fn native<T: NativeType>(values: &[u8], f: fn(T) -> DynamicValue) -> Vec<DynamicValue> {
+    values
+        .chunks_exact(std::mem::size_of::<T>())
+        .map(|x| f(decode::<T>(x)))
+        .collect()
+}
+
+fn byte_stream_split<T: NativeType>(
+    values: &[u8],
+    f: fn(T) -> DynamicValue,
+) -> Result<Vec<DynamicValue>> {
+    Ok(byte_stream_split::Decoder::try_new(values)?
+        .map(f)
+        .collect())
+}
+
+/// Returns the dictionary `dict` as a `T`.
+/// # Error
+/// Errors iff the dictionary is not a `T`, i.e. it was not read for the column's physical type.
+fn downcast_dict<T: 'static>(dict: &dyn std::any::Any) -> Result<&T> {
+    dict.downcast_ref::<T>().ok_or_else(|| {
+        Error::OutOfSpec(
+            "The dictionary of a page does not match the physical type of its column".to_string(),
+        )
+    })
+}
+
+fn dict_native<T: NativeType>(
+    dict: &dyn std::any::Any,
+    indices: impl Iterator<Item = usize>,
+    f: fn(T) -> DynamicValue,
+) -> Result<Vec<DynamicValue>> {
+    let dict = downcast_dict::<PrimitivePageDict<T>>(dict)?.values();
+    indices
+        .map(|index| {
+            dict.get(index).copied().map(f).ok_or_else(|| {
+                Error::OutOfSpec("A dictionary index is larger than the dictionary".to_string())
+            })
+        })
+        .collect()
+}
+
+/// Returns the item of a list whose definition level is `def_level` when the list's items are
+/// defined at `item_def_level`: `None` when the item is defined, `Null` or an empty list otherwise.
+#[inline]
+fn undefined_item(def_level: u32, item_def_level: u32) -> Option<DynamicValue> {
+    if def_level >= item_def_level {
+        None
+    } else if def_level + 1 == item_def_level {
+        Some(DynamicValue::List(vec![]))
+    } else {
+        Some(DynamicValue::Null)
+    }
+}
+
+/// Closes all open lists deeper than `depth`, attaching them to their parents.
+fn close_lists(stack: &mut [Option<Vec<DynamicValue>>], depth: usize) {
+    for d in (depth + 1..stack.len()).rev() {
+        if let Some(items) = stack[d].take() {
+            if let Some(parent) = stack[d - 1].as_mut() {
+                parent.push(DynamicValue::List(items))
+            }
+        }
+    }
+}
+
+/// Assembles the leaf values of a column into one [`DynamicValue`] per row, according to
+/// the Dremel (repetition, definition) levels of its pages.
+///
+/// The values of a row may span several (v1) pages: the last row of a page is only emitted
+/// once the next page starts a new row, or by [`RowAssembler::finish`].
+#[derive(Debug)]
+pub(super) struct RowAssembler {
+    /// The definition level of each repeated node of the column's path
+    repeated_def_levels: Vec<u32>,
+    max_def_level: u32,
+    /// The open (nested) lists of the current row
+    stack: Vec<Option<Vec<DynamicValue>>>,
+    /// The value of the current row when it is not a (open) list
+    current: Option<DynamicValue>,
+}
+
+impl RowAssembler {
+    /// Returns a new [`RowAssembler`] of the column `descriptor`.
+    /// # Error
+    /// Errors iff the path of `descriptor` is inconsistent with its schema.
+    pub(super) fn try_new(descriptor: &ColumnDescriptor) -> Result<Self> {
+        Ok(Self::new(
+            repeated_def_levels(descriptor)?,
+            descriptor.descriptor.max_def_level as u32,
+        ))
+    }
+
+    fn new(repeated_def_levels: Vec<u32>, max_def_level: u32) -> Self {
+        let stack = vec![None; repeated_def_levels.len()];
+        Self {
+            repeated_def_levels,
+            max_def_level,
+            stack,
+            current: None,
+        }
+    }
+
+    /// Decodes `page` and pushes its complete rows to `rows`.
+    pub(super) fn push_page(
+        &mut self,
+        page: &DataPage,
+        rows: &mut Vec<DynamicValue>,
+    ) -> Result<()> {
+        let (rep_levels, def_levels) =
+            decode_levels(page.header(), page.buffer(), &page.descriptor)?;
+        let num_valid = def_levels
+            .iter()
+            .filter(|x| **x == self.max_def_level)
+            .count();
+        let leaves = decode_values(
+            page.header(),
+            page.buffer(),
+            &page.descriptor,
+            page.dictionary_page(),
+            num_valid,
+        )?;
+        self.push(&rep_levels, &def_levels, leaves, rows)
+    }
+
+    /// Pushes the last row, if any, to `rows`.
+    pub(super) fn finish(&mut self, rows: &mut Vec<DynamicValue>) {
+        if self.stack.is_empty() {
+            return;
+        }
+        close_lists(&mut self.stack, 0);
+        if let Some(row) = self.current.take() {
+            rows.push(row);
+        } else if let Some(items) = self.stack[0].take() {
+            rows.push(DynamicValue::List(items));
+        }
+    }
+
+    fn push(
+        &mut self,
+        rep_levels: &[u32],
+        def_levels: &[u32],
+        leaves: Vec<DynamicValue>,
+        rows: &mut Vec<DynamicValue>,
+    ) -> Result<()> {
+        let max_def_level = self.max_def_level;
+        let mut leaves = leaves.into_iter();
+        let mut next_leaf = |def_level: u32| {
+            if def_level == max_def_level {
+                leaves.next().ok_or_else(|| {
+                    Error::OutOfSpec(
+                        "A page has less values than its definition levels".to_string(),
+                    )
+                })
+            } else {
+                Ok(DynamicValue::Null)
+            }
+        };
+
+        if self.repeated_def_levels.is_empty() {
+            for &def_level in def_levels {
+                rows.push(next_leaf(def_level)?);
+            }
+            return Ok(());
+        }
+
+        let max_rep_level = self.repeated_def_levels.len();
+        for (&rep_level, &def_level) in rep_levels.iter().zip(def_levels.iter()) {
+            let rep_level = rep_level as usize;
+            if rep_level > max_rep_level {
+                return Err(Error::OutOfSpec(
+                    "A repetition level is larger than the column's maximum".to_string(),
+                ));
+            }
+            let mut depth = if rep_level == 0 {
+                // a new row
+                self.finish(rows);
+
+                if let Some(item) = undefined_item(def_level, self.repeated_def_levels[0]) {
+                    self.current = Some(item);
+                    continue;
+                }
+                self.stack[0] = Some(vec![]);
+                0
+            } else {
+                close_lists(&mut self.stack, rep_level - 1);
+                rep_level - 1
+            };
+
+            // descend through the nested lists, opening them until the leaf
+            loop {
+                let item = if depth + 1 == max_rep_level {
+                    Some(next_leaf(def_level)?)
+                } else {
+                    undefined_item(def_level, self.repeated_def_levels[depth + 1])
+                };
+                if let Some(item) = item {
+                    self.stack[depth]
+                        .as_mut()
+                        .ok_or_else(|| {
+                            Error::OutOfSpec(
+                                "A repetition level refers to a list that does not exist"
+                                    .to_string(),
+                            )
+                        })?
+                        .push(item);
+                    break;
+                }
+                depth += 1;
+                self.stack[depth] = Some(vec![]);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assemble_list() -> Result<()> {
+        // optional group a (LIST) { repeated group list { optional int32 element; } }
+        // [[0, null], null, [], [1]]
+        let rep_levels = [0, 1, 0, 0, 0];
+        let def_levels = [3, 2, 0, 1, 3];
+        let leaves = vec![DynamicValue::Int32(0), DynamicValue::Int32(1)];
+
+        let mut rows = vec![];
+        let mut assembler = RowAssembler::new(vec![2], 3);
+        assembler.push(&rep_levels, &def_levels, leaves, &mut rows)?;
+        assembler.finish(&mut rows);
+
+        use DynamicValue::*;
+        assert_eq!(
+            rows,
+            vec![
+                List(vec![Int32(0), Null]),
+                Null,
+                List(vec![]),
+                List(vec![Int32(1)]),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn assemble_nested_list() -> Result<()> {
+        // repeated group a { repeated int32 b; }
+        // [[0, 1], [2]], [], [[], [3]]
+        let rep_levels = [0, 2, 1, 0, 0, 1];
+        let def_levels = [2, 2, 2, 0, 1, 2];
+        let leaves = (0..4).map(DynamicValue::Int32).collect();
+
+        let mut rows = vec![];
+        let mut assembler = RowAssembler::new(vec![1, 2], 2);
+        assembler.push(&rep_levels, &def_levels, leaves, &mut rows)?;
+        assembler.finish(&mut rows);
+
+        use DynamicValue::*;
+        assert_eq!(
+            rows,
+            vec![
+                List(vec![List(vec![Int32(0), Int32(1)]), List(vec![Int32(2)])]),
+                List(vec![]),
+                List(vec![List(vec![]), List(vec![Int32(3)])]),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn assemble_across_pages() -> Result<()> {
+        // optional group a (LIST) { repeated group list { optional int32 element; } }
+        // [[0, 1, 2], [3]], where the first list spans two pages
+        let mut rows = vec![];
+        let mut assembler = RowAssembler::new(vec![2], 3);
+        let leaves = vec![DynamicValue::Int32(0), DynamicValue::Int32(1)];
+        assembler.push(&[0, 1], &[3, 3], leaves, &mut rows)?;
+        assert!(rows.is_empty());
+        let leaves = vec![DynamicValue::Int32(2), DynamicValue::Int32(3)];
+        assembler.push(&[1, 0], &[3, 3], leaves, &mut rows)?;
+        assembler.finish(&mut rows);
+
+        use DynamicValue::*;
+        assert_eq!(
+            rows,
+            vec![
+                List(vec![Int32(0), Int32(1), Int32(2)]),
+                List(vec![Int32(3)]),
+            ]
+        );
+
+        // a page can't continue a row that was not started
+        let mut assembler = RowAssembler::new(vec![2], 3);
+        assert!(assembler
+            .push(&[1], &[3], vec![Int32(0)], &mut rows)
+            .is_err());
+        Ok(())
+    }
+}
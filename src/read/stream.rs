@@ -1,11 +1,9 @@
 use std::io::SeekFrom;
 
-use futures::{io::Cursor, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
-use parquet_format_async_temp::thrift::protocol::TCompactInputStreamProtocol;
-use parquet_format_async_temp::FileMetaData as TFileMetaData;
+use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 
-use super::super::{metadata::FileMetaData, DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, PARQUET_MAGIC};
-use super::metadata::metadata_len;
+use super::super::{metadata::FileMetaData, DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE};
+use super::metadata::{deserialize_metadata, footer_metadata_len};
 use crate::error::{Error, Result};
 
 async fn stream_len(
@@ -23,8 +21,17 @@ async fn stream_len(
     Ok(len)
 }
 
-/// Asynchronously reads the files' metadata
-pub async fn read_metadata<R: AsyncRead + AsyncSeek + Send + std::marker::Unpin>(
+/// Asynchronously reads the files' metadata.
+///
+/// Like [`read_metadata`](super::read_metadata), this seeks to and reads the end of the file
+/// and, only when the metadata is larger than the bytes read, the missing bytes before them.
+/// The metadata is deserialized in memory, so that no small reads reach the reader.
+///
+/// The reader is only required to implement [`futures::AsyncRead`] and [`futures::AsyncSeek`];
+/// readers of `tokio` can be used via `TokioReader` (feature `tokio`) and readers of other
+/// runtimes via a compatibility layer. Partial reads from the transport are handled by this
+/// function.
+pub async fn read_metadata<R: AsyncRead + AsyncSeek + std::marker::Unpin>(
     reader: &mut R,
 ) -> Result<FileMetaData> {
     // check file is large enough to hold footer
@@ -43,45 +50,19 @@ pub async fn read_metadata<R: AsyncRead + AsyncSeek + Send + std::marker::Unpin>
     let mut default_len_end_buf = vec![0; default_end_len];
     reader.read_exact(&mut default_len_end_buf).await?;
 
-    // check this is indeed a parquet file
-    if default_len_end_buf[default_end_len - 4..] != PARQUET_MAGIC {
-        return Err(general_err!("Invalid Parquet file. Corrupt footer"));
-    }
-
-    let metadata_len = metadata_len(&default_len_end_buf, default_end_len);
-
-    if metadata_len < 0 {
-        return Err(general_err!(
-            "Invalid Parquet file. Metadata length is less than zero ({})",
-            metadata_len
-        ));
-    }
-    let footer_metadata_len = FOOTER_SIZE + metadata_len as u64;
+    let footer_metadata_len = footer_metadata_len(&default_len_end_buf, file_size)? as usize;
 
-    let metadata = if footer_metadata_len > file_size {
-        return Err(general_err!(
-            "Invalid Parquet file. Metadata start is less than zero ({})",
-            file_size as i64 - footer_metadata_len as i64
-        ));
-    } else if footer_metadata_len < DEFAULT_FOOTER_READ_SIZE {
+    if footer_metadata_len <= default_end_len {
         // the whole metadata is in the bytes we already read
-        // build up the reader covering the entire metadata
-        let mut reader = Cursor::new(default_len_end_buf);
-        reader
-            .seek(SeekFrom::End(-(footer_metadata_len as i64)))
-            .await?;
-
-        let mut prot = TCompactInputStreamProtocol::new(reader);
-        TFileMetaData::stream_from_in_protocol(&mut prot).await?
+        deserialize_metadata(&default_len_end_buf[default_end_len - footer_metadata_len..])
     } else {
-        // the end of file read by default is not long enough, read again including all metadata.
+        // the end of file read by default is not long enough: read the missing bytes before it
         reader
             .seek(SeekFrom::End(-(footer_metadata_len as i64)))
             .await?;
-
-        let mut prot = TCompactInputStreamProtocol::new(reader);
-        TFileMetaData::stream_from_in_protocol(&mut prot).await?
-    };
-
-    FileMetaData::try_from_thrift(metadata)
+        let mut buffer = vec![0; footer_metadata_len - default_end_len];
+        reader.read_exact(&mut buffer).await?;
+        buffer.extend_from_slice(&default_len_end_buf);
+        deserialize_metadata(&buffer)
+    }
 }
@@ -0,0 +1,231 @@
+use std::io::{BufRead, BufReader, Read};
+use std::marker::PhantomData;
+
+use crate::compression::{Compression, DecompressReader};
+use crate::encoding::Encoding;
+use crate::error::{Error, Result};
+use crate::metadata::Descriptor;
+use crate::page::{CompressedDataPage, DataPageHeader, DataPageHeaderExt};
+use crate::types::NativeType;
+
+/// A decompressor of [`CompressedDataPage`] that, contrarily to
+/// [`BasicDecompressor`](super::BasicDecompressor), does not decompress a page into a buffer
+/// of its whole uncompressed size. Instead, the values of each page are decompressed as they
+/// are read from a [`StreamingPage`], `buffer_size` bytes at a time.
+///
+/// Only pages compressed with a codec that supports it can be decompressed this way
+/// (see [`DecompressReader`]).
+pub struct StreamingDecompressor<I: Iterator<Item = Result<CompressedDataPage>>> {
+    iter: I,
+    buffer_size: usize,
+    current: Option<CompressedDataPage>,
+}
+
+impl<I: Iterator<Item = Result<CompressedDataPage>>> StreamingDecompressor<I> {
+    /// Returns a new [`StreamingDecompressor`] that decompresses at most `buffer_size` bytes
+    /// of a page at a time.
+    pub fn new(iter: I, buffer_size: usize) -> Self {
+        Self {
+            iter,
+            buffer_size,
+            current: None,
+        }
+    }
+
+    /// Returns the next page, whose values are decompressed as they are read.
+    /// # Errors
+    /// Errors iff the page cannot be read, its compression cannot be decompressed incrementally,
+    /// or its levels are out of spec.
+    pub fn next_page(&mut self) -> Result<Option<StreamingPage<'_>>> {
+        self.current = self.iter.next().transpose()?;
+        self.current
+            .as_ref()
+            .map(|page| StreamingPage::try_new(page, self.buffer_size))
+            .transpose()
+    }
+
+    /// Returns the iterator of [`CompressedDataPage`], consuming itself.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+/// A data page whose repetition and definition levels are decompressed and whose values are
+/// decompressed as they are read, from [`StreamingPage::values`] or
+/// [`StreamingPage::plain_values`].
+pub struct StreamingPage<'a> {
+    page: &'a CompressedDataPage,
+    rep_levels: Vec<u8>,
+    def_levels: Vec<u8>,
+    values: BufReader<DecompressReader<'a>>,
+}
+
+impl<'a> StreamingPage<'a> {
+    fn try_new(page: &'a CompressedDataPage, buffer_size: usize) -> Result<Self> {
+        let (rep_levels, def_levels, values) = match page.header() {
+            DataPageHeader::V1(_) => {
+                let reader = DecompressReader::try_new(page.compression(), page.buffer())?;
+                let mut reader = BufReader::with_capacity(buffer_size, reader);
+
+                let max_length = page.uncompressed_size();
+                let rep_levels = if page.descriptor.max_rep_level > 0 {
+                    read_levels(&mut reader, max_length)?
+                } else {
+                    vec![]
+                };
+                let def_levels = if page.descriptor.max_def_level > 0 {
+                    read_levels(&mut reader, max_length)?
+                } else {
+                    vec![]
+                };
+                (rep_levels, def_levels, reader)
+            }
+            DataPageHeader::V2(header) => {
+                // the levels of v2 pages are not compressed
+                let rep_length = usize::try_from(header.repetition_levels_byte_length);
+                let def_length = usize::try_from(header.definition_levels_byte_length);
+                let (rep_length, levels_length) = rep_length
+                    .ok()
+                    .zip(def_length.ok())
+                    .and_then(|(rep, def)| rep.checked_add(def).map(|levels| (rep, levels)))
+                    .filter(|(_, levels)| *levels <= page.buffer().len())
+                    .ok_or_else(|| {
+                        Error::OutOfSpec(format!(
+                            "The levels of a data page v2 ({} and {} bytes) must fit in its buffer of {} bytes",
+                            header.repetition_levels_byte_length,
+                            header.definition_levels_byte_length,
+                            page.buffer().len()
+                        ))
+                    })?;
+                let (levels, values) = page.buffer().split_at(levels_length);
+                let (rep_levels, def_levels) = levels.split_at(rep_length);
+
+                let compression = if header.is_compressed.unwrap_or(true) {
+                    page.compression()
+                } else {
+                    Compression::Uncompressed
+                };
+                let reader = DecompressReader::try_new(compression, values)?;
+                (
+                    rep_levels.to_vec(),
+                    def_levels.to_vec(),
+                    BufReader::with_capacity(buffer_size, reader),
+                )
+            }
+        };
+        Ok(Self {
+            page,
+            rep_levels,
+            def_levels,
+            values,
+        })
+    }
+
+    pub fn header(&self) -> &DataPageHeader {
+        self.page.header()
+    }
+
+    pub fn descriptor(&self) -> &Descriptor {
+        &self.page.descriptor
+    }
+
+    pub fn num_values(&self) -> usize {
+        self.page.num_values()
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        match self.page.header() {
+            DataPageHeader::V1(d) => d.encoding(),
+            DataPageHeader::V2(d) => d.encoding(),
+        }
+    }
+
+    /// The encoded repetition levels of this page.
+    pub fn rep_levels(&self) -> &[u8] {
+        &self.rep_levels
+    }
+
+    /// The encoded definition levels of this page.
+    pub fn def_levels(&self) -> &[u8] {
+        &self.def_levels
+    }
+
+    /// A reader of the encoded values of this page, which decompresses them as they are read.
+    pub fn values(&mut self) -> &mut BufReader<DecompressReader<'a>> {
+        &mut self.values
+    }
+
+    /// Returns an iterator of the plain-encoded values of this page.
+    /// The iterator errors if the page is not plain-encoded or its values are out of spec.
+    pub fn plain_values<T: NativeType>(&mut self) -> PlainValues<'_, 'a, T> {
+        let is_plain = self.encoding() == Encoding::Plain;
+        PlainValues {
+            reader: &mut self.values,
+            is_plain,
+            is_finished: false,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Reads the levels of a data page v1, prefixed by their length as a little-endian `u32`.
+fn read_levels<R: Read>(reader: &mut R, max_length: usize) -> Result<Vec<u8>> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length) as usize;
+    if length > max_length {
+        return Err(Error::OutOfSpec(format!(
+            "The levels of a page have {} bytes, but the page only has {} bytes",
+            length, max_length
+        )));
+    }
+    let mut levels = vec![0; length];
+    reader.read_exact(&mut levels)?;
+    Ok(levels)
+}
+
+/// An iterator of the plain-encoded values of a [`StreamingPage`], decoded as they are
+/// decompressed.
+pub struct PlainValues<'b, 'a, T: NativeType> {
+    reader: &'b mut BufReader<DecompressReader<'a>>,
+    is_plain: bool,
+    is_finished: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<'b, 'a, T: NativeType> PlainValues<'b, 'a, T> {
+    fn next_value(&mut self) -> Result<Option<T>> {
+        if !self.is_plain {
+            return Err(Error::General(
+                "Only plain-encoded values can be read from a streaming page".to_string(),
+            ));
+        }
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(None);
+        }
+
+        // the largest native type, `[u32; 3]`, has 12 bytes
+        let mut bytes = [0; 12];
+        let bytes = &mut bytes[..std::mem::size_of::<T::Bytes>()];
+        self.reader.read_exact(bytes).map_err(|_| {
+            Error::OutOfSpec(
+                "The values of a page are not a multiple of the size of its type".to_string(),
+            )
+        })?;
+        Ok(Some(T::from_le_bytes((&*bytes).try_into()?)))
+    }
+}
+
+impl<'b, 'a, T: NativeType> Iterator for PlainValues<'b, 'a, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished {
+            return None;
+        }
+        let value = self.next_value();
+        // values after an error are not read
+        self.is_finished = !matches!(value, Ok(Some(_)));
+        value.transpose()
+    }
+}
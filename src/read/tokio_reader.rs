@@ -0,0 +1,112 @@
+//! Reading of parquet files from readers of the `tokio` runtime.
+use std::io::SeekFrom;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::{ready, AsyncRead, AsyncSeek};
+use tokio::io::ReadBuf;
+
+/// A reader implementing [`AsyncRead`] and [`AsyncSeek`] on top of a reader implementing their
+/// `tokio` counterparts (e.g. `tokio::fs::File`), so that it can be used by the async readers of
+/// this crate, such as [`read_metadata_async`](super::read_metadata_async) and
+/// [`get_page_stream`](super::get_page_stream).
+///
+/// Reads are forwarded to the inner reader, so partial reads are returned as they are.
+#[derive(Debug)]
+pub struct TokioReader<R> {
+    inner: R,
+    /// The position of a seek started on `inner` that has not completed yet
+    seek_pos: Option<SeekFrom>,
+}
+
+impl<R> TokioReader<R> {
+    /// Returns a new [`TokioReader`] of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            seek_pos: None,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: tokio::io::AsyncRead + Unpin> AsyncRead for TokioReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let mut buf = ReadBuf::new(buf);
+        ready!(Pin::new(&mut self.inner).poll_read(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+}
+
+impl<R: tokio::io::AsyncSeek + Unpin> AsyncSeek for TokioReader<R> {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = &mut *self;
+        if this.seek_pos != Some(pos) {
+            // `start_seek` errors while a previous seek is in progress
+            ready!(Pin::new(&mut this.inner).poll_complete(cx))?;
+            Pin::new(&mut this.inner).start_seek(pos)?;
+            this.seek_pos = Some(pos);
+        }
+        let result = ready!(Pin::new(&mut this.inner).poll_complete(cx));
+        this.seek_pos = None;
+        Poll::Ready(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use futures::{AsyncReadExt, AsyncSeekExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn read_and_seek() -> std::io::Result<()> {
+        let data = (0..100u8).collect::<Vec<_>>();
+
+        let mut reader = TokioReader::new(Cursor::new(data.clone()));
+        assert_eq!(reader.seek(SeekFrom::End(-10)).await?, 90);
+        let mut buffer = vec![0; 10];
+        reader.read_exact(&mut buffer).await?;
+        assert_eq!(buffer, &data[90..]);
+        // at the end of the data
+        assert_eq!(reader.read(&mut buffer).await?, 0);
+
+        assert_eq!(reader.seek(SeekFrom::Current(-20)).await?, 80);
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer).await?;
+        assert_eq!(buffer, &data[80..]);
+
+        assert_eq!(reader.seek(SeekFrom::Start(95)).await?, 95);
+        let mut buffer = vec![0; 10];
+        assert_eq!(reader.read(&mut buffer).await?, 5);
+        assert_eq!(&buffer[..5], &data[95..]);
+
+        assert!(reader.seek(SeekFrom::End(-101)).await.is_err());
+        assert_eq!(reader.into_inner().position(), 100);
+        Ok(())
+    }
+}
@@ -82,4 +82,101 @@ mod tests {
     ";
         test_round_trip(message_type).unwrap();
     }
+
+    #[test]
+    fn decimal_logical_type_round_trip() -> Result<()> {
+        use crate::error::Error;
+        use crate::schema::types::{PhysicalType, PrimitiveLogicalType::Decimal};
+
+        let fields = vec![
+            ParquetType::from_physical_with_logical(
+                "int32".to_string(),
+                PhysicalType::Int32,
+                Decimal(9, 2),
+            )?,
+            ParquetType::from_physical_with_logical(
+                "int64".to_string(),
+                PhysicalType::Int64,
+                Decimal(18, 0),
+            )?,
+            ParquetType::from_physical_with_logical(
+                "fixed".to_string(),
+                PhysicalType::FixedLenByteArray(16),
+                Decimal(38, 10),
+            )?,
+            ParquetType::from_physical_with_logical(
+                "binary".to_string(),
+                PhysicalType::ByteArray,
+                Decimal(100, 50),
+            )?,
+        ];
+        let schema = ParquetType::new_root("schema".to_string(), fields);
+
+        let result = ParquetType::try_from_thrift(&schema.to_thrift())?;
+        assert_eq!(result, schema);
+
+        // the precision does not fit in the physical type
+        for (physical_type, precision) in [
+            (PhysicalType::Int32, 10),
+            (PhysicalType::Int64, 19),
+            (PhysicalType::FixedLenByteArray(4), 10),
+            (PhysicalType::FixedLenByteArray(16), 39),
+            (PhysicalType::Double, 5),
+        ] {
+            let result = ParquetType::from_physical_with_logical(
+                "a".to_string(),
+                physical_type,
+                Decimal(precision, 0),
+            );
+            assert!(matches!(result, Err(Error::OutOfSpec(_))), "{:?}", result);
+        }
+        // the scale cannot be larger than the precision, but can be equal to it
+        let result = ParquetType::from_physical_with_logical(
+            "a".to_string(),
+            PhysicalType::Int32,
+            Decimal(2, 3),
+        );
+        assert!(matches!(result, Err(Error::OutOfSpec(_))));
+        ParquetType::from_physical_with_logical(
+            "a".to_string(),
+            PhysicalType::Int32,
+            Decimal(2, 2),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn uuid_logical_type_round_trip() -> Result<()> {
+        use crate::error::Error;
+        use crate::schema::types::{PhysicalType, PrimitiveLogicalType::Uuid};
+
+        let field = ParquetType::from_physical_with_logical(
+            "uuid".to_string(),
+            PhysicalType::FixedLenByteArray(16),
+            Uuid,
+        )?;
+        let schema = ParquetType::new_root("schema".to_string(), vec![field]);
+
+        let result = ParquetType::try_from_thrift(&schema.to_thrift())?;
+        assert_eq!(result, schema);
+
+        for physical_type in [
+            PhysicalType::FixedLenByteArray(15),
+            PhysicalType::ByteArray,
+            PhysicalType::Int64,
+        ] {
+            let result =
+                ParquetType::from_physical_with_logical("a".to_string(), physical_type, Uuid);
+            assert!(matches!(result, Err(Error::OutOfSpec(_))), "{:?}", result);
+        }
+
+        // the annotation of a file is also validated when it is read
+        let mut thrift = schema.to_thrift();
+        thrift[1].type_length = Some(8);
+        assert!(matches!(
+            ParquetType::try_from_thrift(&thrift),
+            Err(Error::OutOfSpec(_))
+        ));
+        Ok(())
+    }
 }
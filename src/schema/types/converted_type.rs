@@ -1,6 +1,8 @@
 use crate::error::Error;
 use parquet_format_async_temp::ConvertedType;
 
+use super::{IntegerType, PrimitiveLogicalType, TimeUnit};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum PrimitiveConvertedType {
     Utf8,
@@ -199,6 +201,55 @@ impl From<PrimitiveConvertedType> for (ConvertedType, Option<(i32, i32)>) {
     }
 }
 
+/// Returns the logical type of a primitive type annotated with `converted` and `logical`.
+///
+/// `logical` is preferred, also when it conflicts with `converted`. Otherwise, the logical type
+/// is derived from `converted` as described in the backward compatibility section of the spec:
+/// times and timestamps are adjusted to UTC, and `Interval`, which has no equivalent logical
+/// type, returns `None`.
+pub fn normalize_logical_type(
+    converted: Option<PrimitiveConvertedType>,
+    logical: Option<PrimitiveLogicalType>,
+) -> Option<PrimitiveLogicalType> {
+    use PrimitiveConvertedType::*;
+    if logical.is_some() {
+        return logical;
+    }
+    Some(match converted? {
+        Utf8 => PrimitiveLogicalType::String,
+        Enum => PrimitiveLogicalType::Enum,
+        Decimal(precision, scale) => PrimitiveLogicalType::Decimal(precision, scale),
+        Date => PrimitiveLogicalType::Date,
+        TimeMillis => PrimitiveLogicalType::Time {
+            unit: TimeUnit::Milliseconds,
+            is_adjusted_to_utc: true,
+        },
+        TimeMicros => PrimitiveLogicalType::Time {
+            unit: TimeUnit::Microseconds,
+            is_adjusted_to_utc: true,
+        },
+        TimestampMillis => PrimitiveLogicalType::Timestamp {
+            unit: TimeUnit::Milliseconds,
+            is_adjusted_to_utc: true,
+        },
+        TimestampMicros => PrimitiveLogicalType::Timestamp {
+            unit: TimeUnit::Microseconds,
+            is_adjusted_to_utc: true,
+        },
+        Uint8 => PrimitiveLogicalType::Integer(IntegerType::UInt8),
+        Uint16 => PrimitiveLogicalType::Integer(IntegerType::UInt16),
+        Uint32 => PrimitiveLogicalType::Integer(IntegerType::UInt32),
+        Uint64 => PrimitiveLogicalType::Integer(IntegerType::UInt64),
+        Int8 => PrimitiveLogicalType::Integer(IntegerType::Int8),
+        Int16 => PrimitiveLogicalType::Integer(IntegerType::Int16),
+        Int32 => PrimitiveLogicalType::Integer(IntegerType::Int32),
+        Int64 => PrimitiveLogicalType::Integer(IntegerType::Int64),
+        Json => PrimitiveLogicalType::Json,
+        Bson => PrimitiveLogicalType::Bson,
+        Interval => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,4 +285,55 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn normalize() -> Result<(), Error> {
+        assert_eq!(
+            normalize_logical_type(Some(PrimitiveConvertedType::Utf8), None),
+            Some(PrimitiveLogicalType::String)
+        );
+        assert_eq!(
+            normalize_logical_type(Some(PrimitiveConvertedType::TimestampMillis), None),
+            Some(PrimitiveLogicalType::Timestamp {
+                unit: TimeUnit::Milliseconds,
+                is_adjusted_to_utc: true
+            })
+        );
+
+        // the precision and scale are carried separately from the converted type
+        let converted = (ConvertedType::DECIMAL, Some((10, 2))).try_into()?;
+        assert_eq!(
+            normalize_logical_type(Some(converted), None),
+            Some(PrimitiveLogicalType::Decimal(10, 2))
+        );
+
+        // no logical equivalent
+        assert_eq!(
+            normalize_logical_type(Some(PrimitiveConvertedType::Interval), None),
+            None
+        );
+        assert_eq!(normalize_logical_type(None, None), None);
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_prefers_logical() {
+        let logical = PrimitiveLogicalType::Timestamp {
+            unit: TimeUnit::Nanoseconds,
+            is_adjusted_to_utc: false,
+        };
+        assert_eq!(normalize_logical_type(None, Some(logical)), Some(logical));
+        // conflicting annotations
+        assert_eq!(
+            normalize_logical_type(Some(PrimitiveConvertedType::Utf8), Some(logical)),
+            Some(logical)
+        );
+        assert_eq!(
+            normalize_logical_type(
+                Some(PrimitiveConvertedType::Interval),
+                Some(PrimitiveLogicalType::Unknown)
+            ),
+            Some(PrimitiveLogicalType::Unknown)
+        );
+    }
 }
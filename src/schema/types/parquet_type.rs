@@ -36,6 +36,22 @@ impl PrimitiveType {
             physical_type,
         }
     }
+
+    /// Helper method to create an optional field with a logical type and no converted type.
+    /// # Error
+    /// Errors iff `logical_type` cannot annotate `physical_type`, e.g. a
+    /// [`PrimitiveLogicalType::Decimal`] whose precision does not fit in the physical type.
+    pub fn from_physical_with_logical(
+        name: String,
+        physical_type: PhysicalType,
+        logical_type: PrimitiveLogicalType,
+    ) -> Result<Self> {
+        spec::check_logical_invariants(&physical_type, &Some(logical_type))?;
+        Ok(Self {
+            logical_type: Some(logical_type),
+            ..Self::from_physical(name, physical_type)
+        })
+    }
 }
 
 /// Representation of a Parquet type describing primitive and nested fields,
@@ -178,6 +194,19 @@ impl ParquetType {
         ParquetType::PrimitiveType(PrimitiveType::from_physical(name, physical_type))
     }
 
+    /// Helper method to create a [`ParquetType::PrimitiveType`] optional field
+    /// with a logical type and no converted type.
+    /// # Error
+    /// Errors iff `logical_type` cannot annotate `physical_type`.
+    pub fn from_physical_with_logical(
+        name: String,
+        physical_type: PhysicalType,
+        logical_type: PrimitiveLogicalType,
+    ) -> Result<Self> {
+        PrimitiveType::from_physical_with_logical(name, physical_type, logical_type)
+            .map(ParquetType::PrimitiveType)
+    }
+
     pub fn from_group(
         name: String,
         repetition: Repetition,
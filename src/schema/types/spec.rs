@@ -9,35 +9,33 @@ fn check_decimal_invariants(
     scale: usize,
 ) -> Result<()> {
     if precision < 1 {
-        return Err(general_err!(
+        return Err(Error::OutOfSpec(format!(
             "DECIMAL precision must be larger than 0; It is {}",
             precision
-        ));
+        )));
     }
-    if scale >= precision {
-        return Err(general_err!(
-            "Invalid DECIMAL: scale ({}) cannot be greater than or equal to precision \
-            ({})",
-            scale,
-            precision
-        ));
+    if scale > precision {
+        return Err(Error::OutOfSpec(format!(
+            "Invalid DECIMAL: scale ({}) cannot be greater than precision ({})",
+            scale, precision
+        )));
     }
 
     match physical_type {
         PhysicalType::Int32 => {
             if !(1..=9).contains(&precision) {
-                return Err(general_err!(
+                return Err(Error::OutOfSpec(format!(
                     "Cannot represent INT32 as DECIMAL with precision {}",
                     precision
-                ));
+                )));
             }
         }
         PhysicalType::Int64 => {
             if !(1..=18).contains(&precision) {
-                return Err(general_err!(
+                return Err(Error::OutOfSpec(format!(
                     "Cannot represent INT64 as DECIMAL with precision {}",
                     precision
-                ));
+                )));
             }
         }
         PhysicalType::FixedLenByteArray(length) => {
@@ -45,19 +43,18 @@ fn check_decimal_invariants(
                 (2f64.powi(8 * (*length as i32) - 1) - 1f64).log10().floor() as usize;
 
             if precision > max_precision {
-                return Err(general_err!(
+                return Err(Error::OutOfSpec(format!(
                     "Cannot represent FIXED_LEN_BYTE_ARRAY as DECIMAL with length {} and \
                     precision {}. The max precision can only be {}",
-                    length,
-                    precision,
-                    max_precision
-                ));
+                    length, precision, max_precision
+                )));
             }
         }
         PhysicalType::ByteArray => {}
         _ => {
-            return Err(general_err!(
+            return Err(Error::OutOfSpec(
                 "DECIMAL can only annotate INT32, INT64, BYTE_ARRAY and FIXED_LEN_BYTE_ARRAY"
+                    .to_string(),
             ))
         }
     };
@@ -155,6 +152,12 @@ pub fn check_logical_invariants(
         (String | Json | Bson, PhysicalType::ByteArray) => {}
         // https://github.com/apache/parquet-format/blob/master/LogicalTypes.md#uuid
         (Uuid, PhysicalType::FixedLenByteArray(16)) => {}
+        (Uuid, other) => {
+            return Err(Error::OutOfSpec(format!(
+                "UUID can only annotate FIXED_LEN_BYTE_ARRAY(16), not {:?}",
+                other
+            )))
+        }
         (a, b) => return Err(general_err!("Cannot annotate {:?} from {:?} fields", a, b)),
     };
     Ok(())
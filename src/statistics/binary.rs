@@ -29,6 +29,10 @@ impl Statistics for BinaryStatistics {
     fn null_count(&self) -> Option<i64> {
         self.null_count
     }
+
+    fn distinct_count(&self) -> Option<i64> {
+        self.distinct_count
+    }
 }
 
 pub fn read(v: &ParquetStatistics, primitive_type: PrimitiveType) -> Result<Arc<dyn Statistics>> {
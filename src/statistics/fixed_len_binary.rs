@@ -4,6 +4,7 @@ use parquet_format_async_temp::Statistics as ParquetStatistics;
 
 use super::Statistics;
 use crate::{
+    deserialize::decimal_from_be_bytes,
     error::{Error, Result},
     schema::types::{PhysicalType, PrimitiveType},
 };
@@ -17,6 +18,34 @@ pub struct FixedLenStatistics {
     pub min_value: Option<Vec<u8>>,
 }
 
+impl FixedLenStatistics {
+    /// The minimum value as the unscaled value of a decimal (see [`flba_to_i128`]).
+    pub fn min_value_i128(&self) -> Option<Result<i128>> {
+        self.min_value.as_deref().map(flba_to_i128)
+    }
+
+    /// The maximum value as the unscaled value of a decimal (see [`flba_to_i128`]).
+    pub fn max_value_i128(&self) -> Option<Result<i128>> {
+        self.max_value.as_deref().map(flba_to_i128)
+    }
+}
+
+/// Interprets the min or max value of the statistics of a `FIXED_LEN_BYTE_ARRAY` column,
+/// a big-endian two's complement integer, as an `i128`. For a `DECIMAL(precision, scale)`
+/// column, this is the unscaled value, i.e. the decimal is `value * 10^{-scale}`, so that
+/// the ranges of columns with the same scale can be compared.
+/// # Errors
+/// Errors iff `bytes` is empty or has more than 16 bytes.
+pub fn flba_to_i128(bytes: &[u8]) -> Result<i128> {
+    if bytes.len() > 16 {
+        return Err(Error::OutOfSpec(format!(
+            "A value of {} bytes cannot be interpreted as an i128",
+            bytes.len()
+        )));
+    }
+    decimal_from_be_bytes(bytes)
+}
+
 impl Statistics for FixedLenStatistics {
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -29,6 +58,10 @@ impl Statistics for FixedLenStatistics {
     fn null_count(&self) -> Option<i64> {
         self.null_count
     }
+
+    fn distinct_count(&self) -> Option<i64> {
+        self.distinct_count
+    }
 }
 
 pub fn read(
@@ -38,16 +71,20 @@ pub fn read(
 ) -> Result<Arc<dyn Statistics>> {
     if let Some(ref v) = v.max_value {
         if v.len() != size {
-            return Err(Error::OutOfSpec(
-                "The max_value of statistics MUST be plain encoded".to_string(),
-            ));
+            return Err(Error::OutOfSpec(format!(
+                "The max_value of statistics MUST be plain encoded with {} bytes, but it has {}",
+                size,
+                v.len()
+            )));
         }
     };
     if let Some(ref v) = v.min_value {
         if v.len() != size {
-            return Err(Error::OutOfSpec(
-                "The min_value of statistics MUST be plain encoded".to_string(),
-            ));
+            return Err(Error::OutOfSpec(format!(
+                "The min_value of statistics MUST be plain encoded with {} bytes, but it has {}",
+                size,
+                v.len()
+            )));
         }
     };
 
@@ -55,14 +92,8 @@ pub fn read(
         primitive_type,
         null_count: v.null_count,
         distinct_count: v.distinct_count,
-        max_value: v.max_value.clone().map(|mut x| {
-            x.truncate(size as usize);
-            x
-        }),
-        min_value: v.min_value.clone().map(|mut x| {
-            x.truncate(size as usize);
-            x
-        }),
+        max_value: v.max_value.clone(),
+        min_value: v.min_value.clone(),
     }))
 }
 
@@ -76,3 +107,57 @@ pub fn write(v: &FixedLenStatistics) -> ParquetStatistics {
         max: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_i128() -> Result<()> {
+        assert_eq!(flba_to_i128(&[0x30, 0x39])?, 12345);
+        // negative values are sign-extended
+        assert_eq!(flba_to_i128(&[0xcf, 0xc7])?, -12345);
+        assert_eq!(flba_to_i128(&[0xff; 9])?, -1);
+        assert_eq!(flba_to_i128(&(-12345i128).to_be_bytes()[7..])?, -12345);
+
+        // the maximum magnitudes of 16 bytes
+        assert_eq!(flba_to_i128(&i128::MAX.to_be_bytes())?, i128::MAX);
+        assert_eq!(flba_to_i128(&i128::MIN.to_be_bytes())?, i128::MIN);
+        let max = 10i128.pow(38) - 1;
+        assert_eq!(flba_to_i128(&max.to_be_bytes())?, max);
+        assert_eq!(flba_to_i128(&(-max).to_be_bytes())?, -max);
+        Ok(())
+    }
+
+    #[test]
+    fn to_i128_invalid() {
+        assert!(matches!(flba_to_i128(&[]), Err(Error::OutOfSpec(_))));
+        // more than 16 bytes, even if they only extend the sign
+        assert!(matches!(flba_to_i128(&[0; 17]), Err(Error::OutOfSpec(_))));
+    }
+
+    #[test]
+    fn min_max_i128() -> Result<()> {
+        let statistics = FixedLenStatistics {
+            primitive_type: PrimitiveType::from_physical(
+                "a".to_string(),
+                PhysicalType::FixedLenByteArray(2),
+            ),
+            null_count: Some(0),
+            distinct_count: None,
+            max_value: Some(vec![0x30, 0x39]),
+            min_value: Some(vec![0xcf, 0xc7]),
+        };
+        assert_eq!(statistics.min_value_i128().transpose()?, Some(-12345));
+        assert_eq!(statistics.max_value_i128().transpose()?, Some(12345));
+
+        let statistics = FixedLenStatistics {
+            max_value: None,
+            min_value: None,
+            ..statistics
+        };
+        assert!(statistics.min_value_i128().is_none());
+        assert!(statistics.max_value_i128().is_none());
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ mod binary;
 mod boolean;
 mod fixed_len_binary;
 mod primitive;
+mod typed;
 
 use std::{any::Any, sync::Arc};
 
@@ -12,17 +13,23 @@ use crate::schema::types::{PhysicalType, PrimitiveType};
 
 pub use binary::BinaryStatistics;
 pub use boolean::BooleanStatistics;
-pub use fixed_len_binary::FixedLenStatistics;
+pub use fixed_len_binary::{flba_to_i128, FixedLenStatistics};
 pub use primitive::PrimitiveStatistics;
+pub use typed::{read_statistics, TypedStatistics};
 
 /// A trait used to describe specific statistics. Each physical type has its own struct.
-/// Match the [`Statistics::physical_type`] to each type and downcast accordingly.
+/// Match the [`Statistics::physical_type`] to each type and downcast accordingly, or convert
+/// it into [`TypedStatistics`].
 pub trait Statistics: Send + Sync + std::fmt::Debug {
     fn as_any(&self) -> &dyn Any;
 
     fn physical_type(&self) -> &PhysicalType;
 
     fn null_count(&self) -> Option<i64>;
+
+    /// The number of distinct values, if known. A count of `0` means that there are no
+    /// non-null values, not that the count is unknown (`None`).
+    fn distinct_count(&self) -> Option<i64>;
 }
 
 impl PartialEq for &dyn Statistics {
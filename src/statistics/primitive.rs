@@ -28,6 +28,10 @@ impl<T: types::NativeType> Statistics for PrimitiveStatistics<T> {
     fn null_count(&self) -> Option<i64> {
         self.null_count
     }
+
+    fn distinct_count(&self) -> Option<i64> {
+        self.distinct_count
+    }
 }
 
 pub fn read<T: types::NativeType>(
@@ -0,0 +1,327 @@
+use std::convert::TryFrom;
+
+use crate::error::{Error, Result};
+use crate::metadata::ColumnChunkMetaData;
+use crate::schema::types::PhysicalType;
+use crate::types::NativeType;
+
+use super::{
+    BinaryStatistics, BooleanStatistics, FixedLenStatistics, PrimitiveStatistics, Statistics,
+};
+
+/// The statistics of a column chunk or page, with one variant per physical type.
+///
+/// Unlike [`Statistics`], its values can be matched on without downcasting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedStatistics {
+    Boolean {
+        min: Option<bool>,
+        max: Option<bool>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    Int32 {
+        min: Option<i32>,
+        max: Option<i32>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    Int64 {
+        min: Option<i64>,
+        max: Option<i64>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    Int96 {
+        min: Option<[u32; 3]>,
+        max: Option<[u32; 3]>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    Float {
+        min: Option<f32>,
+        max: Option<f32>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    Double {
+        min: Option<f64>,
+        max: Option<f64>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    ByteArray {
+        min: Option<Vec<u8>>,
+        max: Option<Vec<u8>>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+    FixedLenByteArray {
+        /// The length of the values
+        size: usize,
+        min: Option<Vec<u8>>,
+        max: Option<Vec<u8>>,
+        null_count: Option<i64>,
+        distinct_count: Option<i64>,
+    },
+}
+
+/// Downcasts `statistics` to `T`, the statistics of its physical type.
+fn downcast<T: 'static>(statistics: &dyn Statistics) -> Result<&T> {
+    statistics.as_any().downcast_ref::<T>().ok_or_else(|| {
+        Error::InvalidParameter(format!(
+            "Statistics of the physical type {:?} must be a {}",
+            statistics.physical_type(),
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// The min, max, null count and distinct count of primitive statistics
+type PrimitiveFields<T> = (Option<T>, Option<T>, Option<i64>, Option<i64>);
+
+fn primitive<T: NativeType>(statistics: &dyn Statistics) -> Result<PrimitiveFields<T>> {
+    let statistics = downcast::<PrimitiveStatistics<T>>(statistics)?;
+    Ok((
+        statistics.min_value,
+        statistics.max_value,
+        statistics.null_count,
+        statistics.distinct_count,
+    ))
+}
+
+impl TryFrom<&dyn Statistics> for TypedStatistics {
+    type Error = Error;
+
+    /// Returns the [`TypedStatistics`] of `statistics`.
+    /// # Error
+    /// Errors iff `statistics` is not the statistics struct of its physical type, e.g.
+    /// [`PrimitiveStatistics`] of `i32` for [`PhysicalType::Int32`].
+    fn try_from(statistics: &dyn Statistics) -> Result<Self> {
+        Ok(match *statistics.physical_type() {
+            PhysicalType::Boolean => {
+                let statistics = downcast::<BooleanStatistics>(statistics)?;
+                Self::Boolean {
+                    min: statistics.min_value,
+                    max: statistics.max_value,
+                    null_count: statistics.null_count,
+                    distinct_count: statistics.distinct_count,
+                }
+            }
+            PhysicalType::Int32 => {
+                let (min, max, null_count, distinct_count) = primitive(statistics)?;
+                Self::Int32 {
+                    min,
+                    max,
+                    null_count,
+                    distinct_count,
+                }
+            }
+            PhysicalType::Int64 => {
+                let (min, max, null_count, distinct_count) = primitive(statistics)?;
+                Self::Int64 {
+                    min,
+                    max,
+                    null_count,
+                    distinct_count,
+                }
+            }
+            PhysicalType::Int96 => {
+                let (min, max, null_count, distinct_count) = primitive(statistics)?;
+                Self::Int96 {
+                    min,
+                    max,
+                    null_count,
+                    distinct_count,
+                }
+            }
+            PhysicalType::Float => {
+                let (min, max, null_count, distinct_count) = primitive(statistics)?;
+                Self::Float {
+                    min,
+                    max,
+                    null_count,
+                    distinct_count,
+                }
+            }
+            PhysicalType::Double => {
+                let (min, max, null_count, distinct_count) = primitive(statistics)?;
+                Self::Double {
+                    min,
+                    max,
+                    null_count,
+                    distinct_count,
+                }
+            }
+            PhysicalType::ByteArray => {
+                let statistics = downcast::<BinaryStatistics>(statistics)?;
+                Self::ByteArray {
+                    min: statistics.min_value.clone(),
+                    max: statistics.max_value.clone(),
+                    null_count: statistics.null_count,
+                    distinct_count: statistics.distinct_count,
+                }
+            }
+            PhysicalType::FixedLenByteArray(size) => {
+                let statistics = downcast::<FixedLenStatistics>(statistics)?;
+                Self::FixedLenByteArray {
+                    size,
+                    min: statistics.min_value.clone(),
+                    max: statistics.max_value.clone(),
+                    null_count: statistics.null_count,
+                    distinct_count: statistics.distinct_count,
+                }
+            }
+        })
+    }
+}
+
+impl TypedStatistics {
+    /// The [`PhysicalType`] of the values of these statistics
+    pub fn physical_type(&self) -> PhysicalType {
+        match self {
+            Self::Boolean { .. } => PhysicalType::Boolean,
+            Self::Int32 { .. } => PhysicalType::Int32,
+            Self::Int64 { .. } => PhysicalType::Int64,
+            Self::Int96 { .. } => PhysicalType::Int96,
+            Self::Float { .. } => PhysicalType::Float,
+            Self::Double { .. } => PhysicalType::Double,
+            Self::ByteArray { .. } => PhysicalType::ByteArray,
+            Self::FixedLenByteArray { size, .. } => PhysicalType::FixedLenByteArray(*size),
+        }
+    }
+
+    /// The number of null values, if known
+    pub fn null_count(&self) -> Option<i64> {
+        match self {
+            Self::Boolean { null_count, .. }
+            | Self::Int32 { null_count, .. }
+            | Self::Int64 { null_count, .. }
+            | Self::Int96 { null_count, .. }
+            | Self::Float { null_count, .. }
+            | Self::Double { null_count, .. }
+            | Self::ByteArray { null_count, .. }
+            | Self::FixedLenByteArray { null_count, .. } => *null_count,
+        }
+    }
+
+    /// The number of distinct values, if known (see [`Statistics::distinct_count`]).
+    pub fn distinct_count(&self) -> Option<i64> {
+        match self {
+            Self::Boolean { distinct_count, .. }
+            | Self::Int32 { distinct_count, .. }
+            | Self::Int64 { distinct_count, .. }
+            | Self::Int96 { distinct_count, .. }
+            | Self::Float { distinct_count, .. }
+            | Self::Double { distinct_count, .. }
+            | Self::ByteArray { distinct_count, .. }
+            | Self::FixedLenByteArray { distinct_count, .. } => *distinct_count,
+        }
+    }
+}
+
+/// Reads the statistics of `column` into [`TypedStatistics`], returning `None` if it has none.
+/// # Error
+/// This function errors iff the statistics are out of spec for the physical type of `column`.
+pub fn read_statistics(column: &ColumnChunkMetaData) -> Result<Option<TypedStatistics>> {
+    column
+        .statistics()
+        .map(|statistics| statistics.map(|x| TypedStatistics::try_from(x.as_ref())))
+        .transpose()?
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::PrimitiveType;
+    use crate::statistics::{deserialize_statistics, ParquetStatistics};
+
+    #[test]
+    fn try_from() -> Result<()> {
+        let statistics = ParquetStatistics {
+            null_count: Some(1),
+            distinct_count: None,
+            max_value: Some(42i32.to_le_bytes().to_vec()),
+            min_value: Some((-1i32).to_le_bytes().to_vec()),
+            min: None,
+            max: None,
+        };
+        let primitive_type = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        let statistics = deserialize_statistics(&statistics, primitive_type)?;
+
+        let statistics = TypedStatistics::try_from(statistics.as_ref())?;
+        assert_eq!(
+            statistics,
+            TypedStatistics::Int32 {
+                min: Some(-1),
+                max: Some(42),
+                null_count: Some(1),
+                distinct_count: None,
+            }
+        );
+        assert_eq!(statistics.physical_type(), PhysicalType::Int32);
+        assert_eq!(statistics.null_count(), Some(1));
+        assert_eq!(statistics.distinct_count(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn fixed_len() -> Result<()> {
+        let statistics = ParquetStatistics {
+            null_count: None,
+            distinct_count: Some(2),
+            max_value: Some(b"bb".to_vec()),
+            min_value: Some(b"aa".to_vec()),
+            min: None,
+            max: None,
+        };
+        let primitive_type =
+            PrimitiveType::from_physical("a".to_string(), PhysicalType::FixedLenByteArray(2));
+        let statistics = deserialize_statistics(&statistics, primitive_type)?;
+
+        let statistics = TypedStatistics::try_from(statistics.as_ref())?;
+        assert_eq!(
+            statistics,
+            TypedStatistics::FixedLenByteArray {
+                size: 2,
+                min: Some(b"aa".to_vec()),
+                max: Some(b"bb".to_vec()),
+                null_count: None,
+                distinct_count: Some(2),
+            }
+        );
+        assert_eq!(
+            statistics.physical_type(),
+            PhysicalType::FixedLenByteArray(2)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn try_from_mismatched_type() {
+        // statistics whose physical type is not the one of its struct
+        #[derive(Debug)]
+        struct Mismatched;
+
+        impl Statistics for Mismatched {
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn physical_type(&self) -> &PhysicalType {
+                &PhysicalType::Int32
+            }
+
+            fn null_count(&self) -> Option<i64> {
+                None
+            }
+
+            fn distinct_count(&self) -> Option<i64> {
+                None
+            }
+        }
+
+        assert!(TypedStatistics::try_from(&Mismatched as &dyn Statistics).is_err());
+    }
+}
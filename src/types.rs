@@ -2,6 +2,9 @@ use std::convert::TryFrom;
 
 use crate::schema::types::PhysicalType;
 
+#[cfg(feature = "chrono")]
+pub mod timestamp;
+
 /// A physical native representation of a Parquet fixed-sized type.
 pub trait NativeType: std::fmt::Debug + Send + Sync + 'static + Copy + Clone {
     type Bytes: AsRef<[u8]> + for<'a> TryFrom<&'a [u8], Error = std::array::TryFromSliceError>;
@@ -12,6 +15,13 @@ pub trait NativeType: std::fmt::Debug + Send + Sync + 'static + Copy + Clone {
 
     fn ord(&self, other: &Self) -> std::cmp::Ordering;
 
+    /// Whether this value is not a number, i.e. it is not ordered with respect to other values.
+    /// Only floating point values can be NaN.
+    #[inline]
+    fn is_nan(&self) -> bool {
+        false
+    }
+
     const TYPE: PhysicalType;
 }
 
@@ -34,6 +44,11 @@ macro_rules! native {
                 self.partial_cmp(other).unwrap_or(std::cmp::Ordering::Equal)
             }
 
+            #[inline]
+            fn is_nan(&self) -> bool {
+                self.partial_cmp(self).is_none()
+            }
+
             const TYPE: PhysicalType = $physical_type;
         }
     };
@@ -93,12 +108,21 @@ impl NativeType for [u32; 3] {
         ]
     }
 
+    /// Compares two timestamps by their Julian day and then by their nanoseconds within the
+    /// day, which, unlike [`int96_to_i64_ns`], does not overflow for any day.
     #[inline]
     fn ord(&self, other: &Self) -> std::cmp::Ordering {
-        int96_to_i64_ns(*self).ord(&int96_to_i64_ns(*other))
+        let nanoseconds = |value: &Self| ((value[1] as u64) << 32) + value[0] as u64;
+        self[2]
+            .cmp(&other[2])
+            .then_with(|| nanoseconds(self).cmp(&nanoseconds(other)))
     }
 }
 
+/// Converts an `INT96` value, as written by Impala and Hive, into nanoseconds since the Unix epoch.
+///
+/// The first 8 bytes are the nanoseconds within the day and the last 4 bytes the Julian day.
+/// Values before the epoch are negative.
 #[inline]
 pub fn int96_to_i64_ns(value: [u32; 3]) -> i64 {
     const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
@@ -139,3 +163,49 @@ pub fn decode<T: NativeType>(chunk: &[u8]) -> T {
     };
     T::from_le_bytes(chunk)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int96_epoch() {
+        assert_eq!(int96_to_i64_ns([0, 0, 2_440_588]), 0);
+    }
+
+    #[test]
+    fn int96_before_epoch() {
+        // 1969-12-31 23:59:59
+        let nanos: u64 = 86_399 * 1_000_000_000;
+        let value = [nanos as u32, (nanos >> 32) as u32, 2_440_587];
+        assert_eq!(int96_to_i64_ns(value), -1_000_000_000);
+
+        // 1900-01-01 00:00:00
+        assert_eq!(
+            int96_to_i64_ns([0, 0, 2_415_021]),
+            -2_208_988_800_000_000_000
+        );
+    }
+
+    #[test]
+    fn int96_impala() {
+        // 2009-03-01 00:01:00, from `alltypes_plain.parquet` written by Impala
+        let bytes = [0, 0x58, 0x47, 0xf8, 0x0d, 0, 0, 0, 0x6c, 0x75, 0x25, 0];
+        let value = <[u32; 3]>::from_le_bytes(bytes);
+        assert_eq!(int96_to_i64_ns(value), 1_235_865_660_000_000_000);
+    }
+
+    #[test]
+    fn int96_ord() {
+        use std::cmp::Ordering::*;
+
+        let noon = 12 * 3_600 * 1_000_000_000u64;
+        let noon = [noon as u32, (noon >> 32) as u32, 2_440_588];
+        // the nanoseconds within the day are only compared within the same day
+        assert_eq!(noon.ord(&[0, 0, 2_440_589]), Less);
+        assert_eq!(noon.ord(&[0, 0, 2_440_588]), Greater);
+        assert_eq!(noon.ord(&noon), Equal);
+        // the largest day would overflow a conversion to nanoseconds
+        assert_eq!([0, 0, u32::MAX].ord(&noon), Greater);
+    }
+}
@@ -0,0 +1,158 @@
+//! Conversions between `INT96` timestamps and [`chrono`] types.
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::error::{Error, Result};
+
+/// The Julian day of the Unix epoch, 1970-01-01
+const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
+/// The number of days from 0001-01-01 (day 1) to the Unix epoch, as counted by
+/// [`chrono::Datelike::num_days_from_ce`]
+const DAYS_FROM_CE_OF_EPOCH: i64 = 719_163;
+const NANOS_PER_DAY: u64 = 86_400 * NANOS_PER_SECOND;
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Converts an `INT96` value, as written by Impala, Hive and Spark, into a [`NaiveDateTime`].
+///
+/// The first 8 bytes are the nanoseconds within the day and the last 4 bytes the Julian day.
+/// # Errors
+/// Errors iff the nanoseconds are not within a day or the day is not representable by
+/// [`NaiveDate`].
+pub fn from_int96(raw: [u32; 3]) -> Result<NaiveDateTime> {
+    let nanoseconds = ((raw[1] as u64) << 32) + raw[0] as u64;
+    if nanoseconds >= NANOS_PER_DAY {
+        return Err(Error::OutOfSpec(format!(
+            "The nanoseconds of an INT96 timestamp must be within a day, but are {}",
+            nanoseconds
+        )));
+    }
+    let time = NaiveTime::from_num_seconds_from_midnight_opt(
+        (nanoseconds / NANOS_PER_SECOND) as u32,
+        (nanoseconds % NANOS_PER_SECOND) as u32,
+    )
+    .unwrap();
+
+    let days_from_ce = raw[2] as i64 - JULIAN_DAY_OF_EPOCH + DAYS_FROM_CE_OF_EPOCH;
+    let date = i32::try_from(days_from_ce)
+        .ok()
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or_else(|| {
+            Error::OutOfSpec(format!(
+                "The Julian day {} of an INT96 timestamp is out of range",
+                raw[2]
+            ))
+        })?;
+    Ok(date.and_time(time))
+}
+
+/// Converts a [`NaiveDateTime`] into an `INT96` value (see [`from_int96`]).
+///
+/// Leap seconds are written as the last second of the day.
+/// # Errors
+/// Errors iff `datetime` is before the first Julian day, -4713-11-24.
+pub fn to_int96(datetime: NaiveDateTime) -> Result<[u32; 3]> {
+    let julian_day =
+        datetime.num_days_from_ce() as i64 - DAYS_FROM_CE_OF_EPOCH + JULIAN_DAY_OF_EPOCH;
+    let julian_day = u32::try_from(julian_day).map_err(|_| {
+        Error::InvalidParameter(format!(
+            "The date {} is before the first Julian day and cannot be written as INT96",
+            datetime.date()
+        ))
+    })?;
+
+    // leap seconds are represented by chrono as a nanosecond >= 1_000_000_000
+    let nanosecond = (datetime.nanosecond() as u64).min(NANOS_PER_SECOND - 1);
+    let nanoseconds = datetime.num_seconds_from_midnight() as u64 * NANOS_PER_SECOND + nanosecond;
+    Ok([nanoseconds as u32, (nanoseconds >> 32) as u32, julian_day])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::int96_to_i64_ns;
+    use super::*;
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32, nano: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_nano_opt(h, min, s, nano)
+            .unwrap()
+    }
+
+    #[test]
+    fn epoch() -> Result<()> {
+        let epoch = datetime(1970, 1, 1, 0, 0, 0, 0);
+        assert_eq!(from_int96([0, 0, 2_440_588])?, epoch);
+        assert_eq!(to_int96(epoch)?, [0, 0, 2_440_588]);
+        Ok(())
+    }
+
+    #[test]
+    fn before_epoch() -> Result<()> {
+        let nanos: u64 = 86_399 * 1_000_000_000 + 999_999_999;
+        let value = [nanos as u32, (nanos >> 32) as u32, 2_440_587];
+        let expected = datetime(1969, 12, 31, 23, 59, 59, 999_999_999);
+        assert_eq!(from_int96(value)?, expected);
+        assert_eq!(to_int96(expected)?, value);
+
+        let expected = datetime(1900, 1, 1, 0, 0, 0, 0);
+        assert_eq!(from_int96([0, 0, 2_415_021])?, expected);
+        assert_eq!(to_int96(expected)?, [0, 0, 2_415_021]);
+
+        // the first Julian day
+        let expected = datetime(-4713, 11, 24, 12, 0, 0, 0);
+        let value = to_int96(expected)?;
+        assert_eq!(value[2], 0);
+        assert_eq!(from_int96(value)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn consistent_with_nanoseconds() -> Result<()> {
+        // 2009-03-01 00:01:00, from `alltypes_plain.parquet` written by Impala
+        let bytes = [0, 0x58, 0x47, 0xf8, 0x0d, 0, 0, 0, 0x6c, 0x75, 0x25, 0];
+        let value = <[u32; 3] as crate::types::NativeType>::from_le_bytes(bytes);
+
+        let result = from_int96(value)?;
+        assert_eq!(result, datetime(2009, 3, 1, 0, 1, 0, 0));
+        assert_eq!(
+            result.and_utc().timestamp_nanos_opt(),
+            Some(int96_to_i64_ns(value))
+        );
+        assert_eq!(to_int96(result)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn leap_second() -> Result<()> {
+        let leap = datetime(2016, 12, 31, 23, 59, 59, 1_500_000_000);
+        let expected = datetime(2016, 12, 31, 23, 59, 59, 999_999_999);
+        assert_eq!(from_int96(to_int96(leap)?)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() {
+        // the day is after the largest date of chrono
+        assert!(matches!(
+            from_int96([0, 0, u32::MAX]),
+            Err(Error::OutOfSpec(_))
+        ));
+
+        // the nanoseconds are more than a day
+        let nanos = 86_400 * NANOS_PER_SECOND;
+        assert!(matches!(
+            from_int96([nanos as u32, (nanos >> 32) as u32, 2_440_588]),
+            Err(Error::OutOfSpec(_))
+        ));
+
+        // the date is before the first Julian day
+        let datetime = datetime(-4713, 11, 23, 23, 59, 59, 0);
+        assert!(matches!(
+            to_int96(datetime),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            to_int96(NaiveDateTime::MIN),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
 };
 
 use super::page::{write_page, write_page_async, PageWriteSpec};
-use super::statistics::reduce;
+use super::statistics::{reduce, truncate};
 use super::DynStreamingIterator;
 
 pub fn write_column_chunk<'a, W, E>(
@@ -26,6 +26,9 @@ pub fn write_column_chunk<'a, W, E>(
     mut offset: u64,
     descriptor: &ColumnDescriptor,
     mut compressed_pages: DynStreamingIterator<'a, CompressedPage, E>,
+    write_crc: bool,
+    write_statistics: bool,
+    statistics_truncate_length: Option<usize>,
 ) -> Result<(ColumnChunk, Vec<PageWriteSpec>, u64)>
 where
     W: Write,
@@ -38,13 +41,20 @@ where
 
     let mut specs = vec![];
     while let Some(compressed_page) = compressed_pages.next()? {
-        let spec = write_page(writer, offset, compressed_page)?;
+        let spec = write_page(
+            writer,
+            offset,
+            compressed_page,
+            write_crc,
+            write_statistics,
+            statistics_truncate_length,
+        )?;
         offset += spec.bytes_written;
         specs.push(spec);
     }
     let mut bytes_written = offset - initial;
 
-    let column_chunk = build_column_chunk(&specs, descriptor)?;
+    let column_chunk = build_column_chunk(&specs, descriptor, statistics_truncate_length)?;
 
     // write metadata
     let mut protocol = TCompactOutputProtocol::new(writer);
@@ -63,6 +73,9 @@ pub async fn write_column_chunk_async<W, E>(
     mut offset: u64,
     descriptor: &ColumnDescriptor,
     mut compressed_pages: DynStreamingIterator<'_, CompressedPage, E>,
+    write_crc: bool,
+    write_statistics: bool,
+    statistics_truncate_length: Option<usize>,
 ) -> Result<(ColumnChunk, Vec<PageWriteSpec>, u64)>
 where
     W: AsyncWrite + Unpin + Send,
@@ -73,13 +86,21 @@ where
     // write every page
     let mut specs = vec![];
     while let Some(compressed_page) = compressed_pages.next()? {
-        let spec = write_page_async(writer, offset, compressed_page).await?;
+        let spec = write_page_async(
+            writer,
+            offset,
+            compressed_page,
+            write_crc,
+            write_statistics,
+            statistics_truncate_length,
+        )
+        .await?;
         offset += spec.bytes_written;
         specs.push(spec);
     }
     let mut bytes_written = offset - initial;
 
-    let column_chunk = build_column_chunk(&specs, descriptor)?;
+    let column_chunk = build_column_chunk(&specs, descriptor, statistics_truncate_length)?;
 
     // write metadata
     let mut protocol = TCompactOutputStreamProtocol::new(writer);
@@ -97,6 +118,7 @@ where
 fn build_column_chunk(
     specs: &[PageWriteSpec],
     descriptor: &ColumnDescriptor,
+    statistics_truncate_length: Option<usize>,
 ) -> Result<ColumnChunk> {
     // compute stats to build header at the end of the chunk
 
@@ -119,12 +141,22 @@ fn build_column_chunk(
         .iter()
         .map(|x| x.header_size as i64 + x.header.compressed_page_size as i64)
         .sum();
-    // SPEC: the total compressed size is the total compressed size of each page + the header size
+    // SPEC: the total uncompressed size is the total uncompressed size of each page + the header size
     let total_uncompressed_size = specs
         .iter()
         .map(|x| x.header_size as i64 + x.header.uncompressed_page_size as i64)
         .sum();
-    let data_page_offset = specs.first().map(|spec| spec.offset).unwrap_or(0) as i64;
+    let start = specs.first().map(|spec| spec.offset).unwrap_or(0) as i64;
+    let is_dictionary_page =
+        |spec: &&PageWriteSpec| spec.header.type_ == PageType::DictionaryPage.into();
+    let dictionary_page_offset = specs
+        .iter()
+        .find(is_dictionary_page)
+        .map(|spec| spec.offset as i64);
+    let data_page_offset = specs
+        .iter()
+        .find(|spec| !is_dictionary_page(spec))
+        .map_or(start, |spec| spec.offset as i64);
     let num_values = specs
         .iter()
         .map(|spec| {
@@ -173,7 +205,17 @@ fn build_column_chunk(
 
     let statistics = specs.iter().map(|x| &x.statistics).collect::<Vec<_>>();
     let statistics = reduce(&statistics)?;
-    let statistics = statistics.map(|x| serialize_statistics(x.as_ref()));
+    let statistics = statistics.map(|x| {
+        let mut statistics = serialize_statistics(x.as_ref());
+        if let Some(length) = statistics_truncate_length {
+            truncate(
+                &mut statistics,
+                &descriptor.descriptor.primitive_type,
+                length,
+            );
+        }
+        statistics
+    });
 
     let (type_, _): (Type, Option<i32>) = descriptor.descriptor.primitive_type.physical_type.into();
 
@@ -188,7 +230,7 @@ fn build_column_chunk(
         key_value_metadata: None,
         data_page_offset,
         index_page_offset: None,
-        dictionary_page_offset: None,
+        dictionary_page_offset,
         statistics,
         encoding_stats: None,
         bloom_filter_offset: None,
@@ -196,7 +238,7 @@ fn build_column_chunk(
 
     Ok(ColumnChunk {
         file_path: None, // same file for now.
-        file_offset: data_page_offset + total_compressed_size,
+        file_offset: start + total_compressed_size,
         meta_data: Some(metadata),
         offset_index_offset: None,
         offset_index_length: None,
@@ -1,44 +1,81 @@
-use crate::compression::CompressionOptions;
+use std::sync::Arc;
+
+use crate::compression::{self, matching_codec, CompressionOptions, CustomCodec};
 use crate::error::{Error, Result};
-use crate::page::{CompressedDictPage, CompressedPage, DataPageHeader, EncodedDictPage};
-use crate::FallibleStreamingIterator;
-use crate::{
-    compression,
-    page::{CompressedDataPage, DataPage, EncodedPage},
+use crate::page::{CompressedDataPage, DataPage, EncodedPage};
+use crate::page::{
+    CompressedDictPage, CompressedPage, DataPageHeader, DataPageHeaderV2, EncodedDictPage,
 };
+use crate::FallibleStreamingIterator;
+
+use super::{DynIter, DynStreamingIterator, RowGroupIter};
+
+/// Returns the length of the (uncompressed) levels of the v2 data page `header` whose buffer
+/// has `length` bytes.
+fn v2_levels_byte_length(header: &DataPageHeaderV2, length: usize) -> Result<usize> {
+    let repetition: Option<usize> = header.repetition_levels_byte_length.try_into().ok();
+    let definition: Option<usize> = header.definition_levels_byte_length.try_into().ok();
+    repetition
+        .zip(definition)
+        .and_then(|(repetition, definition)| repetition.checked_add(definition))
+        .filter(|levels| *levels <= length)
+        .ok_or_else(|| {
+            Error::InvalidParameter(format!(
+                "The levels of a v2 data page ({} and {} bytes) must fit in its buffer of {} bytes",
+                header.repetition_levels_byte_length, header.definition_levels_byte_length, length
+            ))
+        })
+}
 
 /// Compresses a [`DataPage`] into a [`CompressedDataPage`].
+///
+/// The levels of v2 pages are kept uncompressed and their header declares whether their values
+/// are compressed.
 fn compress_data(
     page: DataPage,
     mut compressed_buffer: Vec<u8>,
     compression: CompressionOptions,
+    codec: Option<&dyn CustomCodec>,
 ) -> Result<CompressedDataPage> {
     let DataPage {
         mut buffer,
-        header,
+        mut header,
         dictionary_page,
         descriptor,
         selected_rows,
     } = page;
     let uncompressed_page_size = buffer.len();
     if compression != CompressionOptions::Uncompressed {
-        match &header {
+        match &mut header {
             DataPageHeader::V1(_) => {
-                compression::compress(compression, &buffer, &mut compressed_buffer)?;
+                compress_with(compression, codec, &buffer, &mut compressed_buffer)?;
             }
             DataPageHeader::V2(header) => {
-                let levels_byte_length = (header.repetition_levels_byte_length
-                    + header.definition_levels_byte_length)
-                    as usize;
+                let levels_byte_length = v2_levels_byte_length(header, uncompressed_page_size)?;
+                let start = compressed_buffer.len();
                 compressed_buffer.extend_from_slice(&buffer[..levels_byte_length]);
-                compression::compress(
+                compress_with(
                     compression,
+                    codec,
                     &buffer[levels_byte_length..],
                     &mut compressed_buffer,
                 )?;
+                // v2 pages can declare their values as uncompressed, which avoids storing
+                // values that do not benefit from compression (e.g. already compressed data).
+                // v1 and dictionary pages have no such flag and are always compressed.
+                let is_compressed = compressed_buffer.len() - start < uncompressed_page_size;
+                if !is_compressed {
+                    compressed_buffer.truncate(start + levels_byte_length);
+                    compressed_buffer.extend_from_slice(&buffer[levels_byte_length..]);
+                }
+                header.is_compressed = Some(is_compressed);
             }
         };
     } else {
+        if let DataPageHeader::V2(header) = &mut header {
+            v2_levels_byte_length(header, uncompressed_page_size)?;
+            header.is_compressed = Some(false);
+        }
         std::mem::swap(&mut buffer, &mut compressed_buffer);
     };
     Ok(CompressedDataPage::new_read(
@@ -52,10 +89,12 @@ fn compress_data(
     ))
 }
 
+/// Compresses an [`EncodedDictPage`] into a [`CompressedDictPage`].
 fn compress_dict(
     page: EncodedDictPage,
     mut compressed_buffer: Vec<u8>,
     compression: CompressionOptions,
+    codec: Option<&dyn CustomCodec>,
 ) -> Result<CompressedDictPage> {
     let EncodedDictPage {
         mut buffer,
@@ -63,7 +102,7 @@ fn compress_dict(
     } = page;
     let uncompressed_page_size = buffer.len();
     if compression != CompressionOptions::Uncompressed {
-        compression::compress(compression, &buffer, &mut compressed_buffer)?;
+        compress_with(compression, codec, &buffer, &mut compressed_buffer)?;
     } else {
         std::mem::swap(&mut buffer, &mut compressed_buffer);
     }
@@ -86,22 +125,46 @@ pub fn compress(
     page: EncodedPage,
     compressed_buffer: Vec<u8>,
     compression: CompressionOptions,
+) -> Result<CompressedPage> {
+    compress_with_codec(page, compressed_buffer, compression, None)
+}
+
+/// Compresses an [`EncodedPage`] like [`compress`], using `codec` instead of this crate's
+/// implementation when it matches `compression`.
+pub fn compress_with_codec(
+    page: EncodedPage,
+    compressed_buffer: Vec<u8>,
+    compression: CompressionOptions,
+    codec: Option<&dyn CustomCodec>,
 ) -> Result<CompressedPage> {
     match page {
         EncodedPage::Data(page) => {
-            compress_data(page, compressed_buffer, compression).map(CompressedPage::Data)
+            compress_data(page, compressed_buffer, compression, codec).map(CompressedPage::Data)
         }
         EncodedPage::Dict(page) => {
-            compress_dict(page, compressed_buffer, compression).map(CompressedPage::Dict)
+            compress_dict(page, compressed_buffer, compression, codec).map(CompressedPage::Dict)
         }
     }
 }
 
+fn compress_with(
+    compression: CompressionOptions,
+    codec: Option<&dyn CustomCodec>,
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+) -> Result<()> {
+    match matching_codec(codec, compression.into()) {
+        Some(codec) => codec.compress(input_buf, output_buf),
+        None => compression::compress(compression, input_buf, output_buf),
+    }
+}
+
 /// A [`FallibleStreamingIterator`] that consumes [`EncodedPage`] and yields [`CompressedPage`]
 /// holding a reusable buffer ([`Vec<u8>`]) for compression.
 pub struct Compressor<I: Iterator<Item = Result<EncodedPage>>> {
     iter: I,
     compression: CompressionOptions,
+    codec: Option<Arc<dyn CustomCodec>>,
     buffer: Vec<u8>,
     current: Option<CompressedPage>,
 }
@@ -114,9 +177,21 @@ impl<I: Iterator<Item = Result<EncodedPage>>> Compressor<I> {
 
     /// Creates a new [`Compressor`]
     pub fn new(iter: I, compression: CompressionOptions, buffer: Vec<u8>) -> Self {
+        Self::new_with_codec(iter, compression, buffer, None)
+    }
+
+    /// Creates a new [`Compressor`] that compresses pages with `codec` when it matches
+    /// `compression`.
+    pub fn new_with_codec(
+        iter: I,
+        compression: CompressionOptions,
+        buffer: Vec<u8>,
+        codec: Option<Arc<dyn CustomCodec>>,
+    ) -> Self {
         Self {
             iter,
             compression,
+            codec,
             buffer,
             current: None,
         }
@@ -129,7 +204,7 @@ impl<I: Iterator<Item = Result<EncodedPage>>> FallibleStreamingIterator for Comp
 
     fn advance(&mut self) -> std::result::Result<(), Self::Error> {
         let mut compressed_buffer = if let Some(page) = self.current.as_mut() {
-            std::mem::take(page.buffer())
+            std::mem::take(page.buffer_mut())
         } else {
             std::mem::take(&mut self.buffer)
         };
@@ -138,7 +213,16 @@ impl<I: Iterator<Item = Result<EncodedPage>>> FallibleStreamingIterator for Comp
         let next = self
             .iter
             .next()
-            .map(|x| x.and_then(|page| compress(page, compressed_buffer, self.compression)))
+            .map(|x| {
+                x.and_then(|page| {
+                    compress_with_codec(
+                        page,
+                        compressed_buffer,
+                        self.compression,
+                        self.codec.as_deref(),
+                    )
+                })
+            })
             .transpose()?;
         self.current = next;
         Ok(())
@@ -148,3 +232,37 @@ impl<I: Iterator<Item = Result<EncodedPage>>> FallibleStreamingIterator for Comp
         self.current.as_ref()
     }
 }
+
+/// Returns a [`RowGroupIter`] that compresses the pages of each column of `columns` with the
+/// [`CompressionOptions`] of the same index in `compression`, i.e. keyed by the columns' leaf
+/// index in the schema.
+///
+/// # Errors
+/// Errors iff `columns` and `compression` have different lengths
+pub fn compress_row_group<'a, I>(
+    columns: Vec<I>,
+    compression: &[CompressionOptions],
+) -> Result<RowGroupIter<'a, Error>>
+where
+    I: Iterator<Item = Result<EncodedPage>> + Send + Sync + 'a,
+{
+    if columns.len() != compression.len() {
+        return Err(Error::InvalidParameter(format!(
+            "A row group with {} columns requires {} compression options, but {} were given",
+            columns.len(),
+            columns.len(),
+            compression.len()
+        )));
+    }
+    let columns = columns
+        .into_iter()
+        .zip(compression.to_vec())
+        .map(|(pages, compression)| {
+            Ok(DynStreamingIterator::new(Compressor::new(
+                pages,
+                compression,
+                vec![],
+            )))
+        });
+    Ok(DynIter::new(columns))
+}
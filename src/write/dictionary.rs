@@ -0,0 +1,224 @@
+//! Helpers to write dictionary-encoded column chunks: a dictionary page
+//! ([`EncodedDictPage`](crate::page::EncodedDictPage)) followed by data pages whose values are
+//! indices into the dictionary ([`Encoding::RleDictionary`]).
+use parquet_format_async_temp::Statistics as ParquetStatistics;
+
+use crate::encoding::hybrid_rle::encode_u32;
+use crate::encoding::Encoding;
+use crate::error::{Error, Result};
+use crate::metadata::Descriptor;
+use crate::page::{DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2};
+
+use super::Version;
+
+/// The encoded repetition and definition levels of a data page.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Levels {
+    /// The RLE-hybrid encoded repetition levels, without a length prefix.
+    /// Empty when the column is not repeated.
+    pub repetition: Vec<u8>,
+    /// The RLE-hybrid encoded definition levels, without a length prefix.
+    /// Empty when the column is required and not nested.
+    pub definition: Vec<u8>,
+    /// The number of values of the page, including nulls, i.e. the number of levels
+    pub num_values: usize,
+    /// The number of rows of the page
+    pub num_rows: usize,
+}
+
+/// Encodes `indices` of a dictionary page as the values of a data page encoded with
+/// [`Encoding::RleDictionary`]: the bit width, as one byte, followed by the RLE-hybrid encoded
+/// indices.
+/// # Panics
+/// Panics iff `num_bits` is larger than 32 or an index does not fit in `num_bits`.
+pub fn encode_indices(indices: &[u32], num_bits: u8, buffer: &mut Vec<u8>) {
+    assert!(num_bits <= 32, "indices have at most 32 bits");
+    assert!(
+        indices
+            .iter()
+            .all(|x| num_bits == 32 || *x >> num_bits == 0),
+        "all indices must fit in num_bits"
+    );
+    buffer.push(num_bits);
+    // writing to a `Vec` is infallible
+    encode_u32(buffer, indices.iter().copied(), num_bits).unwrap();
+}
+
+/// Returns a data page of a dictionary-encoded column, whose non-null values are the entries
+/// `indices` of the dictionary, with the header of `version`.
+///
+/// `num_bits` is usually [`get_bit_width`](crate::encoding::get_bit_width) of the largest index.
+///
+/// The levels are prefixed by their length in V1 pages and their lengths are written
+/// in the header of V2 pages.
+/// # Error
+/// Errors iff `num_bits` is larger than 32, an index does not fit in `num_bits`, or there are
+/// more `indices` than `levels.num_values`.
+pub fn dict_data_page(
+    indices: &[u32],
+    num_bits: u8,
+    levels: Levels,
+    statistics: Option<ParquetStatistics>,
+    descriptor: Descriptor,
+    version: Version,
+) -> Result<DataPage> {
+    let Levels {
+        repetition,
+        definition,
+        num_values,
+        num_rows,
+    } = levels;
+    if num_bits > 32 || indices.iter().any(|x| num_bits < 32 && *x >> num_bits != 0) {
+        return Err(Error::InvalidParameter(format!(
+            "The indices of a dictionary-encoded page must fit in {} bits, of at most 32",
+            num_bits
+        )));
+    }
+    if indices.len() > num_values {
+        return Err(Error::InvalidParameter(format!(
+            "A page of {} values can't have {} non-null indices",
+            num_values,
+            indices.len()
+        )));
+    }
+
+    let mut buffer = vec![];
+    let header = match version {
+        Version::V1 => {
+            for (levels, max_level) in [
+                (&repetition, descriptor.max_rep_level),
+                (&definition, descriptor.max_def_level),
+            ] {
+                if max_level > 0 {
+                    buffer.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+                    buffer.extend_from_slice(levels);
+                }
+            }
+            DataPageHeader::V1(DataPageHeaderV1 {
+                num_values: num_values as i32,
+                encoding: Encoding::RleDictionary.into(),
+                definition_level_encoding: Encoding::Rle.into(),
+                repetition_level_encoding: Encoding::Rle.into(),
+                statistics,
+            })
+        }
+        Version::V2 => {
+            buffer.extend_from_slice(&repetition);
+            buffer.extend_from_slice(&definition);
+            DataPageHeader::V2(DataPageHeaderV2 {
+                num_values: num_values as i32,
+                num_nulls: (num_values - indices.len()) as i32,
+                num_rows: num_rows as i32,
+                encoding: Encoding::RleDictionary.into(),
+                definition_levels_byte_length: definition.len() as i32,
+                repetition_levels_byte_length: repetition.len() as i32,
+                is_compressed: None,
+                statistics,
+            })
+        }
+    };
+    encode_indices(indices, num_bits, &mut buffer);
+
+    Ok(DataPage::new(
+        header,
+        buffer,
+        None,
+        descriptor,
+        Some(num_rows),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hybrid_rle::HybridRleDecoder;
+    use crate::page::split_buffer;
+    use crate::schema::types::{PhysicalType, PrimitiveType};
+
+    #[test]
+    fn indices_round_trip() {
+        for num_bits in [0u8, 1, 3, 8, 17, 32] {
+            let max = if num_bits == 32 {
+                u32::MAX
+            } else {
+                (1u32 << num_bits) - 1
+            };
+            let indices = (0..1000u32)
+                .map(|x| x.wrapping_mul(7919) & max)
+                .collect::<Vec<_>>();
+            let mut buffer = vec![];
+            encode_indices(&indices, num_bits, &mut buffer);
+
+            assert_eq!(buffer[0], num_bits);
+            let decoded = HybridRleDecoder::try_new(&buffer[1..], num_bits as u32, indices.len())
+                .unwrap()
+                .collect::<Vec<_>>();
+            assert_eq!(decoded, indices);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_too_large() {
+        encode_indices(&[0, 4], 2, &mut vec![]);
+    }
+
+    #[test]
+    fn invalid_indices() {
+        let descriptor = Descriptor {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32),
+            max_def_level: 1,
+            max_rep_level: 0,
+        };
+        let levels = Levels {
+            num_values: 1,
+            num_rows: 1,
+            ..Default::default()
+        };
+        for (indices, num_bits) in [(&[0, 1][..], 1), (&[4][..], 2), (&[0][..], 33)] {
+            let page = dict_data_page(
+                indices,
+                num_bits,
+                levels.clone(),
+                None,
+                descriptor.clone(),
+                Version::V2,
+            );
+            assert!(matches!(page, Err(Error::InvalidParameter(_))));
+        }
+    }
+
+    #[test]
+    fn levels_v1_and_v2() {
+        let descriptor = Descriptor {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32),
+            max_def_level: 1,
+            max_rep_level: 0,
+        };
+        let levels = Levels {
+            repetition: vec![],
+            definition: vec![3, 0b101],
+            num_values: 3,
+            num_rows: 3,
+        };
+
+        for version in [Version::V1, Version::V2] {
+            let page = dict_data_page(
+                &[1, 0],
+                1,
+                levels.clone(),
+                None,
+                descriptor.clone(),
+                version,
+            )
+            .unwrap();
+            assert_eq!(page.encoding(), Encoding::RleDictionary);
+            assert_eq!(page.num_values(), 3);
+
+            let (rep, def, values) = split_buffer(&page);
+            assert!(rep.is_empty());
+            assert_eq!(def, levels.definition);
+            assert_eq!(values[0], 1);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::Write;
+use std::io::{Read, Seek, SeekFrom, Write};
 
 use parquet_format_async_temp::thrift::protocol::TCompactOutputProtocol;
 use parquet_format_async_temp::thrift::protocol::TOutputProtocol;
@@ -7,16 +7,84 @@ use parquet_format_async_temp::RowGroup;
 
 use crate::{
     error::{Error, Result},
-    metadata::SchemaDescriptor,
-    FOOTER_SIZE, PARQUET_MAGIC,
+    metadata::{FileMetaData as ParquetMetaData, SchemaDescriptor},
+    page::CompressedPage,
+    read::read_metadata,
+    FallibleStreamingIterator, FOOTER_SIZE, PARQUET_MAGIC,
 };
 
 use super::indexes::{write_column_index, write_offset_index};
 use super::page::PageWriteSpec;
-use super::{row_group::write_row_group, RowGroupIter, WriteOptions};
+use super::{
+    row_group::write_row_group, DynIter, DynStreamingIterator, RowGroupIter, WriteOptions,
+};
+
+#[cfg(feature = "bloom_filter")]
+use crate::bloom_filter::{try_new_builder, BloomFilterBuilder};
+
+#[cfg(feature = "bloom_filter")]
+use super::BloomFilterOptions;
 
 pub use crate::metadata::KeyValue;
 
+/// A [`FallibleStreamingIterator`] of owned [`CompressedPage`]s
+struct CompressedPages {
+    pages: std::vec::IntoIter<CompressedPage>,
+    current: Option<CompressedPage>,
+}
+
+impl FallibleStreamingIterator for CompressedPages {
+    type Item = CompressedPage;
+    type Error = Error;
+
+    fn advance(&mut self) -> Result<()> {
+        self.current = self.pages.next();
+        Ok(())
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        self.current.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pages.size_hint()
+    }
+}
+
+/// A [`FallibleStreamingIterator`] of the pages of a column that inserts their values into the
+/// column's [`BloomFilterBuilder`], if any, as they are written.
+#[cfg(feature = "bloom_filter")]
+struct BloomFilterPages<'a, I> {
+    pages: I,
+    builder: Option<&'a mut BloomFilterBuilder>,
+}
+
+#[cfg(feature = "bloom_filter")]
+impl<'a, I, E> FallibleStreamingIterator for BloomFilterPages<'a, I>
+where
+    I: FallibleStreamingIterator<Item = CompressedPage, Error = E>,
+    Error: From<E>,
+{
+    type Item = CompressedPage;
+    type Error = Error;
+
+    fn advance(&mut self) -> Result<()> {
+        self.pages.advance()?;
+        if let (Some(page), Some(builder)) = (self.pages.get(), self.builder.as_mut()) {
+            builder.insert_page(page)?;
+        }
+        Ok(())
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        self.pages.get()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pages.size_hint()
+    }
+}
+
 pub(super) fn start_file<W: Write>(writer: &mut W) -> Result<u64> {
     writer.write_all(&PARQUET_MAGIC)?;
     Ok(PARQUET_MAGIC.len() as u64)
@@ -52,6 +120,14 @@ pub struct FileWriter<W: Write> {
     offset: u64,
     row_groups: Vec<RowGroup>,
     page_specs: Vec<Vec<Vec<PageWriteSpec>>>,
+    key_value_metadata: Option<Vec<KeyValue>>,
+    /// Whether statistics are written, per column
+    column_statistics: Vec<bool>,
+    /// The options of the bloom filter of each column, if any
+    #[cfg(feature = "bloom_filter")]
+    bloom_filters: Vec<Option<BloomFilterOptions>>,
+    /// The metadata written to the footer, set by `end`
+    file_metadata: Option<ParquetMetaData>,
 }
 
 // Accessors
@@ -65,6 +141,24 @@ impl<W: Write> FileWriter<W> {
     pub fn schema(&self) -> &SchemaDescriptor {
         &self.schema
     }
+
+    /// The key-value metadata that is written to the footer of this file
+    /// (see [`FileWriter::add_key_value`]).
+    pub fn metadata(&self) -> &[KeyValue] {
+        self.key_value_metadata.as_deref().unwrap_or_default()
+    }
+
+    /// Whether the statistics of the (leaf) column `column` are written
+    /// (see [`FileWriter::with_column_statistics`]).
+    pub fn writes_statistics(&self, column: usize) -> bool {
+        self.column_statistics.get(column).copied().unwrap_or(true)
+    }
+
+    /// The metadata written to the footer of this file, or `None` if [`FileWriter::end`]
+    /// was not called.
+    pub fn file_metadata(&self) -> Option<&ParquetMetaData> {
+        self.file_metadata.as_ref()
+    }
 }
 
 impl<W: Write> FileWriter<W> {
@@ -83,9 +177,84 @@ impl<W: Write> FileWriter<W> {
             offset: 0,
             row_groups: vec![],
             page_specs: vec![],
+            key_value_metadata: None,
+            column_statistics: vec![],
+            #[cfg(feature = "bloom_filter")]
+            bloom_filters: vec![],
+            file_metadata: None,
         }
     }
 
+    /// Adds the key-value pair `key`, `value` to the key-value metadata written to the footer
+    /// of this file, replacing the value of `key` if it was already added.
+    ///
+    /// The key-value metadata passed to [`FileWriter::end`], if any, takes precedence.
+    pub fn add_key_value(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        let value = Some(value.into());
+        let metadata = self.key_value_metadata.get_or_insert_with(Vec::new);
+        match metadata.iter_mut().find(|x| x.key == key) {
+            Some(existing) => existing.value = value,
+            None => metadata.push(KeyValue { key, value }),
+        }
+        self
+    }
+
+    /// Sets whether statistics are written for each (leaf) column of the schema, e.g. to skip
+    /// the statistics of columns whose statistics are expensive to compute.
+    ///
+    /// The statistics of a column whose value is `false` are omitted entirely: they are removed
+    /// from the headers of its pages and are neither written to its column chunks nor to its
+    /// column index. Columns are written with statistics by default.
+    ///
+    /// Note that [`read_columns_indexes`](crate::read::read_columns_indexes) reads the column
+    /// indexes of column chunks that all have one.
+    /// # Error
+    /// Errors iff `column_statistics` does not have one value per column of the schema.
+    pub fn with_column_statistics(mut self, column_statistics: Vec<bool>) -> Result<Self> {
+        let num_columns = self.schema.columns().len();
+        if column_statistics.len() != num_columns {
+            return Err(Error::InvalidParameter(format!(
+                "The schema has {} columns but statistics were enabled or disabled for {}",
+                num_columns,
+                column_statistics.len()
+            )));
+        }
+        self.column_statistics = column_statistics;
+        Ok(self)
+    }
+
+    /// Sets the options of the bloom filter of each (leaf) column of the schema, or `None` for
+    /// columns without bloom filters. No bloom filters are written by default.
+    ///
+    /// The bloom filter of each column chunk is built from the values of its pages, as they are
+    /// written by [`FileWriter::write`], and is written after its row group.
+    /// Pages are decompressed with this crate's implementation of their compression, i.e.
+    /// columns compressed with an incompatible [`CustomCodec`](crate::compression::CustomCodec)
+    /// can't have bloom filters.
+    /// # Error
+    /// Errors iff `bloom_filters` does not have one value per column of the schema, a boolean
+    /// column has options, or the false-positive probability of an option is not in `(0, 1)`.
+    #[cfg(feature = "bloom_filter")]
+    pub fn with_bloom_filters(
+        mut self,
+        bloom_filters: Vec<Option<BloomFilterOptions>>,
+    ) -> Result<Self> {
+        let columns = self.schema.columns();
+        if bloom_filters.len() != columns.len() {
+            return Err(Error::InvalidParameter(format!(
+                "The schema has {} columns but {} bloom filter options were given",
+                columns.len(),
+                bloom_filters.len()
+            )));
+        }
+        for (column, options) in columns.iter().zip(bloom_filters.iter()) {
+            try_new_builder(column, options.as_ref())?;
+        }
+        self.bloom_filters = bloom_filters;
+        Ok(self)
+    }
+
     /// Writes the header of the file
     pub fn start(&mut self) -> Result<()> {
         self.offset = start_file(&mut self.writer)? as u64;
@@ -106,20 +275,167 @@ impl<W: Write> FileWriter<W> {
             ));
         }
         let ordinal = self.row_groups.len();
-        let (group, specs, size) = write_row_group(
+        let column_statistics = (0..self.schema.columns().len())
+            .map(|column| self.writes_statistics(column))
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "bloom_filter")]
+        let mut builders = self
+            .bloom_filters
+            .iter()
+            .zip(self.schema.columns())
+            .map(|(options, column)| try_new_builder(column, options.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        #[cfg(feature = "bloom_filter")]
+        let row_group = {
+            let mut builders = builders.iter_mut();
+            DynIter::new(row_group.map(move |pages| {
+                let builder = builders.next().and_then(Option::as_mut);
+                pages
+                    .map_err(Error::from)
+                    .map(|pages| DynStreamingIterator::new(BloomFilterPages { pages, builder }))
+            }))
+        };
+
+        // the pages of each column are now `Result<_, Error>`
+        #[cfg(feature = "bloom_filter")]
+        let written = write_row_group::<_, Error>(
+            &mut self.writer,
+            self.offset,
+            self.schema.columns(),
+            row_group,
+            ordinal,
+            &self.options,
+            &column_statistics,
+        );
+        #[cfg(not(feature = "bloom_filter"))]
+        let written = write_row_group(
             &mut self.writer,
             self.offset,
             self.schema.columns(),
             row_group,
             ordinal,
-        )?;
+            &self.options,
+            &column_statistics,
+        );
+        let (group, specs, size) = written?;
         self.offset += size;
         self.row_groups.push(group);
         self.page_specs.push(specs);
+
+        #[cfg(feature = "bloom_filter")]
+        for (column, builder) in builders.into_iter().enumerate() {
+            if let Some(builder) = builder {
+                self.write_bloom_filter(column, &builder.into_filter())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a row group of already compressed pages, one [`Vec`] of pages per column of the
+    /// schema, e.g. pages read with [`read_compressed_pages`](crate::read::read_compressed_pages)
+    /// to copy row groups between files without decompressing them.
+    ///
+    /// Pages are written verbatim and, like in [`FileWriter::write`], the metadata of the
+    /// column chunks, their statistics and page indexes are computed from the headers of
+    /// the pages.
+    /// # Error
+    /// Errors, before writing any page, iff the row group does not have one column per column
+    /// of the schema, or a column has pages compressed with different codecs, a dictionary page
+    /// that is not its first page, or data pages of another column.
+    pub fn write_compressed_pages(&mut self, columns: Vec<Vec<CompressedPage>>) -> Result<()> {
+        let descriptors = self.schema.columns();
+        if columns.len() != descriptors.len() {
+            return Err(Error::InvalidParameter(format!(
+                "The row group has {} columns but the schema has {}",
+                columns.len(),
+                descriptors.len()
+            )));
+        }
+        for (pages, column) in columns.iter().zip(descriptors) {
+            let name = &column.descriptor.primitive_type.field_info.name;
+            if let Some(first) = pages.first() {
+                if pages
+                    .iter()
+                    .any(|page| page.compression() != first.compression())
+                {
+                    return Err(Error::InvalidParameter(format!(
+                        "All pages of the column \"{}\" must be compressed with the same codec",
+                        name
+                    )));
+                }
+            }
+            if pages
+                .iter()
+                .skip(1)
+                .any(|page| matches!(page, CompressedPage::Dict(_)))
+            {
+                return Err(Error::InvalidParameter(format!(
+                    "The dictionary page of the column \"{}\" must be its first page",
+                    name
+                )));
+            }
+            let other_column = |page: &CompressedPage| match page {
+                CompressedPage::Data(page) => page.descriptor != column.descriptor,
+                CompressedPage::Dict(_) => false,
+            };
+            if pages.iter().any(other_column) {
+                return Err(Error::InvalidParameter(format!(
+                    "The data pages of the column \"{}\" must have its descriptor",
+                    name
+                )));
+            }
+        }
+
+        let row_group = columns.into_iter().map(|pages| {
+            Ok(DynStreamingIterator::new(CompressedPages {
+                pages: pages.into_iter(),
+                current: None,
+            }))
+        });
+        self.write::<Error>(DynIter::new(row_group))
+    }
+
+    /// Writes the bloom filter of the column `column` of the last written row group,
+    /// and records its offset in the column's metadata.
+    /// # Error
+    /// Errors if no row group was written or if `column` is out of bounds.
+    #[cfg(feature = "bloom_filter")]
+    pub fn write_bloom_filter(
+        &mut self,
+        column: usize,
+        filter: &crate::bloom_filter::BloomFilter,
+    ) -> Result<()> {
+        let metadata = self
+            .row_groups
+            .last_mut()
+            .ok_or_else(|| {
+                Error::General(
+                    "You must write a row group before writing its bloom filters".to_string(),
+                )
+            })?
+            .columns
+            .get_mut(column)
+            .and_then(|column| column.meta_data.as_mut())
+            .ok_or_else(|| {
+                Error::General(format!(
+                    "The row group has no column {} to write a bloom filter for",
+                    column
+                ))
+            })?;
+
+        metadata.bloom_filter_offset = Some(self.offset as i64);
+        self.offset += crate::bloom_filter::write(&mut self.writer, filter.bitset())?;
         Ok(())
     }
 
     /// Writes the footer of the parquet file. Returns the total size of the file.
+    ///
+    /// The metadata of the footer is then available in [`FileWriter::file_metadata`], e.g. to
+    /// get the offsets of the column chunks without reading the file.
+    ///
+    /// When appending to an existing file (see [`FileWriter::from_existing`]), its key-value
+    /// metadata is kept unless `key_value_metadata` is [`Some`].
     pub fn end(&mut self, key_value_metadata: Option<Vec<KeyValue>>) -> Result<u64> {
         // compute file stats
         let num_rows = self.row_groups.iter().map(|group| group.num_rows).sum();
@@ -130,16 +446,26 @@ impl<W: Write> FileWriter<W> {
                 .iter_mut()
                 .zip(self.page_specs.iter())
                 .try_for_each(|(group, pages)| {
-                    group.columns.iter_mut().zip(pages.iter()).try_for_each(
-                        |(column, pages)| {
+                    group
+                        .columns
+                        .iter_mut()
+                        .zip(pages.iter())
+                        .enumerate()
+                        .filter(|(index, _)| {
+                            self.column_statistics.get(*index).copied().unwrap_or(true)
+                        })
+                        .try_for_each(|(_, (column, pages))| {
                             let offset = self.offset;
                             column.column_index_offset = Some(offset as i64);
-                            self.offset += write_column_index(&mut self.writer, pages)?;
+                            self.offset += write_column_index(
+                                &mut self.writer,
+                                pages,
+                                self.options.statistics_truncate_length,
+                            )?;
                             let length = self.offset - offset;
                             column.column_index_length = Some(length as i32);
                             Result::Ok(())
-                        },
-                    )?;
+                        })?;
                     Result::Ok(())
                 })?;
         };
@@ -168,14 +494,16 @@ impl<W: Write> FileWriter<W> {
             self.schema.clone().into_thrift(),
             num_rows,
             self.row_groups.clone(),
-            key_value_metadata,
+            key_value_metadata.or_else(|| self.key_value_metadata.clone()),
             self.created_by.clone(),
             None,
             None,
             None,
         );
 
+        let file_metadata = ParquetMetaData::try_from_thrift(metadata.clone())?;
         let len = end_file(&mut self.writer, metadata)?;
+        self.file_metadata = Some(file_metadata);
         Ok(self.offset + len)
     }
 
@@ -183,6 +511,75 @@ impl<W: Write> FileWriter<W> {
     pub fn into_inner(self) -> W {
         self.writer
     }
+
+    /// Returns the underlying writer and the metadata written to the footer of the file, or
+    /// `None` if [`FileWriter::end`] was not called.
+    pub fn into_inner_and_metadata(self) -> (W, Option<ParquetMetaData>) {
+        (self.writer, self.file_metadata)
+    }
+}
+
+impl<W: Read + Write + Seek> FileWriter<W> {
+    /// Returns a new [`FileWriter`] that appends row groups to the existing parquet file
+    /// `writer`, whose metadata is `existing_meta`.
+    ///
+    /// The writer is positioned at the start of the existing footer, which is overwritten
+    /// by the new row groups. On [`FileWriter::end`], the footer is written with the existing
+    /// row groups followed by the new ones. No call to [`FileWriter::start`] is needed.
+    ///
+    /// The file is written with the schema and `created_by` of `existing_meta` and the new row
+    /// groups are written according to `options`, whose version is also the version of the file.
+    ///
+    /// Since the new footer may be shorter than the existing one when no row group is written,
+    /// files should be truncated to the size returned by [`FileWriter::end`].
+    /// # Errors
+    /// Errors iff the footer of `writer` cannot be read or does not have the same schema and
+    /// row groups as `existing_meta`.
+    pub fn from_existing(
+        mut writer: W,
+        existing_meta: &ParquetMetaData,
+        options: WriteOptions,
+    ) -> Result<Self> {
+        let metadata = read_metadata(&mut writer)?;
+        if metadata.schema() != existing_meta.schema()
+            || metadata.row_groups.len() != existing_meta.row_groups.len()
+        {
+            return Err(Error::InvalidParameter(
+                "The metadata does not match the footer of the file it appends to".to_string(),
+            ));
+        }
+
+        // SPEC: the footer is [metadata][metadata length: 4 bytes][magic]
+        let end = writer.seek(SeekFrom::End(-(FOOTER_SIZE as i64)))?;
+        let mut metadata_len = [0; 4];
+        writer.read_exact(&mut metadata_len)?;
+        let metadata_len = i32::from_le_bytes(metadata_len) as u64;
+        let offset = writer.seek(SeekFrom::Start(end - metadata_len))?;
+
+        let row_groups = existing_meta
+            .row_groups
+            .iter()
+            .cloned()
+            .map(|group| group.into_thrift())
+            .collect::<Vec<_>>();
+        // the indexes of existing row groups are already written
+        let page_specs = row_groups.iter().map(|_| vec![]).collect();
+
+        Ok(Self {
+            writer,
+            schema: existing_meta.schema().clone(),
+            options,
+            created_by: existing_meta.created_by.clone(),
+            offset,
+            row_groups,
+            page_specs,
+            key_value_metadata: existing_meta.key_value_metadata.clone(),
+            column_statistics: vec![],
+            #[cfg(feature = "bloom_filter")]
+            bloom_filters: vec![],
+            file_metadata: None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -3,24 +3,34 @@ use parquet_format_async_temp::ColumnIndex;
 use parquet_format_async_temp::OffsetIndex;
 use parquet_format_async_temp::PageLocation;
 
+use std::cmp::Ordering;
+
 use crate::error::{Error, Result};
 pub use crate::metadata::KeyValue;
-use crate::statistics::serialize_statistics;
+use crate::metadata::{get_sort_order, SortOrder};
+use crate::schema::types::{PhysicalType, PrimitiveType};
+use crate::statistics::*;
+use crate::types::NativeType;
 
 use crate::write::page::{is_data_page, PageWriteSpec};
+use crate::write::statistics::{is_utf8, truncate_max, truncate_min};
 
-pub fn serialize_column_index(pages: &[PageWriteSpec]) -> Result<ColumnIndex> {
+pub fn serialize_column_index(
+    pages: &[PageWriteSpec],
+    statistics_truncate_length: Option<usize>,
+) -> Result<ColumnIndex> {
     let mut null_pages = Vec::with_capacity(pages.len());
     let mut min_values = Vec::with_capacity(pages.len());
     let mut max_values = Vec::with_capacity(pages.len());
     let mut null_counts = Vec::with_capacity(pages.len());
+    let mut non_null_pages = Vec::with_capacity(pages.len());
 
     pages
         .iter()
         .filter(|x| is_data_page(x))
         .try_for_each(|spec| {
-            if let Some(stats) = &spec.statistics {
-                let stats = serialize_statistics(stats.as_ref());
+            if let Some(statistics) = &spec.statistics {
+                let stats = serialize_statistics(statistics.as_ref());
 
                 let null_count = stats.null_count.ok_or_else(|| {
                     Error::OutOfSpec("null count of a page is required".to_string())
@@ -32,13 +42,27 @@ pub fn serialize_column_index(pages: &[PageWriteSpec]) -> Result<ColumnIndex> {
                     max_values.push(vec![0]);
                     null_pages.push(true)
                 } else {
-                    min_values.push(stats.min_value.ok_or_else(|| {
+                    let min = stats.min_value.ok_or_else(|| {
                         Error::OutOfSpec("min value of a page is required".to_string())
-                    })?);
-                    max_values.push(stats.max_value.ok_or_else(|| {
+                    })?;
+                    let max = stats.max_value.ok_or_else(|| {
                         Error::OutOfSpec("max value of a page is required".to_string())
-                    })?);
-                    null_pages.push(false)
+                    })?;
+                    let binary = statistics.as_any().downcast_ref::<BinaryStatistics>();
+                    let (min, max) = match (statistics_truncate_length, binary) {
+                        (Some(length), Some(binary)) => {
+                            let utf8 = is_utf8(&binary.primitive_type);
+                            // SPEC: non-null pages require a max value, which is kept whole
+                            // when it can't be truncated
+                            let max = truncate_max(&max, length, utf8).unwrap_or(max);
+                            (truncate_min(&min, length, utf8), max)
+                        }
+                        _ => (min, max),
+                    };
+                    min_values.push(min);
+                    max_values.push(max);
+                    null_pages.push(false);
+                    non_null_pages.push(statistics.as_ref());
                 };
 
                 Result::Ok(())
@@ -52,11 +76,147 @@ pub fn serialize_column_index(pages: &[PageWriteSpec]) -> Result<ColumnIndex> {
         null_pages,
         min_values,
         max_values,
-        boundary_order: BoundaryOrder::UNORDERED,
+        boundary_order: boundary_order(&non_null_pages),
         null_counts: Some(null_counts),
     })
 }
 
+/// Returns whether the minimum and maximum values of consecutive pages are both ascending or
+/// both descending, ignoring pages whose values are all null.
+/// Consecutive pages may have equal values, and a column with less than 2 non-null pages is
+/// ascending.
+fn boundary_order(pages: &[&dyn Statistics]) -> BoundaryOrder {
+    let physical_type = match pages.first() {
+        Some(page) => page.physical_type(),
+        None => return BoundaryOrder::ASCENDING,
+    };
+    match physical_type {
+        PhysicalType::Boolean => {
+            let bounds = pages
+                .iter()
+                .map(|page| page.as_any().downcast_ref::<BooleanStatistics>().unwrap())
+                .filter_map(|page| page.min_value.zip(page.max_value));
+            order_of(bounds, |x, y| x.cmp(y))
+        }
+        PhysicalType::Int32 => match primitive_sort_order::<i32>(pages) {
+            SortOrder::Signed => primitive_order::<i32, _>(pages, |x, y| x.cmp(y)),
+            SortOrder::Unsigned => {
+                primitive_order::<i32, _>(pages, |x, y| (*x as u32).cmp(&(*y as u32)))
+            }
+            SortOrder::Undefined => BoundaryOrder::UNORDERED,
+        },
+        PhysicalType::Int64 => match primitive_sort_order::<i64>(pages) {
+            SortOrder::Signed => primitive_order::<i64, _>(pages, |x, y| x.cmp(y)),
+            SortOrder::Unsigned => {
+                primitive_order::<i64, _>(pages, |x, y| (*x as u64).cmp(&(*y as u64)))
+            }
+            SortOrder::Undefined => BoundaryOrder::UNORDERED,
+        },
+        PhysicalType::Float => match primitive_sort_order::<f32>(pages) {
+            SortOrder::Signed => primitive_order::<f32, _>(pages, |x, y| x.ord(y)),
+            _ => BoundaryOrder::UNORDERED,
+        },
+        PhysicalType::Double => match primitive_sort_order::<f64>(pages) {
+            SortOrder::Signed => primitive_order::<f64, _>(pages, |x, y| x.ord(y)),
+            _ => BoundaryOrder::UNORDERED,
+        },
+        // the sort order of `INT96` is undefined
+        PhysicalType::Int96 => BoundaryOrder::UNORDERED,
+        PhysicalType::ByteArray => {
+            let pages = pages
+                .iter()
+                .map(|page| page.as_any().downcast_ref::<BinaryStatistics>().unwrap())
+                .collect::<Vec<_>>();
+            match sort_order(&pages[0].primitive_type) {
+                SortOrder::Unsigned => order_of(
+                    pages
+                        .iter()
+                        .filter_map(|page| page.min_value.as_ref().zip(page.max_value.as_ref())),
+                    |x, y| x.cmp(y),
+                ),
+                // byte arrays of signed values (e.g. decimals) are not compared
+                _ => BoundaryOrder::UNORDERED,
+            }
+        }
+        PhysicalType::FixedLenByteArray(_) => {
+            let pages = pages
+                .iter()
+                .map(|page| page.as_any().downcast_ref::<FixedLenStatistics>().unwrap())
+                .collect::<Vec<_>>();
+            match sort_order(&pages[0].primitive_type) {
+                SortOrder::Unsigned => order_of(
+                    pages
+                        .iter()
+                        .filter_map(|page| page.min_value.as_ref().zip(page.max_value.as_ref())),
+                    |x, y| x.cmp(y),
+                ),
+                _ => BoundaryOrder::UNORDERED,
+            }
+        }
+    }
+}
+
+fn sort_order(primitive_type: &PrimitiveType) -> SortOrder {
+    get_sort_order(
+        &primitive_type.logical_type,
+        &primitive_type.converted_type,
+        &primitive_type.physical_type,
+    )
+}
+
+fn primitive_sort_order<T: NativeType>(pages: &[&dyn Statistics]) -> SortOrder {
+    let page = pages[0]
+        .as_any()
+        .downcast_ref::<PrimitiveStatistics<T>>()
+        .unwrap();
+    sort_order(&page.primitive_type)
+}
+
+fn primitive_order<T: NativeType, F: Fn(&T, &T) -> Ordering>(
+    pages: &[&dyn Statistics],
+    cmp: F,
+) -> BoundaryOrder {
+    let bounds = pages
+        .iter()
+        .map(|page| {
+            page.as_any()
+                .downcast_ref::<PrimitiveStatistics<T>>()
+                .unwrap()
+        })
+        .filter_map(|page| page.min_value.zip(page.max_value))
+        .collect::<Vec<_>>();
+    // NaN is not ordered with respect to other values
+    if bounds.iter().any(|(min, max)| min.is_nan() || max.is_nan()) {
+        return BoundaryOrder::UNORDERED;
+    }
+    order_of(bounds.into_iter(), cmp)
+}
+
+/// Returns the [`BoundaryOrder`] of the `(min, max)` of consecutive pages, compared with `cmp`.
+fn order_of<T, I: Iterator<Item = (T, T)>, F: Fn(&T, &T) -> Ordering>(
+    bounds: I,
+    cmp: F,
+) -> BoundaryOrder {
+    let mut is_ascending = true;
+    let mut is_descending = true;
+    let mut previous: Option<(T, T)> = None;
+    for (min, max) in bounds {
+        if let Some((previous_min, previous_max)) = &previous {
+            let (min_order, max_order) = (cmp(previous_min, &min), cmp(previous_max, &max));
+            is_ascending &= min_order != Ordering::Greater && max_order != Ordering::Greater;
+            is_descending &= min_order != Ordering::Less && max_order != Ordering::Less;
+        }
+        previous = Some((min, max));
+    }
+    if is_ascending {
+        BoundaryOrder::ASCENDING
+    } else if is_descending {
+        BoundaryOrder::DESCENDING
+    } else {
+        BoundaryOrder::UNORDERED
+    }
+}
+
 pub fn serialize_offset_index(pages: &[PageWriteSpec]) -> Result<OffsetIndex> {
     let mut first_row_index = 0;
     let page_locations = pages
@@ -81,3 +241,87 @@ pub fn serialize_offset_index(pages: &[PageWriteSpec]) -> Result<OffsetIndex> {
 
     Ok(OffsetIndex { page_locations })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::schema::types::PrimitiveConvertedType;
+
+    use super::*;
+
+    fn page(primitive_type: &PrimitiveType, min: i32, max: i32) -> PrimitiveStatistics<i32> {
+        PrimitiveStatistics {
+            primitive_type: primitive_type.clone(),
+            null_count: Some(0),
+            distinct_count: None,
+            min_value: Some(min),
+            max_value: Some(max),
+        }
+    }
+
+    fn float_page(min: f64, max: f64) -> PrimitiveStatistics<f64> {
+        PrimitiveStatistics {
+            primitive_type: PrimitiveType::from_physical("a".to_string(), PhysicalType::Double),
+            null_count: Some(0),
+            distinct_count: None,
+            min_value: Some(min),
+            max_value: Some(max),
+        }
+    }
+
+    #[test]
+    fn float_boundary_order() {
+        let order = |pages: &[PrimitiveStatistics<f64>]| {
+            boundary_order(
+                &pages
+                    .iter()
+                    .map(|x| x as &dyn Statistics)
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            order(&[float_page(-1.5, 0.0), float_page(0.0, f64::INFINITY)]),
+            BoundaryOrder::ASCENDING
+        );
+        assert_eq!(
+            order(&[float_page(0.5, 2.0), float_page(-1.0, 0.5)]),
+            BoundaryOrder::DESCENDING
+        );
+        // NaN compares equal to other values with `NativeType::ord`
+        assert_eq!(
+            order(&[float_page(1.0, f64::NAN), float_page(0.0, 2.0)]),
+            BoundaryOrder::UNORDERED
+        );
+        assert_eq!(
+            order(&[float_page(f64::NAN, f64::NAN), float_page(0.0, 2.0)]),
+            BoundaryOrder::UNORDERED
+        );
+    }
+
+    #[test]
+    fn unsigned_boundary_order() {
+        let mut primitive_type = PrimitiveType::from_physical("a".to_string(), PhysicalType::Int32);
+        let pages = [
+            page(&primitive_type, 0, 10),
+            page(&primitive_type, 20, i32::MAX),
+            page(&primitive_type, i32::MIN, -1),
+        ];
+        let pages = pages
+            .iter()
+            .map(|x| x as &dyn Statistics)
+            .collect::<Vec<_>>();
+        assert_eq!(boundary_order(&pages), BoundaryOrder::UNORDERED);
+
+        primitive_type.converted_type = Some(PrimitiveConvertedType::Uint32);
+        let pages = [
+            page(&primitive_type, 0, 10),
+            page(&primitive_type, 20, i32::MAX),
+            page(&primitive_type, i32::MIN, -1),
+        ];
+        let pages = pages
+            .iter()
+            .map(|x| x as &dyn Statistics)
+            .collect::<Vec<_>>();
+        assert_eq!(boundary_order(&pages), BoundaryOrder::ASCENDING);
+    }
+}
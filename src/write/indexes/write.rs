@@ -9,8 +9,12 @@ use crate::write::page::PageWriteSpec;
 
 use super::serialize::{serialize_column_index, serialize_offset_index};
 
-pub fn write_column_index<W: Write>(writer: &mut W, pages: &[PageWriteSpec]) -> Result<u64> {
-    let index = serialize_column_index(pages)?;
+pub fn write_column_index<W: Write>(
+    writer: &mut W,
+    pages: &[PageWriteSpec],
+    statistics_truncate_length: Option<usize>,
+) -> Result<u64> {
+    let index = serialize_column_index(pages, statistics_truncate_length)?;
     let mut protocol = TCompactOutputProtocol::new(writer);
     Ok(index.write_to_out_protocol(&mut protocol)? as u64)
 }
@@ -1,10 +1,13 @@
 mod column_chunk;
 mod compression;
+pub mod dictionary;
 mod file;
 mod indexes;
 pub(crate) mod page;
 mod row_group;
+mod row_group_builder;
 pub(self) mod statistics;
+mod transcode;
 
 #[cfg(feature = "stream")]
 mod stream;
@@ -14,11 +17,14 @@ pub use stream::FileStreamer;
 mod dyn_iter;
 pub use dyn_iter::{DynIter, DynStreamingIterator};
 
-pub use compression::{compress, Compressor};
+pub use compression::{compress, compress_row_group, compress_with_codec, Compressor};
 
 pub use file::FileWriter;
 
 pub use row_group::ColumnOffsetsMetadata;
+pub use row_group_builder::RowGroupBuilder;
+
+pub use transcode::transcode_column;
 
 use crate::page::CompressedPage;
 
@@ -26,12 +32,44 @@ pub type RowGroupIter<'a, E> =
     DynIter<'a, std::result::Result<DynStreamingIterator<'a, CompressedPage, E>, E>>;
 
 /// Write options of different interfaces on this crate
+///
+/// Use [`WriteOptions::default`] to set only some of them, e.g.
+/// `WriteOptions { version: Version::V2, ..Default::default() }`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct WriteOptions {
     /// Whether to write statistics, including indexes
     pub write_statistics: bool,
     /// Which Parquet version to use
     pub version: Version,
+    /// Whether to write the CRC32 checksum of each page in its header
+    pub write_crc: bool,
+    /// The maximum number of bytes of the min and max values of the statistics of
+    /// `BYTE_ARRAY` columns, in page headers, column chunks and column indexes.
+    /// Min values are truncated to a prefix and max values to a prefix whose last byte is
+    /// incremented (or character, for UTF-8 columns). When `None`, values are not truncated.
+    pub statistics_truncate_length: Option<usize>,
+}
+
+impl Default for WriteOptions {
+    /// Statistics are written in v1 pages, without checksums nor truncation.
+    fn default() -> Self {
+        Self {
+            write_statistics: true,
+            version: Version::V1,
+            write_crc: false,
+            statistics_truncate_length: None,
+        }
+    }
+}
+
+/// Options used to size the bloom filter of a column chunk
+/// (see [`BloomFilter::try_from_options`](crate::bloom_filter::BloomFilter::try_from_options))
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BloomFilterOptions {
+    /// The target false-positive probability, in `(0, 1)`
+    pub fpp: f64,
+    /// The expected number of distinct values in the column chunk
+    pub num_distinct_values: u64,
 }
 
 /// The parquet version to use
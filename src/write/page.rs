@@ -15,11 +15,13 @@ use crate::page::{
 };
 use crate::statistics::Statistics;
 
+use super::statistics::truncate;
+
 pub(crate) fn is_data_page(page: &PageWriteSpec) -> bool {
     page.header.type_ == PageType::DATA_PAGE || page.header.type_ == PageType::DATA_PAGE_V2
 }
 
-fn maybe_bytes(uncompressed: usize, compressed: usize) -> Result<(i32, i32)> {
+pub(crate) fn maybe_bytes(uncompressed: usize, compressed: usize) -> Result<(i32, i32)> {
     let uncompressed_page_size: i32 = uncompressed.try_into().map_err(|_| {
         Error::OutOfSpec(format!(
             "A page can only contain i32::MAX uncompressed bytes. This one contains {}",
@@ -49,18 +51,27 @@ pub struct PageWriteSpec {
     pub statistics: Option<Arc<dyn Statistics>>,
 }
 
+/// Writes `compressed_page` to `writer`. When `write_statistics` is false, the statistics of
+/// the page are not written to its header nor returned in its [`PageWriteSpec`].
+/// The statistics of its header are truncated to `statistics_truncate_length`, while those
+/// of its [`PageWriteSpec`] are not.
 pub fn write_page<W: Write>(
     writer: &mut W,
     offset: u64,
     compressed_page: &CompressedPage,
+    write_crc: bool,
+    write_statistics: bool,
+    statistics_truncate_length: Option<usize>,
 ) -> Result<PageWriteSpec> {
     let num_values = compressed_page.num_values();
     let selected_rows = compressed_page.selected_rows();
 
-    let header = match &compressed_page {
-        CompressedPage::Data(compressed_page) => assemble_data_page_header(compressed_page),
-        CompressedPage::Dict(compressed_page) => assemble_dict_page_header(compressed_page),
-    }?;
+    let header = assemble_page_header(
+        compressed_page,
+        write_crc,
+        write_statistics,
+        statistics_truncate_length,
+    )?;
 
     let header_size = write_page_header(writer, &header)?;
     let mut bytes_written = header_size as u64;
@@ -77,8 +88,10 @@ pub fn write_page<W: Write>(
     };
 
     let statistics = match &compressed_page {
-        CompressedPage::Data(compressed_page) => compressed_page.statistics().transpose()?,
-        CompressedPage::Dict(_) => None,
+        CompressedPage::Data(compressed_page) if write_statistics => {
+            compressed_page.statistics().transpose()?
+        }
+        _ => None,
     };
 
     Ok(PageWriteSpec {
@@ -97,14 +110,19 @@ pub async fn write_page_async<W: AsyncWrite + Unpin + Send>(
     writer: &mut W,
     offset: u64,
     compressed_page: &CompressedPage,
+    write_crc: bool,
+    write_statistics: bool,
+    statistics_truncate_length: Option<usize>,
 ) -> Result<PageWriteSpec> {
     let num_values = compressed_page.num_values();
     let selected_rows = compressed_page.selected_rows();
 
-    let header = match &compressed_page {
-        CompressedPage::Data(compressed_page) => assemble_data_page_header(compressed_page),
-        CompressedPage::Dict(compressed_page) => assemble_dict_page_header(compressed_page),
-    }?;
+    let header = assemble_page_header(
+        compressed_page,
+        write_crc,
+        write_statistics,
+        statistics_truncate_length,
+    )?;
 
     let header_size = write_page_header_async(writer, &header).await?;
     let mut bytes_written = header_size as u64;
@@ -121,8 +139,10 @@ pub async fn write_page_async<W: AsyncWrite + Unpin + Send>(
     };
 
     let statistics = match &compressed_page {
-        CompressedPage::Data(compressed_page) => compressed_page.statistics().transpose()?,
-        CompressedPage::Dict(_) => None,
+        CompressedPage::Data(compressed_page) if write_statistics => {
+            compressed_page.statistics().transpose()?
+        }
+        _ => None,
     };
 
     Ok(PageWriteSpec {
@@ -137,6 +157,42 @@ pub async fn write_page_async<W: AsyncWrite + Unpin + Send>(
     })
 }
 
+fn assemble_page_header(
+    page: &CompressedPage,
+    write_crc: bool,
+    write_statistics: bool,
+    statistics_truncate_length: Option<usize>,
+) -> Result<ParquetPageHeader> {
+    let mut header = match page {
+        CompressedPage::Data(page) => assemble_data_page_header(page),
+        CompressedPage::Dict(page) => assemble_dict_page_header(page),
+    }?;
+    let statistics = header
+        .data_page_header
+        .as_mut()
+        .map(|header| &mut header.statistics)
+        .or_else(|| {
+            header
+                .data_page_header_v2
+                .as_mut()
+                .map(|header| &mut header.statistics)
+        });
+    if let (Some(statistics), CompressedPage::Data(page)) = (statistics, page) {
+        if !write_statistics {
+            *statistics = None;
+        } else if let (Some(statistics), Some(length)) =
+            (statistics.as_mut(), statistics_truncate_length)
+        {
+            truncate(statistics, &page.descriptor.primitive_type, length);
+        }
+    }
+    if write_crc {
+        // SPEC: the CRC32 is computed over the (compressed) data of the page, after its header
+        header.crc = Some(crc32fast::hash(page.buffer()) as i32);
+    }
+    Ok(header)
+}
+
 fn assemble_data_page_header(page: &CompressedDataPage) -> Result<ParquetPageHeader> {
     let (uncompressed_page_size, compressed_page_size) =
         maybe_bytes(page.uncompressed_size(), page.compressed_size())?;
@@ -225,12 +281,8 @@ mod tests {
 
     #[test]
     fn dict_too_many_values() {
-        let page = CompressedDictPage::new(
-            vec![],
-            Compression::Uncompressed,
-            0,
-            i32::MAX as usize + 1,
-        );
+        let page =
+            CompressedDictPage::new(vec![], Compression::Uncompressed, 0, i32::MAX as usize + 1);
         assert!(assemble_dict_page_header(&page).is_err());
     }
 }
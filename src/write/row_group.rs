@@ -12,7 +12,7 @@ use crate::{
 use super::{
     column_chunk::{write_column_chunk, write_column_chunk_async},
     page::{is_data_page, PageWriteSpec},
-    DynIter, DynStreamingIterator,
+    DynIter, DynStreamingIterator, WriteOptions,
 };
 
 pub struct ColumnOffsetsMetadata {
@@ -82,19 +82,28 @@ pub fn write_row_group<
     descriptors: &[ColumnDescriptor],
     columns: DynIter<'a, std::result::Result<DynStreamingIterator<'a, CompressedPage, E>, E>>,
     ordinal: usize,
+    options: &WriteOptions,
+    write_statistics: &[bool],
 ) -> Result<(RowGroup, Vec<Vec<PageWriteSpec>>, u64)>
 where
     W: Write,
     Error: From<E>,
     E: std::error::Error,
 {
-    let column_iter = descriptors.iter().zip(columns);
+    let column_iter = descriptors.iter().zip(write_statistics).zip(columns);
 
     let initial = offset;
     let columns = column_iter
-        .map(|(descriptor, page_iter)| {
-            let (column, page_specs, size) =
-                write_column_chunk(writer, offset, descriptor, page_iter?)?;
+        .map(|((descriptor, write_statistics), page_iter)| {
+            let (column, page_specs, size) = write_column_chunk(
+                writer,
+                offset,
+                descriptor,
+                page_iter?,
+                options.write_crc,
+                *write_statistics,
+                options.statistics_truncate_length,
+            )?;
             offset += size;
             Ok((column, page_specs))
         })
@@ -146,6 +155,7 @@ pub async fn write_row_group_async<
     mut offset: u64,
     descriptors: &[ColumnDescriptor],
     columns: DynIter<'a, std::result::Result<DynStreamingIterator<'a, CompressedPage, E>, E>>,
+    options: &WriteOptions,
 ) -> Result<(RowGroup, Vec<Vec<PageWriteSpec>>, u64)>
 where
     W: AsyncWrite + Unpin + Send,
@@ -157,8 +167,16 @@ where
     let initial = offset;
     let mut columns = vec![];
     for (descriptor, page_iter) in column_iter {
-        let (column, page_specs, size) =
-            write_column_chunk_async(writer, offset, descriptor, page_iter?).await?;
+        let (column, page_specs, size) = write_column_chunk_async(
+            writer,
+            offset,
+            descriptor,
+            page_iter?,
+            options.write_crc,
+            true,
+            options.statistics_truncate_length,
+        )
+        .await?;
         offset += size;
         columns.push((column, page_specs));
     }
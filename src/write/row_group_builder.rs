@@ -0,0 +1,156 @@
+use std::io::Write;
+
+use crate::error::{Error, Result};
+use crate::metadata::KeyValue;
+use crate::page::CompressedPage;
+
+use super::FileWriter;
+
+/// A writer of row groups that buffers pages and writes them to a [`FileWriter`] in row groups
+/// of at most a maximum number of rows and bytes.
+///
+/// Pages are added in batches of one data page per column, where all pages have the same
+/// number of rows (see [`RowGroupBuilder::write`]). Batches are never split: a batch larger than
+/// the limits is written as its own row group.
+pub struct RowGroupBuilder<W: Write> {
+    writer: FileWriter<W>,
+    max_rows: Option<usize>,
+    max_bytes: Option<usize>,
+
+    columns: Vec<Vec<CompressedPage>>,
+    num_rows: usize,
+    num_bytes: usize,
+}
+
+/// The number of rows of a data page, as written to the metadata of its row group.
+fn num_rows(page: &CompressedPage) -> Option<usize> {
+    page.selected_rows().map(|x| x.last().unwrap().length)
+}
+
+impl<W: Write> RowGroupBuilder<W> {
+    /// Returns a new [`RowGroupBuilder`] that writes row groups to `writer`, without limits:
+    /// every page is written to a single row group on [`RowGroupBuilder::end`].
+    ///
+    /// `writer` must be started (see [`FileWriter::start`]) before the first row group is written.
+    pub fn new(writer: FileWriter<W>) -> Self {
+        let num_columns = writer.schema().columns().len();
+        Self {
+            writer,
+            max_rows: None,
+            max_bytes: None,
+            columns: (0..num_columns).map(|_| vec![]).collect(),
+            num_rows: 0,
+            num_bytes: 0,
+        }
+    }
+
+    /// Sets the maximum number of rows of a row group.
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Sets the maximum number of bytes of a row group, i.e. the sum of the sizes of the
+    /// (compressed) pages of its columns, excluding their headers.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// The [`FileWriter`] the row groups are written to
+    pub fn writer(&self) -> &FileWriter<W> {
+        &self.writer
+    }
+
+    /// The number of rows buffered for the next row group
+    pub fn buffered_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Adds `pages`, one data page per column of the schema, to the current row group.
+    ///
+    /// The current row group is written before the pages are added if they would exceed one of
+    /// its limits, and after they are added if it reached one of them.
+    /// # Error
+    /// Errors iff `pages` does not have one page per column, a page is a dictionary page
+    /// (dictionary-encoded pages are thus not supported) or does not declare its number of
+    /// rows, the pages have different numbers of rows, or writing a row group errors.
+    pub fn write(&mut self, pages: Vec<CompressedPage>) -> Result<()> {
+        if pages.len() != self.columns.len() {
+            return Err(Error::InvalidParameter(format!(
+                "The batch has {} pages but the schema has {} columns",
+                pages.len(),
+                self.columns.len()
+            )));
+        }
+        if pages
+            .iter()
+            .any(|page| matches!(page, CompressedPage::Dict(_)))
+        {
+            return Err(Error::InvalidParameter(
+                "Dictionary pages are not supported, since each row group needs its own"
+                    .to_string(),
+            ));
+        }
+        let rows = pages.iter().map(num_rows).collect::<Option<Vec<_>>>();
+        let rows = match rows.as_deref() {
+            Some([rows, others @ ..]) if others.iter().all(|x| x == rows) => *rows,
+            Some([]) => 0,
+            _ => {
+                return Err(Error::InvalidParameter(
+                    "All pages of a batch must declare the same number of rows".to_string(),
+                ))
+            }
+        };
+        let bytes = pages.iter().map(|page| page.buffer().len()).sum::<usize>();
+
+        if self.num_rows > 0 && self.exceeds(self.num_rows + rows, self.num_bytes + bytes) {
+            self.flush()?;
+        }
+        for (column, page) in self.columns.iter_mut().zip(pages) {
+            column.push(page);
+        }
+        self.num_rows += rows;
+        self.num_bytes += bytes;
+        if self.reached() {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn exceeds(&self, rows: usize, bytes: usize) -> bool {
+        self.max_rows.is_some_and(|max| rows > max) || self.max_bytes.is_some_and(|max| bytes > max)
+    }
+
+    fn reached(&self) -> bool {
+        self.max_rows.is_some_and(|max| self.num_rows >= max)
+            || self.max_bytes.is_some_and(|max| self.num_bytes >= max)
+    }
+
+    /// Writes the buffered pages as a row group, if any.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.columns.iter().all(|x| x.is_empty()) {
+            return Ok(());
+        }
+        let columns = self
+            .columns
+            .iter_mut()
+            .map(std::mem::take)
+            .collect::<Vec<_>>();
+        self.num_rows = 0;
+        self.num_bytes = 0;
+        self.writer.write_compressed_pages(columns)
+    }
+
+    /// Writes the buffered pages as the last row group and the footer of the file
+    /// (see [`FileWriter::end`]), returning the total size of the file.
+    pub fn end(&mut self, key_value_metadata: Option<Vec<KeyValue>>) -> Result<u64> {
+        self.flush()?;
+        self.writer.end(key_value_metadata)
+    }
+
+    /// Returns the underlying [`FileWriter`]. Pages that were not flushed are dropped.
+    pub fn into_inner(self) -> FileWriter<W> {
+        self.writer
+    }
+}
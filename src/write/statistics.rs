@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use crate::error::{Error, Result};
-use crate::schema::types::PhysicalType;
+use crate::schema::types::{
+    PhysicalType, PrimitiveConvertedType, PrimitiveLogicalType, PrimitiveType,
+};
 use crate::statistics::*;
 use crate::types::NativeType;
 
@@ -25,6 +27,16 @@ fn reduce_vec8(lhs: Option<Vec<u8>>, rhs: &Option<Vec<u8>>, max: bool) -> Option
     }
 }
 
+/// The distinct count of the union of two sets of values is only known when one of them has
+/// no (non-null) values.
+#[inline]
+fn reduce_distinct_count(lhs: Option<i64>, rhs: Option<i64>) -> Option<i64> {
+    match (lhs, rhs) {
+        (Some(0), other) | (other, Some(0)) => other,
+        _ => None,
+    }
+}
+
 pub fn reduce(stats: &[&Option<Arc<dyn Statistics>>]) -> Result<Option<Arc<dyn Statistics>>> {
     if stats.is_empty() {
         return Ok(None);
@@ -64,6 +76,10 @@ pub fn reduce(stats: &[&Option<Arc<dyn Statistics>>]) -> Result<Option<Arc<dyn S
             let stats = stats.iter().map(|x| x.as_any().downcast_ref().unwrap());
             Some(Arc::new(reduce_primitive::<f32, _>(stats)))
         }
+        PhysicalType::Int96 => {
+            let stats = stats.iter().map(|x| x.as_any().downcast_ref().unwrap());
+            Some(Arc::new(reduce_primitive::<[u32; 3], _>(stats)))
+        }
         PhysicalType::Double => {
             let stats = stats.iter().map(|x| x.as_any().downcast_ref().unwrap());
             Some(Arc::new(reduce_primitive::<f64, _>(stats)))
@@ -76,17 +92,112 @@ pub fn reduce(stats: &[&Option<Arc<dyn Statistics>>]) -> Result<Option<Arc<dyn S
             let stats = stats.iter().map(|x| x.as_any().downcast_ref().unwrap());
             Some(Arc::new(reduce_fix_len_binary(stats)))
         }
-        _ => todo!(),
     })
 }
 
+/// Whether the byte arrays of `primitive_type` are UTF-8 encoded strings.
+pub fn is_utf8(primitive_type: &PrimitiveType) -> bool {
+    matches!(
+        primitive_type.logical_type,
+        Some(PrimitiveLogicalType::String)
+            | Some(PrimitiveLogicalType::Enum)
+            | Some(PrimitiveLogicalType::Json)
+    ) || matches!(
+        primitive_type.converted_type,
+        Some(PrimitiveConvertedType::Utf8)
+            | Some(PrimitiveConvertedType::Enum)
+            | Some(PrimitiveConvertedType::Json)
+    )
+}
+
+/// Returns a lower bound of `value` with at most `length` bytes: its longest prefix that fits.
+/// When `utf8` and `value` is valid UTF-8, the prefix ends at a character boundary.
+pub fn truncate_min(value: &[u8], length: usize, utf8: bool) -> Vec<u8> {
+    if value.len() <= length {
+        return value.to_vec();
+    }
+    match std::str::from_utf8(value) {
+        Ok(value) if utf8 => {
+            let end = (0..=length)
+                .rev()
+                .find(|end| value.is_char_boundary(*end))
+                .unwrap_or(0);
+            value.as_bytes()[..end].to_vec()
+        }
+        _ => value[..length].to_vec(),
+    }
+}
+
+/// Returns an upper bound of `value` with at most `length` bytes: its longest prefix that fits,
+/// whose last byte (or character, when `utf8` and `value` is valid UTF-8) is incremented.
+/// Returns `None` when no such bound exists, e.g. when the prefix is all `0xFF`.
+pub fn truncate_max(value: &[u8], length: usize, utf8: bool) -> Option<Vec<u8>> {
+    if value.len() <= length {
+        return Some(value.to_vec());
+    }
+    match std::str::from_utf8(value) {
+        Ok(value) if utf8 => {
+            let mut prefix = value
+                .char_indices()
+                .take_while(|(start, c)| start + c.len_utf8() <= length)
+                .map(|(_, c)| c)
+                .collect::<Vec<_>>();
+            let mut prefix_length = prefix.iter().map(|c| c.len_utf8()).sum::<usize>();
+            while let Some(last) = prefix.pop() {
+                prefix_length -= last.len_utf8();
+                // the next character, skipping surrogates, which are not valid characters
+                let next = match last as u32 + 1 {
+                    0xD800 => Some('\u{E000}'),
+                    next => char::from_u32(next),
+                };
+                if let Some(next) = next.filter(|c| prefix_length + c.len_utf8() <= length) {
+                    prefix.push(next);
+                    return Some(prefix.into_iter().collect::<String>().into_bytes());
+                }
+            }
+            None
+        }
+        _ => {
+            let mut prefix = value[..length].to_vec();
+            while let Some(last) = prefix.pop() {
+                if last != u8::MAX {
+                    prefix.push(last + 1);
+                    return Some(prefix);
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Truncates the min and max values of `statistics` of a `BYTE_ARRAY` column of
+/// `primitive_type` to at most `length` bytes, so that they still bound the values of the
+/// column. The max value is dropped when it can't be truncated.
+/// Statistics of other physical types are not modified.
+pub fn truncate(statistics: &mut ParquetStatistics, primitive_type: &PrimitiveType, length: usize) {
+    if primitive_type.physical_type != PhysicalType::ByteArray {
+        return;
+    }
+    let utf8 = is_utf8(primitive_type);
+    for min in [&mut statistics.min_value, &mut statistics.min] {
+        if let Some(value) = min.as_mut() {
+            *value = truncate_min(value, length, utf8);
+        }
+    }
+    for max in [&mut statistics.max_value, &mut statistics.max] {
+        *max = max
+            .as_ref()
+            .and_then(|value| truncate_max(value, length, utf8));
+    }
+}
+
 fn reduce_binary<'a, I: Iterator<Item = &'a BinaryStatistics>>(mut stats: I) -> BinaryStatistics {
     let initial = stats.next().unwrap().clone();
     stats.fold(initial, |mut acc, new| {
         acc.min_value = reduce_vec8(acc.min_value, &new.min_value, false);
         acc.max_value = reduce_vec8(acc.max_value, &new.max_value, true);
         acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.distinct_count = reduce_distinct_count(acc.distinct_count, new.distinct_count);
         acc
     })
 }
@@ -99,7 +210,7 @@ fn reduce_fix_len_binary<'a, I: Iterator<Item = &'a FixedLenStatistics>>(
         acc.min_value = reduce_vec8(acc.min_value, &new.min_value, false);
         acc.max_value = reduce_vec8(acc.max_value, &new.max_value, true);
         acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.distinct_count = reduce_distinct_count(acc.distinct_count, new.distinct_count);
         acc
     })
 }
@@ -143,32 +254,34 @@ fn reduce_boolean<'a, I: Iterator<Item = &'a BooleanStatistics>>(
             |x, y| if x & !(y) { x } else { y },
         );
         acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.distinct_count = reduce_distinct_count(acc.distinct_count, new.distinct_count);
         acc
     })
 }
 
-fn reduce_primitive<
-    'a,
-    T: NativeType + std::cmp::PartialOrd,
-    I: Iterator<Item = &'a PrimitiveStatistics<T>>,
->(
+fn reduce_primitive<'a, T: NativeType, I: Iterator<Item = &'a PrimitiveStatistics<T>>>(
     mut stats: I,
 ) -> PrimitiveStatistics<T> {
+    use std::cmp::Ordering::*;
+
     let initial = stats.next().unwrap().clone();
     stats.fold(initial, |mut acc, new| {
-        acc.min_value = reduce_single(
-            acc.min_value,
-            new.min_value,
-            |x, y| if x > y { y } else { x },
-        );
-        acc.max_value = reduce_single(
-            acc.max_value,
-            new.max_value,
-            |x, y| if x < y { x } else { y },
-        );
+        acc.min_value = reduce_single(acc.min_value, new.min_value, |x, y| {
+            if x.ord(&y) == Greater {
+                y
+            } else {
+                x
+            }
+        });
+        acc.max_value = reduce_single(acc.max_value, new.max_value, |x, y| {
+            if x.ord(&y) == Less {
+                y
+            } else {
+                x
+            }
+        });
         acc.null_count = reduce_single(acc.null_count, new.null_count, |x, y| x + y);
-        acc.distinct_count = None;
+        acc.distinct_count = reduce_distinct_count(acc.distinct_count, new.distinct_count);
         acc
     })
 }
@@ -265,6 +378,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn int96() -> Result<()> {
+        let primitive_type = PrimitiveType::from_physical("bla".to_string(), PhysicalType::Int96);
+        // the largest nanoseconds within a day are in the smallest day
+        let iter = [
+            PrimitiveStatistics {
+                primitive_type: primitive_type.clone(),
+                null_count: Some(1),
+                distinct_count: None,
+                min_value: Some([0, 10, 2_440_589]),
+                max_value: Some([0, 10, 2_440_590]),
+            },
+            PrimitiveStatistics {
+                primitive_type: primitive_type.clone(),
+                null_count: Some(2),
+                distinct_count: None,
+                min_value: Some([0, 20, 2_440_588]),
+                max_value: Some([0, 0, 2_440_591]),
+            },
+        ];
+        let a = reduce_primitive(iter.iter());
+
+        assert_eq!(
+            a,
+            PrimitiveStatistics {
+                primitive_type,
+                null_count: Some(3),
+                distinct_count: None,
+                min_value: Some([0, 20, 2_440_588]),
+                max_value: Some([0, 0, 2_440_591]),
+            },
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn boolean() -> Result<()> {
         let iter = vec![
@@ -295,4 +444,118 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn distinct_count() {
+        assert_eq!(reduce_distinct_count(Some(3), Some(0)), Some(3));
+        assert_eq!(reduce_distinct_count(Some(0), Some(2)), Some(2));
+        assert_eq!(reduce_distinct_count(Some(0), Some(0)), Some(0));
+        assert_eq!(reduce_distinct_count(Some(0), None), None);
+        assert_eq!(reduce_distinct_count(Some(3), Some(2)), None);
+        assert_eq!(reduce_distinct_count(None, None), None);
+    }
+
+    fn assert_bounds(values: &[&[u8]], length: usize, utf8: bool) {
+        for value in values {
+            let min = truncate_min(value, length, utf8);
+            assert!(min.len() <= length || min == *value);
+            assert!(min.as_slice() <= *value);
+            if let Some(max) = truncate_max(value, length, utf8) {
+                assert!(max.len() <= length || max == *value);
+                assert!(max.as_slice() >= *value);
+            }
+            if utf8 {
+                assert!(std::str::from_utf8(&min).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn truncate_binary() {
+        assert_eq!(truncate_min(b"abcdef", 3, false), b"abc");
+        assert_eq!(truncate_max(b"abcdef", 3, false), Some(b"abd".to_vec()));
+        // short values are kept
+        assert_eq!(truncate_min(b"ab", 3, false), b"ab");
+        assert_eq!(truncate_max(b"ab", 3, false), Some(b"ab".to_vec()));
+        // trailing 0xFF can't be incremented
+        assert_eq!(truncate_max(&[1, 0xff, 0xff, 0], 3, false), Some(vec![2]));
+        assert_eq!(truncate_max(&[0xff, 0xff, 0xff, 0], 3, false), None);
+        assert_eq!(truncate_max(&[0xff, 0xff, 0xff, 0xff], 3, false), None);
+
+        assert_bounds(
+            &[
+                b"abcdef",
+                &[0, 0xff, 0xff, 1],
+                &[0xfe, 0xff, 0xff, 0xff],
+                b"",
+            ],
+            3,
+            false,
+        );
+    }
+
+    #[test]
+    fn truncate_utf8() {
+        // "aé€" is 1 + 2 + 3 bytes
+        let value = "aé€".as_bytes();
+        assert_eq!(truncate_min(value, 2, true), b"a");
+        assert_eq!(truncate_max(value, 2, true), Some(b"b".to_vec()));
+        assert_eq!(truncate_min(value, 4, true), "aé".as_bytes());
+        assert_eq!(truncate_max(value, 4, true), Some("aê".as_bytes().to_vec()));
+        // without UTF-8 awareness, the prefix splits "é"
+        assert_eq!(truncate_min(value, 2, false), &value[..2]);
+
+        // incrementing U+007F requires 2 bytes: the previous character is incremented instead
+        assert_eq!(
+            truncate_max("a\u{7f}b".as_bytes(), 2, true),
+            Some(b"b".to_vec())
+        );
+        // the character after the surrogates
+        assert_eq!(
+            truncate_max("\u{D7FF}a".as_bytes(), 3, true),
+            Some("\u{E000}".as_bytes().to_vec())
+        );
+        // the largest character can't be incremented
+        assert_eq!(truncate_max("\u{10FFFF}a".as_bytes(), 4, true), None);
+        // invalid UTF-8 is truncated as bytes
+        assert_eq!(truncate_min(&[0xff, 0xfe, 0], 2, true), vec![0xff, 0xfe]);
+
+        assert_bounds(
+            &[
+                "aé€".as_bytes(),
+                "\u{10FFFF}\u{10FFFF}".as_bytes(),
+                "z\u{10FFFF}".as_bytes(),
+                "ÿÿÿ".as_bytes(),
+            ],
+            3,
+            true,
+        );
+    }
+
+    #[test]
+    fn truncate_statistics() {
+        let mut primitive_type =
+            PrimitiveType::from_physical("a".to_string(), PhysicalType::ByteArray);
+        let mut statistics = ParquetStatistics {
+            null_count: Some(0),
+            distinct_count: None,
+            max_value: Some(vec![0xff, 0xff, 1]),
+            min_value: Some(b"abc".to_vec()),
+            min: None,
+            max: None,
+        };
+        truncate(&mut statistics, &primitive_type, 2);
+        assert_eq!(statistics.min_value, Some(b"ab".to_vec()));
+        assert_eq!(statistics.max_value, None);
+
+        // other physical types are not truncated
+        primitive_type.physical_type = PhysicalType::FixedLenByteArray(3);
+        let mut statistics = ParquetStatistics {
+            max_value: Some(b"abc".to_vec()),
+            min_value: Some(b"abc".to_vec()),
+            ..Default::default()
+        };
+        truncate(&mut statistics, &primitive_type, 2);
+        assert_eq!(statistics.max_value, Some(b"abc".to_vec()));
+    }
 }
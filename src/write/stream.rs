@@ -44,6 +44,8 @@ async fn end_file<W: AsyncWrite + Unpin + Send>(
 /// An interface to write a parquet file asynchronously.
 /// Use `start` to write the header, `write` to write a row group,
 /// and `end` to write the footer.
+///
+/// Unlike [`FileWriter`](super::FileWriter), it does not write bloom filters.
 pub struct FileStreamer<W: AsyncWrite + Unpin + Send> {
     writer: W,
     schema: SchemaDescriptor,
@@ -107,6 +109,7 @@ impl<W: AsyncWrite + Unpin + Send> FileStreamer<W> {
             self.offset,
             self.schema.columns(),
             row_group,
+            &self.options,
         )
         .await?;
         self.offset += size;
@@ -0,0 +1,183 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use parquet_format_async_temp::thrift::protocol::{TCompactOutputProtocol, TOutputProtocol};
+
+use crate::compression::{decompress_into, Compression, CompressionOptions};
+use crate::error::{Error, Result};
+use crate::metadata::ColumnChunkMetaData;
+use crate::page::{
+    CompressedDataPage, CompressedPage, DataPageHeader, EncodedDictPage, EncodedPage, PageType,
+    ParquetPageHeader,
+};
+use crate::read::{decompress, page_size, read_page_header, DEFAULT_MAX_PAGE_SIZE};
+
+use super::compression::compress;
+use super::page::maybe_bytes;
+
+fn missing_header(page_type: PageType) -> Error {
+    Error::OutOfSpec(format!(
+        "The header of a {:?} page is missing from its page header",
+        page_type
+    ))
+}
+
+/// Decompresses the page of `header` and `buffer` from `column` into an [`EncodedPage`].
+fn decompress_page(
+    header: &ParquetPageHeader,
+    buffer: Vec<u8>,
+    column: &ColumnChunkMetaData,
+) -> Result<EncodedPage> {
+    let uncompressed_size = page_size(
+        "uncompressed",
+        header.uncompressed_page_size,
+        DEFAULT_MAX_PAGE_SIZE,
+    )?;
+
+    let page_type = header.type_.try_into()?;
+    let data_header = match page_type {
+        PageType::DictionaryPage => {
+            let dict_header = header
+                .dictionary_page_header
+                .as_ref()
+                .ok_or_else(|| missing_header(page_type))?;
+
+            let buffer = if column.compression() != Compression::Uncompressed {
+                let mut decompressed = vec![];
+                decompress_into(
+                    column.compression(),
+                    &buffer,
+                    uncompressed_size,
+                    &mut decompressed,
+                )?;
+                decompressed
+            } else {
+                buffer
+            };
+            return Ok(EncodedPage::Dict(EncodedDictPage::new(
+                buffer,
+                dict_header.num_values as usize,
+            )));
+        }
+        PageType::DataPage => header
+            .data_page_header
+            .clone()
+            .map(DataPageHeader::V1)
+            .ok_or_else(|| missing_header(page_type))?,
+        PageType::DataPageV2 => header
+            .data_page_header_v2
+            .clone()
+            .map(DataPageHeader::V2)
+            .ok_or_else(|| missing_header(page_type))?,
+    };
+
+    let page = CompressedDataPage::new_read(
+        data_header,
+        buffer,
+        column.compression(),
+        uncompressed_size,
+        None,
+        column.descriptor().descriptor.clone(),
+        None,
+    );
+    let mut page = decompress(page, &mut vec![])?;
+    if let DataPageHeader::V2(header) = &mut page.header {
+        // whether the values are compressed is decided again when they are compressed
+        header.is_compressed = None;
+    }
+    Ok(EncodedPage::Data(page))
+}
+
+/// Copies the column chunk `column` from `reader` to `writer`, re-compressing its pages with
+/// `compression`. Pages are decompressed but not decoded, and their headers (including
+/// statistics) and the dictionary page are preserved.
+///
+/// `offset` is the position of `writer` in the destination file, from which the offsets of
+/// the returned [`ColumnChunkMetaData`] are computed.
+/// The returned metadata has no page indexes nor bloom filter, since these are not copied.
+pub fn transcode_column<R: Read + Seek, W: Write>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+    compression: CompressionOptions,
+    writer: &mut W,
+    offset: u64,
+) -> Result<ColumnChunkMetaData> {
+    let (start, _) = column.byte_range();
+    reader.seek(SeekFrom::Start(start))?;
+
+    let mut position = offset;
+    let mut dictionary_page_offset = None;
+    let mut data_page_offset = None;
+    let mut total_compressed_size = 0;
+    let mut total_uncompressed_size = 0;
+
+    let mut seen_values = 0;
+    while seen_values < column.num_values() {
+        let mut header = read_page_header(reader)?;
+        let compressed_size = page_size(
+            "compressed",
+            header.compressed_page_size,
+            DEFAULT_MAX_PAGE_SIZE,
+        )?;
+        let mut buffer = vec![0; compressed_size];
+        reader.read_exact(&mut buffer)?;
+
+        let page = decompress_page(&header, buffer, column)?;
+        let page = compress(page, vec![], compression)?;
+
+        let uncompressed_size = match &page {
+            CompressedPage::Data(page) => {
+                seen_values += page.num_values() as i64;
+                if let DataPageHeader::V2(page_header) = page.header() {
+                    header.data_page_header_v2 = Some(page_header.clone());
+                }
+                data_page_offset.get_or_insert(position);
+                page.uncompressed_size()
+            }
+            CompressedPage::Dict(page) => {
+                dictionary_page_offset.get_or_insert(position);
+                page.uncompressed_size()
+            }
+        };
+        let (uncompressed_page_size, compressed_page_size) =
+            maybe_bytes(uncompressed_size, page.buffer().len())?;
+        header.uncompressed_page_size = uncompressed_page_size;
+        header.compressed_page_size = compressed_page_size;
+        header.crc = None;
+
+        let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+        let header_size = header.write_to_out_protocol(&mut protocol)? as u64;
+        writer.write_all(page.buffer())?;
+
+        // SPEC: the total sizes of a column chunk include the sizes of the page headers
+        total_compressed_size += header_size + page.buffer().len() as u64;
+        total_uncompressed_size += header_size + uncompressed_size as u64;
+        position += header_size + page.buffer().len() as u64;
+    }
+
+    let mut column_chunk = column.column_chunk().clone();
+    column_chunk.file_path = None;
+    column_chunk.file_offset = position as i64;
+    column_chunk.offset_index_offset = None;
+    column_chunk.offset_index_length = None;
+    column_chunk.column_index_offset = None;
+    column_chunk.column_index_length = None;
+
+    let metadata = column_chunk.meta_data.as_mut().unwrap();
+    metadata.codec = Compression::from(compression).into();
+    metadata.total_compressed_size = total_compressed_size as i64;
+    metadata.total_uncompressed_size = total_uncompressed_size as i64;
+    metadata.data_page_offset = data_page_offset.unwrap_or(offset) as i64;
+    metadata.dictionary_page_offset = dictionary_page_offset.map(|x| x as i64);
+    metadata.index_page_offset = None;
+    metadata.bloom_filter_offset = None;
+
+    // like other column chunks, the column's metadata follows its pages
+    let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+    metadata.write_to_out_protocol(&mut protocol)?;
+    protocol.flush()?;
+
+    Ok(ColumnChunkMetaData::new(
+        column_chunk,
+        column.descriptor().clone(),
+    ))
+}
@@ -70,6 +70,18 @@ pub fn get_path() -> PathBuf {
     PathBuf::from(dir).join("testing/parquet-testing/data")
 }
 
+/// Returns an endless sequence of pseudo-random numbers (xorshift), so that tests are
+/// deterministic.
+pub fn xorshift() -> impl Iterator<Item = u64> {
+    let mut state = 0x2545f4914f6cdd1du64;
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
+
 pub fn alltypes_plain(column: &str) -> Array {
     match column {
         "id" => {
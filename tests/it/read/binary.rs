@@ -24,5 +24,15 @@ pub fn page_to_vec(page: &DataPage) -> Result<Vec<Option<Vec<u8>>>> {
                 .map(|x| dict.dict.value(x).map(|x| x.to_vec()).unwrap());
             deserialize_optional(validity, values)
         }
+        BinaryPageState::RequiredDelta(values) => {
+            Ok(values.map(|x| x.to_vec()).map(Some).collect())
+        }
+        BinaryPageState::OptionalDelta(validity, values) => {
+            deserialize_optional(validity, values.map(|x| x.to_vec()))
+        }
+        BinaryPageState::RequiredDeltaByteArray(values) => Ok(values.map(Some).collect()),
+        BinaryPageState::OptionalDeltaByteArray(validity, values) => {
+            deserialize_optional(validity, values)
+        }
     }
 }
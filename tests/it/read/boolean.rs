@@ -15,5 +15,9 @@ pub fn page_to_vec(page: &DataPage) -> Result<Vec<Option<bool>>> {
             .into_iter()
             .map(Some)
             .collect()),
+        BooleanPageState::OptionalRle(validity, values) => {
+            deserialize_optional(validity, values.map(|x| x == 1))
+        }
+        BooleanPageState::RequiredRle(values) => Ok(values.map(|x| Some(x == 1)).collect()),
     }
 }
@@ -0,0 +1,1011 @@
+//! Tests of the read path against files written by this crate
+
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::sync::Arc;
+
+#[cfg(feature = "bloom_filter")]
+use parquet2::bloom_filter::{read_bloom_filter, BloomFilter};
+use parquet2::compression::{compress, Compression, CompressionOptions};
+use parquet2::encoding::Encoding;
+use parquet2::error::{Error, Result};
+use parquet2::metadata::{ColumnChunkMetaData, KeyValue, RowGroupMetaData, SchemaDescriptor};
+use parquet2::page::{
+    split_buffer, CompressedDataPage, DataPage, DataPageHeader, DataPageHeaderV2, ParquetPageHeader,
+};
+use parquet2::read::{
+    get_page_iterator, read_metadata, DatasetReader, DynamicRow, DynamicValue, RowGroupRowIterator,
+};
+use parquet2::read::{
+    predicate, BasicDecompressor, PageReader, ReadOptions, DEFAULT_MAX_PAGE_SIZE,
+};
+use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveLogicalType, PrimitiveType};
+use parquet2::schema::Repetition;
+use parquet2::statistics::{
+    read_statistics, serialize_statistics, PrimitiveStatistics, TypedStatistics,
+};
+use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter};
+use parquet2::FallibleStreamingIterator;
+use parquet2::{metadata::Descriptor, page::EncodedPage, write::WriteOptions};
+use parquet_format_async_temp::thrift::protocol::{TCompactInputProtocol, TCompactOutputProtocol};
+
+use super::super::alltypes_plain;
+use super::super::write::primitive::array_to_page_v1;
+use super::super::write::{
+    array_to_page, binary, read_pages, write_column, write_pages, write_single_column,
+    PartialReader,
+};
+use super::super::Array;
+
+#[test]
+fn rows() -> Result<()> {
+    let array = Array::Int32(vec![Some(1), None, Some(3)]);
+    let data = write_column(&array, CompressionOptions::Snappy)?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let rows = RowGroupRowIterator::try_new(&mut reader, &metadata, 0)?.collect::<Vec<_>>();
+
+    let expected = [
+        DynamicValue::Int32(1),
+        DynamicValue::Null,
+        DynamicValue::Int32(3),
+    ]
+    .into_iter()
+    .map(|value| std::iter::once(("col".to_string(), value)).collect::<DynamicRow>())
+    .collect::<Vec<_>>();
+    assert_eq!(rows, expected);
+    Ok(())
+}
+
+#[test]
+fn dataset() -> Result<()> {
+    let arrays = [
+        Array::Int32(vec![Some(1), None]),
+        Array::Int32(vec![Some(3)]),
+        Array::Int64(vec![Some(4)]),
+    ];
+    let paths = arrays
+        .iter()
+        .enumerate()
+        .map(|(i, array)| {
+            let path = std::env::temp_dir().join(format!(
+                "parquet2_dataset_{}_{}.parquet",
+                std::process::id(),
+                i
+            ));
+            std::fs::write(
+                &path,
+                write_column(array, CompressionOptions::Uncompressed)?,
+            )?;
+            Ok(path)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let dataset = DatasetReader::new(paths[..2].to_vec())?;
+    assert_eq!(dataset.num_rows(), 3);
+
+    let mut rows = vec![];
+    let mut sources = vec![];
+    for row_group in dataset.row_groups() {
+        sources.push(row_group.source_file.to_path_buf());
+        let mut file = row_group.open()?;
+        rows.extend(
+            RowGroupRowIterator::try_new(&mut file, row_group.file_metadata, row_group.index)?
+                .map(|row| row["col"].clone()),
+        );
+    }
+    assert_eq!(sources, paths[..2].to_vec());
+    assert_eq!(
+        rows,
+        vec![
+            DynamicValue::Int32(1),
+            DynamicValue::Null,
+            DynamicValue::Int32(3)
+        ]
+    );
+
+    let incompatible = DatasetReader::new(paths.clone());
+    assert!(matches!(incompatible, Err(Error::InvalidParameter(_))));
+
+    assert!(matches!(
+        DatasetReader::new(vec![]),
+        Err(Error::InvalidParameter(_))
+    ));
+
+    for path in paths {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Writes a file whose columns are the int32 `arrays`, named by `names`
+fn write_named_columns(names: &[&str], arrays: &[Array]) -> Result<Vec<u8>> {
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        names
+            .iter()
+            .map(|name| ParquetType::from_physical(name.to_string(), PhysicalType::Int32))
+            .collect(),
+    );
+    let columns = arrays
+        .iter()
+        .zip(schema.columns())
+        .map(|(array, column)| {
+            let page = array_to_page(array, &options, &column.descriptor);
+            Ok(DynStreamingIterator::new(Compressor::new_from_vec(
+                DynIter::new(std::iter::once(page)),
+                CompressionOptions::Uncompressed,
+                vec![],
+            )))
+        })
+        .collect::<Vec<_>>();
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns.into_iter()))?;
+    writer.end(None)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+#[test]
+fn dataset_column_order() -> Result<()> {
+    let files = [
+        write_named_columns(
+            &["a", "b"],
+            &[Array::Int32(vec![Some(1)]), Array::Int32(vec![Some(2)])],
+        )?,
+        write_named_columns(
+            &["b", "a"],
+            &[Array::Int32(vec![Some(4)]), Array::Int32(vec![Some(3)])],
+        )?,
+        write_named_columns(&["b"], &[Array::Int32(vec![Some(6)])])?,
+    ];
+    let paths = files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let path = std::env::temp_dir().join(format!(
+                "parquet2_dataset_column_order_{}_{}.parquet",
+                std::process::id(),
+                i
+            ));
+            std::fs::write(&path, file)?;
+            Ok(path)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let dataset = DatasetReader::new(paths.clone())?;
+    let mut values = vec![];
+    for row_group in dataset.row_groups() {
+        let mut file = row_group.open()?;
+        let mut read = |index| -> Result<_> {
+            row_group
+                .column(index)
+                .map(|column| read_pages(&mut file, column))
+                .transpose()
+        };
+        values.push((read(0)?, read(1)?));
+        assert!(row_group.column(2).is_none());
+    }
+    let array = |value| Some(vec![Array::Int32(vec![Some(value)])]);
+    assert_eq!(
+        values,
+        vec![(array(1), array(2)), (array(3), array(4)), (None, array(6))]
+    );
+
+    for path in paths {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Returns `data` compressed with the raw LZ4 block format, optionally framed as done by Hadoop
+fn compress_lz4(data: &[u8], hadoop: bool) -> Result<Vec<u8>> {
+    let mut compressed = vec![];
+    compress(CompressionOptions::Lz4Raw, data, &mut compressed)?;
+    if !hadoop {
+        return Ok(compressed);
+    }
+    let mut framed = vec![];
+    framed.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    framed.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+    framed.extend_from_slice(&compressed);
+    Ok(framed)
+}
+
+#[test]
+fn legacy_lz4_pages() -> Result<()> {
+    let array = alltypes_plain("id");
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let descriptor = Descriptor {
+        primitive_type: PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32),
+        max_def_level: 1,
+        max_rep_level: 0,
+    };
+    let page = match array_to_page(&array, &options, &descriptor)? {
+        EncodedPage::Data(page) => page,
+        _ => unreachable!(),
+    };
+
+    for hadoop in [true, false] {
+        let compressed = CompressedDataPage::new(
+            page.header().clone(),
+            compress_lz4(page.buffer(), hadoop)?,
+            Compression::Lz4,
+            page.buffer().len(),
+            None,
+            descriptor.clone(),
+            None,
+        );
+
+        let mut pages = BasicDecompressor::new(std::iter::once(Ok(compressed)), vec![]);
+        let result = super::page_to_array(pages.next()?.unwrap())?;
+        assert_eq!(array, result);
+    }
+    Ok(())
+}
+
+#[test]
+fn corrupted_compressed_pages() -> Result<()> {
+    let array = alltypes_plain("id");
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let descriptor = Descriptor {
+        primitive_type: PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32),
+        max_def_level: 1,
+        max_rep_level: 0,
+    };
+    let page = match array_to_page(&array, &options, &descriptor)? {
+        EncodedPage::Data(page) => page,
+        _ => unreachable!(),
+    };
+
+    // the values of this v2 header are the whole buffer; only the sizes matter here
+    let header_v2 = DataPageHeader::V2(DataPageHeaderV2 {
+        num_values: array.len() as i32,
+        num_nulls: 0,
+        num_rows: array.len() as i32,
+        encoding: Encoding::Plain.into(),
+        definition_levels_byte_length: 0,
+        repetition_levels_byte_length: 0,
+        is_compressed: None,
+        statistics: None,
+    });
+
+    // codecs whose feature is not active are skipped
+    let compressions = [CompressionOptions::Snappy, CompressionOptions::Zstd(None)]
+        .into_iter()
+        .filter(|compression| Compression::from(*compression).is_supported());
+    for compression in compressions {
+        for header in [page.header().clone(), header_v2.clone()] {
+            let mut compressed = vec![];
+            compress(compression, page.buffer(), &mut compressed)?;
+            let truncated = compressed[..compressed.len() / 2].to_vec();
+
+            let decompress = |buffer, uncompressed_size| {
+                let compressed_page = CompressedDataPage::new(
+                    header.clone(),
+                    buffer,
+                    compression.into(),
+                    uncompressed_size,
+                    None,
+                    descriptor.clone(),
+                    None,
+                );
+                let mut pages =
+                    BasicDecompressor::new(std::iter::once(Ok(compressed_page)), vec![]);
+                pages.next().map(|_| ())
+            };
+
+            assert!(decompress(truncated, page.buffer().len()).is_err());
+
+            // the page decompresses to fewer bytes than declared
+            let error = decompress(compressed, page.buffer().len() + 1).unwrap_err();
+            assert!(
+                matches!(&error, Error::OutOfSpec(message) if message.contains("\"col\"")),
+                "{:?}",
+                error
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn oversized_pages() -> Result<()> {
+    let array = Array::Int32((0..100).map(Some).collect());
+    let data = write_column(&array, CompressionOptions::Snappy)?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    // the column chunk has a single data page
+    let (start, length) = column.byte_range();
+    let mut chunk = vec![0; length as usize];
+    reader.seek(SeekFrom::Start(start))?;
+    reader.read_exact(&mut chunk)?;
+    let mut page = chunk.as_slice();
+    let header =
+        ParquetPageHeader::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut page))?;
+
+    let read = |uncompressed_page_size: i32, max_page_size: usize| -> Result<()> {
+        let mut header = header.clone();
+        header.uncompressed_page_size = uncompressed_page_size;
+        let mut chunk = vec![];
+        header.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut chunk))?;
+        chunk.extend_from_slice(page);
+
+        let options = ReadOptions {
+            max_page_size,
+            ..Default::default()
+        };
+        let pages = PageReader::new_with_options(
+            Cursor::new(chunk),
+            column,
+            Arc::new(|_, _| true),
+            vec![],
+            options,
+        );
+        let mut pages = BasicDecompressor::new(pages, vec![]);
+        pages.next().map(|_| ())
+    };
+
+    read(header.uncompressed_page_size, DEFAULT_MAX_PAGE_SIZE)?;
+    for (uncompressed_page_size, max_page_size) in [
+        (-1, DEFAULT_MAX_PAGE_SIZE),
+        (i32::MIN, DEFAULT_MAX_PAGE_SIZE),
+        (i32::MAX, 1024 * 1024),
+        // the compressed size is also limited
+        (header.uncompressed_page_size, 10),
+    ] {
+        let error = read(uncompressed_page_size, max_page_size).unwrap_err();
+        assert!(matches!(error, Error::OutOfSpec(_)), "{:?}", error);
+    }
+
+    // pages that do not come from a reader are limited by the decompressor
+    let terabytes = 4usize << 40;
+    let decompress = |max_page_size| {
+        let compressed_page = CompressedDataPage::new(
+            DataPageHeader::V1(header.data_page_header.clone().unwrap()),
+            page.to_vec(),
+            Compression::Snappy,
+            terabytes,
+            None,
+            column.descriptor().descriptor.clone(),
+            None,
+        );
+        let pages = std::iter::once(Ok(compressed_page));
+        let mut pages = match max_page_size {
+            Some(max_page_size) => {
+                BasicDecompressor::new_with_page_size(pages, vec![], max_page_size)
+            }
+            None => BasicDecompressor::new(pages, vec![]),
+        };
+        pages.next().map(|_| ())
+    };
+    for max_page_size in [None, Some(1024)] {
+        let error = decompress(max_page_size).unwrap_err();
+        assert!(
+            matches!(&error, Error::OutOfSpec(message) if message.contains(&terabytes.to_string())),
+            "{:?}",
+            error
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn read_key_value_metadata() -> Result<()> {
+    let arrays = [Array::Int64(vec![Some(1), None])];
+
+    // files without key-value metadata
+    let data = write_pages(&arrays, CompressionOptions::Uncompressed)?;
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    assert_eq!(metadata.key_value_metadata(), None);
+
+    // keys without values
+    let expected = vec![
+        KeyValue {
+            key: "pandas".to_string(),
+            value: Some("{}".to_string()),
+        },
+        KeyValue {
+            key: "empty".to_string(),
+            value: None,
+        },
+    ];
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, WriteOptions::default(), None);
+    writer.start()?;
+    writer.end(Some(expected.clone()))?;
+    let data = writer.into_inner().into_inner();
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    assert_eq!(metadata.key_value_metadata(), Some(expected.as_slice()));
+    Ok(())
+}
+
+/// Checks that the pages of `column` are decompressed by [`StreamingDecompressor`] into the
+/// same levels and values as by [`BasicDecompressor`]
+fn check_streaming(data: &[u8], column: &ColumnChunkMetaData, buffer_size: usize) -> Result<usize> {
+    let pages = get_page_iterator(column, Cursor::new(data), None, vec![])?;
+    let mut expected = BasicDecompressor::new(pages, vec![]);
+    let pages = get_page_iterator(column, Cursor::new(data), None, vec![])?;
+    let mut pages = BasicDecompressor::new_streaming(pages, buffer_size);
+
+    let mut num_pages = 0;
+    while let Some(mut page) = pages.next_page()? {
+        let expected = expected.next()?.unwrap();
+        let (rep, def, values) = split_buffer(expected);
+        assert_eq!(page.rep_levels(), rep);
+        assert_eq!(page.def_levels(), def);
+
+        let result = page.plain_values::<i64>().collect::<Result<Vec<_>>>()?;
+        let values = values
+            .chunks_exact(8)
+            .map(|x| i64::from_le_bytes(x.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(result, values);
+        num_pages += 1;
+    }
+    assert!(expected.next()?.is_none());
+    Ok(num_pages)
+}
+
+#[test]
+fn streaming_decompression() -> Result<()> {
+    let buffer_size = 1024;
+    // pages much larger than the buffer
+    let arrays = [
+        Array::Int64(
+            (0..100_000)
+                .map(|x| (x % 7 != 0).then(|| x % 1000))
+                .collect(),
+        ),
+        Array::Int64(vec![None; 10]),
+    ];
+
+    let compressions = [
+        CompressionOptions::Uncompressed,
+        CompressionOptions::Gzip(None),
+        CompressionOptions::Zstd(None),
+        CompressionOptions::Brotli(None),
+    ];
+    // codecs whose feature is not active are skipped
+    let is_supported =
+        |compression: &CompressionOptions| Compression::from(*compression).is_supported();
+    for compression in compressions.into_iter().filter(is_supported) {
+        let data = write_pages(&arrays, compression)?;
+        let metadata = read_metadata(&mut Cursor::new(&data))?;
+        let column = &metadata.row_groups[0].columns()[0];
+        assert!(column.uncompressed_size() as usize > 100 * buffer_size);
+
+        assert_eq!(check_streaming(&data, column, buffer_size)?, 2);
+    }
+
+    // data pages v2, whose levels are not compressed
+    let mut primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int64);
+    primitive_type.field_info.repetition = Repetition::Required;
+    for (compression, values) in [
+        (
+            CompressionOptions::Zstd(None),
+            (0..100_000).collect::<Vec<i64>>(),
+        ),
+        // values that do not benefit from compression are stored uncompressed
+        (CompressionOptions::Gzip(None), vec![1]),
+    ]
+    .into_iter()
+    .filter(|(compression, _)| is_supported(compression))
+    {
+        let data = write_single_column(
+            ParquetType::PrimitiveType(primitive_type.clone()),
+            WriteOptions::default(),
+            |descriptor| {
+                let header = DataPageHeaderV2 {
+                    num_values: values.len() as i32,
+                    encoding: Encoding::Plain.into(),
+                    num_nulls: 0,
+                    num_rows: values.len() as i32,
+                    definition_levels_byte_length: 0,
+                    repetition_levels_byte_length: 0,
+                    is_compressed: None,
+                    statistics: Some(serialize_statistics(&PrimitiveStatistics {
+                        primitive_type: descriptor.primitive_type.clone(),
+                        null_count: Some(0),
+                        distinct_count: None,
+                        max_value: values.iter().max().copied(),
+                        min_value: values.iter().min().copied(),
+                    })),
+                };
+                let page = DataPage::new(
+                    DataPageHeader::V2(header),
+                    values.iter().flat_map(|x| x.to_le_bytes()).collect(),
+                    None,
+                    descriptor.clone(),
+                    Some(values.len()),
+                );
+                vec![Ok(EncodedPage::Data(page))]
+            },
+            compression,
+        )?;
+        let metadata = read_metadata(&mut Cursor::new(&data))?;
+        let column = &metadata.row_groups[0].columns()[0];
+
+        assert_eq!(check_streaming(&data, column, buffer_size)?, 1);
+    }
+
+    // snappy has no streaming format
+    let data = write_pages(&arrays, CompressionOptions::Snappy)?;
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    let pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new_streaming(pages, buffer_size);
+    assert!(matches!(pages.next_page(), Err(Error::InvalidParameter(_))));
+    Ok(())
+}
+
+#[test]
+fn streaming_invalid_v2_levels() -> Result<()> {
+    let descriptor = Descriptor {
+        primitive_type: PrimitiveType::from_physical("col".to_string(), PhysicalType::Int64),
+        max_def_level: 1,
+        max_rep_level: 1,
+    };
+    for (repetition, definition) in [(-1, 0), (i32::MAX, i32::MAX), (4, 5)] {
+        let header = DataPageHeaderV2 {
+            num_values: 1,
+            encoding: Encoding::Plain.into(),
+            num_nulls: 0,
+            num_rows: 1,
+            definition_levels_byte_length: definition,
+            repetition_levels_byte_length: repetition,
+            is_compressed: Some(false),
+            statistics: None,
+        };
+        let page = CompressedDataPage::new(
+            DataPageHeader::V2(header),
+            vec![0; 8],
+            Compression::Uncompressed,
+            8,
+            None,
+            descriptor.clone(),
+            None,
+        );
+        let mut pages = BasicDecompressor::new_streaming(vec![Ok(page)].into_iter(), 1024);
+        assert!(matches!(pages.next_page(), Err(Error::OutOfSpec(_))));
+    }
+    Ok(())
+}
+
+fn decimal_page(
+    array: &[Option<Vec<u8>>],
+    physical_type: PhysicalType,
+    logical_type: PrimitiveLogicalType,
+) -> Result<DataPage> {
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let field =
+        ParquetType::from_physical_with_logical("col".to_string(), physical_type, logical_type)?;
+    let schema = SchemaDescriptor::new("schema".to_string(), vec![field]);
+    match binary::array_to_page_v1(
+        array,
+        &options,
+        &schema.columns()[0].descriptor,
+        Encoding::Plain,
+    )? {
+        EncodedPage::Data(page) => Ok(page),
+        EncodedPage::Dict(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn read_decimal() -> Result<()> {
+    use parquet2::deserialize::read_decimal;
+
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+
+    // INT32 and INT64
+    for (physical_type, precision) in [(PhysicalType::Int32, 9), (PhysicalType::Int64, 18)] {
+        let field = ParquetType::from_physical_with_logical(
+            "col".to_string(),
+            physical_type,
+            PrimitiveLogicalType::Decimal(precision, 2),
+        )?;
+        let schema = SchemaDescriptor::new("schema".to_string(), vec![field]);
+        let descriptor = &schema.columns()[0].descriptor;
+        let page = match physical_type {
+            PhysicalType::Int32 => {
+                array_to_page_v1(&[Some(12345i32), None, Some(-1)], &options, descriptor)?
+            }
+            _ => array_to_page_v1(&[Some(12345i64), None, Some(-1)], &options, descriptor)?,
+        };
+        let page = match page {
+            EncodedPage::Data(page) => page,
+            EncodedPage::Dict(_) => unreachable!(),
+        };
+        assert_eq!(
+            read_decimal(&page, precision, 2)?,
+            vec![Some(12345), None, Some(-1)]
+        );
+        // 12345 has more digits than a precision of 4
+        assert!(read_decimal(&page, 4, 2).is_err());
+    }
+
+    // FIXED_LEN_BYTE_ARRAY: the largest and smallest decimals of precision 38
+    let max = 10i128.pow(38) - 1;
+    let array = vec![
+        Some(max.to_be_bytes().to_vec()),
+        None,
+        Some((-max).to_be_bytes().to_vec()),
+        Some((-1i128).to_be_bytes().to_vec()),
+    ];
+    let page = decimal_page(
+        &array,
+        PhysicalType::FixedLenByteArray(16),
+        PrimitiveLogicalType::Decimal(38, 10),
+    )?;
+    assert_eq!(
+        read_decimal(&page, 38, 10)?,
+        vec![Some(max), None, Some(-max), Some(-1)]
+    );
+    let array = vec![Some((max + 1).to_be_bytes().to_vec())];
+    let page = decimal_page(
+        &array,
+        PhysicalType::FixedLenByteArray(16),
+        PrimitiveLogicalType::Decimal(38, 10),
+    )?;
+    assert!(matches!(
+        read_decimal(&page, 38, 10),
+        Err(Error::OutOfSpec(_))
+    ));
+
+    // BYTE_ARRAY: values of different lengths, sign-extended beyond 16 bytes
+    let array = vec![
+        Some(vec![0x01, 0x00]),
+        Some(vec![0xff]),
+        None,
+        Some(vec![0xff; 20]),
+        Some([vec![0; 4], max.to_be_bytes().to_vec()].concat()),
+    ];
+    let page = decimal_page(
+        &array,
+        PhysicalType::ByteArray,
+        PrimitiveLogicalType::Decimal(38, 0),
+    )?;
+    assert_eq!(
+        read_decimal(&page, 38, 0)?,
+        vec![Some(256), Some(-1), None, Some(-1), Some(max)]
+    );
+    // a value that does not fit in an i128
+    let array = vec![Some([vec![1], vec![0; 16]].concat())];
+    let page = decimal_page(
+        &array,
+        PhysicalType::ByteArray,
+        PrimitiveLogicalType::Decimal(38, 0),
+    )?;
+    assert!(matches!(
+        read_decimal(&page, 38, 0),
+        Err(Error::OutOfSpec(_))
+    ));
+
+    // invalid parameters
+    assert!(matches!(
+        read_decimal(&page, 39, 0),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        read_decimal(&page, 2, 3),
+        Err(Error::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "bloom_filter")]
+fn bloom_filter_longer_than_file() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let array = vec![Some(1), Some(2)];
+    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+        DynIter::new(std::iter::once(array_to_page_v1(
+            &array,
+            &options,
+            &schema.columns()[0].descriptor,
+        ))),
+        CompressionOptions::Uncompressed,
+        vec![],
+    ));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(std::iter::once(Ok(pages))))?;
+    writer.write_bloom_filter(0, &BloomFilter::new(1024))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let column = &metadata.row_groups[0].columns()[0];
+    let offset = column.metadata().bloom_filter_offset.unwrap() as usize;
+
+    // the file ends in the middle of the filter's bitset
+    let mut reader = Cursor::new(&data[..offset + 100]);
+    assert!(matches!(
+        read_bloom_filter(column, &mut reader),
+        Err(Error::OutOfSpec(_))
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_metadata_async() -> Result<()> {
+    // metadata smaller and larger than the bytes read by default from the end of the file
+    for value_len in [10, 100 * 1024] {
+        let options = WriteOptions {
+            write_statistics: false,
+            ..Default::default()
+        };
+        let schema = SchemaDescriptor::new(
+            "schema".to_string(),
+            vec![ParquetType::from_physical(
+                "col".to_string(),
+                PhysicalType::Int32,
+            )],
+        );
+        let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None)
+            .add_key_value("large", "a".repeat(value_len));
+        writer.start()?;
+        writer.end(None)?;
+        let data = writer.into_inner().into_inner();
+
+        let expected = read_metadata(&mut Cursor::new(&data))?;
+        assert_eq!(
+            expected.key_value_metadata().unwrap()[0]
+                .value
+                .as_ref()
+                .map(|x| x.len()),
+            Some(value_len)
+        );
+
+        let metadata =
+            parquet2::read::read_metadata_async(&mut futures::io::Cursor::new(&data)).await?;
+        assert_eq!(metadata.schema(), expected.schema());
+        assert_eq!(metadata.key_value_metadata(), expected.key_value_metadata());
+
+        let mut reader = PartialReader(futures::io::Cursor::new(&data));
+        let metadata = parquet2::read::read_metadata_async(&mut reader).await?;
+        assert_eq!(metadata.key_value_metadata(), expected.key_value_metadata());
+
+        // the footer announces more metadata than the (truncated) file has
+        let truncated = &data[data.len().saturating_sub(80 * 1024)..];
+        if truncated.len() < data.len() {
+            let mut reader = futures::io::Cursor::new(truncated);
+            assert!(parquet2::read::read_metadata_async(&mut reader)
+                .await
+                .is_err());
+            assert!(read_metadata(&mut Cursor::new(truncated)).is_err());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "object_store")]
+#[tokio::test]
+async fn object_store_reader() -> Result<()> {
+    use object_store::{memory::InMemory, path::Path, ObjectStore, PutPayload};
+    use parquet2::read::ObjectStoreReader;
+
+    let arrays = [
+        Array::Int64((0..1000).map(Some).collect()),
+        Array::Int64((1000..1500).map(Some).collect()),
+    ];
+    let data = write_pages(&arrays, CompressionOptions::Uncompressed)?;
+    let expected = read_metadata(&mut Cursor::new(&data))?;
+
+    let store = Arc::new(InMemory::new());
+    let path = Path::from("data/file.parquet");
+    store.put(&path, PutPayload::from(data.clone())).await?;
+
+    let mut reader = ObjectStoreReader::new(store, path);
+    let metadata = reader.read_metadata().await?;
+    assert_eq!(metadata.schema(), expected.schema());
+    assert_eq!(metadata.num_rows, 1500);
+
+    let columns = metadata.row_groups[0].columns();
+    let chunks = reader.read_column_chunks(columns).await?;
+    assert_eq!(chunks.len(), 1);
+    let (start, length) = columns[0].byte_range();
+    assert_eq!(chunks[0], &data[start as usize..(start + length) as usize]);
+
+    // the pages of the column chunk are read from its bytes
+    let pages = PageReader::new(
+        Cursor::new(&chunks[0]),
+        &columns[0],
+        Arc::new(|_, _| true),
+        vec![],
+    )
+    .collect::<Result<Vec<_>>>()?;
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[1].num_values(), 500);
+    Ok(())
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_reader() -> Result<()> {
+    use futures::StreamExt;
+    use parquet2::read::{get_page_stream, read_metadata_async, TokioReader};
+
+    let arrays = [
+        Array::Int64((0..1000).map(Some).collect()),
+        Array::Int64((1000..1500).map(Some).collect()),
+    ];
+    let data = write_pages(&arrays, CompressionOptions::Snappy)?;
+    let expected = read_metadata(&mut Cursor::new(&data))?;
+
+    // `std::io::Cursor` implements `tokio`'s `AsyncRead` and `AsyncSeek`
+    let mut reader = TokioReader::new(Cursor::new(&data));
+    let metadata = read_metadata_async(&mut reader).await?;
+    assert_eq!(metadata.schema(), expected.schema());
+    assert_eq!(metadata.num_rows, 1500);
+
+    let column = &metadata.row_groups[0].columns()[0];
+    let pages = get_page_stream(column, &mut reader, vec![], Arc::new(|_, _| true))
+        .await?
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[1].num_values(), 500);
+    Ok(())
+}
+
+#[test]
+fn filter_row_groups_with_predicate() -> Result<()> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let descriptor = schema.columns()[0].descriptor.clone();
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    // one row group of [start, start + 10) per start, with a null in the last one
+    for start in [0, 33, 42, 50] {
+        let mut values = (start..start + 10).map(Some).collect::<Vec<_>>();
+        if start == 50 {
+            values[0] = None;
+        }
+        let page = array_to_page(&Array::Int32(values), &options, &descriptor);
+        let column = DynStreamingIterator::new(Compressor::new_from_vec(
+            DynIter::new(std::iter::once(page)),
+            CompressionOptions::Uncompressed,
+            vec![],
+        ));
+        writer.write(DynIter::new(std::iter::once(Ok(column))))?;
+    }
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    let first_values = |row_groups: Vec<&RowGroupMetaData>| {
+        row_groups
+            .into_iter()
+            .map(|row_group| {
+                let statistics = row_group.columns()[0].statistics().unwrap().unwrap();
+                let statistics = statistics
+                    .as_any()
+                    .downcast_ref::<PrimitiveStatistics<i32>>()
+                    .unwrap();
+                statistics.min_value.unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // the row group whose max is 9 is pruned, and so is the one whose min is 50
+    let row_groups =
+        predicate::filter_row_groups(&metadata.row_groups, &predicate::EqPredicate::new(0, 42i32));
+    assert_eq!(first_values(row_groups), vec![33, 42]);
+
+    let row_groups = predicate::filter_row_groups(
+        &metadata.row_groups,
+        &predicate::RangePredicate::new(0, None, Some(5i32)),
+    );
+    assert_eq!(first_values(row_groups), vec![0]);
+
+    let row_groups =
+        predicate::filter_row_groups(&metadata.row_groups, &predicate::NullPredicate::new(0));
+    assert_eq!(first_values(row_groups), vec![51]);
+    Ok(())
+}
+
+#[test]
+fn read_typed_statistics() -> Result<()> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("a".to_string(), PhysicalType::Int64),
+            ParquetType::from_physical("b".to_string(), PhysicalType::ByteArray),
+        ],
+    );
+    let arrays = [
+        Array::Int64(vec![Some(3), None, Some(-2)]),
+        Array::Binary(vec![Some(b"b".to_vec()), Some(b"a".to_vec()), None]),
+    ];
+    let columns = arrays
+        .iter()
+        .zip(schema.columns())
+        .map(|(array, column)| {
+            let page = array_to_page(array, &options, &column.descriptor);
+            Ok(DynStreamingIterator::new(Compressor::new_from_vec(
+                DynIter::new(std::iter::once(page)),
+                CompressionOptions::Uncompressed,
+                vec![],
+            )))
+        })
+        .collect::<Vec<_>>();
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns.into_iter()))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    let columns = metadata.row_groups[0].columns();
+    match read_statistics(&columns[0])? {
+        Some(TypedStatistics::Int64 {
+            min,
+            max,
+            null_count,
+            ..
+        }) => {
+            assert_eq!((min, max, null_count), (Some(-2), Some(3), Some(1)));
+        }
+        other => panic!("unexpected statistics {:?}", other),
+    }
+    let statistics = read_statistics(&columns[1])?.unwrap();
+    assert_eq!(statistics.physical_type(), PhysicalType::ByteArray);
+    assert_eq!(statistics.null_count(), Some(1));
+    assert!(matches!(
+        statistics,
+        TypedStatistics::ByteArray { min: Some(min), max: Some(max), .. }
+            if min == b"a" && max == b"b"
+    ));
+    Ok(())
+}
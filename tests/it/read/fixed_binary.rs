@@ -14,17 +14,20 @@ pub fn page_to_vec(page: &DataPage) -> Result<Vec<Option<Vec<u8>>>> {
         FixedLenBinaryPageState::Required(values) => {
             Ok(values.map(|x| x.to_vec()).map(Some).collect())
         }
-        FixedLenBinaryPageState::RequiredDictionary(dict) => Ok(dict
+        FixedLenBinaryPageState::RequiredDictionary(dict) => dict
             .indexes
             .map(|x| x as usize)
-            .map(|x| dict.dict.value(x).to_vec())
-            .map(Some)
-            .collect()),
+            .map(|x| dict.dict.value(x).map(|x| x.to_vec()).map(Some))
+            .collect(),
         FixedLenBinaryPageState::OptionalDictionary(validity, dict) => {
             let values = dict
                 .indexes
                 .map(|x| x as usize)
-                .map(|x| dict.dict.value(x).to_vec());
+                .map(|x| dict.dict.value(x).map(|x| x.to_vec()).unwrap());
+            deserialize_optional(validity, values)
+        }
+        FixedLenBinaryPageState::RequiredByteStreamSplit(values) => Ok(values.map(Some).collect()),
+        FixedLenBinaryPageState::OptionalByteStreamSplit(validity, values) => {
             deserialize_optional(validity, values)
         }
     }
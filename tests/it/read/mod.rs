@@ -4,6 +4,7 @@
 mod binary;
 mod boolean;
 mod deserialize;
+mod file;
 mod fixed_binary;
 mod indexes;
 mod primitive;
@@ -79,7 +80,7 @@ where
             let mut iterator = BasicDecompressor::new(pages, vec![]);
             while let Some(page) = iterator.next()? {
                 if !has_filled {
-                    struct_::extend_validity(&mut validity, page);
+                    struct_::extend_validity(&mut validity, page)?;
                 }
                 // todo: this is wrong: multiple pages -> array
                 arrays.push(page_to_array(page)?)
@@ -437,6 +438,26 @@ fn pyarrow_v2_non_dict_int64_optional_compressed() -> Result<()> {
     test_pyarrow_integration("basic", "int64", 2, false, false, "/snappy")
 }
 
+#[test]
+fn pyarrow_v1_byte_stream_split_float64_required() -> Result<()> {
+    test_pyarrow_integration("basic", "float64", 1, true, false, "/byte_stream_split")
+}
+
+#[test]
+fn pyarrow_v1_byte_stream_split_float64_optional() -> Result<()> {
+    test_pyarrow_integration("basic", "float64", 1, false, false, "/byte_stream_split")
+}
+
+#[test]
+fn pyarrow_v2_byte_stream_split_float64_required() -> Result<()> {
+    test_pyarrow_integration("basic", "float64", 2, true, false, "/byte_stream_split")
+}
+
+#[test]
+fn pyarrow_v2_byte_stream_split_float64_optional() -> Result<()> {
+    test_pyarrow_integration("basic", "float64", 2, false, false, "/byte_stream_split")
+}
+
 #[test]
 fn pyarrow_v1_boolean_optional() -> Result<()> {
     test_pyarrow_integration("basic", "bool", 1, false, false, "")
@@ -516,3 +537,71 @@ fn pyarrow_v1_struct_required() -> Result<()> {
 fn pyarrow_v2_struct_required() -> Result<()> {
     test_pyarrow_integration("struct", "struct_required", 2, false, false, "")
 }
+
+#[cfg(feature = "arrow-schema")]
+#[test]
+fn pyarrow_arrow_schema() -> Result<()> {
+    use parquet2::interop::arrow::{match_fields, read_arrow_schema, ArrowDataType, TimeUnit};
+
+    if std::env::var("PARQUET2_IGNORE_PYARROW_TESTS").is_ok() {
+        return Ok(());
+    }
+    let read = |path: &str| -> Result<_> {
+        let metadata = read_metadata(&mut File::open(path)?)?;
+        let arrow = read_arrow_schema(metadata.key_value_metadata.as_deref())?.unwrap();
+        Ok((metadata, arrow))
+    };
+    let int64 = ArrowDataType::Int {
+        bit_width: 64,
+        is_signed: true,
+    };
+
+    let (_, arrow) = read("fixtures/pyarrow3/v1/non_dict/basic_nullable_10.parquet")?;
+    let field = |name| arrow.field(name).map(|x| x.data_type.clone());
+    assert_eq!(field("int64"), Some(int64.clone()));
+    assert_eq!(field("string"), Some(ArrowDataType::Utf8));
+    assert_eq!(
+        field("date"),
+        Some(ArrowDataType::Timestamp(TimeUnit::Millisecond, None))
+    );
+    assert_eq!(
+        field("uint32"),
+        Some(ArrowDataType::Int {
+            bit_width: 32,
+            is_signed: false
+        })
+    );
+    assert_eq!(
+        field("fixed_binary"),
+        Some(ArrowDataType::FixedSizeBinary(2))
+    );
+
+    // the element of a list is matched with the item of the Arrow list
+    let (metadata, arrow) = read("fixtures/pyarrow3/v1/non_dict/nested_nullable_10.parquet")?;
+    let matched = match_fields(metadata.schema(), &arrow);
+    let list = &matched[0];
+    assert_eq!(list.arrow.unwrap().data_type, ArrowDataType::List);
+    let item = &list.children[0];
+    assert!(matches!(item.parquet, ParquetType::PrimitiveType(_)));
+    assert_eq!(item.arrow.unwrap().data_type, int64);
+
+    // the key-value group of a map is matched with the entries of the Arrow map
+    let (metadata, arrow) = read("fixtures/pyarrow3/v1/non_dict/map_nullable_10.parquet")?;
+    let matched = match_fields(metadata.schema(), &arrow);
+    let map = &matched[0];
+    assert!(matches!(
+        map.arrow.unwrap().data_type,
+        ArrowDataType::Map { .. }
+    ));
+    let entries = &map.children[0];
+    let types = entries
+        .children
+        .iter()
+        .map(|x| (x.parquet.name(), x.arrow.map(|x| x.data_type.clone())))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![("key", Some(ArrowDataType::Utf8)), ("value", Some(int64))]
+    );
+    Ok(())
+}
@@ -98,6 +98,15 @@ pub fn page_to_vec<T: NativeType>(page: &DataPage) -> Result<Vec<Option<T>>, Err
                 let values = dict.indexes.map(|x| x as usize).map(|x| dict.values[x]);
                 deserialize_optional(validity, values)
             }
+            NativePageState::RequiredByteStreamSplit(values) => Ok(values.map(Some).collect()),
+            NativePageState::OptionalByteStreamSplit(validity, values) => {
+                deserialize_optional(validity, values)
+            }
+            NativePageState::RequiredDelta(values) => values.map(|x| x.map(Some)).collect(),
+            NativePageState::OptionalDelta(validity, values) => {
+                let values = values.collect::<Result<Vec<_>, _>>()?;
+                deserialize_optional(validity, values.into_iter())
+            }
         },
         PageState::Filtered(state) => match state {
             FilteredPageState::Optional(values) => Ok(values.collect()),
@@ -70,7 +70,7 @@ fn read_array_impl<T: NativeType, I: Iterator<Item = i64>>(
     length: usize,
     rep_level_encoding: (&Encoding, i16),
     def_level_encoding: (&Encoding, i16),
-) -> Array {
+) -> Result<Array> {
     let max_rep_level = rep_level_encoding.1 as u32;
     let max_def_level = def_level_encoding.1 as u32;
 
@@ -78,41 +78,47 @@ fn read_array_impl<T: NativeType, I: Iterator<Item = i64>>(
         (rep_level_encoding.0, max_rep_level == 0),
         (def_level_encoding.0, max_def_level == 0),
     ) {
-        ((Encoding::Rle, true), (Encoding::Rle, true)) => compose_array(
+        ((Encoding::Rle, true), (Encoding::Rle, true)) => Ok(compose_array(
             std::iter::repeat(0).take(length as usize),
             std::iter::repeat(0).take(length as usize),
             max_rep_level,
             max_def_level,
             values,
-        ),
+        )),
         ((Encoding::Rle, false), (Encoding::Rle, true)) => {
             let num_bits = get_bit_width(rep_level_encoding.1);
-            let rep_levels = HybridRleDecoder::new(rep_levels, num_bits, length);
-            compose_array(
+            let rep_levels = HybridRleDecoder::try_new(rep_levels, num_bits, length)?;
+            Ok(compose_array(
                 rep_levels,
                 std::iter::repeat(0).take(length as usize),
                 max_rep_level,
                 max_def_level,
                 values,
-            )
+            ))
         }
         ((Encoding::Rle, true), (Encoding::Rle, false)) => {
             let num_bits = get_bit_width(def_level_encoding.1);
-            let def_levels = HybridRleDecoder::new(def_levels, num_bits, length);
-            compose_array(
+            let def_levels = HybridRleDecoder::try_new(def_levels, num_bits, length)?;
+            Ok(compose_array(
                 std::iter::repeat(0).take(length as usize),
                 def_levels,
                 max_rep_level,
                 max_def_level,
                 values,
-            )
+            ))
         }
         ((Encoding::Rle, false), (Encoding::Rle, false)) => {
             let rep_levels =
-                HybridRleDecoder::new(rep_levels, get_bit_width(rep_level_encoding.1), length);
+                HybridRleDecoder::try_new(rep_levels, get_bit_width(rep_level_encoding.1), length)?;
             let def_levels =
-                HybridRleDecoder::new(def_levels, get_bit_width(def_level_encoding.1), length);
-            compose_array(rep_levels, def_levels, max_rep_level, max_def_level, values)
+                HybridRleDecoder::try_new(def_levels, get_bit_width(def_level_encoding.1), length)?;
+            Ok(compose_array(
+                rep_levels,
+                def_levels,
+                max_rep_level,
+                max_def_level,
+                values,
+            ))
         }
         _ => todo!(),
     }
@@ -125,7 +131,7 @@ fn read_array<T: NativeType>(
     length: u32,
     rep_level_encoding: (&Encoding, i16),
     def_level_encoding: (&Encoding, i16),
-) -> Array {
+) -> Result<Array> {
     let values = read_buffer::<i64>(values);
     read_array_impl::<T, _>(
         rep_levels,
@@ -141,7 +147,7 @@ pub fn page_to_array<T: NativeType>(page: &DataPage) -> Result<Array> {
     let (rep_levels, def_levels, values) = split_buffer(page);
 
     match (&page.encoding(), &page.dictionary_page()) {
-        (Encoding::Plain, None) => Ok(read_array::<T>(
+        (Encoding::Plain, None) => read_array::<T>(
             rep_levels,
             def_levels,
             values,
@@ -154,7 +160,7 @@ pub fn page_to_array<T: NativeType>(page: &DataPage) -> Result<Array> {
                 &page.definition_level_encoding(),
                 page.descriptor.max_def_level,
             ),
-        )),
+        ),
         _ => todo!(),
     }
 }
@@ -167,13 +173,13 @@ fn read_dict_array<T: NativeType>(
     dict: &PrimitivePageDict<i64>,
     rep_level_encoding: (&Encoding, i16),
     def_level_encoding: (&Encoding, i16),
-) -> Array {
+) -> Result<Array> {
     let dict_values = dict.values();
 
     let bit_width = values[0];
     let values = &values[1..];
 
-    let (_, consumed) = uleb128::decode(values);
+    let (_, consumed) = uleb128::decode(values)?;
     let values = &values[consumed..];
 
     let indices = bitpacking::Decoder::new(values, bit_width, length as usize);
@@ -196,7 +202,7 @@ pub fn page_dict_to_array<T: NativeType>(page: &DataPage) -> Result<Array> {
     let (rep_levels, def_levels, values) = split_buffer(page);
 
     match (page.encoding(), &page.dictionary_page()) {
-        (Encoding::PlainDictionary, Some(dict)) => Ok(read_dict_array::<T>(
+        (Encoding::PlainDictionary, Some(dict)) => read_dict_array::<T>(
             rep_levels,
             def_levels,
             values,
@@ -210,7 +216,7 @@ pub fn page_dict_to_array<T: NativeType>(page: &DataPage) -> Result<Array> {
                 &page.definition_level_encoding(),
                 page.descriptor.max_def_level,
             ),
-        )),
+        ),
         (_, None) => Err(Error::OutOfSpec(
             "A dictionary-encoded page MUST be preceeded by a dictionary page".to_string(),
         )),
@@ -1,13 +1,14 @@
 use parquet2::encoding::hybrid_rle::HybridRleDecoder;
+use parquet2::error::Result;
 use parquet2::page::{split_buffer, DataPage};
 use parquet2::read::levels::get_bit_width;
 
-pub fn extend_validity(val: &mut Vec<bool>, page: &DataPage) {
+pub fn extend_validity(val: &mut Vec<bool>, page: &DataPage) -> Result<()> {
     let (_, def_levels, _) = split_buffer(page);
     let length = page.num_values();
 
     if page.descriptor.max_def_level == 0 {
-        return;
+        return Ok(());
     }
 
     let def_level_encoding = (
@@ -15,7 +16,9 @@ pub fn extend_validity(val: &mut Vec<bool>, page: &DataPage) {
         page.descriptor.max_def_level,
     );
 
-    let def_levels = HybridRleDecoder::new(def_levels, get_bit_width(def_level_encoding.1), length);
+    let def_levels =
+        HybridRleDecoder::try_new(def_levels, get_bit_width(def_level_encoding.1), length)?;
 
     val.extend(def_levels.map(|x| x != 0));
+    Ok(())
 }
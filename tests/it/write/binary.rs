@@ -1,14 +1,19 @@
 use parquet2::{
-    encoding::Encoding,
+    encoding::{byte_stream_split, encode_binary, Encoding},
     metadata::Descriptor,
     page::{DataPage, DataPageHeader, DataPageHeaderV1, EncodedPage},
-    statistics::{serialize_statistics, BinaryStatistics, Statistics},
+    schema::types::PhysicalType,
+    statistics::{serialize_statistics, BinaryStatistics, FixedLenStatistics, Statistics},
     types::ord_binary,
     write::WriteOptions,
-    {encoding::hybrid_rle::encode_bool, error::Result},
+    {encoding::hybrid_rle::encode_bool, error::Error, error::Result},
 };
 
-fn unzip_option(array: &[Option<Vec<u8>>]) -> Result<(Vec<u8>, Vec<u8>)> {
+fn unzip_option(
+    array: &[Option<Vec<u8>>],
+    physical_type: PhysicalType,
+    encoding: Encoding,
+) -> Result<(Vec<u8>, Vec<u8>)> {
     // leave the first 4 bytes anouncing the length of the def level
     // this will be overwritten at the end, once the length is known.
     // This is unknown at this point because of the uleb128 encoding,
@@ -16,17 +21,32 @@ fn unzip_option(array: &[Option<Vec<u8>>]) -> Result<(Vec<u8>, Vec<u8>)> {
     let mut validity = std::io::Cursor::new(vec![0; 4]);
     validity.set_position(4);
 
+    let iter = array.iter().map(|value| value.is_some());
+    encode_bool(&mut validity, iter)?;
+
     let mut values = vec![];
-    let iter = array.iter().map(|value| {
-        if let Some(item) = value {
-            values.extend_from_slice(&(item.len() as i32).to_le_bytes());
-            values.extend_from_slice(item.as_ref());
-            true
-        } else {
-            false
+    match (encoding, physical_type) {
+        (Encoding::Plain, PhysicalType::FixedLenByteArray(_)) => array
+            .iter()
+            .flatten()
+            .for_each(|item| values.extend_from_slice(item)),
+        (Encoding::ByteStreamSplit, PhysicalType::FixedLenByteArray(size)) => {
+            let array = array
+                .iter()
+                .flatten()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            byte_stream_split::encode_fixed_len(&array, size, &mut values)
         }
-    });
-    encode_bool(&mut validity, iter)?;
+        (other, PhysicalType::FixedLenByteArray(_)) => {
+            return Err(Error::InvalidParameter(format!(
+                "The values of a fixed-length byte array column cannot be encoded with {:?}",
+                other
+            )))
+        }
+        _ => encode_binary(array.iter().flatten(), encoding, &mut values)?,
+    }
 
     // write the length, now that it is known
     let mut validity = validity.into_inner();
@@ -45,13 +65,15 @@ pub fn array_to_page_v1(
     array: &[Option<Vec<u8>>],
     options: &WriteOptions,
     descriptor: &Descriptor,
+    encoding: Encoding,
 ) -> Result<EncodedPage> {
-    let (values, mut buffer) = unzip_option(array)?;
+    let physical_type = descriptor.primitive_type.physical_type;
+    let (values, mut buffer) = unzip_option(array, physical_type, encoding)?;
 
     buffer.extend_from_slice(&values);
 
     let statistics = if options.write_statistics {
-        let statistics = &BinaryStatistics {
+        let statistics = BinaryStatistics {
             primitive_type: descriptor.primitive_type.clone(),
             null_count: Some((array.len() - array.iter().flatten().count()) as i64),
             distinct_count: None,
@@ -65,15 +87,25 @@ pub fn array_to_page_v1(
                 .flatten()
                 .min_by(|x, y| ord_binary(x, y))
                 .cloned(),
-        } as &dyn Statistics;
-        Some(serialize_statistics(statistics))
+        };
+        let statistics: Box<dyn Statistics> = match physical_type {
+            PhysicalType::FixedLenByteArray(_) => Box::new(FixedLenStatistics {
+                primitive_type: statistics.primitive_type,
+                null_count: statistics.null_count,
+                distinct_count: statistics.distinct_count,
+                max_value: statistics.max_value,
+                min_value: statistics.min_value,
+            }),
+            _ => Box::new(statistics),
+        };
+        Some(serialize_statistics(statistics.as_ref()))
     } else {
         None
     };
 
     let header = DataPageHeaderV1 {
         num_values: array.len() as i32,
-        encoding: Encoding::Plain.into(),
+        encoding: encoding.into(),
         definition_level_encoding: Encoding::Rle.into(),
         repetition_level_encoding: Encoding::Rle.into(),
         statistics,
@@ -0,0 +1,294 @@
+use std::io::Cursor;
+
+use parquet2::bloom_filter::{read_bloom_filter, BloomFilter};
+use parquet2::compression::CompressionOptions;
+use parquet2::error::Result;
+use parquet2::metadata::{ColumnChunkMetaData, SchemaDescriptor};
+use parquet2::page::EncodedDictPage;
+use parquet2::read::read_metadata;
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write::dictionary;
+use parquet2::write::{
+    BloomFilterOptions, Compressor, DynIter, DynStreamingIterator, FileWriter, Version,
+};
+use parquet2::{page::EncodedPage, write::WriteOptions};
+
+use crate::Array;
+
+use super::dict::dict_levels;
+use super::primitive::array_to_page_v1;
+use super::read_column;
+
+#[test]
+fn bloom_filter() -> Result<()> {
+    let array = (0..1000).map(Some).collect::<Vec<_>>();
+
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+
+    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+        DynIter::new(std::iter::once(array_to_page_v1(
+            &array,
+            &options,
+            &schema.columns()[0].descriptor,
+        ))),
+        CompressionOptions::Uncompressed,
+        vec![],
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut filter = BloomFilter::try_from_options(&BloomFilterOptions {
+        fpp: 0.01,
+        num_distinct_values: 1000,
+    })?;
+    array
+        .iter()
+        .flatten()
+        .for_each(|x| filter.insert_native(*x));
+
+    let writer = Cursor::new(vec![]);
+    let mut writer = FileWriter::new(writer, schema, options, None);
+
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.write_bloom_filter(0, &filter)?;
+    assert!(writer.write_bloom_filter(1, &filter).is_err());
+    writer.end(None)?;
+
+    let data = writer.into_inner().into_inner();
+    let mut reader = Cursor::new(data);
+
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert!(column.metadata().bloom_filter_offset.is_some());
+
+    let result = read_bloom_filter(column, &mut reader)?.unwrap();
+    assert_eq!(result, filter);
+    assert!((0..1000).all(|x| result.might_contain_native(x)));
+    let false_positives = (1000..11000)
+        .filter(|x| result.might_contain_native(*x))
+        .count();
+    assert!(false_positives < 200);
+
+    // the data is still readable
+    let (read, _) = read_column(&mut reader)?;
+    assert_eq!(read, Array::Int32(array));
+
+    // a column without bloom filter
+    let mut column_chunk = column.column_chunk().clone();
+    column_chunk.meta_data.as_mut().unwrap().bloom_filter_offset = None;
+    let column = ColumnChunkMetaData::new(column_chunk, column.descriptor().clone());
+    assert!(read_bloom_filter(&column, &mut reader)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn bloom_filter_all_null() -> Result<()> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let array = vec![None; 100];
+    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+        DynIter::new(std::iter::once(array_to_page_v1(
+            &array,
+            &options,
+            &schema.columns()[0].descriptor,
+        ))),
+        CompressionOptions::Uncompressed,
+        vec![],
+    ));
+
+    // no value is inserted
+    let filter = BloomFilter::try_from_options(&BloomFilterOptions {
+        fpp: 0.01,
+        num_distinct_values: 0,
+    })?;
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(std::iter::once(Ok(pages))))?;
+    writer.write_bloom_filter(0, &filter)?;
+    writer.end(None)?;
+
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    let result = read_bloom_filter(column, &mut reader)?.unwrap();
+    assert_eq!(result, filter);
+    assert!((-100..100).all(|x| !result.might_contain_native(x)));
+
+    let (read, _) = read_column(&mut reader)?;
+    assert_eq!(read, Array::Int32(array));
+    Ok(())
+}
+
+#[test]
+fn bloom_filter_from_pages() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("plain".to_string(), PhysicalType::Int32),
+            ParquetType::from_physical("dict".to_string(), PhysicalType::Int32),
+            ParquetType::from_physical("null".to_string(), PhysicalType::Int32),
+        ],
+    );
+    let bloom_options = BloomFilterOptions {
+        fpp: 0.01,
+        num_distinct_values: 1000,
+    };
+
+    let plain = (0..1000)
+        .map(|x| (x % 10 != 0).then(|| x))
+        .collect::<Vec<_>>();
+    let plain_page = array_to_page_v1(&plain, &options, &schema.columns()[0].descriptor)?;
+
+    let dict = [10i32, 20, 30];
+    let dict_page = EncodedDictPage::new(
+        dict.iter().flat_map(|x| x.to_le_bytes()).collect(),
+        dict.len(),
+    );
+    let indices = (0..100u32)
+        .map(|x| (x % 7 != 0).then(|| x % 3))
+        .collect::<Vec<_>>();
+    let (levels, indices) = dict_levels(&indices)?;
+    let dict_data_page = dictionary::dict_data_page(
+        &indices,
+        2,
+        levels,
+        None,
+        schema.columns()[1].descriptor.clone(),
+        Version::V1,
+    )?;
+
+    let null = vec![None::<i32>; 100];
+    let null_page = array_to_page_v1(&null, &options, &schema.columns()[2].descriptor)?;
+
+    let columns = vec![
+        vec![Ok(plain_page)],
+        vec![
+            Ok(EncodedPage::Dict(dict_page)),
+            Ok(EncodedPage::Data(dict_data_page)),
+        ],
+        vec![Ok(null_page)],
+    ]
+    .into_iter()
+    .map(|pages| {
+        Ok(DynStreamingIterator::new(Compressor::new(
+            DynIter::new(pages.into_iter()),
+            CompressionOptions::Snappy,
+            vec![],
+        )))
+    });
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None)
+        .with_bloom_filters(vec![
+            Some(bloom_options),
+            Some(bloom_options),
+            Some(bloom_options),
+        ])?;
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+
+    let mut reader = Cursor::new(writer.into_inner().into_inner());
+    let metadata = read_metadata(&mut reader)?;
+    let columns = metadata.row_groups[0].columns();
+
+    let mut expected = BloomFilter::try_from_options(&bloom_options)?;
+    plain
+        .iter()
+        .flatten()
+        .for_each(|x| expected.insert_native(*x));
+    let filter = read_bloom_filter(&columns[0], &mut reader)?.unwrap();
+    assert_eq!(filter, expected);
+    assert!(!filter.might_contain_native(0i32));
+
+    let filter = read_bloom_filter(&columns[1], &mut reader)?.unwrap();
+    assert!(dict.iter().all(|x| filter.might_contain_native(*x)));
+    assert!(!filter.might_contain_native(0i32));
+
+    let filter = read_bloom_filter(&columns[2], &mut reader)?.unwrap();
+    assert!(filter.bitset().iter().all(|x| *x == 0));
+    Ok(())
+}
+
+#[test]
+fn bloom_filter_invalid_options() -> Result<()> {
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("a".to_string(), PhysicalType::Int32),
+            ParquetType::from_physical("b".to_string(), PhysicalType::Boolean),
+        ],
+    );
+    let bloom_options = BloomFilterOptions {
+        fpp: 0.01,
+        num_distinct_values: 10,
+    };
+    let writer = || {
+        FileWriter::new(
+            Cursor::new(vec![]),
+            schema.clone(),
+            WriteOptions::default(),
+            None,
+        )
+    };
+
+    assert!(writer()
+        .with_bloom_filters(vec![Some(bloom_options)])
+        .is_err());
+    // boolean columns have no bloom filters
+    assert!(writer()
+        .with_bloom_filters(vec![None, Some(bloom_options)])
+        .is_err());
+    let invalid = BloomFilterOptions {
+        fpp: 0.0,
+        num_distinct_values: 10,
+    };
+    assert!(writer()
+        .with_bloom_filters(vec![Some(invalid), None])
+        .is_err());
+    assert!(writer()
+        .with_bloom_filters(vec![Some(bloom_options), None])
+        .is_ok());
+    Ok(())
+}
+
+#[test]
+fn bloom_filter_without_row_group() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    assert!(writer.write_bloom_filter(0, &BloomFilter::new(32)).is_err());
+    Ok(())
+}
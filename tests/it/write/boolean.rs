@@ -0,0 +1,102 @@
+use parquet2::{
+    encoding::hybrid_rle::encode_bool,
+    encoding::{encode_boolean, Encoding},
+    error::Result,
+    metadata::Descriptor,
+    page::{DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2, EncodedPage},
+    statistics::{serialize_statistics, BooleanStatistics, Statistics},
+    write::WriteOptions,
+};
+
+fn unzip_option(array: &[Option<bool>], encoding: Encoding) -> Result<(Vec<u8>, Vec<u8>)> {
+    // leave the first 4 bytes anouncing the length of the def level
+    // this will be overwritten at the end, once the length is known.
+    // This is unknown at this point because of the uleb128 encoding,
+    // whose length is variable.
+    let mut validity = std::io::Cursor::new(vec![0; 4]);
+    validity.set_position(4);
+
+    let iter = array.iter().map(|value| value.is_some());
+    encode_bool(&mut validity, iter)?;
+
+    let mut values = vec![];
+    encode_boolean(array.iter().flatten().copied(), encoding, &mut values)?;
+
+    // write the length, now that it is known
+    let mut validity = validity.into_inner();
+    let length = (validity.len() - 4) as u32;
+    validity[..4].copy_from_slice(&length.to_le_bytes());
+
+    Ok((values, validity))
+}
+
+pub fn array_to_page_v1(
+    array: &[Option<bool>],
+    options: &WriteOptions,
+    descriptor: &Descriptor,
+    encoding: Encoding,
+) -> Result<EncodedPage> {
+    let (values, mut buffer) = unzip_option(array, encoding)?;
+
+    buffer.extend_from_slice(&values);
+
+    let statistics = if options.write_statistics {
+        let statistics = &BooleanStatistics {
+            null_count: Some((array.len() - array.iter().flatten().count()) as i64),
+            distinct_count: None,
+            max_value: array.iter().flatten().max().copied(),
+            min_value: array.iter().flatten().min().copied(),
+        } as &dyn Statistics;
+        Some(serialize_statistics(statistics))
+    } else {
+        None
+    };
+
+    let header = DataPageHeaderV1 {
+        num_values: array.len() as i32,
+        encoding: encoding.into(),
+        definition_level_encoding: Encoding::Rle.into(),
+        repetition_level_encoding: Encoding::Rle.into(),
+        statistics,
+    };
+
+    Ok(EncodedPage::Data(DataPage::new(
+        DataPageHeader::V1(header),
+        buffer,
+        None,
+        descriptor.clone(),
+        Some(array.len()),
+    )))
+}
+
+pub fn array_to_page_v2(
+    array: &[Option<bool>],
+    descriptor: &Descriptor,
+    encoding: Encoding,
+) -> Result<EncodedPage> {
+    // the levels of v2 pages are not prefixed by their length
+    let mut buffer = vec![];
+    encode_bool(&mut buffer, array.iter().map(|value| value.is_some()))?;
+    let definition_levels_byte_length = buffer.len() as i32;
+
+    encode_boolean(array.iter().flatten().copied(), encoding, &mut buffer)?;
+
+    let header = DataPageHeaderV2 {
+        num_values: array.len() as i32,
+        num_nulls: (array.len() - array.iter().flatten().count()) as i32,
+        num_rows: array.len() as i32,
+        encoding: encoding.into(),
+        definition_levels_byte_length,
+        repetition_levels_byte_length: 0,
+        is_compressed: None,
+        statistics: None,
+    };
+
+    Ok(EncodedPage::Data(DataPage::new(
+        DataPageHeader::V2(header),
+        buffer,
+        None,
+        descriptor.clone(),
+        Some(array.len()),
+    )))
+}
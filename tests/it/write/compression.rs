@@ -0,0 +1,795 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use parquet2::compression::{
+    compress, decompress, BrotliLevel, Compression, CompressionOptions, CustomCodec, GzipLevel,
+};
+#[cfg(feature = "zstd")]
+use parquet2::compression::{ZstdDictionaryCodec, ZstdLevel};
+use parquet2::encoding::Encoding;
+#[cfg(not(feature = "zstd"))]
+use parquet2::error::Feature;
+use parquet2::error::{Error, Result};
+#[cfg(feature = "zstd")]
+use parquet2::metadata::KeyValue;
+use parquet2::metadata::SchemaDescriptor;
+#[cfg(feature = "zstd")]
+use parquet2::page::{read_dict_page_with_codec, FixedLenByteArrayPageDict};
+use parquet2::page::{CompressedPage, DataPage, DataPageHeader, DataPageHeaderV2, EncodedDictPage};
+use parquet2::read::BasicDecompressor;
+use parquet2::read::{
+    check_compression, get_page_iterator, get_page_iterator_with_options, read_metadata,
+    ReadOptions,
+};
+use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveType};
+use parquet2::schema::Repetition;
+use parquet2::write;
+use parquet2::write::dictionary;
+use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter, Version};
+use parquet2::FallibleStreamingIterator;
+use parquet2::{metadata::Descriptor, page::EncodedPage, write::WriteOptions};
+
+use crate::Array;
+use crate::{alltypes_plain, xorshift};
+
+use super::dict::{dict_indices_page, dict_levels};
+use super::{
+    array_to_page, read_column, read_pages, test_column, write_column, write_single_column,
+};
+
+/// Writes a required binary column with a single v2 page whose values are incompressible
+fn write_incompressible_v2(values: &[Vec<u8>], compression: CompressionOptions) -> Result<Vec<u8>> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V2,
+        ..Default::default()
+    };
+
+    let mut primitive_type =
+        PrimitiveType::from_physical("col".to_string(), PhysicalType::ByteArray);
+    primitive_type.field_info.repetition = Repetition::Required;
+    let buffer = values
+        .iter()
+        .flat_map(|x| (x.len() as i32).to_le_bytes().into_iter().chain(x.clone()))
+        .collect::<Vec<_>>();
+    let header = DataPageHeaderV2 {
+        num_values: values.len() as i32,
+        num_nulls: 0,
+        num_rows: values.len() as i32,
+        encoding: Encoding::Plain.into(),
+        definition_levels_byte_length: 0,
+        repetition_levels_byte_length: 0,
+        is_compressed: None,
+        statistics: None,
+    };
+
+    write_single_column(
+        ParquetType::PrimitiveType(primitive_type),
+        options,
+        |descriptor| {
+            vec![Ok(EncodedPage::Data(DataPage::new(
+                DataPageHeader::V2(header),
+                buffer,
+                None,
+                descriptor.clone(),
+                Some(values.len()),
+            )))]
+        },
+        compression,
+    )
+}
+
+#[test]
+fn incompressible_page_v2() -> Result<()> {
+    // pseudo-random bytes, which no codec can compress
+    let mut random = xorshift();
+    let values = (0..100)
+        .map(|_| {
+            random
+                .by_ref()
+                .take(100)
+                .map(|x| x as u8)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let uncompressed = write_incompressible_v2(&values, CompressionOptions::Uncompressed)?;
+    let compressed = write_incompressible_v2(&values, CompressionOptions::Snappy)?;
+    // the page header declares the values as uncompressed, which costs a single byte
+    assert!(compressed.len() <= uncompressed.len() + 1);
+
+    let mut reader = Cursor::new(compressed);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column.compression(), Compression::Snappy);
+
+    let mut pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let page = pages.next().unwrap()?;
+    assert_eq!(page.compressed_size(), page.uncompressed_size());
+    drop(pages);
+
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(
+        result,
+        Array::Binary(values.into_iter().map(Some).collect())
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_v2_levels_length() -> Result<()> {
+    let descriptor = Descriptor {
+        primitive_type: PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32),
+        max_def_level: 1,
+        max_rep_level: 0,
+    };
+    let buffer = vec![0; 8];
+    for (definition, repetition) in [(9, 0), (4, 5), (-1, 0), (i32::MAX, i32::MAX)] {
+        let header = DataPageHeader::V2(DataPageHeaderV2 {
+            num_values: 2,
+            num_nulls: 0,
+            num_rows: 2,
+            encoding: Encoding::Plain.into(),
+            definition_levels_byte_length: definition,
+            repetition_levels_byte_length: repetition,
+            is_compressed: None,
+            statistics: None,
+        });
+        for compression in [CompressionOptions::Uncompressed, CompressionOptions::Snappy] {
+            let page = DataPage::new(
+                header.clone(),
+                buffer.clone(),
+                None,
+                descriptor.clone(),
+                None,
+            );
+            let result = write::compress(EncodedPage::Data(page), vec![], compression);
+            assert!(
+                matches!(result, Err(Error::InvalidParameter(_))),
+                "{:?}",
+                result
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A "codec" that flips the bits of every byte, registered as LZO
+#[derive(Debug)]
+struct NotCodec;
+
+impl CustomCodec for NotCodec {
+    fn compression(&self) -> Compression {
+        Compression::Lzo
+    }
+
+    fn compress(&self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+        output_buf.extend(input_buf.iter().map(|x| !x));
+        Ok(())
+    }
+
+    fn decompress(&self, input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize> {
+        output_buf
+            .iter_mut()
+            .zip(input_buf)
+            .for_each(|(output, input)| *output = !input);
+        Ok(input_buf.len().min(output_buf.len()))
+    }
+}
+
+#[test]
+fn custom_codec() -> Result<()> {
+    let array = alltypes_plain("id");
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let codec: Arc<dyn CustomCodec> = Arc::new(NotCodec);
+
+    let page = array_to_page(&array, &options, &schema.columns()[0].descriptor);
+    let pages = DynStreamingIterator::new(Compressor::new_with_codec(
+        DynIter::new(std::iter::once(page)),
+        CompressionOptions::Lzo,
+        vec![],
+        Some(codec.clone()),
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column.compression(), Compression::Lzo);
+
+    let pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new_with_codec(pages, vec![], Some(codec));
+    let result = crate::read::page_to_array(pages.next()?.unwrap())?;
+    assert_eq!(array, result);
+    drop(pages);
+
+    // without the codec, the page can't be decompressed
+    let pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new(pages, vec![]);
+    assert!(pages.next().is_err());
+    Ok(())
+}
+
+/// A codec that flips the bits of snappy-compressed data, registered as snappy
+#[derive(Debug)]
+struct NotSnappyCodec;
+
+impl CustomCodec for NotSnappyCodec {
+    fn compression(&self) -> Compression {
+        Compression::Snappy
+    }
+
+    fn compress(&self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+        let start = output_buf.len();
+        compress(CompressionOptions::Snappy, input_buf, output_buf)?;
+        output_buf[start..].iter_mut().for_each(|x| *x = !*x);
+        Ok(())
+    }
+
+    fn decompress(&self, input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize> {
+        let input = input_buf.iter().map(|x| !x).collect::<Vec<_>>();
+        decompress(Compression::Snappy, &input, output_buf)
+    }
+}
+
+#[test]
+fn custom_codec_dictionary() -> Result<()> {
+    let dict = [10i32, 20, 30];
+    let array = (0..100u32)
+        .map(|x| (x % 7 != 0).then(|| x % 3))
+        .collect::<Vec<_>>();
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let codec: Arc<dyn CustomCodec> = Arc::new(NotSnappyCodec);
+
+    let dict_page = EncodedDictPage::new(
+        dict.iter().flat_map(|x| x.to_le_bytes()).collect(),
+        dict.len(),
+    );
+    let (levels, indices) = dict_levels(&array)?;
+    let data_page = dictionary::dict_data_page(
+        &indices,
+        2,
+        levels,
+        None,
+        schema.columns()[0].descriptor.clone(),
+        Version::V1,
+    )?;
+    let pages = vec![
+        Ok(EncodedPage::Dict(dict_page)),
+        Ok(EncodedPage::Data(data_page)),
+    ];
+    let pages = DynStreamingIterator::new(Compressor::new_with_codec(
+        DynIter::new(pages.into_iter()),
+        CompressionOptions::Snappy,
+        vec![],
+        Some(codec.clone()),
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    // both the dictionary page and the data page are compressed with the codec
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    let read_options = ReadOptions {
+        codec: Some(codec.clone()),
+        ..Default::default()
+    };
+    let pages =
+        get_page_iterator_with_options(column, &mut reader, None, vec![], read_options.clone())?;
+    let mut pages = BasicDecompressor::new_with_codec(pages, vec![], Some(codec.clone()));
+    let result = crate::read::page_to_array(pages.next()?.unwrap())?;
+    let expected = Array::Int32(array.iter().map(|x| x.map(|x| dict[x as usize])).collect());
+    assert_eq!(result, expected);
+    assert!(pages.next()?.is_none());
+    drop(pages);
+
+    // without the codec, the dictionary page can't be decompressed
+    let mut pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    assert!(pages.next().unwrap().is_err());
+
+    // the codec can be combined with a maximum page size
+    let pages = get_page_iterator_with_options(column, &mut reader, None, vec![], read_options)?;
+    let mut pages = BasicDecompressor::new_with_options(pages, vec![], Some(codec), 1);
+    assert!(pages.next().is_err());
+    Ok(())
+}
+
+/// Writes `arrays` as pages of a binary column compressed by zstd with `codec`, storing
+/// `metadata` in the file's key-value metadata
+#[cfg(feature = "zstd")]
+pub(crate) fn write_zstd_pages(
+    arrays: &[Array],
+    codec: Option<Arc<dyn CustomCodec>>,
+    metadata: Option<Vec<KeyValue>>,
+) -> Result<Vec<u8>> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::ByteArray,
+        )],
+    );
+    let pages = arrays
+        .iter()
+        .map(|array| array_to_page(array, &options, &schema.columns()[0].descriptor))
+        .collect::<Vec<_>>();
+    let pages = DynStreamingIterator::new(Compressor::new_with_codec(
+        DynIter::new(pages.into_iter()),
+        CompressionOptions::Zstd(None),
+        vec![],
+        codec,
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.end(metadata)?;
+    Ok(writer.into_inner().into_inner())
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_dictionary() -> Result<()> {
+    let options = WriteOptions::default();
+    // many small pages of similar values
+    let arrays = (0..200)
+        .map(|page| {
+            Array::Binary(
+                (0..5)
+                    .map(|i| {
+                        let value = format!(
+                            r#"{{"event": "page_view", "user": {}, "path": "/products/{}", "referrer": "https://www.example.com/"}}"#,
+                            page * 7 + i,
+                            (page + i) % 13
+                        );
+                        Some(value.into_bytes())
+                    })
+                    .collect(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // train the dictionary on the encoded pages
+    let descriptor = Descriptor {
+        primitive_type: PrimitiveType::from_physical("col".to_string(), PhysicalType::ByteArray),
+        max_def_level: 1,
+        max_rep_level: 0,
+    };
+    let samples = arrays
+        .iter()
+        .map(|array| {
+            Ok(array_to_page(array, &options, &descriptor)?
+                .buffer()
+                .to_vec())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let dictionary = zstd::dict::from_samples(&samples, 4096)?;
+    let codec: Arc<dyn CustomCodec> = Arc::new(ZstdDictionaryCodec::new(&dictionary, None));
+
+    // the dictionary is stored in the file, hex-encoded
+    let encoded = dictionary.iter().map(|x| format!("{:02x}", x)).collect();
+    let metadata = vec![KeyValue {
+        key: "zstd_dictionary".to_string(),
+        value: Some(encoded),
+    }];
+    let data = write_zstd_pages(&arrays, Some(codec), Some(metadata))?;
+
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let encoded = metadata
+        .key_value_metadata()
+        .and_then(|metadata| metadata.iter().find(|x| x.key == "zstd_dictionary"))
+        .and_then(|x| x.value.as_ref())
+        .unwrap();
+    let dictionary = (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16).unwrap())
+        .collect::<Vec<_>>();
+    let codec: Arc<dyn CustomCodec> = Arc::new(ZstdDictionaryCodec::new(&dictionary, None));
+
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column.compression(), Compression::Zstd);
+    let pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new_with_codec(pages, vec![], Some(codec));
+    let mut result = vec![];
+    while let Some(page) = pages.next()? {
+        result.push(crate::read::page_to_array(page)?);
+    }
+    assert_eq!(result, arrays);
+    drop(pages);
+
+    // the dictionary reduces the size of the pages
+    let pages_size = |data: &[u8]| -> Result<usize> {
+        let metadata = read_metadata(&mut Cursor::new(data))?;
+        let column = &metadata.row_groups[0].columns()[0];
+        get_page_iterator(column, Cursor::new(data), None, vec![])?
+            .map(|page| Ok(page?.compressed_size()))
+            .sum()
+    };
+    let without_dictionary = write_zstd_pages(&arrays, None, None)?;
+    assert!(pages_size(&data)? * 2 < pages_size(&without_dictionary)?);
+
+    // without the dictionary, pages can't be decompressed
+    let pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new(pages, vec![]);
+    match pages.next() {
+        Err(Error::InvalidParameter(message)) => assert!(message.contains("zstd dictionary")),
+        other => panic!("{:?}", other.map(|_| ())),
+    }
+    drop(pages);
+
+    // nor with another dictionary
+    let other = zstd::dict::from_samples(&samples[..100], 2048)?;
+    let codec: Arc<dyn CustomCodec> = Arc::new(ZstdDictionaryCodec::new(&other, None));
+    let pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new_with_codec(pages, vec![], Some(codec.clone()));
+    assert!(matches!(pages.next(), Err(Error::InvalidParameter(_))));
+
+    // dictionary pages are also compressed with the codec
+    let dict_page = EncodedDictPage::new(samples[0].clone(), 1);
+    let compressed = write::compress_with_codec(
+        EncodedPage::Dict(dict_page),
+        vec![],
+        CompressionOptions::Zstd(None),
+        Some(codec.as_ref()),
+    )?;
+    let compressed = match compressed {
+        CompressedPage::Dict(page) => page,
+        CompressedPage::Data(_) => unreachable!(),
+    };
+    let dict_page = EncodedDictPage::new(compressed.buffer().to_vec(), 1);
+    let compression = (Compression::Zstd, compressed.uncompressed_size());
+    let read = |codec: Option<&dyn CustomCodec>| {
+        read_dict_page_with_codec(
+            &dict_page,
+            compression,
+            false,
+            PhysicalType::FixedLenByteArray(samples[0].len()),
+            codec,
+        )
+    };
+    let dict = read(Some(codec.as_ref()))?;
+    let dict = dict
+        .as_any()
+        .downcast_ref::<FixedLenByteArrayPageDict>()
+        .unwrap();
+    assert_eq!(dict.values(), samples[0]);
+    assert!(read(None).is_err());
+    Ok(())
+}
+
+#[test]
+fn check_compression_of_written_file() -> Result<()> {
+    let array = alltypes_plain("id");
+    let data = write_column(&array, CompressionOptions::Snappy)?;
+
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    check_compression(&metadata)
+}
+
+/// The [`NotCodec`] "codec", registered as zstd
+#[cfg(not(feature = "zstd"))]
+#[derive(Debug)]
+struct NotZstdCodec;
+
+#[cfg(not(feature = "zstd"))]
+impl CustomCodec for NotZstdCodec {
+    fn compression(&self) -> Compression {
+        Compression::Zstd
+    }
+
+    fn compress(&self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+        NotCodec.compress(input_buf, output_buf)
+    }
+
+    fn decompress(&self, input_buf: &[u8], output_buf: &mut [u8]) -> Result<usize> {
+        NotCodec.decompress(input_buf, output_buf)
+    }
+}
+
+#[cfg(not(feature = "zstd"))]
+#[test]
+fn check_compression_without_zstd() -> Result<()> {
+    assert!(!Compression::Zstd.is_supported());
+
+    // write a zstd column chunk through a custom codec, since this crate can't compress it
+    let array = alltypes_plain("id");
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    );
+    let page = array_to_page(&array, &options, &schema.columns()[0].descriptor);
+    let pages = DynStreamingIterator::new(Compressor::new_with_codec(
+        DynIter::new(std::iter::once(page)),
+        CompressionOptions::Zstd(None),
+        vec![],
+        Some(Arc::new(NotZstdCodec)),
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    match check_compression(&metadata) {
+        Err(Error::FeatureNotActive(Feature::Zstd, reason)) => {
+            assert!(reason.contains("\"col\""), "{}", reason)
+        }
+        other => panic!("expected the zstd feature to be missing, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn int32_zstd() -> Result<()> {
+    test_column("id", CompressionOptions::Zstd(None))
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn int32_zstd_levels() -> Result<()> {
+    test_column("id", CompressionOptions::Zstd(Some(ZstdLevel::try_new(1)?)))?;
+    test_column(
+        "id",
+        CompressionOptions::Zstd(Some(ZstdLevel::try_new(19)?)),
+    )
+}
+
+#[test]
+fn int32_gzip_levels() -> Result<()> {
+    test_column("id", CompressionOptions::Gzip(None))?;
+    test_column("id", CompressionOptions::Gzip(Some(GzipLevel::try_new(1)?)))?;
+    test_column("id", CompressionOptions::Gzip(Some(GzipLevel::try_new(9)?)))
+}
+
+#[test]
+fn page_buffers() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        ..Default::default()
+    };
+    let descriptor = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int32,
+        )],
+    )
+    .columns()[0]
+        .descriptor
+        .clone();
+
+    // both pages end with the plain-encoded values
+    let plain = (0..1000i32)
+        .flat_map(|x| (x % 10).to_le_bytes())
+        .collect::<Vec<_>>();
+
+    let array = Array::Int32((0..1000).map(|x| Some(x % 10)).collect());
+    let data_page = array_to_page(&array, &options, &descriptor)?;
+    let dict_page = EncodedPage::Dict(EncodedDictPage::new(plain.clone(), 1000));
+
+    for page in [data_page, dict_page] {
+        let encoded = page.buffer().to_vec();
+        assert!(encoded.ends_with(&plain));
+
+        let compressed = write::compress(page, vec![], CompressionOptions::Snappy)?;
+        let mut decompressed = vec![0; encoded.len()];
+        decompress(Compression::Snappy, compressed.buffer(), &mut decompressed)?;
+        assert_eq!(decompressed, encoded);
+
+        match compressed {
+            CompressedPage::Data(page) => {
+                assert_eq!(page.buffer().len(), page.compressed_size());
+                assert_eq!(page.uncompressed_size(), encoded.len());
+            }
+            CompressedPage::Dict(page) => {
+                assert_eq!(page.uncompressed_size(), encoded.len());
+                assert_eq!(page.num_values(), 1000);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn compression_per_column() -> Result<()> {
+    let options = WriteOptions::default();
+    let arrays = [
+        Array::Binary(
+            (0..1000)
+                .map(|x| Some(format!("a long string value {}", x % 10).into_bytes()))
+                .collect(),
+        ),
+        Array::Int64((0..1000).map(|x| Some(x % 10)).collect()),
+    ];
+    let compression = [CompressionOptions::Snappy, CompressionOptions::Uncompressed];
+
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("binary".to_string(), PhysicalType::ByteArray),
+            ParquetType::from_physical("int64".to_string(), PhysicalType::Int64),
+        ],
+    );
+    let columns = arrays
+        .iter()
+        .zip(schema.columns())
+        .map(|(array, column)| std::iter::once(array_to_page(array, &options, &column.descriptor)))
+        .collect();
+    let row_group = write::compress_row_group(columns, &compression)?;
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema.clone(), options, None);
+    writer.start()?;
+    writer.write(row_group)?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let columns = metadata.row_groups[0].columns();
+    for ((column, array), compression) in columns.iter().zip(arrays).zip(compression) {
+        assert_eq!(column.compression(), compression.into());
+        assert_eq!(read_pages(&mut reader, column)?, vec![array]);
+    }
+
+    let result = write::compress_row_group(vec![std::iter::empty()], &compression);
+    assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    Ok(())
+}
+
+#[test]
+fn transcode_column() -> Result<()> {
+    let dict = [100i64, 200, 300, 400];
+    let pages = [
+        (0..1000).map(|x| x % 4).collect::<Vec<u32>>(),
+        vec![3; 10],
+        (0..500).map(|x| x % 3).collect(),
+    ];
+
+    let mut primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int64);
+    primitive_type.field_info.repetition = Repetition::Required;
+
+    for (from, to) in [
+        (CompressionOptions::Snappy, CompressionOptions::Zstd(None)),
+        (
+            CompressionOptions::Uncompressed,
+            CompressionOptions::Gzip(None),
+        ),
+        (
+            CompressionOptions::Zstd(None),
+            CompressionOptions::Uncompressed,
+        ),
+    ]
+    .into_iter()
+    // codecs whose feature is not active are skipped
+    .filter(|(from, to)| {
+        Compression::from(*from).is_supported() && Compression::from(*to).is_supported()
+    }) {
+        let data = write_single_column(
+            ParquetType::PrimitiveType(primitive_type.clone()),
+            WriteOptions::default(),
+            |descriptor| {
+                let dict_page = EncodedDictPage::new(
+                    dict.iter().flat_map(|x| x.to_le_bytes()).collect(),
+                    dict.len(),
+                );
+                std::iter::once(Ok(EncodedPage::Dict(dict_page)))
+                    .chain(
+                        pages
+                            .iter()
+                            .map(|indices| dict_indices_page(indices, &dict, descriptor)),
+                    )
+                    .collect()
+            },
+            from,
+        )?;
+        let mut reader = Cursor::new(data);
+        let metadata = read_metadata(&mut reader)?;
+        let column = &metadata.row_groups[0].columns()[0];
+        let expected = read_pages(&mut reader, column)?;
+        assert_eq!(expected.len(), 3);
+
+        // the column chunk is written after some other data
+        let mut transcoded = b"PAR1".to_vec();
+        let result = write::transcode_column(&mut reader, column, to, &mut transcoded, 4)?;
+
+        assert_eq!(result.compression(), to.into());
+        assert_eq!(result.num_values(), column.num_values());
+        assert_eq!(result.column_encoding(), column.column_encoding());
+        assert_eq!(result.metadata().statistics, column.metadata().statistics);
+        assert!(result.metadata().statistics.is_some());
+        assert_eq!(result.dictionary_page_offset(), Some(4));
+        assert_eq!(result.compressed_size(), result.file_offset() - 4);
+
+        assert_eq!(read_pages(&mut Cursor::new(transcoded), &result)?, expected);
+    }
+    Ok(())
+}
+
+/// Writes a compressible column with a `low` and `high` compression level and checks that
+/// both are read back and that the `high` level is not larger
+fn test_levels_size(low: CompressionOptions, high: CompressionOptions) -> Result<()> {
+    let array = Array::Int32((0..10000).map(|x| Some(x % 100)).collect());
+
+    let low = write_column(&array, low)?;
+    let high = write_column(&array, high)?;
+    assert!(high.len() <= low.len());
+
+    let (result, _) = read_column(&mut Cursor::new(low))?;
+    assert_eq!(array, result);
+    let (result, _) = read_column(&mut Cursor::new(high))?;
+    assert_eq!(array, result);
+    Ok(())
+}
+
+#[test]
+fn int32_gzip_levels_size() -> Result<()> {
+    test_levels_size(
+        CompressionOptions::Gzip(Some(GzipLevel::try_new(1)?)),
+        CompressionOptions::Gzip(Some(GzipLevel::try_new(9)?)),
+    )
+}
+
+#[test]
+fn int32_brotli_levels_size() -> Result<()> {
+    test_levels_size(
+        CompressionOptions::Brotli(Some(BrotliLevel::try_new(1)?)),
+        CompressionOptions::Brotli(Some(BrotliLevel::try_new(11)?)),
+    )
+}
+
+#[test]
+fn gzip_invalid_level() {
+    assert!(matches!(
+        GzipLevel::try_new(10),
+        Err(Error::InvalidParameter(_))
+    ));
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_invalid_level() {
+    assert!(matches!(
+        ZstdLevel::try_new(100),
+        Err(Error::InvalidParameter(_))
+    ));
+}
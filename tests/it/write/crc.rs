@@ -0,0 +1,136 @@
+use std::io::{Cursor, Seek, SeekFrom};
+use std::sync::Arc;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::error::{Error, Result};
+use parquet2::page::CompressedDataPage;
+use parquet2::read::{get_page_iterator, get_page_iterator_with_options, read_metadata};
+use parquet2::read::{PageReader, ReadOptions};
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write::WriteOptions;
+
+use super::primitive::array_to_page_v1;
+use super::write_single_column;
+
+/// Writes a column of `array` whose pages have a CRC32 iff `write_crc`
+fn write_column_with_crc(array: &[Option<i32>], write_crc: bool) -> Result<Vec<u8>> {
+    let options = WriteOptions {
+        write_crc,
+        ..Default::default()
+    };
+
+    write_single_column(
+        ParquetType::from_physical("col".to_string(), PhysicalType::Int32),
+        options,
+        |descriptor| vec![array_to_page_v1(array, &options, descriptor)],
+        CompressionOptions::Snappy,
+    )
+}
+
+fn read_pages_with_crc(data: &[u8], verify_crc: bool) -> Result<Vec<CompressedDataPage>> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    reader.seek(SeekFrom::Start(column.byte_range().0))?;
+
+    let options = ReadOptions {
+        verify_crc,
+        ..Default::default()
+    };
+    PageReader::new_with_options(reader, column, Arc::new(|_, _| true), vec![], options).collect()
+}
+
+async fn read_pages_with_crc_async(
+    data: &[u8],
+    verify_crc: bool,
+) -> Result<Vec<CompressedDataPage>> {
+    let mut reader = futures::io::Cursor::new(data);
+    let metadata = parquet2::read::read_metadata_async(&mut reader).await?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    let options = ReadOptions {
+        verify_crc,
+        ..Default::default()
+    };
+    let pages = parquet2::read::get_page_stream_with_options(
+        column,
+        &mut reader,
+        vec![],
+        Arc::new(|_, _| true),
+        options,
+    )
+    .await?;
+    futures::TryStreamExt::try_collect(pages).await
+}
+
+/// Returns `data` with a byte of the data of its (single) page flipped
+fn corrupt_page(data: &[u8]) -> Result<Vec<u8>> {
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    let column = &metadata.row_groups[0].columns()[0];
+    // the page's data are the last bytes of the column chunk
+    let (start, length) = column.byte_range();
+    let page_length = read_pages_with_crc(data, false)?[0].compressed_size();
+    let position = (start + length) as usize - page_length / 2;
+
+    let mut corrupted = data.to_vec();
+    corrupted[position] ^= 0xff;
+    Ok(corrupted)
+}
+
+#[test]
+fn page_crc() -> Result<()> {
+    let array = (0..100).map(Some).collect::<Vec<_>>();
+
+    let data = write_column_with_crc(&array, true)?;
+    let pages = read_pages_with_crc(&data, true)?;
+    assert_eq!(pages.len(), 1);
+
+    let corrupted = corrupt_page(&data)?;
+    let error = read_pages_with_crc(&corrupted, true).unwrap_err();
+    assert!(matches!(error, Error::Corruption(_)), "{}", error);
+
+    // the checksum is only verified when requested
+    assert!(read_pages_with_crc(&corrupted, false).is_ok());
+
+    // pages without checksum are not verified
+    let data = write_column_with_crc(&array, false)?;
+    assert!(read_pages_with_crc(&corrupt_page(&data)?, true).is_ok());
+    Ok(())
+}
+
+#[tokio::test]
+async fn page_crc_async() -> Result<()> {
+    let array = (0..100).map(Some).collect::<Vec<_>>();
+
+    let data = write_column_with_crc(&array, true)?;
+    assert_eq!(read_pages_with_crc_async(&data, true).await?.len(), 1);
+
+    let corrupted = corrupt_page(&data)?;
+    let error = read_pages_with_crc_async(&corrupted, true)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::Corruption(_)), "{}", error);
+    assert!(read_pages_with_crc_async(&corrupted, false).await.is_ok());
+    Ok(())
+}
+
+#[test]
+fn page_crc_get_page_iterator() -> Result<()> {
+    let array = (0..100).map(Some).collect::<Vec<_>>();
+    let corrupted = corrupt_page(&write_column_with_crc(&array, true)?)?;
+
+    let mut reader = Cursor::new(corrupted);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    let options = ReadOptions {
+        verify_crc: true,
+        ..Default::default()
+    };
+    let mut pages = get_page_iterator_with_options(column, &mut reader, None, vec![], options)?;
+    assert!(matches!(pages.next(), Some(Err(Error::Corruption(_)))));
+
+    // without options, the checksum is not verified
+    let mut pages = get_page_iterator(column, &mut reader, None, vec![])?;
+    assert!(pages.next().unwrap().is_ok());
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::encoding::hybrid_rle::encode_bool;
+use parquet2::encoding::Encoding;
+use parquet2::error::Result;
+use parquet2::page::{EncodedDictPage, PrimitivePageDict};
+use parquet2::read::BasicDecompressor;
+use parquet2::read::{get_page_iterator, read_metadata};
+use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveType};
+use parquet2::statistics::{serialize_statistics, PrimitiveStatistics};
+use parquet2::write::dictionary;
+use parquet2::write::Version;
+use parquet2::FallibleStreamingIterator;
+use parquet2::{metadata::Descriptor, page::EncodedPage, write::WriteOptions};
+
+use crate::Array;
+
+use super::{read_pages, write_single_column};
+
+/// A required page of `indices` into a dictionary of `i64`, with statistics of its values
+pub(crate) fn dict_indices_page(
+    indices: &[u32],
+    dict: &[i64],
+    descriptor: &Descriptor,
+) -> Result<EncodedPage> {
+    let values = indices.iter().map(|&index| dict[index as usize]);
+    let statistics = PrimitiveStatistics {
+        primitive_type: descriptor.primitive_type.clone(),
+        null_count: Some(0),
+        distinct_count: None,
+        max_value: values.clone().max(),
+        min_value: values.min(),
+    };
+    let levels = dictionary::Levels {
+        num_values: indices.len(),
+        num_rows: indices.len(),
+        ..Default::default()
+    };
+    dictionary::dict_data_page(
+        indices,
+        2,
+        levels,
+        Some(serialize_statistics(&statistics)),
+        descriptor.clone(),
+        Version::V1,
+    )
+    .map(EncodedPage::Data)
+}
+
+/// Returns the levels of an optional, non-nested column of `array`, and the indices of its
+/// non-null values.
+pub(crate) fn dict_levels(array: &[Option<u32>]) -> Result<(dictionary::Levels, Vec<u32>)> {
+    let mut definition = vec![];
+    encode_bool(&mut definition, array.iter().map(|x| x.is_some()))?;
+    let levels = dictionary::Levels {
+        repetition: vec![],
+        definition,
+        num_values: array.len(),
+        num_rows: array.len(),
+    };
+    Ok((levels, array.iter().flatten().copied().collect()))
+}
+
+#[test]
+fn dictionary_encoded_column_chunk() -> Result<()> {
+    let dict = [10i32, 20, 30, 40, 50];
+    let pages = [
+        (0..1000u32)
+            .map(|x| (x % 7 != 0).then(|| x % 5))
+            .collect::<Vec<_>>(),
+        vec![None; 10],
+        vec![Some(4); 100],
+    ];
+    let primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32);
+
+    for version in [Version::V1, Version::V2] {
+        let data = write_single_column(
+            ParquetType::PrimitiveType(primitive_type.clone()),
+            WriteOptions::default(),
+            |descriptor| {
+                let dict_page = EncodedDictPage::new(
+                    dict.iter().flat_map(|x| x.to_le_bytes()).collect(),
+                    dict.len(),
+                );
+                std::iter::once(Ok(EncodedPage::Dict(dict_page)))
+                    .chain(pages.iter().map(|array| {
+                        let (levels, indices) = dict_levels(array)?;
+                        let values = indices.iter().map(|&index| dict[index as usize]);
+                        let statistics = PrimitiveStatistics {
+                            primitive_type: descriptor.primitive_type.clone(),
+                            null_count: Some((array.len() - indices.len()) as i64),
+                            distinct_count: None,
+                            max_value: values.clone().max(),
+                            min_value: values.min(),
+                        };
+                        dictionary::dict_data_page(
+                            &indices,
+                            3,
+                            levels,
+                            Some(serialize_statistics(&statistics)),
+                            descriptor.clone(),
+                            version,
+                        )
+                        .map(EncodedPage::Data)
+                    }))
+                    .collect()
+            },
+            CompressionOptions::Snappy,
+        )?;
+
+        let mut reader = Cursor::new(data);
+        let metadata = read_metadata(&mut reader)?;
+        let column = &metadata.row_groups[0].columns()[0];
+        assert!(column.dictionary_page_offset().is_some());
+        assert!(column
+            .column_encoding()
+            .contains(&Encoding::RleDictionary.into()));
+
+        // the dictionary page is read (with `read_dict_page`) and shared by the data pages
+        let pages_iter = get_page_iterator(column, &mut reader, None, vec![])?;
+        let mut decompressor = BasicDecompressor::new(pages_iter, vec![]);
+        while let Some(page) = decompressor.next()? {
+            let dict_page = page.dictionary_page().expect("pages have a dictionary");
+            let values = dict_page
+                .as_any()
+                .downcast_ref::<PrimitivePageDict<i32>>()
+                .unwrap()
+                .values();
+            assert_eq!(values, dict);
+        }
+
+        let expected = pages
+            .iter()
+            .map(|array| Array::Int32(array.iter().map(|x| x.map(|x| dict[x as usize])).collect()))
+            .collect::<Vec<_>>();
+        assert_eq!(read_pages(&mut reader, column)?, expected);
+    }
+    Ok(())
+}
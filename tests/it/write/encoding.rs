@@ -0,0 +1,387 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::encoding::Encoding;
+use parquet2::error::Result;
+use parquet2::read::{read_metadata, DynamicValue, RowGroupRowIterator};
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write::Version;
+use parquet2::write::WriteOptions;
+
+use crate::Array;
+
+use super::{read_column, write_column_encoded, write_single_column};
+
+/// Returns the bits of the values of a floating point [`Array`], so that NaNs can be compared
+fn float_bits(array: &Array) -> Vec<Option<u64>> {
+    match array {
+        Array::Float32(array) => array
+            .iter()
+            .map(|x| x.map(|x| x.to_bits() as u64))
+            .collect(),
+        Array::Float64(array) => array.iter().map(|x| x.map(|x| x.to_bits())).collect(),
+        _ => unreachable!(),
+    }
+}
+
+fn test_byte_stream_split(array: Array) -> Result<()> {
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Snappy,
+        Encoding::ByteStreamSplit,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    assert!(metadata.row_groups[0].columns()[0]
+        .column_encoding()
+        .contains(&Encoding::ByteStreamSplit.into()));
+
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(float_bits(&array), float_bits(&result));
+    Ok(())
+}
+
+#[test]
+fn float32_byte_stream_split() -> Result<()> {
+    test_byte_stream_split(Array::Float32(vec![
+        Some(1.5),
+        None,
+        Some(f32::NAN),
+        Some(f32::MIN_POSITIVE / 2.0),
+        Some(-0.0),
+        Some(f32::MAX),
+    ]))
+}
+
+#[test]
+fn float64_byte_stream_split() -> Result<()> {
+    test_byte_stream_split(Array::Float64(vec![
+        Some(1.5),
+        None,
+        Some(f64::NAN),
+        Some(f64::MIN_POSITIVE / 2.0),
+        Some(-0.0),
+        Some(f64::MAX),
+    ]))
+}
+
+#[test]
+fn fixed_len_binary_byte_stream_split() -> Result<()> {
+    let array = Array::FixedLenBinary(
+        (0..300u32)
+            .map(|x| {
+                if x % 9 == 0 {
+                    None
+                } else {
+                    Some((x * 1000).to_be_bytes()[1..].to_vec())
+                }
+            })
+            .collect(),
+    );
+
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Snappy,
+        Encoding::ByteStreamSplit,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(array, result);
+
+    let metadata = read_metadata(&mut reader)?;
+    let rows = RowGroupRowIterator::try_new(&mut reader, &metadata, 0)?.collect::<Vec<_>>();
+    assert_eq!(
+        rows[1].get("col"),
+        Some(&DynamicValue::FixedLenBinary(vec![0, 3, 232]))
+    );
+    Ok(())
+}
+
+#[test]
+fn boolean_rle() -> Result<()> {
+    let arrays = [
+        vec![Some(true); 10000],
+        vec![Some(false); 10000],
+        vec![Some(true)],
+        vec![None, Some(false), None],
+        (0..1000)
+            .map(|x| match x % 50 {
+                0..=2 => None,
+                3..=20 => Some(true),
+                _ => Some(x % 3 == 0),
+            })
+            .collect(),
+    ];
+    for array in arrays {
+        let array = Array::Boolean(array);
+        let data = write_column_encoded(&array, CompressionOptions::Uncompressed, Encoding::Rle)?;
+
+        let mut reader = Cursor::new(data);
+        let (result, _) = read_column(&mut reader)?;
+        assert_eq!(array, result);
+
+        let metadata = read_metadata(&mut reader)?;
+        let rows = RowGroupRowIterator::try_new(&mut reader, &metadata, 0)?.collect::<Vec<_>>();
+        assert_eq!(rows.len(), array.len());
+    }
+    Ok(())
+}
+
+#[test]
+fn boolean_rle_size() -> Result<()> {
+    let array = Array::Boolean(vec![Some(true); 10000]);
+
+    let page_size = |encoding| -> Result<i64> {
+        let data = write_column_encoded(&array, CompressionOptions::Uncompressed, encoding)?;
+        let metadata = read_metadata(&mut Cursor::new(data))?;
+        Ok(metadata.row_groups[0].columns()[0].uncompressed_size())
+    };
+
+    let plain = page_size(Encoding::Plain)?;
+    let rle = page_size(Encoding::Rle)?;
+    // the validity is bitpacked in both cases; only the 1250 bytes of values shrink
+    assert!(rle + 1200 < plain, "{} vs {}", rle, plain);
+    Ok(())
+}
+
+#[test]
+fn boolean_rle_v2() -> Result<()> {
+    let options = WriteOptions {
+        write_statistics: false,
+        version: Version::V2,
+        ..Default::default()
+    };
+    // dense alternating values and long runs
+    let alternating = (0..1000)
+        .map(|x| (x % 7 != 0).then(|| x % 2 == 0))
+        .collect::<Vec<_>>();
+    let runs = (0..1000)
+        .map(|x| (x % 100 != 0).then(|| x < 500))
+        .collect::<Vec<_>>();
+
+    let write = |array: &[Option<bool>], encoding| {
+        write_single_column(
+            ParquetType::from_physical("col".to_string(), PhysicalType::Boolean),
+            options,
+            |descriptor| {
+                vec![super::boolean::array_to_page_v2(
+                    array, descriptor, encoding,
+                )]
+            },
+            CompressionOptions::Uncompressed,
+        )
+    };
+
+    for array in [&alternating, &runs] {
+        let data = write(array, Encoding::Rle)?;
+        let (result, _) = read_column(&mut Cursor::new(data))?;
+        assert_eq!(result, Array::Boolean(array.clone()));
+    }
+
+    let plain = write(&runs, Encoding::Plain)?.len();
+    let rle = write(&runs, Encoding::Rle)?.len();
+    assert!(rle + 100 < plain, "{} vs {}", rle, plain);
+    Ok(())
+}
+
+#[test]
+fn int32_delta_binary_packed() -> Result<()> {
+    // more values than a block, negative deltas and nulls
+    let array = (0..300)
+        .map(|x| {
+            if x % 7 == 0 {
+                None
+            } else {
+                Some((x % 50) - 25)
+            }
+        })
+        .collect::<Vec<_>>();
+    let array = Array::Int32(array);
+
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Uncompressed,
+        Encoding::DeltaBinaryPacked,
+    )?;
+
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(array, result);
+    Ok(())
+}
+
+#[test]
+fn int64_delta_binary_packed() -> Result<()> {
+    let array = (0..131i64)
+        .map(|x| Some(1_000_000_000_000 - x * x * 1000))
+        .collect::<Vec<_>>();
+    let array = Array::Int64(array);
+
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Snappy,
+        Encoding::DeltaBinaryPacked,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(array, result);
+
+    let metadata = read_metadata(&mut reader)?;
+    let rows = RowGroupRowIterator::try_new(&mut reader, &metadata, 0)?.collect::<Vec<_>>();
+    assert_eq!(rows.len(), 131);
+    assert_eq!(
+        rows[130].get("col"),
+        Some(&DynamicValue::Int64(1_000_000_000_000 - 130 * 130 * 1000))
+    );
+    Ok(())
+}
+
+#[test]
+fn delta_binary_packed_extreme_values() -> Result<()> {
+    // deltas between these values overflow and are encoded with wrapping arithmetic
+    let array = vec![
+        Some(i32::MIN),
+        Some(i32::MAX),
+        None,
+        Some(0),
+        Some(i32::MIN),
+    ];
+    let array = Array::Int32(array);
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Uncompressed,
+        Encoding::DeltaBinaryPacked,
+    )?;
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(array, result);
+
+    let array = vec![
+        Some(i64::MAX),
+        Some(i64::MIN),
+        Some(-1),
+        None,
+        Some(i64::MAX),
+    ];
+    let array = Array::Int64(array);
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Uncompressed,
+        Encoding::DeltaBinaryPacked,
+    )?;
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(array, result);
+    Ok(())
+}
+
+#[test]
+fn binary_delta_length_byte_array() -> Result<()> {
+    // empty values, nulls, more values than a block and a last value ending the page
+    let array = (0..300)
+        .map(|x| match x % 5 {
+            0 => None,
+            1 => Some(vec![]),
+            _ => Some(format!("value {}", x).into_bytes()),
+        })
+        .chain(std::iter::once(Some(b"last".to_vec())))
+        .collect::<Vec<_>>();
+    let array = Array::Binary(array);
+
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Uncompressed,
+        Encoding::DeltaLengthByteArray,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(array, result);
+
+    let metadata = read_metadata(&mut reader)?;
+    let rows = RowGroupRowIterator::try_new(&mut reader, &metadata, 0)?.collect::<Vec<_>>();
+    assert_eq!(rows.len(), 301);
+    assert_eq!(rows[1].get("col"), Some(&DynamicValue::Binary(vec![])));
+    assert_eq!(
+        rows[300].get("col"),
+        Some(&DynamicValue::Binary(b"last".to_vec()))
+    );
+    Ok(())
+}
+
+#[test]
+fn binary_delta_length_byte_array_nulls() -> Result<()> {
+    // a page without values still has the header of its (empty) lengths
+    let array = Array::Binary(vec![None; 10]);
+
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Snappy,
+        Encoding::DeltaLengthByteArray,
+    )?;
+
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(array, result);
+    Ok(())
+}
+
+#[test]
+fn binary_delta_byte_array() -> Result<()> {
+    // sorted keys with long common prefixes, empty values and nulls
+    let mut keys = (0..300)
+        .map(|x| format!("key-{:05}", x * 7 % 300))
+        .collect::<Vec<_>>();
+    keys.sort();
+    let array = std::iter::once(Some(vec![]))
+        .chain(keys.into_iter().enumerate().map(|(i, key)| {
+            if i % 11 == 0 {
+                None
+            } else {
+                Some(key.into_bytes())
+            }
+        }))
+        .collect::<Vec<_>>();
+    let array = Array::Binary(array);
+
+    let data = write_column_encoded(&array, CompressionOptions::Snappy, Encoding::DeltaByteArray)?;
+
+    let mut reader = Cursor::new(data);
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(array, result);
+
+    let metadata = read_metadata(&mut reader)?;
+    let rows = RowGroupRowIterator::try_new(&mut reader, &metadata, 0)?.collect::<Vec<_>>();
+    assert_eq!(rows.len(), 301);
+    assert_eq!(
+        rows[300].get("col"),
+        Some(&DynamicValue::Binary(b"key-00299".to_vec()))
+    );
+    Ok(())
+}
+
+#[test]
+fn binary_delta_byte_array_paths() -> Result<()> {
+    // sorted paths with repeated values and bytes that are not valid utf8
+    let array = [
+        b"/data/2022/01/part-0.parquet".to_vec(),
+        b"/data/2022/01/part-0.parquet".to_vec(),
+        b"/data/2022/01/part-1.parquet".to_vec(),
+        b"/data/2022/02/\xff\xfe".to_vec(),
+        b"/data/2022/02/\xff\xfe".to_vec(),
+        b"/data/2022/02/\xff\xfe\x00".to_vec(),
+    ]
+    .into_iter()
+    .map(Some)
+    .collect::<Vec<_>>();
+    let array = Array::Binary(array);
+
+    let data = write_column_encoded(
+        &array,
+        CompressionOptions::Uncompressed,
+        Encoding::DeltaByteArray,
+    )?;
+
+    let (result, _) = read_column(&mut Cursor::new(data))?;
+    assert_eq!(array, result);
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use parquet2::compression::{Compression, CompressionOptions};
+use parquet2::encoding::hybrid_rle::encode_u32;
+use parquet2::encoding::Encoding;
+use parquet2::error::{Error, Result};
+use parquet2::metadata::Descriptor;
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::page::{
+    CompressedDataPage, DataPageHeader, DataPageHeaderV1, FixedLenByteArrayPageDict,
+};
+use parquet2::read::{get_page_iterator, read_metadata};
+use parquet2::read::{BasicDecompressor, ColumnFormatter};
+use parquet2::schema::types::{
+    ParquetType, PhysicalType, PrimitiveLogicalType, PrimitiveType, TimeUnit,
+};
+use parquet2::schema::Repetition;
+use parquet2::FallibleStreamingIterator;
+
+use crate::Array;
+
+use super::write_column;
+
+#[test]
+fn format_column() -> Result<()> {
+    let array = Array::Int64(vec![Some(-1), None, Some(1_609_459_200_000)]);
+    let data = write_column(&array, CompressionOptions::Snappy)?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    let format = |reader: &mut Cursor<Vec<u8>>, descriptor| -> Result<Vec<String>> {
+        let pages = get_page_iterator(column, reader, None, vec![])?;
+        let pages = BasicDecompressor::new(pages, vec![]);
+        let mut values = ColumnFormatter::try_new(pages, descriptor)?;
+        let mut result = vec![];
+        while let Some(value) = values.next()? {
+            result.push(value.clone());
+        }
+        Ok(result)
+    };
+
+    let descriptor = column.descriptor().clone();
+    assert_eq!(
+        format(&mut reader, descriptor.clone())?,
+        vec!["-1", "null", "1609459200000"]
+    );
+
+    // the same values as timestamps in milliseconds
+    let mut descriptor = descriptor;
+    descriptor.descriptor.primitive_type.logical_type = Some(PrimitiveLogicalType::Timestamp {
+        unit: TimeUnit::Milliseconds,
+        is_adjusted_to_utc: true,
+    });
+    assert_eq!(
+        format(&mut reader, descriptor)?,
+        vec![
+            "1969-12-31T23:59:59.999Z",
+            "null",
+            "2021-01-01T00:00:00.000Z"
+        ]
+    );
+    Ok(())
+}
+
+/// A v1 page of a repeated `INT32` column with `values` whose repetition levels are `rep_levels`
+pub(crate) fn repeated_page(
+    values: &[i32],
+    rep_levels: &[u32],
+    descriptor: &Descriptor,
+) -> CompressedDataPage {
+    let mut buffer = vec![];
+    for levels in [rep_levels.to_vec(), vec![1; values.len()]] {
+        let mut encoded = vec![];
+        encode_u32(&mut encoded, levels.into_iter(), 1).unwrap();
+        buffer.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&encoded);
+    }
+    buffer.extend(values.iter().flat_map(|x| x.to_le_bytes()));
+    let header = DataPageHeaderV1 {
+        num_values: values.len() as i32,
+        encoding: Encoding::Plain.into(),
+        definition_level_encoding: Encoding::Rle.into(),
+        repetition_level_encoding: Encoding::Rle.into(),
+        statistics: None,
+    };
+    let size = buffer.len();
+    CompressedDataPage::new(
+        DataPageHeader::V1(header),
+        buffer,
+        Compression::Uncompressed,
+        size,
+        None,
+        descriptor.clone(),
+        None,
+    )
+}
+
+#[test]
+fn format_list_across_pages() -> Result<()> {
+    let mut primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32);
+    primitive_type.field_info.repetition = Repetition::Repeated;
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::PrimitiveType(primitive_type)],
+    );
+    let descriptor = schema.columns()[0].clone();
+
+    // [[1, 2, 3], [4]], whose first list spans both pages
+    let pages = vec![
+        Ok(repeated_page(&[1, 2], &[0, 1], &descriptor.descriptor)),
+        Ok(repeated_page(&[3, 4], &[1, 0], &descriptor.descriptor)),
+    ];
+    let pages = BasicDecompressor::new(pages.into_iter(), vec![]);
+    let mut values = ColumnFormatter::try_new(pages, descriptor)?;
+    let mut result = vec![];
+    while let Some(value) = values.next()? {
+        result.push(value.clone());
+    }
+    assert_eq!(result, vec!["[1, 2, 3]", "[4]"]);
+    assert!(values.next()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn format_invalid_fixed_len_dict_index() -> Result<()> {
+    let mut primitive_type =
+        PrimitiveType::from_physical("col".to_string(), PhysicalType::FixedLenByteArray(2));
+    primitive_type.field_info.repetition = Repetition::Required;
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::PrimitiveType(primitive_type)],
+    );
+    let descriptor = schema.columns()[0].clone();
+
+    // a dictionary of 2 values and a RLE run of the index 3
+    let dict =
+        FixedLenByteArrayPageDict::new(vec![0, 1, 2, 3], PhysicalType::FixedLenByteArray(2), 2);
+    let buffer = vec![2, 1 << 1, 3];
+    let header = DataPageHeaderV1 {
+        num_values: 1,
+        encoding: Encoding::RleDictionary.into(),
+        definition_level_encoding: Encoding::Rle.into(),
+        repetition_level_encoding: Encoding::Rle.into(),
+        statistics: None,
+    };
+    let size = buffer.len();
+    let page = CompressedDataPage::new(
+        DataPageHeader::V1(header),
+        buffer,
+        Compression::Uncompressed,
+        size,
+        Some(Arc::new(dict)),
+        descriptor.descriptor.clone(),
+        None,
+    );
+
+    let pages = BasicDecompressor::new(std::iter::once(Ok(page)), vec![]);
+    let mut values = ColumnFormatter::try_new(pages, descriptor)?;
+    assert!(matches!(values.next(), Err(Error::OutOfSpec(_))));
+    Ok(())
+}
@@ -1,13 +1,15 @@
 use std::io::Cursor;
 
 use parquet2::compression::CompressionOptions;
-use parquet2::error::Result;
+use parquet2::error::{Error, Result};
 use parquet2::indexes::{
     select_pages, BoundaryOrder, Index, Interval, NativeIndex, PageIndex, PageLocation,
 };
-use parquet2::metadata::SchemaDescriptor;
+use parquet2::metadata::{ColumnChunkMetaData, SchemaDescriptor};
+use parquet2::page::DataPageHeader;
 use parquet2::read::{
-    read_columns_indexes, read_metadata, read_pages_locations, BasicDecompressor, IndexedPageReader,
+    get_page_iterator, read_columns_indexes, read_metadata, read_offset_index,
+    read_pages_locations, BasicDecompressor, IndexedPageReader,
 };
 use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveType};
 use parquet2::write::WriteOptions;
@@ -17,15 +19,30 @@ use parquet2::FallibleStreamingIterator;
 use crate::read::page_to_array;
 use crate::Array;
 
-use super::primitive::array_to_page_v1;
+use super::primitive::{array_to_page_v1, array_to_page_v2};
 
 fn write_file() -> Result<Vec<u8>> {
     let page1 = vec![Some(0), Some(1), None, Some(3), Some(4), Some(5), Some(6)];
     let page2 = vec![Some(10), Some(11)];
 
+    write_pages(&[page1, page2])
+}
+
+/// Writes a file with a single column whose pages are `pages`
+fn write_pages(pages: &[Vec<Option<i32>>]) -> Result<Vec<u8>> {
+    write_pages_with(pages, Version::V1, CompressionOptions::Uncompressed)
+}
+
+/// Writes a file with a single column whose pages are `pages`, as pages of `version`
+/// compressed with `compression`
+fn write_pages_with(
+    pages: &[Vec<Option<i32>>],
+    version: Version,
+    compression: CompressionOptions,
+) -> Result<Vec<u8>> {
     let options = WriteOptions {
-        write_statistics: true,
-        version: Version::V1,
+        version,
+        ..Default::default()
     };
 
     let schema = SchemaDescriptor::new(
@@ -36,14 +53,18 @@ fn write_file() -> Result<Vec<u8>> {
         )],
     );
 
-    let pages = vec![
-        array_to_page_v1::<i32>(&page1, &options, &schema.columns()[0].descriptor),
-        array_to_page_v1::<i32>(&page2, &options, &schema.columns()[0].descriptor),
-    ];
+    let descriptor = &schema.columns()[0].descriptor;
+    let pages = pages
+        .iter()
+        .map(|page| match version {
+            Version::V1 => array_to_page_v1::<i32>(page, &options, descriptor),
+            Version::V2 => array_to_page_v2::<i32>(page, &options, descriptor),
+        })
+        .collect::<Vec<_>>();
 
     let pages = DynStreamingIterator::new(Compressor::new(
         DynIter::new(pages.into_iter()),
-        CompressionOptions::Uncompressed,
+        compression,
         vec![],
     ));
     let columns = std::iter::once(Ok(pages));
@@ -90,6 +111,49 @@ fn read_indexed_page() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn read_indexed_page_v2() -> Result<()> {
+    let pages = vec![
+        (0..10).map(Some).collect::<Vec<_>>(),
+        (10..20).map(|x| (x % 3 != 0).then(|| x)).collect(),
+        (20..30).map(Some).collect(),
+    ];
+    let expected = vec![
+        Array::Int32(vec![Some(17), None, Some(19)]),
+        Array::Int32(vec![Some(20), Some(21)]),
+    ];
+
+    for compression in [CompressionOptions::Uncompressed, CompressionOptions::Snappy] {
+        let data = write_pages_with(&pages, Version::V2, compression)?;
+        let mut reader = Cursor::new(data);
+
+        let metadata = read_metadata(&mut reader)?;
+        let columns = &metadata.row_groups[0].columns();
+
+        // the last rows of the second page and the first rows of the third page
+        let intervals = &[Interval::new(17, 5)];
+        let locations = read_pages_locations(&mut reader, columns)?;
+        let selected = select_pages(intervals, &locations[0], metadata.row_groups[0].num_rows())?;
+        assert_eq!(
+            selected
+                .iter()
+                .filter(|page| !page.selected_rows.is_empty())
+                .count(),
+            2
+        );
+
+        let pages = IndexedPageReader::new(reader, &columns[0], selected, vec![], vec![]);
+        let mut pages = BasicDecompressor::new(pages, vec![]);
+
+        let mut arrays = vec![];
+        while let Some(page) = pages.next()? {
+            arrays.push(page_to_array(page)?)
+        }
+        assert_eq!(arrays, expected);
+    }
+    Ok(())
+}
+
 #[test]
 fn read_indexes_and_locations() -> Result<()> {
     let data = write_file()?;
@@ -125,7 +189,7 @@ fn read_indexes_and_locations() -> Result<()> {
                 null_count: Some(0),
             },
         ],
-        boundary_order: BoundaryOrder::Unordered,
+        boundary_order: BoundaryOrder::Ascending,
     }) as Box<dyn Index>];
 
     let indexes = read_columns_indexes(&mut reader, columns)?;
@@ -136,3 +200,173 @@ fn read_indexes_and_locations() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn read_indexes_and_locations_v2() -> Result<()> {
+    let page1 = vec![Some(0), Some(1), None, Some(3), Some(4), Some(5), Some(6)];
+    let page2 = vec![Some(10), Some(11)];
+    let data = write_pages_with(
+        &[page1.clone(), page2.clone()],
+        Version::V2,
+        CompressionOptions::Snappy,
+    )?;
+    let mut reader = Cursor::new(data);
+
+    let metadata = read_metadata(&mut reader)?;
+    let columns = &metadata.row_groups[0].columns();
+
+    let expected_index = vec![Box::new(NativeIndex::<i32> {
+        primitive_type: PrimitiveType::from_physical("col1".to_string(), PhysicalType::Int32),
+        indexes: vec![
+            PageIndex {
+                min: Some(0),
+                max: Some(6),
+                null_count: Some(1),
+            },
+            PageIndex {
+                min: Some(10),
+                max: Some(11),
+                null_count: Some(0),
+            },
+        ],
+        boundary_order: BoundaryOrder::Ascending,
+    }) as Box<dyn Index>];
+    let indexes = read_columns_indexes(&mut reader, columns)?;
+    assert_eq!(&indexes, &expected_index);
+
+    let locations = read_pages_locations(&mut reader, columns)?;
+    let first_rows = locations[0]
+        .iter()
+        .map(|location| location.first_row_index)
+        .collect::<Vec<_>>();
+    assert_eq!(first_rows, vec![0, 7]);
+
+    let pages = get_page_iterator(&columns[0], &mut reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new(pages, vec![]);
+    let mut arrays = vec![];
+    while let Some(page) = pages.next()? {
+        match page.header() {
+            DataPageHeader::V2(header) => {
+                // the levels are not compressed and the header declares whether the values are
+                assert!(header.is_compressed.is_some());
+                assert_eq!(header.repetition_levels_byte_length, 0);
+                assert!(header.definition_levels_byte_length > 0);
+            }
+            DataPageHeader::V1(_) => panic!("The pages must be v2 pages"),
+        }
+        arrays.push(page_to_array(page)?)
+    }
+    assert_eq!(arrays, vec![Array::Int32(page1), Array::Int32(page2)]);
+    Ok(())
+}
+
+#[test]
+fn read_single_offset_index() -> Result<()> {
+    let data = write_file()?;
+    let mut reader = Cursor::new(data);
+
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+
+    let pages = read_offset_index(&mut reader, column)?;
+    assert_eq!(
+        pages,
+        read_pages_locations(&mut reader, metadata.row_groups[0].columns())?.pop()
+    );
+    assert_eq!(pages.map(|pages| pages.len()), Some(2));
+
+    // a column chunk without offset index
+    let mut chunk = column.column_chunk().clone();
+    chunk.offset_index_offset = None;
+    chunk.offset_index_length = None;
+    let column = ColumnChunkMetaData::new(chunk, column.descriptor().clone());
+    assert_eq!(read_offset_index(&mut reader, &column)?, None);
+    Ok(())
+}
+
+#[test]
+fn corrupted_column_index() -> Result<()> {
+    let mut data = write_file()?;
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let chunk = metadata.row_groups[0].columns()[0].column_chunk();
+    let offset = chunk.column_index_offset.unwrap() as usize;
+    let length = chunk.column_index_length.unwrap() as usize;
+    data[offset..offset + length].fill(0xff);
+
+    let mut reader = Cursor::new(data);
+    let error = read_columns_indexes(&mut reader, metadata.row_groups[0].columns()).unwrap_err();
+    assert!(matches!(error, Error::OutOfSpec(_)));
+    assert!(
+        error.to_string().starts_with("column 'col1': "),
+        "{}",
+        error
+    );
+    Ok(())
+}
+
+fn read_boundary_order(data: Vec<u8>) -> Result<BoundaryOrder> {
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let indexes = read_columns_indexes(&mut reader, metadata.row_groups[0].columns())?;
+    let index = indexes[0]
+        .as_any()
+        .downcast_ref::<NativeIndex<i32>>()
+        .unwrap();
+    Ok(index.boundary_order)
+}
+
+#[test]
+fn boundary_order() -> Result<()> {
+    let increasing = (0..10)
+        .map(|page| (page * 100..(page + 1) * 100).map(Some).collect())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        read_boundary_order(write_pages(&increasing)?)?,
+        BoundaryOrder::Ascending
+    );
+
+    let mut decreasing = increasing.clone();
+    decreasing.reverse();
+    assert_eq!(
+        read_boundary_order(write_pages(&decreasing)?)?,
+        BoundaryOrder::Descending
+    );
+
+    let mut unordered = increasing;
+    unordered.swap(1, 2);
+    assert_eq!(
+        read_boundary_order(write_pages(&unordered)?)?,
+        BoundaryOrder::Unordered
+    );
+
+    // pages whose values are all null are ignored and adjacent pages may have the same bounds
+    let pages = vec![
+        vec![Some(1), Some(3)],
+        vec![None, None],
+        vec![Some(3), None, Some(1)],
+        vec![Some(3), Some(5)],
+    ];
+    assert_eq!(
+        read_boundary_order(write_pages(&pages)?)?,
+        BoundaryOrder::Ascending
+    );
+
+    // the min is ascending but the max descending
+    let pages = vec![vec![Some(1), Some(10)], vec![Some(2), Some(5)]];
+    assert_eq!(
+        read_boundary_order(write_pages(&pages)?)?,
+        BoundaryOrder::Unordered
+    );
+
+    // a single page and only null pages
+    assert_eq!(
+        read_boundary_order(write_pages(&[vec![Some(2), Some(1)]])?)?,
+        BoundaryOrder::Ascending
+    );
+    assert_eq!(
+        read_boundary_order(write_pages(&[vec![None], vec![None]])?)?,
+        BoundaryOrder::Ascending
+    );
+    Ok(())
+}
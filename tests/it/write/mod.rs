@@ -1,23 +1,45 @@
-mod binary;
+pub(crate) mod binary;
+#[cfg(feature = "bloom_filter")]
+mod bloom_filter;
+mod boolean;
+mod compression;
+mod crc;
+mod dict;
+mod encoding;
+mod format;
 mod indexes;
-mod primitive;
+pub(crate) mod primitive;
+mod row_group;
+mod statistics;
 
 use std::io::{Cursor, Read, Seek};
 use std::sync::Arc;
 
-use parquet2::compression::CompressionOptions;
-use parquet2::error::Result;
-use parquet2::metadata::SchemaDescriptor;
-use parquet2::read::read_metadata;
-use parquet2::schema::types::{ParquetType, PhysicalType};
-use parquet2::statistics::Statistics;
+#[cfg(feature = "zstd")]
+use parquet2::compression::ZstdLevel;
+use parquet2::compression::{compress, Compression, CompressionOptions, GzipLevel};
+use parquet2::encoding::Encoding;
+use parquet2::error::{Error, Result};
+use parquet2::metadata::{ColumnChunkMetaData, KeyValue, SchemaDescriptor};
+use parquet2::page::{CompressedDataPage, CompressedPage, EncodedDictPage};
+use parquet2::read::{
+    get_page_iterator, read_columns_indexes, read_compressed_pages, read_metadata, DynamicValue,
+    RowGroupRowIterator,
+};
+use parquet2::read::{BasicDecompressor, ReadOptions};
+use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveLogicalType, PrimitiveType};
+use parquet2::schema::Repetition;
+use parquet2::statistics::{serialize_statistics, PrimitiveStatistics, Statistics};
+use parquet2::write::dictionary;
 use parquet2::write::FileStreamer;
 use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter, Version};
+use parquet2::FallibleStreamingIterator;
 use parquet2::{metadata::Descriptor, page::EncodedPage, write::WriteOptions};
 
 use super::Array;
 use super::{alltypes_plain, alltypes_statistics};
-use primitive::array_to_page_v1;
+use dict::dict_levels;
+use primitive::{array_to_page_v1, array_to_page_v1_encoded};
 
 pub fn array_to_page(
     array: &Array,
@@ -25,13 +47,25 @@ pub fn array_to_page(
     descriptor: &Descriptor,
 ) -> Result<EncodedPage> {
     // using plain encoding format
+    array_to_page_encoded(array, options, descriptor, Encoding::Plain)
+}
+
+fn array_to_page_encoded(
+    array: &Array,
+    options: &WriteOptions,
+    descriptor: &Descriptor,
+    encoding: Encoding,
+) -> Result<EncodedPage> {
     match array {
-        Array::Int32(array) => primitive::array_to_page_v1(array, options, descriptor),
-        Array::Int64(array) => primitive::array_to_page_v1(array, options, descriptor),
-        Array::Int96(array) => primitive::array_to_page_v1(array, options, descriptor),
-        Array::Float32(array) => primitive::array_to_page_v1(array, options, descriptor),
-        Array::Float64(array) => primitive::array_to_page_v1(array, options, descriptor),
-        Array::Binary(array) => binary::array_to_page_v1(array, options, descriptor),
+        Array::Int32(array) => array_to_page_v1_encoded(array, options, descriptor, encoding),
+        Array::Int64(array) => array_to_page_v1_encoded(array, options, descriptor, encoding),
+        Array::Int96(array) => array_to_page_v1_encoded(array, options, descriptor, encoding),
+        Array::Float32(array) => array_to_page_v1_encoded(array, options, descriptor, encoding),
+        Array::Float64(array) => array_to_page_v1_encoded(array, options, descriptor, encoding),
+        Array::Boolean(array) => boolean::array_to_page_v1(array, options, descriptor, encoding),
+        Array::Binary(array) | Array::FixedLenBinary(array) => {
+            binary::array_to_page_v1(array, options, descriptor, encoding)
+        }
         _ => todo!(),
     }
 }
@@ -50,13 +84,16 @@ async fn read_column_async<
     Ok((a, statistics))
 }
 
-fn test_column(column: &str, compression: CompressionOptions) -> Result<()> {
-    let array = alltypes_plain(column);
+pub(crate) fn write_column(array: &Array, compression: CompressionOptions) -> Result<Vec<u8>> {
+    write_column_encoded(array, compression, Encoding::Plain)
+}
 
-    let options = WriteOptions {
-        write_statistics: true,
-        version: Version::V1,
-    };
+fn write_column_encoded(
+    array: &Array,
+    compression: CompressionOptions,
+    encoding: Encoding,
+) -> Result<Vec<u8>> {
+    let options = WriteOptions::default();
 
     // prepare schema
     let type_ = match array {
@@ -65,36 +102,26 @@ fn test_column(column: &str, compression: CompressionOptions) -> Result<()> {
         Array::Int96(_) => PhysicalType::Int96,
         Array::Float32(_) => PhysicalType::Float,
         Array::Float64(_) => PhysicalType::Double,
+        Array::Boolean(_) => PhysicalType::Boolean,
         Array::Binary(_) => PhysicalType::ByteArray,
+        Array::FixedLenBinary(array) => {
+            PhysicalType::FixedLenByteArray(array.iter().flatten().next().map_or(1, |x| x.len()))
+        }
         _ => todo!(),
     };
 
-    let schema = SchemaDescriptor::new(
-        "schema".to_string(),
-        vec![ParquetType::from_physical("col".to_string(), type_)],
-    );
-
-    let a = schema.columns();
-
-    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
-        DynIter::new(std::iter::once(array_to_page(
-            &array,
-            &options,
-            &a[0].descriptor,
-        ))),
+    write_single_column(
+        ParquetType::from_physical("col".to_string(), type_),
+        options,
+        |descriptor| vec![array_to_page_encoded(array, &options, descriptor, encoding)],
         compression,
-        vec![],
-    ));
-    let columns = std::iter::once(Ok(pages));
-
-    let writer = Cursor::new(vec![]);
-    let mut writer = FileWriter::new(writer, schema, options, None);
+    )
+}
 
-    writer.start()?;
-    writer.write(DynIter::new(columns))?;
-    writer.end(None)?;
+fn test_column(column: &str, compression: CompressionOptions) -> Result<()> {
+    let array = alltypes_plain(column);
 
-    let data = writer.into_inner().into_inner();
+    let data = write_column(&array, compression)?;
 
     let (result, statistics) = read_column(&mut Cursor::new(data))?;
     assert_eq!(array, result);
@@ -128,7 +155,444 @@ fn int32_lz4_short_i32_array() -> Result<()> {
 
 #[test]
 fn int32_brotli() -> Result<()> {
-    test_column("id", CompressionOptions::Brotli)
+    test_column("id", CompressionOptions::Brotli(None))
+}
+
+#[test]
+fn append_row_group() -> Result<()> {
+    let data = write_column(
+        &Array::Int32(vec![Some(1), None]),
+        CompressionOptions::Uncompressed,
+    )?;
+    let mut file = Cursor::new(data);
+    let metadata = read_metadata(&mut file)?;
+
+    let mut writer = FileWriter::from_existing(&mut file, &metadata, WriteOptions::default())?;
+    let descriptor = writer.schema().columns()[0].descriptor.clone();
+    let page = array_to_page(
+        &Array::Int32(vec![Some(3), Some(4)]),
+        writer.options(),
+        &descriptor,
+    );
+    let pages = DynStreamingIterator::new(Compressor::new(
+        DynIter::new(std::iter::once(page)),
+        CompressionOptions::Snappy,
+        vec![],
+    ));
+    writer.write(DynIter::new(std::iter::once(Ok(pages))))?;
+    let size = writer.end(None)?;
+
+    let mut data = file.into_inner();
+    assert_eq!(data.len() as u64, size);
+
+    let mut reader = Cursor::new(&mut data);
+    let appended = read_metadata(&mut reader)?;
+    assert_eq!(appended.row_groups.len(), 2);
+    assert_eq!(appended.num_rows, 4);
+    assert_eq!(appended.schema(), metadata.schema());
+
+    let mut values = vec![];
+    for row_group in 0..2 {
+        values.extend(
+            RowGroupRowIterator::try_new(&mut reader, &appended, row_group)?
+                .map(|row| row["col"].clone()),
+        );
+    }
+    assert_eq!(
+        values,
+        vec![
+            DynamicValue::Int32(1),
+            DynamicValue::Null,
+            DynamicValue::Int32(3),
+            DynamicValue::Int32(4),
+        ]
+    );
+
+    // the indexes of both the existing and the appended row groups are readable
+    for row_group in &appended.row_groups {
+        assert_eq!(
+            read_columns_indexes(&mut reader, row_group.columns())?.len(),
+            1
+        );
+    }
+
+    // metadata of another file is rejected
+    let other = write_column(
+        &Array::Int64(vec![Some(1)]),
+        CompressionOptions::Uncompressed,
+    )?;
+    let other = read_metadata(&mut Cursor::new(other))?;
+    assert!(matches!(
+        FileWriter::from_existing(&mut reader, &other, WriteOptions::default()),
+        Err(Error::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn add_key_value() -> Result<()> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "col".to_string(),
+            PhysicalType::Int64,
+        )],
+    );
+
+    let writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    assert!(writer.metadata().is_empty());
+    let mut writer = writer
+        .add_key_value("registry", "https://example.com/schemas/1")
+        .add_key_value("ARROW:schema", "bytes")
+        .add_key_value("registry", "https://example.com/schemas/2");
+    let expected = vec![
+        KeyValue {
+            key: "registry".to_string(),
+            value: Some("https://example.com/schemas/2".to_string()),
+        },
+        KeyValue {
+            key: "ARROW:schema".to_string(),
+            value: Some("bytes".to_string()),
+        },
+    ];
+    assert_eq!(writer.metadata(), expected.as_slice());
+
+    writer.start()?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    assert_eq!(metadata.key_value_metadata(), Some(expected.as_slice()));
+    Ok(())
+}
+
+/// Writes a file whose single column is `field`, with a column chunk made of the `pages`
+/// returned for the column's descriptor
+pub(crate) fn write_single_column(
+    field: ParquetType,
+    options: WriteOptions,
+    pages: impl FnOnce(&Descriptor) -> Vec<Result<EncodedPage>>,
+    compression: CompressionOptions,
+) -> Result<Vec<u8>> {
+    let schema = SchemaDescriptor::new("schema".to_string(), vec![field]);
+    let pages = pages(&schema.columns()[0].descriptor);
+
+    let pages = DynStreamingIterator::new(Compressor::new_from_vec(
+        DynIter::new(pages.into_iter()),
+        compression,
+        vec![],
+    ));
+    let columns = std::iter::once(Ok(pages));
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    writer.write(DynIter::new(columns))?;
+    writer.end(None)?;
+
+    Ok(writer.into_inner().into_inner())
+}
+
+/// Writes each array of `arrays` as a page of a single column chunk
+pub(crate) fn write_pages(arrays: &[Array], compression: CompressionOptions) -> Result<Vec<u8>> {
+    let options = WriteOptions::default();
+
+    write_single_column(
+        ParquetType::from_physical("col".to_string(), PhysicalType::Int64),
+        options,
+        |descriptor| {
+            arrays
+                .iter()
+                .map(|array| array_to_page(array, &options, descriptor))
+                .collect()
+        },
+        compression,
+    )
+}
+
+#[test]
+fn column_chunk_sizes() -> Result<()> {
+    let arrays = [Array::Int64((0..10_000).map(|x| Some(x % 10)).collect())];
+
+    let data = write_pages(&arrays, CompressionOptions::Uncompressed)?;
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(
+        column.total_compressed_size(),
+        column.total_uncompressed_size()
+    );
+    assert_eq!(column.compression_ratio(), 1.0);
+
+    let data = write_pages(&arrays, CompressionOptions::Snappy)?;
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(column.total_compressed_size(), column.compressed_size());
+    assert_eq!(column.total_uncompressed_size(), column.uncompressed_size());
+    assert!(column.compression_ratio() > 1.0);
+
+    // the sizes are the sizes of the pages plus their headers
+    let pages = get_page_iterator(column, &mut Cursor::new(&data), None, vec![])?
+        .collect::<Result<Vec<_>>>()?;
+    let compressed = pages
+        .iter()
+        .map(|page| page.compressed_size())
+        .sum::<usize>();
+    let uncompressed = pages
+        .iter()
+        .map(|page| page.uncompressed_size())
+        .sum::<usize>();
+    assert!(compressed < uncompressed);
+    assert!((compressed as i64) < column.total_compressed_size());
+    assert!((uncompressed as i64) < column.total_uncompressed_size());
+    assert_eq!(
+        column.total_uncompressed_size() - uncompressed as i64,
+        column.total_compressed_size() - compressed as i64
+    );
+    assert_eq!(column.byte_range().1, column.total_compressed_size() as u64);
+    Ok(())
+}
+
+/// Reads the pages of `column` into one [`Array`] per page
+pub(crate) fn read_pages<R: Read + Seek>(
+    reader: &mut R,
+    column: &ColumnChunkMetaData,
+) -> Result<Vec<Array>> {
+    let pages = get_page_iterator(column, reader, None, vec![])?;
+    let mut pages = BasicDecompressor::new(pages, vec![]);
+    let mut result = vec![];
+    while let Some(page) = pages.next()? {
+        result.push(super::read::page_to_array(page)?);
+    }
+    Ok(result)
+}
+
+#[test]
+fn multiple_pages() -> Result<()> {
+    // pages of different sizes, so that the re-used encoders see both
+    // larger and smaller inputs than before
+    let arrays = [1000, 10, 100000, 0, 1, 5000]
+        .iter()
+        .enumerate()
+        .map(|(i, &length)| {
+            Array::Int64(
+                (0..length)
+                    .map(|x| (x % 7 != 0).then(|| (x % 100 + i) as i64))
+                    .collect(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let compressions = vec![
+        CompressionOptions::Gzip(None),
+        CompressionOptions::Gzip(Some(GzipLevel::try_new(9)?)),
+        CompressionOptions::Snappy,
+    ];
+    #[cfg(feature = "zstd")]
+    let compressions = [
+        CompressionOptions::Zstd(None),
+        CompressionOptions::Zstd(Some(ZstdLevel::try_new(19)?)),
+    ]
+    .into_iter()
+    .chain(compressions);
+    for compression in compressions {
+        let data = write_pages(&arrays, compression)?;
+
+        let mut reader = Cursor::new(data);
+        let metadata = read_metadata(&mut reader)?;
+        let column = &metadata.row_groups[0].columns()[0];
+        assert_eq!(column.compression(), compression.into());
+
+        assert_eq!(read_pages(&mut reader, column)?, arrays);
+    }
+    Ok(())
+}
+
+#[test]
+fn copy_compressed_pages() -> Result<()> {
+    let dict = [10i32, 20, 30, 40, 50];
+    let pages = [
+        (0..1000u32)
+            .map(|x| (x % 7 != 0).then(|| x % 5))
+            .collect::<Vec<_>>(),
+        vec![Some(4); 100],
+    ];
+    let primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32);
+    let data = write_single_column(
+        ParquetType::PrimitiveType(primitive_type.clone()),
+        WriteOptions::default(),
+        |descriptor| {
+            let dict_page = EncodedDictPage::new(
+                dict.iter().flat_map(|x| x.to_le_bytes()).collect(),
+                dict.len(),
+            );
+            std::iter::once(Ok(EncodedPage::Dict(dict_page)))
+                .chain(pages.iter().map(|array| {
+                    let (levels, indices) = dict_levels(array)?;
+                    let values = indices.iter().map(|&index| dict[index as usize]);
+                    let statistics = PrimitiveStatistics {
+                        primitive_type: descriptor.primitive_type.clone(),
+                        null_count: Some((array.len() - indices.len()) as i64),
+                        distinct_count: None,
+                        max_value: values.clone().max(),
+                        min_value: values.min(),
+                    };
+                    dictionary::dict_data_page(
+                        &indices,
+                        3,
+                        levels,
+                        Some(serialize_statistics(&statistics)),
+                        descriptor.clone(),
+                        Version::V1,
+                    )
+                    .map(EncodedPage::Data)
+                }))
+                .collect()
+        },
+        CompressionOptions::Snappy,
+    )?;
+
+    let mut reader = Cursor::new(&data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    // the pages are not `Clone`: they are read again for each copy
+    let read_pages_again =
+        || read_compressed_pages(&mut Cursor::new(&data), column, ReadOptions::default());
+    let pages = read_pages_again()?;
+    assert_eq!(pages.len(), 3);
+    assert!(matches!(pages[0], CompressedPage::Dict(_)));
+
+    let options = WriteOptions::default();
+    let schema = metadata.schema().clone();
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema.clone(), options, None);
+    writer.start()?;
+    writer.write_compressed_pages(vec![read_pages_again()?])?;
+    writer.end(None)?;
+    let copy = writer.into_inner().into_inner();
+
+    let mut reader = Cursor::new(&copy);
+    let copy_metadata = read_metadata(&mut reader)?;
+    let copy_column = &copy_metadata.row_groups[0].columns()[0];
+    let copy_pages = read_compressed_pages(&mut reader, copy_column, ReadOptions::default())?;
+
+    // the pages are copied byte for byte
+    assert_eq!(copy_pages.len(), pages.len());
+    for (copy_page, page) in copy_pages.iter().zip(pages.iter()) {
+        assert_eq!(copy_page.buffer(), page.buffer());
+        if let (CompressedPage::Data(copy_page), CompressedPage::Data(page)) = (copy_page, page) {
+            assert_eq!(
+                format!("{:?}", copy_page.header()),
+                format!("{:?}", page.header())
+            );
+        }
+    }
+
+    // the metadata of the column chunk, its statistics and indexes, are carried over
+    assert_eq!(copy_column.compression(), Compression::Snappy);
+    assert_eq!(copy_column.num_values(), column.num_values());
+    assert_eq!(copy_column.compressed_size(), column.compressed_size());
+    assert_eq!(
+        copy_column.dictionary_page_offset(),
+        column.dictionary_page_offset()
+    );
+    assert_eq!(copy_column.data_page_offset(), column.data_page_offset());
+    assert_eq!(
+        copy_column.statistics().unwrap()?.as_ref(),
+        column.statistics().unwrap()?.as_ref()
+    );
+    assert_eq!(
+        copy_column.column_chunk().column_index_length,
+        column.column_chunk().column_index_length
+    );
+    let indexes = read_columns_indexes(&mut Cursor::new(&copy), &[copy_column.clone()])?;
+    let expected = read_columns_indexes(&mut Cursor::new(&data), &[column.clone()])?;
+    assert_eq!(format!("{:?}", indexes), format!("{:?}", expected));
+    assert_eq!(read_pages(&mut reader, copy_column)?, {
+        let mut reader = Cursor::new(&data);
+        read_pages(&mut reader, column)?
+    });
+
+    // invalid row groups are not written
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    assert!(writer.write_compressed_pages(vec![]).is_err());
+    let mut misplaced_dict = read_pages_again()?;
+    misplaced_dict.swap(0, 1);
+    assert!(writer.write_compressed_pages(vec![misplaced_dict]).is_err());
+    let mut other_column = pages;
+    if let CompressedPage::Data(page) = &other_column[1] {
+        let mut descriptor = column.descriptor().descriptor.clone();
+        descriptor.max_def_level = 0;
+        other_column[1] = CompressedPage::Data(CompressedDataPage::new(
+            page.header().clone(),
+            page.buffer().to_vec(),
+            page.compression(),
+            page.uncompressed_size(),
+            None,
+            descriptor,
+            None,
+        ));
+    }
+    assert!(writer.write_compressed_pages(vec![other_column]).is_err());
+    writer.end(None)?;
+    let empty = writer.into_inner().into_inner();
+    assert!(read_metadata(&mut Cursor::new(&empty))?
+        .row_groups
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn read_compressed_pages_wrong_uncompressed_size() -> Result<()> {
+    let mut primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32);
+    primitive_type.field_info.repetition = Repetition::Repeated;
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::PrimitiveType(primitive_type)],
+    );
+    let descriptor = schema.columns()[0].descriptor.clone();
+
+    // the rows of v1 pages of repeated columns are counted from their decompressed levels
+    let page = format::repeated_page(&[1, 2, 3], &[0, 1, 0], &descriptor);
+    let mut buffer = vec![];
+    compress(CompressionOptions::Snappy, page.buffer(), &mut buffer)?;
+    for (uncompressed_size, is_ok) in [
+        (page.uncompressed_size(), true),
+        (page.uncompressed_size() + 1, false),
+    ] {
+        let page = CompressedDataPage::new(
+            page.header().clone(),
+            buffer.clone(),
+            Compression::Snappy,
+            uncompressed_size,
+            None,
+            descriptor.clone(),
+            Some(2),
+        );
+        let options = WriteOptions {
+            write_statistics: false,
+            ..Default::default()
+        };
+        let mut writer = FileWriter::new(Cursor::new(vec![]), schema.clone(), options, None);
+        writer.start()?;
+        writer.write_compressed_pages(vec![vec![CompressedPage::Data(page)]])?;
+        writer.end(None)?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = Cursor::new(&data);
+        let metadata = read_metadata(&mut reader)?;
+        let column = &metadata.row_groups[0].columns()[0];
+        let pages = read_compressed_pages(&mut reader, column, ReadOptions::default());
+        if is_ok {
+            assert_eq!(pages?.len(), 1);
+        } else {
+            assert!(
+                matches!(pages, Err(Error::OutOfSpec(_))),
+                "{:?}",
+                pages.err()
+            );
+        }
+    }
+    Ok(())
 }
 
 #[test]
@@ -186,7 +650,7 @@ fn basic() -> Result<()> {
 
     let options = WriteOptions {
         write_statistics: false,
-        version: Version::V1,
+        ..Default::default()
     };
 
     let schema = SchemaDescriptor::new(
@@ -230,13 +694,108 @@ fn basic() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn decimal() -> Result<()> {
+    let options = WriteOptions::default();
+    // 123.45, -0.01, null and 9999999.99
+    let array = vec![Some(12345), Some(-1), None, Some(999_999_999)];
+    let data = write_single_column(
+        ParquetType::from_physical_with_logical(
+            "col".to_string(),
+            PhysicalType::Int32,
+            PrimitiveLogicalType::Decimal(9, 2),
+        )?,
+        options,
+        |descriptor| vec![array_to_page_v1(&array, &options, descriptor)],
+        CompressionOptions::Uncompressed,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let primitive_type = &metadata.schema().columns()[0].descriptor.primitive_type;
+    assert_eq!(
+        primitive_type.logical_type,
+        Some(PrimitiveLogicalType::Decimal(9, 2))
+    );
+    assert_eq!(primitive_type.converted_type, None);
+
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(result, Array::Int32(array));
+    Ok(())
+}
+
+#[test]
+fn uuid() -> Result<()> {
+    let options = WriteOptions::default();
+    let array = vec![
+        Some((0..16).collect::<Vec<u8>>()),
+        None,
+        Some(vec![255; 16]),
+    ];
+    let data = write_single_column(
+        ParquetType::from_physical_with_logical(
+            "col".to_string(),
+            PhysicalType::FixedLenByteArray(16),
+            PrimitiveLogicalType::Uuid,
+        )?,
+        options,
+        |descriptor| {
+            vec![binary::array_to_page_v1(
+                &array,
+                &options,
+                descriptor,
+                Encoding::Plain,
+            )]
+        },
+        CompressionOptions::Uncompressed,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let primitive_type = &metadata.schema().columns()[0].descriptor.primitive_type;
+    assert_eq!(
+        primitive_type.logical_type,
+        Some(PrimitiveLogicalType::Uuid)
+    );
+    assert_eq!(
+        primitive_type.physical_type,
+        PhysicalType::FixedLenByteArray(16)
+    );
+
+    let (result, _) = read_column(&mut reader)?;
+    assert_eq!(result, Array::FixedLenBinary(array));
+    Ok(())
+}
+
+/// An async reader that returns at most one byte per read, emulating a transport
+/// with partial reads.
+pub(crate) struct PartialReader<R>(pub R);
+
+impl<R: futures::AsyncRead + Unpin> futures::AsyncRead for PartialReader<R> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut [u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        let len = buf.len().min(1);
+        std::pin::Pin::new(&mut self.0).poll_read(cx, &mut buf[..len])
+    }
+}
+
+impl<R: futures::AsyncSeek + Unpin> futures::AsyncSeek for PartialReader<R> {
+    fn poll_seek(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        pos: std::io::SeekFrom,
+    ) -> std::task::Poll<std::io::Result<u64>> {
+        std::pin::Pin::new(&mut self.0).poll_seek(cx, pos)
+    }
+}
+
 async fn test_column_async(column: &str) -> Result<()> {
     let array = alltypes_plain(column);
 
-    let options = WriteOptions {
-        write_statistics: true,
-        version: Version::V1,
-    };
+    let options = WriteOptions::default();
 
     // prepare schema
     let type_ = match array {
@@ -275,13 +834,18 @@ async fn test_column_async(column: &str) -> Result<()> {
 
     let data = writer.into_inner();
 
-    let (result, statistics) = read_column_async(&mut futures::io::Cursor::new(data)).await?;
+    let (result, statistics) =
+        read_column_async(&mut futures::io::Cursor::new(data.clone())).await?;
     assert_eq!(array, result);
     let stats = alltypes_statistics(column);
     assert_eq!(
         statistics.as_ref().map(|x| x.as_ref()),
         Some(stats).as_ref().map(|x| x.as_ref())
     );
+
+    let mut reader = PartialReader(futures::io::Cursor::new(data));
+    let (result, _) = read_column_async(&mut reader).await?;
+    assert_eq!(array, result);
     Ok(())
 }
 
@@ -289,3 +853,65 @@ async fn test_column_async(column: &str) -> Result<()> {
 async fn test_async() -> Result<()> {
     test_column_async("float_col").await
 }
+
+#[test]
+fn file_metadata_after_end() -> Result<()> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("a".to_string(), PhysicalType::Int32),
+            ParquetType::from_physical("b".to_string(), PhysicalType::ByteArray),
+        ],
+    );
+    let descriptors = schema
+        .columns()
+        .iter()
+        .map(|column| column.descriptor.clone())
+        .collect::<Vec<_>>();
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    for start in [0, 100] {
+        let arrays = [
+            Array::Int32((start..start + 100).map(Some).collect()),
+            Array::Binary((start..start + 100).map(|x| Some(vec![x as u8])).collect()),
+        ];
+        let columns = arrays
+            .iter()
+            .zip(&descriptors)
+            .map(|(array, descriptor)| {
+                let page = array_to_page(array, &options, descriptor);
+                Ok(DynStreamingIterator::new(Compressor::new_from_vec(
+                    DynIter::new(std::iter::once(page)),
+                    CompressionOptions::Uncompressed,
+                    vec![],
+                )))
+            })
+            .collect::<Vec<_>>();
+        writer.write(DynIter::new(columns.into_iter()))?;
+    }
+    assert!(writer.file_metadata().is_none());
+    let size = writer.end(None)?;
+
+    let (writer, metadata) = writer.into_inner_and_metadata();
+    let metadata = metadata.unwrap();
+    let data = writer.into_inner();
+    assert_eq!(size as usize, data.len());
+
+    let expected = read_metadata(&mut Cursor::new(data))?;
+    assert_eq!(metadata.num_rows, expected.num_rows);
+    assert_eq!(metadata.schema(), expected.schema());
+    assert_eq!(metadata.row_groups.len(), 2);
+    assert_eq!(metadata.row_groups.len(), expected.row_groups.len());
+    for (row_group, expected) in metadata.row_groups.iter().zip(expected.row_groups.iter()) {
+        assert_eq!(row_group.num_rows(), expected.num_rows());
+        for (column, expected) in row_group.columns().iter().zip(expected.columns()) {
+            assert_eq!(column.byte_range(), expected.byte_range());
+            assert_eq!(column.data_page_offset(), expected.data_page_offset());
+            // including the offsets of the column and offset indexes
+            assert_eq!(column.column_chunk(), expected.column_chunk());
+        }
+    }
+    Ok(())
+}
@@ -1,14 +1,18 @@
 use parquet2::{
-    encoding::Encoding,
+    encoding::{byte_stream_split, delta_bitpacked, Encoding},
     metadata::Descriptor,
-    page::{DataPage, DataPageHeader, DataPageHeaderV1, EncodedPage},
+    page::{DataPage, DataPageHeader, DataPageHeaderV1, DataPageHeaderV2, EncodedPage},
     statistics::{serialize_statistics, PrimitiveStatistics, Statistics},
     types::NativeType,
     write::WriteOptions,
-    {encoding::hybrid_rle::encode_bool, error::Result},
+    {encoding::hybrid_rle::encode_bool, error::Error, error::Result},
 };
+use parquet_format_async_temp::Statistics as ParquetStatistics;
 
-fn unzip_option<T: NativeType>(array: &[Option<T>]) -> Result<(Vec<u8>, Vec<u8>)> {
+fn unzip_option<T: NativeType>(
+    array: &[Option<T>],
+    encoding: Encoding,
+) -> Result<(Vec<u8>, Vec<u8>)> {
     // leave the first 4 bytes anouncing the length of the def level
     // this will be overwritten at the end, once the length is known.
     // This is unknown at this point because of the uleb128 encoding,
@@ -19,7 +23,7 @@ fn unzip_option<T: NativeType>(array: &[Option<T>]) -> Result<(Vec<u8>, Vec<u8>)
     let mut values = vec![];
     let iter = array.iter().map(|value| {
         if let Some(item) = value {
-            values.extend_from_slice(item.to_le_bytes().as_ref());
+            values.push(*item);
             true
         } else {
             false
@@ -37,6 +41,44 @@ fn unzip_option<T: NativeType>(array: &[Option<T>]) -> Result<(Vec<u8>, Vec<u8>)
     validity[2] = length[2];
     validity[3] = length[3];
 
+    let values = match encoding {
+        Encoding::Plain => values
+            .iter()
+            .flat_map(|x| x.to_le_bytes().as_ref().to_vec())
+            .collect(),
+        Encoding::ByteStreamSplit => {
+            let mut buffer = vec![];
+            byte_stream_split::encode(&values, &mut buffer);
+            buffer
+        }
+        Encoding::DeltaBinaryPacked => {
+            let mut buffer = vec![];
+            let values = values.iter().map(|x| x.to_le_bytes());
+            match std::mem::size_of::<T>() {
+                4 => delta_bitpacked::encode_i32(
+                    values.map(|x| i32::from_le_bytes(x.as_ref().try_into().unwrap())),
+                    &mut buffer,
+                ),
+                8 => delta_bitpacked::encode(
+                    values.map(|x| i64::from_le_bytes(x.as_ref().try_into().unwrap())),
+                    &mut buffer,
+                ),
+                _ => {
+                    return Err(Error::InvalidParameter(
+                        "Only 32 and 64-bit integers can be delta binary packed".to_string(),
+                    ))
+                }
+            }
+            buffer
+        }
+        other => {
+            return Err(Error::InvalidParameter(format!(
+                "The values of a primitive column cannot be encoded with {:?}",
+                other
+            )))
+        }
+    };
+
     Ok((values, validity))
 }
 
@@ -45,11 +87,15 @@ pub fn array_to_page_v1<T: NativeType>(
     options: &WriteOptions,
     descriptor: &Descriptor,
 ) -> Result<EncodedPage> {
-    let (values, mut buffer) = unzip_option(array)?;
-
-    buffer.extend_from_slice(&values);
+    array_to_page_v1_encoded(array, options, descriptor, Encoding::Plain)
+}
 
-    let statistics = if options.write_statistics {
+fn statistics<T: NativeType>(
+    array: &[Option<T>],
+    options: &WriteOptions,
+    descriptor: &Descriptor,
+) -> Option<ParquetStatistics> {
+    if options.write_statistics {
         let statistics = &PrimitiveStatistics {
             primitive_type: descriptor.primitive_type.clone(),
             null_count: Some((array.len() - array.iter().flatten().count()) as i64),
@@ -60,14 +106,25 @@ pub fn array_to_page_v1<T: NativeType>(
         Some(serialize_statistics(statistics))
     } else {
         None
-    };
+    }
+}
+
+pub fn array_to_page_v1_encoded<T: NativeType>(
+    array: &[Option<T>],
+    options: &WriteOptions,
+    descriptor: &Descriptor,
+    encoding: Encoding,
+) -> Result<EncodedPage> {
+    let (values, mut buffer) = unzip_option(array, encoding)?;
+
+    buffer.extend_from_slice(&values);
 
     let header = DataPageHeaderV1 {
         num_values: array.len() as i32,
-        encoding: Encoding::Plain.into(),
+        encoding: encoding.into(),
         definition_level_encoding: Encoding::Rle.into(),
         repetition_level_encoding: Encoding::Rle.into(),
-        statistics,
+        statistics: statistics(array, options, descriptor),
     };
 
     Ok(EncodedPage::Data(DataPage::new(
@@ -78,3 +135,35 @@ pub fn array_to_page_v1<T: NativeType>(
         Some(array.len()),
     )))
 }
+
+/// Returns a plain-encoded v2 page of `array`, whose definition levels are not prefixed by
+/// their length.
+pub fn array_to_page_v2<T: NativeType>(
+    array: &[Option<T>],
+    options: &WriteOptions,
+    descriptor: &Descriptor,
+) -> Result<EncodedPage> {
+    let (values, validity) = unzip_option(array, Encoding::Plain)?;
+    let mut buffer = validity[4..].to_vec();
+    let definition_levels_byte_length = buffer.len() as i32;
+    buffer.extend_from_slice(&values);
+
+    let header = DataPageHeaderV2 {
+        num_values: array.len() as i32,
+        num_nulls: (array.len() - array.iter().flatten().count()) as i32,
+        num_rows: array.len() as i32,
+        encoding: Encoding::Plain.into(),
+        definition_levels_byte_length,
+        repetition_levels_byte_length: 0,
+        is_compressed: None,
+        statistics: statistics(array, options, descriptor),
+    };
+
+    Ok(EncodedPage::Data(DataPage::new(
+        DataPageHeader::V2(header),
+        buffer,
+        None,
+        descriptor.clone(),
+        Some(array.len()),
+    )))
+}
@@ -0,0 +1,151 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::error::{Error, Result};
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::read::read_metadata;
+use parquet2::schema::types::{ParquetType, PhysicalType};
+use parquet2::write;
+use parquet2::write::WriteOptions;
+use parquet2::write::{FileWriter, RowGroupBuilder, Version};
+
+use crate::Array;
+
+use super::array_to_page;
+
+/// Writes `num_rows` rows of an int64 and a binary column in batches of `batch` rows with
+/// `builder`, returning the number of rows of each row group of the file.
+fn write_row_groups(
+    builder: impl FnOnce(FileWriter<Cursor<Vec<u8>>>) -> RowGroupBuilder<Cursor<Vec<u8>>>,
+    num_rows: i64,
+    batch: i64,
+) -> Result<Vec<i64>> {
+    let options = WriteOptions {
+        version: Version::V2,
+        ..Default::default()
+    };
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("a".to_string(), PhysicalType::Int64),
+            ParquetType::from_physical("b".to_string(), PhysicalType::ByteArray),
+        ],
+    );
+    let descriptors = schema
+        .columns()
+        .iter()
+        .map(|column| column.descriptor.clone())
+        .collect::<Vec<_>>();
+
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    let mut builder = builder(writer);
+    for start in (0..num_rows).step_by(batch as usize) {
+        let values = start..(start + batch).min(num_rows);
+        let arrays = [
+            Array::Int64(values.clone().map(Some).collect()),
+            Array::Binary(values.map(|x| Some(x.to_string().into_bytes())).collect()),
+        ];
+        let pages = arrays
+            .iter()
+            .zip(&descriptors)
+            .map(|(array, descriptor)| {
+                let page = array_to_page(array, &options, descriptor)?;
+                write::compress(page, vec![], CompressionOptions::Snappy)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        builder.write(pages)?;
+    }
+    builder.end(None)?;
+    let data = builder.into_inner().into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(data))?;
+    assert_eq!(metadata.num_rows as i64, num_rows);
+    Ok(metadata
+        .row_groups
+        .iter()
+        .map(|row_group| row_group.num_rows() as i64)
+        .collect())
+}
+
+#[test]
+fn row_group_builder_max_rows() -> Result<()> {
+    let row_groups = write_row_groups(
+        |writer| RowGroupBuilder::new(writer).with_max_rows(10_000),
+        25_000,
+        1_000,
+    )?;
+    assert_eq!(row_groups, vec![10_000, 10_000, 5_000]);
+
+    // batches are not split: a row group is written before a batch that would exceed the limit
+    let row_groups = write_row_groups(
+        |writer| RowGroupBuilder::new(writer).with_max_rows(10_000),
+        25_000,
+        3_000,
+    )?;
+    assert_eq!(row_groups, vec![9_000, 9_000, 7_000]);
+
+    // without limits, all rows are written to a single row group
+    let row_groups = write_row_groups(RowGroupBuilder::new, 25_000, 1_000)?;
+    assert_eq!(row_groups, vec![25_000]);
+    Ok(())
+}
+
+#[test]
+fn row_group_builder_max_bytes() -> Result<()> {
+    // the byte limit is reached before the row limit
+    let row_groups = write_row_groups(
+        |writer| {
+            RowGroupBuilder::new(writer)
+                .with_max_rows(10_000)
+                .with_max_bytes(20_000)
+        },
+        25_000,
+        1_000,
+    )?;
+    assert!(row_groups.len() > 3);
+    assert!(row_groups.iter().all(|x| *x < 10_000));
+    assert_eq!(row_groups.iter().sum::<i64>(), 25_000);
+
+    // the row limit is reached before the byte limit
+    let row_groups = write_row_groups(
+        |writer| {
+            RowGroupBuilder::new(writer)
+                .with_max_rows(10_000)
+                .with_max_bytes(usize::MAX)
+        },
+        25_000,
+        1_000,
+    )?;
+    assert_eq!(row_groups, vec![10_000, 10_000, 5_000]);
+    Ok(())
+}
+
+#[test]
+fn row_group_builder_invalid_batch() -> Result<()> {
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![ParquetType::from_physical(
+            "a".to_string(),
+            PhysicalType::Int64,
+        )],
+    );
+    let options = WriteOptions::default();
+    let descriptor = schema.columns()[0].descriptor.clone();
+    let mut writer = FileWriter::new(Cursor::new(vec![]), schema, options, None);
+    writer.start()?;
+    let mut builder = RowGroupBuilder::new(writer);
+
+    assert!(matches!(
+        builder.write(vec![]),
+        Err(Error::InvalidParameter(_))
+    ));
+
+    let array = Array::Int64(vec![Some(1)]);
+    let page = array_to_page(&array, &options, &descriptor)?;
+    let page = write::compress(page, vec![], CompressionOptions::Uncompressed)?;
+    builder.write(vec![page])?;
+    assert_eq!(builder.buffered_rows(), 1);
+    builder.end(None)?;
+    Ok(())
+}
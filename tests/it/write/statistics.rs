@@ -0,0 +1,397 @@
+use std::io::Cursor;
+
+use parquet2::compression::CompressionOptions;
+use parquet2::encoding::Encoding;
+use parquet2::error::{Error, Result};
+use parquet2::indexes::ByteIndex;
+use parquet2::metadata::SchemaDescriptor;
+use parquet2::page::EncodedDictPage;
+use parquet2::read::{get_page_iterator, read_columns_indexes, read_metadata};
+use parquet2::schema::types::{ParquetType, PhysicalType, PrimitiveLogicalType, PrimitiveType};
+use parquet2::statistics::{
+    deserialize_statistics, serialize_statistics, BinaryStatistics, FixedLenStatistics,
+    PrimitiveStatistics, Statistics,
+};
+use parquet2::write::dictionary;
+use parquet2::write::{Compressor, DynIter, DynStreamingIterator, FileWriter, Version};
+use parquet2::{page::EncodedPage, write::WriteOptions};
+
+use crate::Array;
+
+use super::dict::dict_levels;
+use super::{array_to_page, read_pages, write_single_column};
+
+#[test]
+fn column_statistics() -> Result<()> {
+    let options = WriteOptions::default();
+    let schema = SchemaDescriptor::new(
+        "schema".to_string(),
+        vec![
+            ParquetType::from_physical("a".to_string(), PhysicalType::Int64),
+            ParquetType::from_physical("b".to_string(), PhysicalType::ByteArray),
+        ],
+    );
+    let a = Array::Int64((0..100).map(|x| Some(x % 10)).collect());
+    let b = Array::Binary((0..100).map(|x| Some(vec![x as u8; 100])).collect());
+
+    let columns = schema
+        .columns()
+        .iter()
+        .zip([&a, &b])
+        .map(|(column, array)| {
+            // the pages of both columns have statistics
+            let page = array_to_page(array, &options, &column.descriptor);
+            Ok(DynStreamingIterator::new(Compressor::new_from_vec(
+                DynIter::new(std::iter::once(page)),
+                CompressionOptions::Uncompressed,
+                vec![],
+            )))
+        })
+        .collect::<Vec<_>>();
+
+    let writer = || FileWriter::new(Cursor::new(vec![]), schema.clone(), options, None);
+    assert!(matches!(
+        writer().with_column_statistics(vec![true]),
+        Err(Error::InvalidParameter(_))
+    ));
+    let mut writer = writer().with_column_statistics(vec![true, false])?;
+    assert!(writer.writes_statistics(0));
+    assert!(!writer.writes_statistics(1));
+    writer.start()?;
+    writer.write(DynIter::new(columns.into_iter()))?;
+    writer.end(None)?;
+    let data = writer.into_inner().into_inner();
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let columns = metadata.row_groups[0].columns();
+    for (column, has_statistics) in columns.iter().zip([true, false]) {
+        assert_eq!(column.statistics().is_some(), has_statistics);
+        assert_eq!(
+            column.column_chunk().column_index_offset.is_some(),
+            has_statistics
+        );
+        // the offset index does not depend on statistics
+        assert!(column.column_chunk().offset_index_offset.is_some());
+
+        let pages = get_page_iterator(column, &mut Cursor::new(&data), None, vec![])?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].statistics().is_some(), has_statistics);
+    }
+
+    // the column index of the column with statistics is valid
+    let indexes = read_columns_indexes(&mut Cursor::new(&data), &columns[..1])?;
+    assert_eq!(indexes.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn int96() -> Result<()> {
+    let options = WriteOptions::default();
+    let nanos = |hours: u64| hours * 3_600 * 1_000_000_000;
+    let timestamp = |day: u32, hours: u64| [nanos(hours) as u32, (nanos(hours) >> 32) as u32, day];
+    // the smallest timestamp has the largest nanoseconds and the largest the smallest
+    let arrays = vec![
+        Array::Int96(vec![
+            Some(timestamp(2_440_589, 1)),
+            None,
+            Some(timestamp(2_440_588, 23)),
+        ]),
+        Array::Int96(vec![None, None]),
+        Array::Int96(vec![Some(timestamp(2_440_590, 0)), None]),
+    ];
+
+    let data = write_single_column(
+        ParquetType::from_physical("col".to_string(), PhysicalType::Int96),
+        options,
+        |descriptor| {
+            arrays
+                .iter()
+                .map(|array| array_to_page(array, &options, descriptor))
+                .collect()
+        },
+        CompressionOptions::Snappy,
+    )?;
+
+    let mut reader = Cursor::new(data);
+    let metadata = read_metadata(&mut reader)?;
+    let column = &metadata.row_groups[0].columns()[0];
+    assert_eq!(read_pages(&mut reader, column)?, arrays);
+
+    let statistics = column.statistics().unwrap()?;
+    let statistics = statistics
+        .as_any()
+        .downcast_ref::<PrimitiveStatistics<[u32; 3]>>()
+        .unwrap();
+    assert_eq!(statistics.null_count, Some(4));
+    assert_eq!(statistics.min_value, Some(timestamp(2_440_588, 23)));
+    assert_eq!(statistics.max_value, Some(timestamp(2_440_590, 0)));
+    Ok(())
+}
+
+#[test]
+fn decimal_fixed_len_statistics() -> Result<()> {
+    let logical_type = PrimitiveLogicalType::Decimal(20, 2);
+    let physical_type = PhysicalType::FixedLenByteArray(9);
+    let field =
+        ParquetType::from_physical_with_logical("col".to_string(), physical_type, logical_type)?;
+    let primitive_type = match field {
+        ParquetType::PrimitiveType(primitive_type) => primitive_type,
+        _ => unreachable!(),
+    };
+    let decimal = |x: i128| Some(x.to_be_bytes()[7..].to_vec());
+    let array = vec![decimal(12345), None, decimal(99_999_999), decimal(7)];
+
+    let data = write_single_column(
+        ParquetType::PrimitiveType(primitive_type.clone()),
+        WriteOptions::default(),
+        |descriptor| {
+            let options = WriteOptions::default();
+            vec![super::binary::array_to_page_v1(
+                &array,
+                &options,
+                descriptor,
+                Encoding::Plain,
+            )]
+        },
+        CompressionOptions::Uncompressed,
+    )?;
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let statistics = metadata.row_groups[0].columns()[0].statistics().unwrap()?;
+    let statistics = statistics
+        .as_any()
+        .downcast_ref::<FixedLenStatistics>()
+        .unwrap();
+    assert_eq!(statistics.null_count, Some(1));
+    assert_eq!(statistics.min_value, decimal(7));
+    assert_eq!(statistics.max_value, decimal(99_999_999));
+    assert_eq!(statistics.primitive_type.logical_type, Some(logical_type));
+
+    // statistics with only the null count
+    let raw = parquet_format_async_temp::Statistics {
+        max: None,
+        min: None,
+        null_count: Some(3),
+        distinct_count: None,
+        max_value: None,
+        min_value: None,
+    };
+    let statistics = deserialize_statistics(&raw, primitive_type.clone())?;
+    let statistics = statistics
+        .as_any()
+        .downcast_ref::<FixedLenStatistics>()
+        .unwrap();
+    assert_eq!(statistics.null_count, Some(3));
+    assert_eq!(statistics.min_value, None);
+    assert_eq!(statistics.max_value, None);
+
+    // the min or max value does not have the size of the type
+    for (min_value, max_value) in [(Some(vec![0; 8]), None), (None, Some(vec![0; 10]))] {
+        let raw = parquet_format_async_temp::Statistics {
+            max: None,
+            min: None,
+            null_count: None,
+            distinct_count: None,
+            max_value,
+            min_value,
+        };
+        assert!(matches!(
+            deserialize_statistics(&raw, primitive_type.clone()),
+            Err(Error::OutOfSpec(_))
+        ));
+    }
+    Ok(())
+}
+
+/// Writes a dictionary-encoded column chunk with a page per array of indices, whose statistics
+/// have the respective distinct count, and returns the distinct count of the column chunk.
+fn write_distinct_counts(pages: &[(Vec<Option<u32>>, Option<i64>)]) -> Result<Option<i64>> {
+    let dict = [1i32, 2, 3];
+    let primitive_type = PrimitiveType::from_physical("col".to_string(), PhysicalType::Int32);
+    let data = write_single_column(
+        ParquetType::PrimitiveType(primitive_type),
+        WriteOptions::default(),
+        |descriptor| {
+            let dict_page = EncodedDictPage::new(
+                dict.iter().flat_map(|x| x.to_le_bytes()).collect(),
+                dict.len(),
+            );
+            std::iter::once(Ok(EncodedPage::Dict(dict_page)))
+                .chain(pages.iter().map(|(array, distinct_count)| {
+                    let (levels, indices) = dict_levels(array)?;
+                    let values = indices.iter().map(|&index| dict[index as usize]);
+                    let statistics = PrimitiveStatistics {
+                        primitive_type: descriptor.primitive_type.clone(),
+                        null_count: Some((array.len() - indices.len()) as i64),
+                        distinct_count: *distinct_count,
+                        max_value: values.clone().max(),
+                        min_value: values.min(),
+                    };
+                    dictionary::dict_data_page(
+                        &indices,
+                        2,
+                        levels,
+                        Some(serialize_statistics(&statistics)),
+                        descriptor.clone(),
+                        Version::V1,
+                    )
+                    .map(EncodedPage::Data)
+                }))
+                .collect()
+        },
+        CompressionOptions::Uncompressed,
+    )?;
+
+    let metadata = read_metadata(&mut Cursor::new(&data))?;
+    let statistics = metadata.row_groups[0].columns()[0].statistics().unwrap()?;
+    assert_eq!(
+        statistics.distinct_count(),
+        statistics
+            .as_any()
+            .downcast_ref::<PrimitiveStatistics<i32>>()
+            .unwrap()
+            .distinct_count
+    );
+    Ok(statistics.distinct_count())
+}
+
+#[test]
+fn distinct_count() -> Result<()> {
+    let values = vec![Some(0), Some(1), None, Some(2), Some(1)];
+    let nulls = vec![None; 4];
+
+    // a single page
+    assert_eq!(
+        write_distinct_counts(&[(values.clone(), Some(3))])?,
+        Some(3)
+    );
+    assert_eq!(write_distinct_counts(&[(values.clone(), None)])?, None);
+
+    // a page without values has legitimately no distinct values
+    assert_eq!(write_distinct_counts(&[(nulls.clone(), Some(0))])?, Some(0));
+    assert_eq!(
+        write_distinct_counts(&[(nulls.clone(), Some(0)), (values.clone(), Some(3))])?,
+        Some(3)
+    );
+
+    // the distinct values of different pages may overlap
+    assert_eq!(
+        write_distinct_counts(&[(values.clone(), Some(3)), (values, Some(3))])?,
+        None
+    );
+    Ok(())
+}
+
+/// Writes `pages` of a `BYTE_ARRAY` column (annotated as strings iff `utf8`) with
+/// statistics truncated to `length` bytes.
+fn write_truncated_statistics(
+    pages: &[Vec<Vec<u8>>],
+    utf8: bool,
+    length: usize,
+) -> Result<Vec<u8>> {
+    let options = WriteOptions {
+        statistics_truncate_length: Some(length),
+        ..Default::default()
+    };
+    let field = if utf8 {
+        ParquetType::from_physical_with_logical(
+            "col".to_string(),
+            PhysicalType::ByteArray,
+            PrimitiveLogicalType::String,
+        )?
+    } else {
+        ParquetType::from_physical("col".to_string(), PhysicalType::ByteArray)
+    };
+    write_single_column(
+        field,
+        options,
+        |descriptor| {
+            pages
+                .iter()
+                .map(|values| {
+                    let array = Array::Binary(values.iter().cloned().map(Some).collect());
+                    array_to_page(&array, &options, descriptor)
+                })
+                .collect()
+        },
+        CompressionOptions::Uncompressed,
+    )
+}
+
+fn binary_bounds(statistics: &dyn Statistics) -> (Option<Vec<u8>>, Option<Vec<u8>>) {
+    let statistics = statistics
+        .as_any()
+        .downcast_ref::<BinaryStatistics>()
+        .unwrap();
+    (statistics.min_value.clone(), statistics.max_value.clone())
+}
+
+#[test]
+fn statistics_truncate_length() -> Result<()> {
+    let pages = vec![
+        vec![b"abcdefgh".to_vec(), b"bcdefghi".to_vec()],
+        // the max can't be incremented: it is dropped from the page header
+        vec![b"xyz".to_vec(), vec![0xff; 10]],
+        vec![
+            "aaaé€".as_bytes().to_vec(),
+            "zzz\u{10FFFF}".as_bytes().to_vec(),
+        ],
+    ];
+    let values = pages.iter().flatten().collect::<Vec<_>>();
+
+    for utf8 in [false, true] {
+        let data = write_truncated_statistics(&pages, utf8, 4)?;
+        let metadata = read_metadata(&mut Cursor::new(&data))?;
+        let column = &metadata.row_groups[0].columns()[0];
+
+        // the statistics of the column chunk bound its values
+        let (min, max) = binary_bounds(column.statistics().unwrap()?.as_ref());
+        let min = min.unwrap();
+        assert!(min.len() <= 4);
+        assert!(values
+            .iter()
+            .all(|value| min.as_slice() <= value.as_slice()));
+        // the largest value is all 0xFF
+        assert_eq!(max, None);
+
+        let headers = get_page_iterator(column, &mut Cursor::new(&data), None, vec![])?
+            .map(|page| Ok(binary_bounds(page?.statistics().unwrap()?.as_ref())))
+            .collect::<Result<Vec<_>>>()?;
+        for ((min, max), values) in headers.iter().zip(pages.iter()) {
+            let min = min.as_ref().unwrap();
+            assert!(min.len() <= 4);
+            assert!(values.iter().all(|value| min <= value));
+            if let Some(max) = max {
+                assert!(max.len() <= 4);
+                assert!(values.iter().all(|value| max >= value));
+            }
+            if utf8 {
+                assert!(std::str::from_utf8(min).is_ok());
+            }
+        }
+        assert_eq!(headers[0], (Some(b"abcd".to_vec()), Some(b"bcdf".to_vec())));
+        assert_eq!(headers[1].1, None);
+        if utf8 {
+            // "aaaé" and "zzz\u{10FFFF}" are 5 and 7 bytes long
+            assert_eq!(headers[2], (Some(b"aaa".to_vec()), Some(b"zz{".to_vec())));
+        } else {
+            assert_eq!(
+                headers[2],
+                (Some(b"aaa\xc3".to_vec()), Some(b"zzz\xf5".to_vec()))
+            );
+        }
+
+        // the column index keeps the max values that can't be truncated
+        let indexes = read_columns_indexes(&mut Cursor::new(&data), &[column.clone()])?;
+        let index = indexes[0].as_any().downcast_ref::<ByteIndex>().unwrap();
+        for (index, values) in index.indexes.iter().zip(pages.iter()) {
+            let min = index.min.as_ref().unwrap();
+            let max = index.max.as_ref().unwrap();
+            assert!(min.len() <= 4);
+            assert!(values.iter().all(|value| min <= value && max >= value));
+        }
+        assert_eq!(index.indexes[1].max, Some(vec![0xff; 10]));
+    }
+    Ok(())
+}
@@ -122,12 +122,34 @@ def case_struct(size):
     )
 
 
+def case_map(size):
+    items = [[("a", 0)], None, [], [("b", 1), ("c", None)]]
+    fields = [
+        pa.field("map_int64", pa.map_(pa.utf8(), pa.int64())),
+    ]
+    schema = pa.schema(fields)
+    return (
+        {
+            "map_int64": items * size,
+        },
+        schema,
+        f"map_nullable_{size*10}.parquet",
+    )
+
+
 def write_pyarrow(
-    case, size=1, page_version=1, use_dictionary=False, compression=None
+    case,
+    size=1,
+    page_version=1,
+    use_dictionary=False,
+    compression=None,
+    use_byte_stream_split=False,
 ):
     data, schema, path = case(size)
 
     compression_path = f"/{compression}" if compression else ""
+    if use_byte_stream_split:
+        compression_path = f"{compression_path}/byte_stream_split"
 
     if use_dictionary:
         base_path = f"{PYARROW_PATH}/v{page_version}/dict{compression_path}"
@@ -144,6 +166,7 @@ def write_pyarrow(
         write_statistics=True,
         compression=compression,
         use_dictionary=use_dictionary,
+        use_byte_stream_split=use_byte_stream_split,
     )
 
 
@@ -152,3 +175,11 @@ for case in [case_basic_nullable, case_basic_required, case_nested, case_struct]
         for use_dict in [False, True]:
             for compression in [None, "snappy", "lz4"]:
                 write_pyarrow(case, 1, version, use_dict, compression)
+
+# the Arrow schema of nested types, stored by pyarrow in the key-value metadata
+write_pyarrow(case_map, 1, 1, False, None)
+
+# `BYTE_STREAM_SPLIT` only applies to floating point columns, the others are plain-encoded
+for case in [case_basic_nullable, case_basic_required]:
+    for version in [1, 2]:
+        write_pyarrow(case, 1, version, False, None, ["float64"])